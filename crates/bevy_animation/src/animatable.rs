@@ -1,7 +1,7 @@
 //! Traits and type for interpolating between values.
 
 use crate::util;
-use bevy_color::{Laba, LinearRgba, Oklaba, Srgba, Xyza};
+use bevy_color::{Color, Laba, LinearRgba, Mix, Oklaba, Srgba, Xyza};
 use bevy_ecs::world::World;
 use bevy_math::*;
 use bevy_reflect::Reflect;
@@ -100,6 +100,25 @@ impl_color_animatable!(Oklaba);
 impl_color_animatable!(Srgba);
 impl_color_animatable!(Xyza);
 
+// Color is interpolated in the color space of its first operand, and blended
+// in linear space.
+impl Animatable for Color {
+    #[inline]
+    fn interpolate(a: &Self, b: &Self, t: f32) -> Self {
+        a.mix(b, t)
+    }
+
+    #[inline]
+    fn blend(inputs: impl Iterator<Item = BlendInput<Self>>) -> Self {
+        LinearRgba::blend(inputs.map(|input| BlendInput {
+            weight: input.weight,
+            value: input.value.into(),
+            additive: input.additive,
+        }))
+        .into()
+    }
+}
+
 // Vec3 is special cased to use Vec3A internally for blending
 impl Animatable for Vec3 {
     #[inline]
@@ -136,6 +155,21 @@ impl Animatable for bool {
     }
 }
 
+impl Animatable for usize {
+    #[inline]
+    fn interpolate(a: &Self, b: &Self, t: f32) -> Self {
+        util::step_unclamped(*a, *b, t)
+    }
+
+    #[inline]
+    fn blend(inputs: impl Iterator<Item = BlendInput<Self>>) -> Self {
+        inputs
+            .max_by(|a, b| FloatOrd(a.weight).cmp(&FloatOrd(b.weight)))
+            .map(|input| input.value)
+            .unwrap_or(0)
+    }
+}
+
 impl Animatable for Transform {
     fn interpolate(a: &Self, b: &Self, t: f32) -> Self {
         Self {
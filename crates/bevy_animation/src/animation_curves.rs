@@ -0,0 +1,712 @@
+//! Animation curves that drive arbitrary reflected fields of components and
+//! assets.
+//!
+//! [`VariableCurve`]s can only animate [`Transform`]s and [`MorphWeights`].
+//! The curves in this module can animate any field of any reflected
+//! component, such as the intensity of a light or the color of a UI node, as
+//! well as any field of an asset that an entity holds a [`Handle`] to, such
+//! as the base color of a material.
+//!
+//! Values are stored and sampled through the [`Curve`] trait, so no
+//! reflection is involved in sampling; the animated field is looked up by its
+//! reflect path once per curve per frame, and then blended with the sampled
+//! value through the [`Animatable`] trait.
+//!
+//! ```
+//! # use bevy_animation::{
+//! #     animation_curves::{AnimatableCurve, AnimatableKeyframeCurve, AnimatedField},
+//! #     AnimationClip, AnimationTargetId,
+//! # };
+//! # use bevy_core::Name;
+//! # use bevy_ecs::component::Component;
+//! # use bevy_reflect::Reflect;
+//! #[derive(Component, Reflect)]
+//! struct Lamp {
+//!     intensity: f32,
+//! }
+//!
+//! let flicker = AnimatableKeyframeCurve::new([(0.0, 800.0f32), (0.1, 200.0), (0.2, 800.0)])
+//!     .expect("the keyframes should be valid");
+//!
+//! let mut clip = AnimationClip::default();
+//! clip.add_property_curve_to_target(
+//!     AnimationTargetId::from_name(&Name::new("lamp")),
+//!     AnimatableCurve::new(AnimatedField::component::<Lamp>("intensity"), flicker),
+//! );
+//! ```
+//!
+//! Animated components must be registered with `#[reflect(Component)]`, and
+//! animated assets with [`register_asset_reflect`].
+//!
+//! [`VariableCurve`]: crate::VariableCurve
+//! [`Transform`]: bevy_transform::prelude::Transform
+//! [`MorphWeights`]: bevy_render::mesh::morph::MorphWeights
+//! [`register_asset_reflect`]: bevy_asset::AssetApp::register_asset_reflect
+
+use std::{
+    fmt::{self, Debug, Formatter},
+    marker::PhantomData,
+};
+
+use bevy_asset::{Asset, Handle, ReflectAsset, ReflectHandle};
+use bevy_ecs::{component::Component, entity::Entity, reflect::ReflectComponent, world::World};
+use bevy_math::curve::{
+    cores::{UnevenCore, UnevenCoreError},
+    Curve, Interval,
+};
+use bevy_reflect::{
+    reflect_trait, FromReflect, GetPath, GetTypeRegistration, PartialReflect, Reflect, TypeData,
+    TypePath, TypeRegistration, TypeRegistry, Typed,
+};
+use thiserror::Error;
+
+use crate::animatable::Animatable;
+
+/// A curve that animates some part of an [`AnimationTarget`] entity.
+///
+/// [`AnimatableCurve`] implements this for any reflected field, but this
+/// trait can also be implemented directly to animate values in other ways.
+/// Registering `#[reflect(AnimationCurve)]` on an implementor allows clips
+/// containing it to be deserialized.
+///
+/// [`AnimationTarget`]: crate::AnimationTarget
+#[reflect_trait(trait_object)]
+pub trait AnimationCurve: Reflect + Debug {
+    /// Clones this curve into a new box.
+    fn clone_curve(&self) -> Box<dyn AnimationCurve>;
+
+    /// The range of times, in seconds, over which this curve is defined.
+    fn domain(&self) -> Interval;
+
+    /// Samples this curve at `seek_time`, clamped to its [domain], and blends
+    /// the result into the animated value of `entity` with the given
+    /// `weight`.
+    ///
+    /// A `weight` of 1.0 replaces the current value with the sampled one.
+    ///
+    /// [domain]: AnimationCurve::domain
+    fn apply(
+        &self,
+        world: &mut World,
+        entity: Entity,
+        registry: &TypeRegistry,
+        seek_time: f32,
+        weight: f32,
+    ) -> Result<(), AnimationEvaluationError>;
+}
+
+impl Clone for Box<dyn AnimationCurve> {
+    fn clone(&self) -> Self {
+        self.clone_curve()
+    }
+}
+
+/// An error that occurs when an [`AnimationCurve`] can't be applied to its
+/// target.
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum AnimationEvaluationError {
+    /// The animated type isn't registered, or lacks the type data needed to
+    /// access it.
+    #[error("`{type_path}` isn't registered with `{type_data}` type data")]
+    MissingTypeData {
+        /// The type path of the animated type.
+        type_path: String,
+        /// The name of the missing type data.
+        type_data: &'static str,
+    },
+    /// The animation target doesn't exist or lacks the animated component.
+    #[error("the entity {entity} has no `{type_path}` component")]
+    MissingComponent {
+        /// The animation target.
+        entity: Entity,
+        /// The type path of the animated component.
+        type_path: String,
+    },
+    /// The asset that the animation target holds a handle to isn't loaded.
+    #[error("the asset referenced by the `{type_path}` of entity {entity} isn't loaded")]
+    MissingAsset {
+        /// The animation target.
+        entity: Entity,
+        /// The type path of the handle component.
+        type_path: String,
+    },
+    /// The reflect path of the animated field couldn't be resolved.
+    #[error("the field path `{path}` is invalid: {message}")]
+    InvalidPath {
+        /// The reflect path of the animated field.
+        path: String,
+        /// A description of the failure.
+        message: String,
+    },
+    /// The animated field isn't of the type that the curve produces.
+    #[error("expected the field `{path}` to be a `{expected}`, but found a `{found}`")]
+    MismatchedType {
+        /// The reflect path of the animated field.
+        path: String,
+        /// The type path of the values that the curve produces.
+        expected: &'static str,
+        /// The type path of the animated field.
+        found: String,
+    },
+}
+
+/// Where the value of an [`AnimatedField`] is stored.
+#[derive(Clone, Debug, PartialEq, Reflect)]
+enum FieldOwner {
+    /// A component of the animation target, named by its type path.
+    Component(String),
+    /// The asset that a handle component of the animation target refers to.
+    /// The handle component is named by its type path.
+    Asset(String),
+}
+
+/// A reference to a field of a component, or of an asset, of an
+/// [`AnimationTarget`] entity.
+///
+/// The field is named by a reflect path relative to the component or asset
+/// (see [`GetPath`] for the syntax). An empty path refers to the whole
+/// component or asset.
+///
+/// [`AnimationTarget`]: crate::AnimationTarget
+#[derive(Clone, Debug, PartialEq, Reflect)]
+pub struct AnimatedField {
+    owner: FieldOwner,
+    path: String,
+}
+
+impl AnimatedField {
+    /// Refers to the field at `path` in the `C` component of the animation
+    /// target.
+    ///
+    /// For example, `AnimatedField::component::<PointLight>("intensity")` or
+    /// `AnimatedField::component::<BackgroundColor>(".0")`.
+    pub fn component<C: Component + TypePath>(path: impl Into<String>) -> Self {
+        Self {
+            owner: FieldOwner::Component(C::type_path().to_owned()),
+            path: path.into(),
+        }
+    }
+
+    /// Refers to the field at `path` in the `A` asset that the
+    /// [`Handle<A>`] component of the animation target refers to.
+    ///
+    /// For example,
+    /// `AnimatedField::asset::<StandardMaterial>("base_color")`. Note that the
+    /// asset is modified in place, so every entity sharing it will see the
+    /// animated value.
+    pub fn asset<A: Asset>(path: impl Into<String>) -> Self {
+        Self {
+            owner: FieldOwner::Asset(Handle::<A>::type_path().to_owned()),
+            path: path.into(),
+        }
+    }
+
+    /// The reflect path of this field, relative to its component or asset.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Calls `f` with the value of this field on `entity`.
+    pub fn with_field_mut<R>(
+        &self,
+        world: &mut World,
+        entity: Entity,
+        registry: &TypeRegistry,
+        f: impl FnOnce(&mut dyn PartialReflect) -> Result<R, AnimationEvaluationError>,
+    ) -> Result<R, AnimationEvaluationError> {
+        match self.owner {
+            FieldOwner::Component(ref type_path) => {
+                let reflect_component =
+                    type_data::<ReflectComponent>(registry, type_path, "ReflectComponent")?;
+                let missing_component = || AnimationEvaluationError::MissingComponent {
+                    entity,
+                    type_path: type_path.clone(),
+                };
+                let mut entity_mut = world.get_entity_mut(entity).ok_or_else(missing_component)?;
+                let mut component = reflect_component
+                    .reflect_mut(&mut entity_mut)
+                    .ok_or_else(missing_component)?;
+                f(self.resolve(component.as_reflect_mut())?)
+            }
+
+            FieldOwner::Asset(ref type_path) => {
+                let reflect_component =
+                    type_data::<ReflectComponent>(registry, type_path, "ReflectComponent")?;
+                let reflect_handle =
+                    type_data::<ReflectHandle>(registry, type_path, "ReflectHandle")?;
+                let handle = world
+                    .get_entity(entity)
+                    .and_then(|entity_ref| reflect_component.reflect(entity_ref))
+                    .and_then(|handle| reflect_handle.downcast_handle_untyped(handle.as_any()))
+                    .ok_or_else(|| AnimationEvaluationError::MissingComponent {
+                        entity,
+                        type_path: type_path.clone(),
+                    })?;
+
+                let reflect_asset = registry
+                    .get(reflect_handle.asset_type_id())
+                    .and_then(TypeRegistration::data::<ReflectAsset>)
+                    .ok_or_else(|| AnimationEvaluationError::MissingTypeData {
+                        type_path: reflect_handle.asset_type_path().to_owned(),
+                        type_data: "ReflectAsset",
+                    })?;
+                let asset = reflect_asset.get_mut(world, handle).ok_or_else(|| {
+                    AnimationEvaluationError::MissingAsset {
+                        entity,
+                        type_path: type_path.clone(),
+                    }
+                })?;
+                f(self.resolve(asset)?)
+            }
+        }
+    }
+
+    fn resolve<'a>(
+        &self,
+        value: &'a mut dyn Reflect,
+    ) -> Result<&'a mut dyn PartialReflect, AnimationEvaluationError> {
+        value.reflect_path_mut(self.path.as_str()).map_err(|error| {
+            AnimationEvaluationError::InvalidPath {
+                path: self.path.clone(),
+                message: error.to_string(),
+            }
+        })
+    }
+}
+
+fn type_data<'a, T: TypeData>(
+    registry: &'a TypeRegistry,
+    type_path: &str,
+    type_data: &'static str,
+) -> Result<&'a T, AnimationEvaluationError> {
+    registry
+        .get_with_type_path(type_path)
+        .and_then(TypeRegistration::data::<T>)
+        .ok_or_else(|| AnimationEvaluationError::MissingTypeData {
+            type_path: type_path.to_owned(),
+            type_data,
+        })
+}
+
+/// An [`AnimationCurve`] that animates an [`AnimatedField`] of type `T` with
+/// the values of a [`Curve<T>`].
+///
+/// To deserialize clips containing this curve, register each concrete
+/// instantiation with the type registry, e.g.
+/// `app.register_type::<AnimatableCurve<f32, AnimatableKeyframeCurve<f32>>>()`.
+#[derive(Reflect)]
+#[reflect(
+    AnimationCurve,
+    where T: Animatable + TypePath + Clone + Debug,
+    C: Curve<T> + Typed + GetTypeRegistration + Clone + Debug
+)]
+pub struct AnimatableCurve<T, C> {
+    /// The field that this curve animates.
+    pub field: AnimatedField,
+    /// The values of the field over time.
+    pub curve: C,
+    #[reflect(ignore)]
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T, C> AnimatableCurve<T, C>
+where
+    T: Animatable,
+    C: Curve<T>,
+{
+    /// Creates a curve that animates `field` with the values of `curve`.
+    pub fn new(field: AnimatedField, curve: C) -> Self {
+        Self {
+            field,
+            curve,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T, C: Clone> Clone for AnimatableCurve<T, C> {
+    fn clone(&self) -> Self {
+        Self {
+            field: self.field.clone(),
+            curve: self.curve.clone(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T, C: Debug> Debug for AnimatableCurve<T, C> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AnimatableCurve")
+            .field("field", &self.field)
+            .field("curve", &self.curve)
+            .finish()
+    }
+}
+
+impl<T, C> AnimationCurve for AnimatableCurve<T, C>
+where
+    T: Animatable + TypePath + Clone + Debug,
+    C: Curve<T> + FromReflect + Typed + GetTypeRegistration + Clone + Debug,
+{
+    fn clone_curve(&self) -> Box<dyn AnimationCurve> {
+        Box::new(self.clone())
+    }
+
+    fn domain(&self) -> Interval {
+        self.curve.domain()
+    }
+
+    fn apply(
+        &self,
+        world: &mut World,
+        entity: Entity,
+        registry: &TypeRegistry,
+        seek_time: f32,
+        weight: f32,
+    ) -> Result<(), AnimationEvaluationError> {
+        let sample = self.curve.sample_clamped(seek_time);
+        self.field.with_field_mut(world, entity, registry, |field| {
+            let found = field.reflect_type_path().to_owned();
+            let value = field.try_downcast_mut::<T>().ok_or_else(|| {
+                AnimationEvaluationError::MismatchedType {
+                    path: self.field.path.clone(),
+                    expected: T::type_path(),
+                    found,
+                }
+            })?;
+            *value = T::interpolate(value, &sample, weight);
+            Ok(())
+        })
+    }
+}
+
+/// A keyframe-defined curve that interpolates between its keyframes with
+/// [`Animatable::interpolate`].
+///
+/// Unlike [`UnevenSampleAutoCurve`], this works for any [`Animatable`] type,
+/// including colors.
+///
+/// [`UnevenSampleAutoCurve`]: bevy_math::curve::UnevenSampleAutoCurve
+#[derive(Clone, Debug, Reflect)]
+pub struct AnimatableKeyframeCurve<T> {
+    core: UnevenCore<T>,
+}
+
+impl<T: Animatable> AnimatableKeyframeCurve<T> {
+    /// Creates a curve from `(time, value)` keyframes.
+    ///
+    /// The keyframes are sorted by time, and those with non-finite times are
+    /// discarded. Returns an error if fewer than two keyframes remain; use a
+    /// [`ConstantCurve`] to hold a single value.
+    ///
+    /// [`ConstantCurve`]: bevy_math::curve::ConstantCurve
+    pub fn new(keyframes: impl IntoIterator<Item = (f32, T)>) -> Result<Self, UnevenCoreError> {
+        Ok(Self {
+            core: UnevenCore::new(keyframes)?,
+        })
+    }
+}
+
+impl<T: Animatable + Clone> Curve<T> for AnimatableKeyframeCurve<T> {
+    #[inline]
+    fn domain(&self) -> Interval {
+        self.core.domain()
+    }
+
+    #[inline]
+    fn sample_unchecked(&self, t: f32) -> T {
+        self.core.sample_with(t, T::interpolate)
+    }
+}
+
+/// A keyframe-defined curve that holds the value of each keyframe until the
+/// next one, without interpolating.
+///
+/// This suits values that can't be interpolated, such as the frame index of a
+/// flipbook animation, or whether a sprite is flipped.
+#[derive(Clone, Debug, Reflect)]
+pub struct SteppedKeyframeCurve<T> {
+    core: UnevenCore<T>,
+}
+
+impl<T> SteppedKeyframeCurve<T> {
+    /// Creates a curve from `(time, value)` keyframes.
+    ///
+    /// The keyframes are sorted by time, and those with non-finite times are
+    /// discarded. Returns an error if fewer than two keyframes remain.
+    pub fn new(keyframes: impl IntoIterator<Item = (f32, T)>) -> Result<Self, UnevenCoreError> {
+        Ok(Self {
+            core: UnevenCore::new(keyframes)?,
+        })
+    }
+
+    /// Creates a curve that starts at time 0 and holds each value for the
+    /// given duration in seconds, one after the other.
+    ///
+    /// The last value is also held at the end of the curve. Frames whose
+    /// duration isn't positive are skipped, since they would never be shown.
+    /// Returns an error if the total duration isn't positive.
+    pub fn from_durations(
+        frames: impl IntoIterator<Item = (T, f32)>,
+    ) -> Result<Self, UnevenCoreError>
+    where
+        T: Clone,
+    {
+        let mut keyframes = vec![];
+        let mut time = 0.0;
+        for (value, duration) in frames {
+            if duration <= 0.0 || duration.is_nan() {
+                continue;
+            }
+            keyframes.push((time, value));
+            time += duration;
+        }
+        if let Some((_, last)) = keyframes.last() {
+            let last = last.clone();
+            keyframes.push((time, last));
+        }
+        Self::new(keyframes)
+    }
+}
+
+impl<T: Clone> Curve<T> for SteppedKeyframeCurve<T> {
+    #[inline]
+    fn domain(&self) -> Interval {
+        self.core.domain()
+    }
+
+    #[inline]
+    fn sample_unchecked(&self, t: f32) -> T {
+        self.core.sample_with(t, |a, _, _| a.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_asset::{Asset, Assets, Handle, ReflectAsset, ReflectHandle};
+    use bevy_color::{Color, LinearRgba};
+    use bevy_core::Name;
+    use bevy_ecs::{
+        component::Component,
+        reflect::{AppTypeRegistry, ReflectComponent},
+        system::RunSystemOnce,
+        world::World,
+    };
+    use bevy_math::curve::{ConstantCurve, Curve, Interval};
+    use bevy_reflect::{Reflect, TypeRegistry};
+
+    use super::{
+        AnimatableCurve, AnimatableKeyframeCurve, AnimatedField, AnimationCurve,
+        AnimationEvaluationError, SteppedKeyframeCurve,
+    };
+    use crate::{
+        animate_properties, graph::AnimationGraph, AnimationClip, AnimationPlayer, AnimationTarget,
+        AnimationTargetId,
+    };
+
+    #[derive(Component, Reflect)]
+    #[reflect(Component)]
+    struct Lamp {
+        intensity: f32,
+        color: Color,
+    }
+
+    #[derive(Asset, Reflect)]
+    struct Glow {
+        strength: f32,
+    }
+
+    fn registry() -> TypeRegistry {
+        let mut registry = TypeRegistry::default();
+        registry.register::<Lamp>();
+        registry.register::<Glow>();
+        registry.register::<Handle<Glow>>();
+        registry.register_type_data::<Glow, ReflectAsset>();
+        registry.register_type_data::<Handle<Glow>, ReflectHandle>();
+        registry
+    }
+
+    fn lamp() -> Lamp {
+        Lamp {
+            intensity: 100.0,
+            color: Color::BLACK,
+        }
+    }
+
+    #[test]
+    fn keyframe_curve() {
+        let curve = AnimatableKeyframeCurve::new([
+            (1.0, LinearRgba::BLACK),
+            (0.0, LinearRgba::WHITE),
+            (2.0, LinearRgba::RED),
+        ])
+        .unwrap();
+        assert_eq!(curve.domain(), Interval::new(0.0, 2.0).unwrap());
+        assert_eq!(curve.sample_clamped(-1.0), LinearRgba::WHITE);
+        assert_eq!(curve.sample_clamped(0.5), LinearRgba::rgb(0.5, 0.5, 0.5));
+        assert_eq!(curve.sample_clamped(1.5), LinearRgba::rgb(0.5, 0.0, 0.0));
+        assert_eq!(curve.sample_clamped(3.0), LinearRgba::RED);
+
+        assert!(AnimatableKeyframeCurve::new([(0.0, 1.0)]).is_err());
+    }
+
+    #[test]
+    fn stepped_curve() {
+        let curve =
+            SteppedKeyframeCurve::from_durations([(3usize, 0.1), (4, 0.2), (5, 0.1)]).unwrap();
+        assert_eq!(curve.domain(), Interval::new(0.0, 0.4).unwrap());
+        assert_eq!(curve.sample_clamped(0.0), 3);
+        assert_eq!(curve.sample_clamped(0.09), 3);
+        assert_eq!(curve.sample_clamped(0.1), 4);
+        assert_eq!(curve.sample_clamped(0.29), 4);
+        assert_eq!(curve.sample_clamped(0.35), 5);
+        assert_eq!(curve.sample_clamped(1.0), 5);
+
+        assert!(SteppedKeyframeCurve::<usize>::from_durations([]).is_err());
+
+        // Frames without a duration are skipped.
+        let curve =
+            SteppedKeyframeCurve::from_durations([(3usize, 0.1), (4, 0.0), (5, 0.1), (6, -1.0)])
+                .unwrap();
+        assert_eq!(curve.domain(), Interval::new(0.0, 0.2).unwrap());
+        assert_eq!(curve.sample_clamped(0.1), 5);
+        assert_eq!(curve.sample_clamped(0.2), 5);
+    }
+
+    #[test]
+    fn animate_component_field() {
+        let registry = registry();
+        let mut world = World::new();
+        let entity = world.spawn(lamp()).id();
+
+        let curve = AnimatableCurve::new(
+            AnimatedField::component::<Lamp>("intensity"),
+            AnimatableKeyframeCurve::new([(0.0, 0.0f32), (1.0, 10.0)]).unwrap(),
+        );
+        curve
+            .apply(&mut world, entity, &registry, 0.5, 1.0)
+            .unwrap();
+        assert_eq!(world.get::<Lamp>(entity).unwrap().intensity, 5.0);
+
+        // A weight of 0.5 moves halfway from the current value.
+        curve
+            .apply(&mut world, entity, &registry, 1.0, 0.5)
+            .unwrap();
+        assert_eq!(world.get::<Lamp>(entity).unwrap().intensity, 7.5);
+
+        let curve = AnimatableCurve::new(
+            AnimatedField::component::<Lamp>("color"),
+            ConstantCurve::new(Interval::EVERYWHERE, Color::WHITE),
+        );
+        curve
+            .apply(&mut world, entity, &registry, 0.0, 1.0)
+            .unwrap();
+        assert_eq!(world.get::<Lamp>(entity).unwrap().color, Color::WHITE);
+    }
+
+    #[test]
+    fn animate_asset_field() {
+        let registry = registry();
+        let mut world = World::new();
+        let mut glows = Assets::<Glow>::default();
+        let handle = glows.add(Glow { strength: 1.0 });
+        world.insert_resource(glows);
+        let entity = world.spawn(handle.clone()).id();
+
+        let curve = AnimatableCurve::new(
+            AnimatedField::asset::<Glow>("strength"),
+            AnimatableKeyframeCurve::new([(0.0, 2.0f32), (1.0, 4.0)]).unwrap(),
+        );
+        curve
+            .apply(&mut world, entity, &registry, 0.5, 1.0)
+            .unwrap();
+        let glows = world.resource::<Assets<Glow>>();
+        assert_eq!(glows.get(&handle).unwrap().strength, 3.0);
+    }
+
+    #[test]
+    fn evaluation_errors() {
+        let registry = registry();
+        let mut world = World::new();
+        let entity = world.spawn(lamp()).id();
+        let empty = world.spawn_empty().id();
+
+        let apply = |world: &mut World, entity, field: AnimatedField| {
+            AnimatableCurve::new(field, ConstantCurve::new(Interval::EVERYWHERE, 1.0f32))
+                .apply(world, entity, &registry, 0.0, 1.0)
+        };
+
+        assert!(matches!(
+            apply(
+                &mut world,
+                empty,
+                AnimatedField::component::<Lamp>("intensity")
+            ),
+            Err(AnimationEvaluationError::MissingComponent { .. })
+        ));
+        assert!(matches!(
+            apply(
+                &mut world,
+                entity,
+                AnimatedField::component::<Lamp>("brightness")
+            ),
+            Err(AnimationEvaluationError::InvalidPath { .. })
+        ));
+        assert!(matches!(
+            apply(
+                &mut world,
+                entity,
+                AnimatedField::component::<Lamp>("color")
+            ),
+            Err(AnimationEvaluationError::MismatchedType { .. })
+        ));
+        assert!(matches!(
+            apply(&mut world, entity, AnimatedField::component::<Name>("")),
+            Err(AnimationEvaluationError::MissingTypeData { .. })
+        ));
+    }
+
+    #[test]
+    fn animate_properties_system() {
+        let mut world = World::new();
+        world.init_resource::<AppTypeRegistry>();
+        world
+            .resource::<AppTypeRegistry>()
+            .write()
+            .register::<Lamp>();
+
+        let target_id = AnimationTargetId::from_name(&Name::new("lamp"));
+        let mut clip = AnimationClip::default();
+        clip.add_property_curve_to_target(
+            target_id,
+            AnimatableCurve::new(
+                AnimatedField::component::<Lamp>("intensity"),
+                AnimatableKeyframeCurve::new([(0.0, 0.0f32), (2.0, 20.0)]).unwrap(),
+            ),
+        );
+        assert_eq!(clip.duration(), 2.0);
+
+        let mut clips = Assets::<AnimationClip>::default();
+        let (graph, node_index) = AnimationGraph::from_clip(clips.add(clip));
+        let mut graphs = Assets::<AnimationGraph>::default();
+        let graph = graphs.add(graph);
+        world.insert_resource(clips);
+        world.insert_resource(graphs);
+
+        let mut player = AnimationPlayer::default();
+        player.play(node_index).seek_to(0.5);
+        let player = world.spawn((player, graph)).id();
+        let lamp = world
+            .spawn((
+                lamp(),
+                AnimationTarget {
+                    id: target_id,
+                    player,
+                },
+            ))
+            .id();
+
+        world.run_system_once(animate_properties);
+        assert_eq!(world.get::<Lamp>(lamp).unwrap().intensity, 5.0);
+    }
+}
@@ -0,0 +1,377 @@
+//! Blend spaces, which weight animations according to a point in a parameter
+//! space.
+
+use bevy_math::{FloatExt, Vec2};
+use bevy_reflect::Reflect;
+use serde::{Deserialize, Serialize};
+
+use crate::graph::AnimationNodeIndex;
+
+/// A set of animation graph nodes placed at points in a one- or
+/// two-dimensional parameter space, such as speed and direction.
+///
+/// Given a point in that space, the blend space weights the nodes closest to
+/// it so that the animations blend smoothly as the point moves. In one
+/// dimension, the two nodes on either side of the point are interpolated
+/// linearly. In two dimensions, the nodes are triangulated, and the three
+/// nodes of the triangle containing the point are weighted by the point's
+/// barycentric coordinates. Points outside the nodes are clamped to the
+/// nearest edge.
+///
+/// Blend spaces are attached to blend nodes of an [`AnimationGraph`], for
+/// example with [`AnimationGraph::add_blend_space_1d`], and the point is set
+/// on the [`AnimationPlayer`] with
+/// [`AnimationPlayer::set_blend_space_point`]. The nodes in the blend space
+/// must be playing for it to have an effect.
+///
+/// [`AnimationGraph`]: crate::graph::AnimationGraph
+/// [`AnimationGraph::add_blend_space_1d`]: crate::graph::AnimationGraph::add_blend_space_1d
+/// [`AnimationPlayer`]: crate::AnimationPlayer
+/// [`AnimationPlayer::set_blend_space_point`]: crate::AnimationPlayer::set_blend_space_point
+#[derive(Clone, Debug, Default, Reflect, Serialize, Deserialize)]
+#[serde(from = "SerializedBlendSpace", into = "SerializedBlendSpace")]
+pub struct BlendSpace {
+    /// The nodes and their positions. In one dimension, these are sorted by
+    /// `x`, and `y` is zero.
+    points: Vec<BlendSpacePoint>,
+    /// The triangulation of the points, as indices into `points`, or `None`
+    /// for a one-dimensional blend space.
+    triangles: Option<Vec<[usize; 3]>>,
+}
+
+/// An animation graph node placed in a [`BlendSpace`].
+#[derive(Clone, Copy, Debug, PartialEq, Reflect)]
+pub struct BlendSpacePoint {
+    /// The node that this point weights.
+    pub node: AnimationNodeIndex,
+    /// The position of the node in the parameter space.
+    pub position: Vec2,
+}
+
+/// A version of [`BlendSpace`] suitable for serializing as part of an
+/// animation graph, without the triangulation.
+#[derive(Clone, Serialize, Deserialize)]
+pub enum SerializedBlendSpace {
+    /// A one-dimensional blend space, as pairs of node and position.
+    OneDimensional(Vec<(AnimationNodeIndex, f32)>),
+    /// A two-dimensional blend space, as pairs of node and position.
+    TwoDimensional(Vec<(AnimationNodeIndex, [f32; 2])>),
+}
+
+impl BlendSpace {
+    /// Creates a one-dimensional blend space from nodes and their positions.
+    pub fn one_dimensional(points: impl IntoIterator<Item = (AnimationNodeIndex, f32)>) -> Self {
+        let mut points: Vec<_> = points
+            .into_iter()
+            .map(|(node, x)| BlendSpacePoint {
+                node,
+                position: Vec2::new(x, 0.0),
+            })
+            .collect();
+        points.sort_by(|a, b| a.position.x.total_cmp(&b.position.x));
+        Self {
+            points,
+            triangles: None,
+        }
+    }
+
+    /// Creates a two-dimensional blend space from nodes and their positions,
+    /// triangulating them.
+    ///
+    /// If all the positions are collinear, the node nearest to the point gets
+    /// all the weight.
+    pub fn two_dimensional(points: impl IntoIterator<Item = (AnimationNodeIndex, Vec2)>) -> Self {
+        let points: Vec<_> = points
+            .into_iter()
+            .map(|(node, position)| BlendSpacePoint { node, position })
+            .collect();
+        let triangles = triangulate(&points);
+        Self {
+            points,
+            triangles: Some(triangles),
+        }
+    }
+
+    /// Returns true if this blend space is one-dimensional.
+    pub fn is_one_dimensional(&self) -> bool {
+        self.triangles.is_none()
+    }
+
+    /// The nodes in this blend space and their positions.
+    pub fn points(&self) -> &[BlendSpacePoint] {
+        &self.points
+    }
+
+    /// Calls `f` with each node of this blend space that has a nonzero weight
+    /// at `point`, along with that weight. The weights sum to 1.
+    ///
+    /// For one-dimensional blend spaces, only `point.x` is used.
+    pub fn weights(&self, point: Vec2, mut f: impl FnMut(AnimationNodeIndex, f32)) {
+        let mut emit = |index: usize, weight: f32| {
+            if weight > 0.0 {
+                f(self.points[index].node, weight);
+            }
+        };
+
+        match self.triangles {
+            None => {
+                let Some(last) = self.points.len().checked_sub(1) else {
+                    return;
+                };
+                let upper = self.points.partition_point(|p| p.position.x <= point.x);
+                if upper == 0 {
+                    emit(0, 1.0);
+                } else if upper > last {
+                    emit(last, 1.0);
+                } else {
+                    let (a, b) = (
+                        self.points[upper - 1].position.x,
+                        self.points[upper].position.x,
+                    );
+                    let t = f32::inverse_lerp(a, b, point.x);
+                    emit(upper - 1, 1.0 - t);
+                    emit(upper, t);
+                }
+            }
+
+            Some(ref triangles) if !triangles.is_empty() => {
+                let (triangle, weights) = triangles
+                    .iter()
+                    .map(|triangle| {
+                        let corners = triangle.map(|index| self.points[index].position);
+                        (triangle, closest_point_weights(corners, point))
+                    })
+                    .min_by(|(_, (a, _)), (_, (b, _))| a.total_cmp(b))
+                    .map(|(triangle, (_, weights))| (triangle, weights))
+                    .unwrap();
+                for (&index, weight) in triangle.iter().zip(weights) {
+                    emit(index, weight);
+                }
+            }
+
+            Some(_) => {
+                let nearest = self.points.iter().enumerate().min_by(|(_, a), (_, b)| {
+                    a.position
+                        .distance_squared(point)
+                        .total_cmp(&b.position.distance_squared(point))
+                });
+                if let Some((index, _)) = nearest {
+                    emit(index, 1.0);
+                }
+            }
+        }
+    }
+}
+
+/// Returns the squared distance from `point` to the closest point of the
+/// triangle `corners`, and the barycentric coordinates of that closest point.
+fn closest_point_weights(corners: [Vec2; 3], point: Vec2) -> (f32, [f32; 3]) {
+    let [a, b, c] = corners;
+    let area = (b - a).perp_dot(c - a);
+    let u = (b - point).perp_dot(c - point) / area;
+    let v = (c - point).perp_dot(a - point) / area;
+    let w = 1.0 - u - v;
+    if u >= 0.0 && v >= 0.0 && w >= 0.0 {
+        return (0.0, [u, v, w]);
+    }
+
+    // The point is outside the triangle, so clamp it to the nearest edge.
+    [(0, 1), (1, 2), (2, 0)]
+        .into_iter()
+        .map(|(i, j)| {
+            let (start, end) = (corners[i], corners[j]);
+            let t =
+                ((point - start).dot(end - start) / (end - start).length_squared()).clamp(0.0, 1.0);
+            let mut weights = [0.0; 3];
+            weights[i] = 1.0 - t;
+            weights[j] = t;
+            (start.lerp(end, t).distance_squared(point), weights)
+        })
+        .min_by(|(a, _), (b, _)| a.total_cmp(b))
+        .unwrap()
+}
+
+/// Computes the Delaunay triangulation of `points`.
+///
+/// Blend spaces have few points, so this simply keeps every triangle whose
+/// circumcircle contains no other point. Where four or more points are
+/// cocircular, the first of the overlapping triangles wins.
+fn triangulate(points: &[BlendSpacePoint]) -> Vec<[usize; 3]> {
+    let position = |index: usize| points[index].position;
+    let mut triangles: Vec<[usize; 3]> = vec![];
+
+    for i in 0..points.len() {
+        for j in (i + 1)..points.len() {
+            for k in (j + 1)..points.len() {
+                let (a, b, c) = (position(i), position(j), position(k));
+                let orientation = (b - a).perp_dot(c - a);
+                if orientation.abs() <= f32::EPSILON {
+                    continue;
+                }
+
+                let is_delaunay = (0..points.len())
+                    .filter(|&l| l != i && l != j && l != k)
+                    .all(|l| in_circumcircle(a, b, c, position(l)) * orientation.signum() <= 0.0);
+                if !is_delaunay {
+                    continue;
+                }
+
+                let centroid = (a + b + c) / 3.0;
+                let overlaps = triangles.iter().any(|triangle| {
+                    let corners = triangle.map(position);
+                    closest_point_weights(corners, centroid).0 == 0.0
+                        || closest_point_weights(
+                            [a, b, c],
+                            (corners[0] + corners[1] + corners[2]) / 3.0,
+                        )
+                        .0 == 0.0
+                });
+                if !overlaps {
+                    triangles.push([i, j, k]);
+                }
+            }
+        }
+    }
+
+    triangles
+}
+
+/// Returns a value that is positive if `d` lies inside the circumcircle of the
+/// counterclockwise triangle `a`, `b`, `c`, negative if it lies outside, and
+/// zero if it lies on it. The sign is flipped for clockwise triangles.
+fn in_circumcircle(a: Vec2, b: Vec2, c: Vec2, d: Vec2) -> f32 {
+    let (a, b, c) = (a - d, b - d, c - d);
+    a.length_squared() * b.perp_dot(c) - b.length_squared() * a.perp_dot(c)
+        + c.length_squared() * a.perp_dot(b)
+}
+
+impl From<SerializedBlendSpace> for BlendSpace {
+    fn from(serialized: SerializedBlendSpace) -> Self {
+        match serialized {
+            SerializedBlendSpace::OneDimensional(points) => Self::one_dimensional(points),
+            SerializedBlendSpace::TwoDimensional(points) => Self::two_dimensional(
+                points
+                    .into_iter()
+                    .map(|(node, position)| (node, Vec2::from(position))),
+            ),
+        }
+    }
+}
+
+impl From<BlendSpace> for SerializedBlendSpace {
+    fn from(blend_space: BlendSpace) -> Self {
+        let points = blend_space.points.into_iter();
+        if blend_space.triangles.is_none() {
+            Self::OneDimensional(points.map(|point| (point.node, point.position.x)).collect())
+        } else {
+            Self::TwoDimensional(
+                points
+                    .map(|point| (point.node, point.position.to_array()))
+                    .collect(),
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_math::Vec2;
+
+    use super::BlendSpace;
+    use crate::graph::AnimationNodeIndex;
+
+    fn node(index: usize) -> AnimationNodeIndex {
+        AnimationNodeIndex::new(index)
+    }
+
+    fn weights(blend_space: &BlendSpace, point: Vec2) -> Vec<(usize, f32)> {
+        let mut weights = vec![];
+        blend_space.weights(point, |node, weight| weights.push((node.index(), weight)));
+        weights.sort_by_key(|&(node, _)| node);
+        weights
+    }
+
+    #[test]
+    fn one_dimensional() {
+        let blend_space =
+            BlendSpace::one_dimensional([(node(3), 4.0), (node(1), 0.0), (node(2), 1.0)]);
+        assert_eq!(weights(&blend_space, Vec2::new(-1.0, 0.0)), vec![(1, 1.0)]);
+        assert_eq!(weights(&blend_space, Vec2::new(0.0, 5.0)), vec![(1, 1.0)]);
+        assert_eq!(
+            weights(&blend_space, Vec2::new(0.25, 0.0)),
+            vec![(1, 0.75), (2, 0.25)]
+        );
+        assert_eq!(
+            weights(&blend_space, Vec2::new(2.5, 0.0)),
+            vec![(2, 0.5), (3, 0.5)]
+        );
+        assert_eq!(weights(&blend_space, Vec2::new(4.0, 0.0)), vec![(3, 1.0)]);
+        assert_eq!(weights(&blend_space, Vec2::new(9.0, 0.0)), vec![(3, 1.0)]);
+    }
+
+    #[test]
+    fn two_dimensional() {
+        // A square with a center point, as for locomotion in four directions.
+        let blend_space = BlendSpace::two_dimensional([
+            (node(0), Vec2::ZERO),
+            (node(1), Vec2::new(1.0, 1.0)),
+            (node(2), Vec2::new(-1.0, 1.0)),
+            (node(3), Vec2::new(-1.0, -1.0)),
+            (node(4), Vec2::new(1.0, -1.0)),
+        ]);
+        assert_eq!(blend_space.triangles.as_ref().unwrap().len(), 4);
+
+        assert_eq!(weights(&blend_space, Vec2::ZERO), vec![(0, 1.0)]);
+        assert_eq!(weights(&blend_space, Vec2::new(1.0, 1.0)), vec![(1, 1.0)]);
+        assert_eq!(
+            weights(&blend_space, Vec2::new(0.0, 0.5)),
+            vec![(0, 0.5), (1, 0.25), (2, 0.25)]
+        );
+
+        // Points outside are clamped to the nearest edge.
+        assert_eq!(
+            weights(&blend_space, Vec2::new(0.0, 3.0)),
+            vec![(1, 0.5), (2, 0.5)]
+        );
+
+        let total: f32 = weights(&blend_space, Vec2::new(0.3, -0.6))
+            .iter()
+            .map(|&(_, weight)| weight)
+            .sum();
+        assert!((total - 1.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn cocircular_points() {
+        let blend_space = BlendSpace::two_dimensional([
+            (node(0), Vec2::new(0.0, 0.0)),
+            (node(1), Vec2::new(1.0, 0.0)),
+            (node(2), Vec2::new(1.0, 1.0)),
+            (node(3), Vec2::new(0.0, 1.0)),
+        ]);
+        assert_eq!(blend_space.triangles.as_ref().unwrap().len(), 2);
+        let total: f32 = weights(&blend_space, Vec2::new(0.2, 0.7))
+            .iter()
+            .map(|&(_, weight)| weight)
+            .sum();
+        assert!((total - 1.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn serialization() {
+        let blend_space = BlendSpace::two_dimensional([
+            (node(0), Vec2::ZERO),
+            (node(1), Vec2::X),
+            (node(2), Vec2::Y),
+        ]);
+        let serialized = ron::to_string(&blend_space).unwrap();
+        let deserialized: BlendSpace = ron::from_str(&serialized).unwrap();
+        assert_eq!(deserialized.points, blend_space.points);
+        assert_eq!(deserialized.triangles, blend_space.triangles);
+
+        let blend_space: BlendSpace =
+            ron::from_str("OneDimensional([(2, 1.0), (1, 0.0)])").unwrap();
+        assert!(blend_space.is_one_dimensional());
+        assert_eq!(blend_space.points()[0].node, node(1));
+    }
+}
@@ -0,0 +1,718 @@
+//! Compression of animation clips, which removes redundant keyframes and
+//! quantizes rotations to reduce the memory that clips take up.
+//!
+//! Animation clips imported from glTF often store a keyframe for every
+//! sampled frame, even where the animation is linear or holds still. Calling
+//! [`AnimationClip::compress`] removes the keyframes that interpolating
+//! between their neighbors reproduces within the tolerances of the
+//! [`AnimationCompressionSettings`], and stores rotations as
+//! [`Keyframes::CompressedRotation`], which take 6 bytes per keyframe instead
+//! of 16 and are sampled directly during playback.
+//!
+//! Clips can also be compressed ahead of time by the asset processor. The
+//! [`AnimationClipCompressionProcessor`] loads clips saved in the
+//! `.animclip.ron` format by [`AnimationClipSaver`], compresses them with
+//! [`AnimationCompressor`], and saves the result in the same format.
+
+use std::{
+    any::Any, borrow::Cow, convert::Infallible, f32::consts::SQRT_2, io, marker::PhantomData,
+};
+
+use bevy_asset::{
+    io::{Reader, Writer},
+    processor::LoadTransformAndSave,
+    saver::{AssetSaver, SavedAsset},
+    transformer::{AssetTransformer, TransformedAsset},
+    Asset, AssetLoader, AsyncWriteExt, LoadContext,
+};
+use bevy_math::{FloatExt, Quat, Vec3};
+use bevy_reflect::Reflect;
+use ron::de::SpannedError;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{AnimationClip, AnimationCurves, Interpolation, Keyframes, VariableCurve};
+
+/// The largest value that a quantized quaternion component can have.
+const QUANTIZED_MAX: f32 = 0x7fff as f32;
+
+/// A unit quaternion quantized with smallest-three encoding.
+///
+/// The largest component of the quaternion isn't stored; since the quaternion
+/// has unit length, it can be recomputed from the other three, which are
+/// quantized to 15 bits each. The two bits that remain store the index of the
+/// largest component. This has a precision of about 0.00005 per component.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Reflect, Serialize, Deserialize)]
+pub struct CompressedQuat([u16; 3]);
+
+/// Tolerances for [`AnimationClip::compress`].
+#[derive(Clone, Debug, Reflect, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AnimationCompressionSettings {
+    /// The largest distance that translations may deviate from the original
+    /// clip by.
+    pub translation_tolerance: f32,
+    /// The largest angle, in radians, that rotations may deviate from the
+    /// original clip by.
+    pub rotation_tolerance: f32,
+    /// The largest distance that scales may deviate from the original clip
+    /// by.
+    pub scale_tolerance: f32,
+    /// The largest amount that morph target weights may deviate from the
+    /// original clip by.
+    pub morph_weight_tolerance: f32,
+    /// Whether to store rotations as [`Keyframes::CompressedRotation`].
+    pub quantize_rotations: bool,
+}
+
+/// An [`AssetTransformer`] that compresses the [`AnimationClip`]s in an
+/// asset, such as a glTF file, with [`AnimationClip::compress`].
+///
+/// The asset itself is compressed if it's an [`AnimationClip`], as are all
+/// of its labeled [`AnimationClip`] sub-assets. See
+/// [`AnimationClipCompressionProcessor`] for a processor that uses it.
+pub struct AnimationCompressor<A> {
+    marker: PhantomData<fn() -> A>,
+}
+
+/// A processor that compresses `.animclip.ron` files with
+/// [`AnimationCompressor`].
+///
+/// [`AnimationPlugin`](crate::AnimationPlugin) registers this processor, but
+/// doesn't make it the default for any extension, since compression is
+/// lossy. Select it in the `.meta` file of the clips to compress.
+pub type AnimationClipCompressionProcessor = LoadTransformAndSave<
+    AnimationClipAssetLoader,
+    AnimationCompressor<AnimationClip>,
+    AnimationClipSaver,
+>;
+
+/// An [`AssetLoader`] that can load [`AnimationClip`]s saved by
+/// [`AnimationClipSaver`].
+///
+/// The canonical extension for these clips is `.animclip.ron`.
+#[derive(Default)]
+pub struct AnimationClipAssetLoader;
+
+/// An [`AssetSaver`] that writes [`AnimationClip`]s in RON format, to be
+/// loaded by [`AnimationClipAssetLoader`].
+///
+/// Only the [`VariableCurve`]s and the duration of a clip are saved. Clips
+/// with property curves or events can't be saved, since those are trait
+/// objects.
+#[derive(Default)]
+pub struct AnimationClipSaver;
+
+/// Various errors that can occur when saving or loading animation clips to
+/// and from RON, respectively.
+#[derive(Error, Debug)]
+pub enum AnimationClipLoadError {
+    /// An I/O error occurred.
+    #[error("I/O")]
+    Io(#[from] io::Error),
+    /// An error occurred in RON serialization or deserialization.
+    #[error("RON serialization")]
+    Ron(#[from] ron::Error),
+    /// An error occurred in RON deserialization, and the location of the error
+    /// is supplied.
+    #[error("RON serialization")]
+    SpannedRon(#[from] SpannedError),
+    /// The clip to save has property curves or events.
+    #[error("animation clips with property curves or events can't be saved")]
+    Unsupported,
+}
+
+/// The parts of an [`AnimationClip`] that [`AnimationClipSaver`] writes.
+#[derive(Serialize, Deserialize)]
+struct SavedAnimationClip<'a> {
+    curves: Cow<'a, AnimationCurves>,
+    duration: f32,
+}
+
+impl CompressedQuat {
+    /// Quantizes the given quaternion, which is normalized first.
+    pub fn from_quat(quat: Quat) -> Self {
+        let mut components = quat.normalize().to_array();
+        let largest = (0..4)
+            .max_by(|&a, &b| components[a].abs().total_cmp(&components[b].abs()))
+            .unwrap();
+
+        // `q` and `-q` are the same rotation, so make the largest component
+        // positive in order not to have to store its sign.
+        if components[largest] < 0.0 {
+            components = components.map(|component| -component);
+        }
+
+        // The other components lie within ±1/√2.
+        let mut quantized = [0; 3];
+        for (quantized, &component) in quantized.iter_mut().zip(
+            components
+                .iter()
+                .enumerate()
+                .filter(|&(index, _)| index != largest)
+                .map(|(_, component)| component),
+        ) {
+            let normalized = (component * SQRT_2).clamp(-1.0, 1.0) * 0.5 + 0.5;
+            *quantized = (normalized * QUANTIZED_MAX).round() as u16;
+        }
+        quantized[0] |= ((largest & 1) as u16) << 15;
+        quantized[1] |= ((largest >> 1) as u16) << 15;
+        Self(quantized)
+    }
+
+    /// Restores the quaternion.
+    pub fn to_quat(self) -> Quat {
+        let largest = (self.0[0] >> 15) as usize | ((self.0[1] >> 15) as usize) << 1;
+        let mut others = self
+            .0
+            .map(|quantized| ((quantized & 0x7fff) as f32 / QUANTIZED_MAX * 2.0 - 1.0) / SQRT_2);
+        let largest_component = (1.0 - others.iter().map(|c| c * c).sum::<f32>())
+            .max(0.0)
+            .sqrt();
+
+        let mut components = [0.0; 4];
+        let mut others = others.iter_mut();
+        for (index, component) in components.iter_mut().enumerate() {
+            *component = if index == largest {
+                largest_component
+            } else {
+                *others.next().unwrap()
+            };
+        }
+        Quat::from_array(components).normalize()
+    }
+}
+
+impl From<Quat> for CompressedQuat {
+    fn from(quat: Quat) -> Self {
+        Self::from_quat(quat)
+    }
+}
+
+impl From<CompressedQuat> for Quat {
+    fn from(quat: CompressedQuat) -> Self {
+        quat.to_quat()
+    }
+}
+
+impl Default for AnimationCompressionSettings {
+    fn default() -> Self {
+        Self {
+            translation_tolerance: 0.0001,
+            rotation_tolerance: 0.001,
+            scale_tolerance: 0.0001,
+            morph_weight_tolerance: 0.001,
+            quantize_rotations: true,
+        }
+    }
+}
+
+impl<A> Default for AnimationCompressor<A> {
+    fn default() -> Self {
+        Self {
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<A: Asset> AssetTransformer for AnimationCompressor<A> {
+    type AssetInput = A;
+    type AssetOutput = A;
+    type Settings = AnimationCompressionSettings;
+    type Error = Infallible;
+
+    async fn transform<'a>(
+        &'a self,
+        mut asset: TransformedAsset<A>,
+        settings: &'a Self::Settings,
+    ) -> Result<TransformedAsset<A>, Infallible> {
+        if let Some(clip) = (asset.get_mut() as &mut dyn Any).downcast_mut::<AnimationClip>() {
+            clip.compress(settings);
+        }
+
+        let labels: Vec<String> = asset.iter_labels().map(ToOwned::to_owned).collect();
+        for label in labels {
+            if let Some(mut clip) = asset.get_labeled::<AnimationClip, str>(&label) {
+                clip.get_mut().compress(settings);
+            }
+        }
+        Ok(asset)
+    }
+}
+
+impl AssetLoader for AnimationClipAssetLoader {
+    type Asset = AnimationClip;
+
+    type Settings = ();
+
+    type Error = AnimationClipLoadError;
+
+    async fn load<'a>(
+        &'a self,
+        reader: &'a mut dyn Reader,
+        _: &'a Self::Settings,
+        _: &'a mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+
+        let mut deserializer = ron::de::Deserializer::from_bytes(&bytes)?;
+        let saved_clip = SavedAnimationClip::deserialize(&mut deserializer)
+            .map_err(|err| deserializer.span_error(err))?;
+        Ok(AnimationClip {
+            curves: saved_clip.curves.into_owned(),
+            duration: saved_clip.duration,
+            ..AnimationClip::default()
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["animclip.ron"]
+    }
+}
+
+impl AssetSaver for AnimationClipSaver {
+    type Asset = AnimationClip;
+    type Settings = ();
+    type OutputLoader = AnimationClipAssetLoader;
+    type Error = AnimationClipLoadError;
+
+    async fn save<'a>(
+        &'a self,
+        writer: &'a mut Writer,
+        asset: SavedAsset<'a, Self::Asset>,
+        _settings: &'a Self::Settings,
+    ) -> Result<(), Self::Error> {
+        let clip = asset.get();
+        if !clip.property_curves.is_empty() || !clip.events.is_empty() {
+            return Err(AnimationClipLoadError::Unsupported);
+        }
+
+        let saved_clip = SavedAnimationClip {
+            curves: Cow::Borrowed(&clip.curves),
+            duration: clip.duration,
+        };
+        let bytes = ron::ser::to_string(&saved_clip)?.into_bytes();
+        writer.write_all(&bytes).await?;
+        Ok(())
+    }
+}
+
+impl AnimationClip {
+    /// Compresses all the curves of this clip with
+    /// [`VariableCurve::compress`].
+    pub fn compress(&mut self, settings: &AnimationCompressionSettings) {
+        for curves in self.curves.values_mut() {
+            for curve in curves {
+                curve.compress(settings);
+            }
+        }
+    }
+}
+
+impl VariableCurve {
+    /// Removes the keyframes of this curve that interpolating between the
+    /// remaining keyframes reproduces within the tolerances of `settings`,
+    /// and quantizes its rotations if requested.
+    ///
+    /// Curves with [`Interpolation::CubicSpline`] aren't reduced or
+    /// quantized, since their tangents would have to be refitted.
+    pub fn compress(&mut self, settings: &AnimationCompressionSettings) {
+        if matches!(self.interpolation, Interpolation::CubicSpline) {
+            return;
+        }
+
+        self.reduce_keyframes(settings);
+
+        if settings.quantize_rotations {
+            if let Keyframes::Rotation(ref rotations) = self.keyframes {
+                self.keyframes = Keyframes::CompressedRotation(
+                    rotations
+                        .iter()
+                        .copied()
+                        .map(CompressedQuat::from)
+                        .collect(),
+                );
+            }
+        }
+    }
+
+    fn reduce_keyframes(&mut self, settings: &AnimationCompressionSettings) {
+        let timestamps = &self.keyframe_timestamps;
+        let step = matches!(self.interpolation, Interpolation::Step);
+
+        // The fraction of the way from keyframe `start` to keyframe `end` that
+        // keyframe `index` is at, which is always 0 for step interpolation.
+        let fraction = |start: usize, end: usize, index: usize| {
+            if step {
+                0.0
+            } else {
+                f32::inverse_lerp(timestamps[start], timestamps[end], timestamps[index])
+            }
+        };
+
+        let kept = match self.keyframes {
+            Keyframes::Rotation(ref keyframes) => kept_keyframes(timestamps.len(), |s, e, i| {
+                let rotation = keyframes[s].slerp(keyframes[e], fraction(s, e, i));
+                rotation.angle_between(keyframes[i]) <= settings.rotation_tolerance
+            }),
+            Keyframes::CompressedRotation(ref keyframes) => {
+                kept_keyframes(timestamps.len(), |s, e, i| {
+                    let rotation = keyframes[s]
+                        .to_quat()
+                        .slerp(keyframes[e].to_quat(), fraction(s, e, i));
+                    rotation.angle_between(keyframes[i].to_quat()) <= settings.rotation_tolerance
+                })
+            }
+            Keyframes::Translation(ref keyframes) => kept_keyframes(timestamps.len(), |s, e, i| {
+                within(
+                    keyframes,
+                    s,
+                    e,
+                    i,
+                    fraction(s, e, i),
+                    settings.translation_tolerance,
+                )
+            }),
+            Keyframes::Scale(ref keyframes) => kept_keyframes(timestamps.len(), |s, e, i| {
+                within(
+                    keyframes,
+                    s,
+                    e,
+                    i,
+                    fraction(s, e, i),
+                    settings.scale_tolerance,
+                )
+            }),
+            Keyframes::Weights(ref keyframes) => {
+                let Some(target_count) = keyframes.len().checked_div(timestamps.len()) else {
+                    return;
+                };
+                let keyframe =
+                    |index: usize| &keyframes[(index * target_count)..((index + 1) * target_count)];
+                kept_keyframes(timestamps.len(), |s, e, i| {
+                    let t = fraction(s, e, i);
+                    keyframe(s).iter().zip(keyframe(e)).zip(keyframe(i)).all(
+                        |((start, end), original)| {
+                            (start.lerp(*end, t) - original).abs()
+                                <= settings.morph_weight_tolerance
+                        },
+                    )
+                })
+            }
+        };
+
+        let count = timestamps.len();
+        if kept.len() == count {
+            return;
+        }
+
+        self.keyframe_timestamps = kept
+            .iter()
+            .map(|&index| self.keyframe_timestamps[index])
+            .collect();
+        match self.keyframes {
+            Keyframes::Rotation(ref mut keyframes) => retain_keyframes(keyframes, &kept, 1),
+            Keyframes::CompressedRotation(ref mut keyframes) => {
+                retain_keyframes(keyframes, &kept, 1);
+            }
+            Keyframes::Translation(ref mut keyframes) | Keyframes::Scale(ref mut keyframes) => {
+                retain_keyframes(keyframes, &kept, 1);
+            }
+            Keyframes::Weights(ref mut keyframes) => {
+                let target_count = keyframes.len() / count;
+                retain_keyframes(keyframes, &kept, target_count);
+            }
+        }
+    }
+}
+
+/// Returns true if interpolating between the keyframes at `start` and `end`
+/// by `t` is within `tolerance` of the keyframe at `index`.
+fn within(
+    keyframes: &[Vec3],
+    start: usize,
+    end: usize,
+    index: usize,
+    t: f32,
+    tolerance: f32,
+) -> bool {
+    keyframes[start]
+        .lerp(keyframes[end], t)
+        .distance(keyframes[index])
+        <= tolerance
+}
+
+/// Returns the indices of the keyframes to keep out of `count`, given a
+/// function that checks whether interpolating from the keyframe `start` to
+/// the keyframe `end` reproduces the keyframe `index` between them.
+fn kept_keyframes(
+    count: usize,
+    mut reproduces: impl FnMut(usize, usize, usize) -> bool,
+) -> Vec<usize> {
+    if count <= 2 {
+        return (0..count).collect();
+    }
+
+    // Extend each segment for as long as interpolating over it reproduces all
+    // the keyframes inside it.
+    let mut kept = vec![0];
+    let (mut start, mut end) = (0, 2);
+    while end < count {
+        if ((start + 1)..end).all(|index| reproduces(start, end, index)) {
+            end += 1;
+        } else {
+            start = end - 1;
+            kept.push(start);
+            end = start + 2;
+        }
+    }
+    kept.push(count - 1);
+    kept
+}
+
+/// Keeps only the keyframes at the `kept` indices, where each keyframe is
+/// made of `stride` values.
+fn retain_keyframes<T: Copy>(keyframes: &mut Vec<T>, kept: &[usize], stride: usize) {
+    *keyframes = kept
+        .iter()
+        .flat_map(|&index| {
+            keyframes[(index * stride)..((index + 1) * stride)]
+                .iter()
+                .copied()
+        })
+        .collect();
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use bevy_app::App;
+    use bevy_asset::{
+        io::{
+            memory::{Dir, MemoryAssetReader},
+            AssetSource, AssetSourceId,
+        },
+        saver::{AssetSaver, SavedAsset},
+        transformer::{AssetTransformer, TransformedAsset},
+        AssetApp, AssetPlugin, AssetServer, Assets, Handle, LoadedAsset,
+    };
+    use bevy_core::{Name, TaskPoolPlugin};
+    use bevy_ecs::{system::RunSystemOnce, world::World};
+    use bevy_math::{Quat, Vec3};
+    use bevy_tasks::block_on;
+    use bevy_transform::components::Transform;
+
+    use super::{
+        AnimationClipAssetLoader, AnimationClipSaver, AnimationCompressionSettings,
+        AnimationCompressor, CompressedQuat,
+    };
+    use crate::{
+        animate_targets, graph::AnimationGraph, AnimationClip, AnimationPlayer, AnimationTarget,
+        AnimationTargetId, Interpolation, Keyframes, VariableCurve,
+    };
+
+    #[test]
+    fn quantize_rotations() {
+        for rotation in [
+            Quat::IDENTITY,
+            Quat::from_rotation_x(2.0),
+            Quat::from_euler(bevy_math::EulerRot::YXZ, 0.3, -1.2, 2.9),
+            -Quat::from_rotation_z(0.1),
+            Quat::from_xyzw(0.5, -0.5, 0.5, -0.5),
+        ] {
+            let restored = CompressedQuat::from_quat(rotation).to_quat();
+            assert!(restored.angle_between(rotation) < 0.001, "{rotation}");
+        }
+    }
+
+    #[test]
+    fn reduce_keyframes() {
+        // A straight line with a hold at the end.
+        let timestamps: Vec<f32> = (0..=20).map(|frame| frame as f32 * 0.1).collect();
+        let mut curve = VariableCurve {
+            keyframes: Keyframes::Translation(
+                timestamps
+                    .iter()
+                    .map(|&time| Vec3::X * time.min(1.0))
+                    .collect(),
+            ),
+            keyframe_timestamps: timestamps.clone(),
+            interpolation: Interpolation::Linear,
+        };
+        curve.compress(&AnimationCompressionSettings::default());
+        assert_eq!(curve.keyframe_timestamps, vec![0.0, 1.0, 2.0]);
+        assert_eq!(curve.keyframes.len(), 3);
+
+        let mut curve = VariableCurve {
+            keyframes: Keyframes::Weights(
+                timestamps
+                    .iter()
+                    .flat_map(|&time| [1.0, if time < 0.55 { 0.0 } else { 1.0 }])
+                    .collect(),
+            ),
+            keyframe_timestamps: timestamps,
+            interpolation: Interpolation::Step,
+        };
+        curve.compress(&AnimationCompressionSettings::default());
+        assert_eq!(curve.keyframe_timestamps.len(), 3);
+        let Keyframes::Weights(ref weights) = curve.keyframes else {
+            panic!("expected weights");
+        };
+        assert_eq!(weights, &[1.0, 0.0, 1.0, 1.0, 1.0, 1.0]);
+    }
+
+    /// Plays `clip` on a bone with the given target ID and returns the
+    /// bone's rotation at `time`.
+    fn sample_rotation(clip: AnimationClip, target_id: AnimationTargetId, time: f32) -> Quat {
+        let mut world = World::new();
+        let mut clips = Assets::<AnimationClip>::default();
+        let (graph, node_index) = AnimationGraph::from_clip(clips.add(clip));
+        let mut graphs = Assets::<AnimationGraph>::default();
+        let graph = graphs.add(graph);
+        world.insert_resource(clips);
+        world.insert_resource(graphs);
+
+        let mut player = AnimationPlayer::default();
+        player.play(node_index).seek_to(time);
+        let player = world.spawn((player, graph)).id();
+        let bone = world
+            .spawn((
+                Transform::default(),
+                AnimationTarget {
+                    id: target_id,
+                    player,
+                },
+            ))
+            .id();
+
+        world.run_system_once(animate_targets);
+        world.get::<Transform>(bone).unwrap().rotation
+    }
+
+    #[test]
+    fn play_compressed_clip() {
+        let target_id = AnimationTargetId::from_name(&Name::new("bone"));
+        let mut clip = AnimationClip::default();
+        clip.add_curve_to_target(
+            target_id,
+            VariableCurve {
+                keyframe_timestamps: (0..=10).map(|frame| frame as f32 * 0.1).collect(),
+                keyframes: Keyframes::Rotation(
+                    (0..=10)
+                        .map(|frame| Quat::from_rotation_y(frame as f32 * 0.1))
+                        .collect(),
+                ),
+                interpolation: Interpolation::Linear,
+            },
+        );
+        clip.compress(&AnimationCompressionSettings::default());
+        let curves = clip.curves_for_target(target_id).unwrap();
+        assert_eq!(curves[0].keyframe_timestamps.len(), 2);
+        assert!(matches!(
+            curves[0].keyframes,
+            Keyframes::CompressedRotation(_)
+        ));
+
+        let rotation = sample_rotation(clip, target_id, 0.25);
+        assert!(rotation.angle_between(Quat::from_rotation_y(0.25)) < 0.001);
+    }
+
+    #[test]
+    fn process_clip() {
+        let target_id = AnimationTargetId::from_name(&Name::new("bone"));
+        let mut clip = AnimationClip::default();
+        clip.add_curve_to_target(
+            target_id,
+            VariableCurve {
+                keyframe_timestamps: (0..=10).map(|frame| frame as f32 * 0.1).collect(),
+                keyframes: Keyframes::Rotation(
+                    (0..=10)
+                        .map(|frame| Quat::from_rotation_y(frame as f32 * 0.1))
+                        .collect(),
+                ),
+                interpolation: Interpolation::Linear,
+            },
+        );
+
+        // Compress and save the clip like `AnimationClipCompressionProcessor`.
+        let asset = TransformedAsset::from_loaded(LoadedAsset::from(clip).into()).unwrap();
+        let asset = block_on(
+            AnimationCompressor::default()
+                .transform(asset, &AnimationCompressionSettings::default()),
+        )
+        .unwrap();
+        let mut bytes = Vec::new();
+        block_on(AnimationClipSaver.save(&mut bytes, SavedAsset::from_transformed(&asset), &()))
+            .unwrap();
+
+        let dir = Dir::default();
+        dir.insert_asset(Path::new("clip.animclip.ron"), bytes);
+        let mut app = App::new();
+        app.register_asset_source(
+            AssetSourceId::Default,
+            AssetSource::build()
+                .with_reader(move || Box::new(MemoryAssetReader { root: dir.clone() })),
+        )
+        .add_plugins((TaskPoolPlugin::default(), AssetPlugin::default()))
+        .init_asset::<AnimationClip>()
+        .init_asset_loader::<AnimationClipAssetLoader>();
+
+        let handle: Handle<AnimationClip> = app
+            .world()
+            .resource::<AssetServer>()
+            .load("clip.animclip.ron");
+        for _ in 0..10000 {
+            app.update();
+            if app
+                .world()
+                .resource::<Assets<AnimationClip>>()
+                .contains(&handle)
+            {
+                break;
+            }
+        }
+
+        let clips = app.world().resource::<Assets<AnimationClip>>();
+        let clip = clips.get(&handle).expect("the clip should load");
+        let curves = clip.curves_for_target(target_id).unwrap();
+        assert_eq!(curves[0].keyframe_timestamps, vec![0.0, 1.0]);
+        let Keyframes::CompressedRotation(ref rotations) = curves[0].keyframes else {
+            panic!("expected compressed rotations");
+        };
+        assert!(
+            rotations[1]
+                .to_quat()
+                .angle_between(Quat::from_rotation_y(1.0))
+                < 0.001
+        );
+    }
+
+    #[test]
+    fn play_compressed_cubic_spline_clip() {
+        // The tangents of cubic spline keyframes are ignored, so they must not
+        // be mistaken for values.
+        let tangent = CompressedQuat::from(Quat::from_rotation_x(1.0));
+        let target_id = AnimationTargetId::from_name(&Name::new("bone"));
+        let mut clip = AnimationClip::default();
+        clip.add_curve_to_target(
+            target_id,
+            VariableCurve {
+                keyframe_timestamps: vec![0.0, 1.0],
+                keyframes: Keyframes::CompressedRotation(vec![
+                    tangent,
+                    CompressedQuat::from(Quat::IDENTITY),
+                    tangent,
+                    tangent,
+                    CompressedQuat::from(Quat::from_rotation_y(1.0)),
+                    tangent,
+                ]),
+                interpolation: Interpolation::CubicSpline,
+            },
+        );
+
+        let rotation = sample_rotation(clip, target_id, 0.25);
+        assert!(rotation.angle_between(Quat::from_rotation_y(0.25)) < 0.001);
+    }
+}
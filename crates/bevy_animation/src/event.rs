@@ -0,0 +1,225 @@
+//! Events that animation clips fire at specific times.
+//!
+//! See [`AnimationClip::add_event`] for more information.
+//!
+//! [`AnimationClip::add_event`]: crate::AnimationClip::add_event
+
+use std::fmt::{self, Debug, Formatter};
+
+use bevy_ecs::{entity::Entity, event::Event, system::Commands};
+use bevy_reflect::{reflect_trait, Reflect};
+use bevy_utils::hashbrown::HashMap;
+
+use crate::{ActiveAnimation, AnimationTargetId};
+
+/// An event that an [`AnimationClip`] can fire at a specific time.
+///
+/// Animation events are triggered as [observer] events, targeting either the
+/// entity with the [`AnimationPlayer`] or an [`AnimationTarget`] entity,
+/// depending on how they were added to the clip.
+///
+/// This trait is implemented for every [`Event`] that implements [`Reflect`]
+/// and [`Clone`]. Registering `#[reflect(AnimationEvent)]` on such an event
+/// allows clips containing it to be deserialized.
+///
+/// ```
+/// # use bevy_animation::{AnimationClip, event::ReflectAnimationEvent};
+/// # use bevy_ecs::event::Event;
+/// # use bevy_reflect::Reflect;
+/// #[derive(Event, Reflect, Clone)]
+/// #[reflect(AnimationEvent)]
+/// struct Footstep;
+///
+/// let mut clip = AnimationClip::default();
+/// clip.add_event(0.42, Footstep);
+/// ```
+///
+/// [`AnimationClip`]: crate::AnimationClip
+/// [observer]: bevy_ecs::observer::Observer
+/// [`AnimationPlayer`]: crate::AnimationPlayer
+/// [`AnimationTarget`]: crate::AnimationTarget
+#[reflect_trait(trait_object)]
+pub trait AnimationEvent: Reflect {
+    /// Triggers this event, targeting the given entity.
+    fn trigger(&self, commands: &mut Commands, entity: Entity);
+
+    /// Clones this event into a new box.
+    fn clone_event(&self) -> Box<dyn AnimationEvent>;
+}
+
+impl<E: Event + Reflect + Clone> AnimationEvent for E {
+    fn trigger(&self, commands: &mut Commands, entity: Entity) {
+        commands.trigger_targets(self.clone(), entity);
+    }
+
+    fn clone_event(&self) -> Box<dyn AnimationEvent> {
+        Box::new(self.clone())
+    }
+}
+
+/// An [`AnimationEvent`] along with the time within the clip at which it fires.
+#[derive(Reflect)]
+pub struct TimedAnimationEvent {
+    /// The time of the event, in seconds from the start of the clip.
+    pub time: f32,
+    /// The event to trigger.
+    pub event: Box<dyn AnimationEvent>,
+}
+
+impl Clone for TimedAnimationEvent {
+    fn clone(&self) -> Self {
+        Self {
+            time: self.time,
+            event: self.event.clone_event(),
+        }
+    }
+}
+
+impl Debug for TimedAnimationEvent {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TimedAnimationEvent")
+            .field("time", &self.time)
+            .field("event", &self.event.as_reflect())
+            .finish()
+    }
+}
+
+/// The entity that an [`AnimationEvent`] is triggered on.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Reflect)]
+#[reflect(Hash, PartialEq)]
+pub enum AnimationEventTarget {
+    /// The entity with the [`AnimationPlayer`](crate::AnimationPlayer) playing the clip.
+    Root,
+    /// The [`AnimationTarget`](crate::AnimationTarget) entity with the given ID.
+    Node(AnimationTargetId),
+}
+
+/// A mapping from [`AnimationEventTarget`] to the events fired on it, sorted by
+/// time.
+pub type AnimationEvents = HashMap<AnimationEventTarget, Vec<TimedAnimationEvent>>;
+
+impl ActiveAnimation {
+    /// Calls `f` for each of the `events` that this animation passed over
+    /// during the last frame, in playback order.
+    ///
+    /// An event is passed over when its time lies between the seek time of
+    /// the previous frame (inclusive) and the current seek time (exclusive),
+    /// taking the playback direction and wrapping around the end of looping
+    /// clips into account. `events` must be sorted by time.
+    pub(crate) fn for_each_crossed_event<'a>(
+        &self,
+        events: &'a [TimedAnimationEvent],
+        f: impl FnMut(&'a TimedAnimationEvent),
+    ) {
+        let Some(last_seek_time) = self.last_seek_time else {
+            return;
+        };
+        for_each_crossed_event(
+            events,
+            last_seek_time,
+            self.seek_time,
+            self.is_playback_reversed(),
+            f,
+        );
+    }
+}
+
+/// Calls `f` for each of the `events` between the times `from` and `to`.
+///
+/// If playback isn't `reverse` and `to` is before `from`, or playback is
+/// `reverse` and `to` is after `from`, playback is assumed to have wrapped
+/// around the clip once.
+fn for_each_crossed_event<'a>(
+    events: &'a [TimedAnimationEvent],
+    from: f32,
+    to: f32,
+    reverse: bool,
+    mut f: impl FnMut(&'a TimedAnimationEvent),
+) {
+    // Index of the first event at or after `time`.
+    let at_or_after = |time: f32| events.partition_point(|event| event.time < time);
+    // Index of the first event strictly after `time`.
+    let after = |time: f32| events.partition_point(|event| event.time <= time);
+
+    let wrapped = if reverse { to > from } else { to < from };
+    let (first, second) = match (reverse, wrapped) {
+        // [from, to)
+        (false, false) => (at_or_after(from)..at_or_after(to), 0..0),
+        // [from, end], then [start, to)
+        (false, true) => (at_or_after(from)..events.len(), 0..at_or_after(to)),
+        // (to, from], backwards
+        (true, false) => (after(to)..after(from), 0..0),
+        // [start, from], then (to, end], backwards
+        (true, true) => (0..after(from), after(to)..events.len()),
+    };
+
+    if reverse {
+        events[first].iter().rev().for_each(&mut f);
+        events[second].iter().rev().for_each(&mut f);
+    } else {
+        events[first].iter().for_each(&mut f);
+        events[second].iter().for_each(&mut f);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::event::Event;
+    use bevy_reflect::Reflect;
+
+    use super::{for_each_crossed_event, TimedAnimationEvent};
+
+    #[derive(Event, Reflect, Clone)]
+    struct Marker;
+
+    fn events(times: &[f32]) -> Vec<TimedAnimationEvent> {
+        times
+            .iter()
+            .map(|&time| TimedAnimationEvent {
+                time,
+                event: Box::new(Marker),
+            })
+            .collect()
+    }
+
+    fn crossed(times: &[f32], from: f32, to: f32, reverse: bool) -> Vec<f32> {
+        let events = events(times);
+        let mut crossed = vec![];
+        for_each_crossed_event(&events, from, to, reverse, |event| crossed.push(event.time));
+        crossed
+    }
+
+    #[test]
+    fn forward() {
+        let times = [0.0, 0.25, 0.5, 0.75, 1.0];
+        assert_eq!(crossed(&times, 0.0, 0.1, false), vec![0.0]);
+        assert_eq!(crossed(&times, 0.1, 0.25, false), Vec::<f32>::new());
+        assert_eq!(crossed(&times, 0.25, 0.8, false), vec![0.25, 0.5, 0.75]);
+        assert_eq!(crossed(&times, 0.8, 0.8, false), Vec::<f32>::new());
+        // Finishing past the end of the clip.
+        assert_eq!(crossed(&times, 0.8, 1.1, false), vec![1.0]);
+    }
+
+    #[test]
+    fn forward_wrapping() {
+        let times = [0.0, 0.25, 0.5, 0.75, 1.0];
+        assert_eq!(crossed(&times, 0.8, 0.3, false), vec![1.0, 0.0, 0.25]);
+        assert_eq!(crossed(&times, 0.5, 0.0, false), vec![0.5, 0.75, 1.0]);
+    }
+
+    #[test]
+    fn reverse() {
+        let times = [0.0, 0.25, 0.5, 0.75, 1.0];
+        assert_eq!(crossed(&times, 1.0, 0.9, true), vec![1.0]);
+        assert_eq!(crossed(&times, 0.9, 0.2, true), vec![0.75, 0.5, 0.25]);
+        assert_eq!(crossed(&times, 0.25, 0.25, true), Vec::<f32>::new());
+        // Finishing past the start of the clip.
+        assert_eq!(crossed(&times, 0.1, -0.1, true), vec![0.0]);
+    }
+
+    #[test]
+    fn reverse_wrapping() {
+        let times = [0.0, 0.25, 0.5, 0.75, 1.0];
+        assert_eq!(crossed(&times, 0.3, 0.8, true), vec![0.25, 0.0, 1.0]);
+    }
+}
@@ -4,6 +4,7 @@ use std::io::{self, Write};
 use std::ops::{Index, IndexMut};
 
 use bevy_asset::{io::Reader, Asset, AssetId, AssetLoader, AssetPath, Handle, LoadContext};
+use bevy_math::Vec2;
 use bevy_reflect::{Reflect, ReflectSerialize};
 use bevy_utils::HashMap;
 use petgraph::graph::{DiGraph, NodeIndex};
@@ -11,7 +12,7 @@ use ron::de::SpannedError;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-use crate::{AnimationClip, AnimationTargetId};
+use crate::{blend_space::BlendSpace, AnimationClip, AnimationTargetId};
 
 /// A graph structure that describes how animation clips are to be blended
 /// together.
@@ -57,6 +58,21 @@ use crate::{AnimationClip, AnimationTargetId};
 /// their weights will be halved and finally blended with the Idle animation.
 /// Thus the weight of Run and Walk are effectively half of the weight of Idle.
 ///
+/// Blend nodes can also hold a [`BlendSpace`], which places their children at
+/// points in a one- or two-dimensional parameter space, such as speed and
+/// direction. The children's weights are then further multiplied by weights
+/// derived from the point set with
+/// [`crate::AnimationPlayer::set_blend_space_point`].
+///
+/// Nodes can also be *additive*. An additive clip doesn't replace the pose
+/// produced by the other clips; instead, the difference between each of its
+/// keyframes and its first keyframe, the reference pose, is added on top of
+/// it, scaled by the weight of the clip. This is useful for aim offsets and
+/// hit reactions. Additive nodes make all of their descendants additive.
+/// Only the [`crate::VariableCurve`]s of additive clips are applied; their
+/// [`crate::animation_curves::AnimationCurve`]s, which animate reflected
+/// properties, are ignored, and a warning is logged.
+///
 /// Nodes can optionally have a *mask*, a bitfield that restricts the set of
 /// animation targets that the node and its descendants affect. Each bit in the
 /// mask corresponds to a *mask group*, which is a set of animation targets
@@ -147,6 +163,20 @@ pub struct AnimationGraphNode {
     /// has weight 0.3 and its parent blend node has weight 0.6, the computed
     /// weight of the animation clip is 0.18.
     pub weight: f32,
+
+    /// Whether this node and its descendants are blended additively, adding
+    /// the difference between their keyframes and their first keyframes on
+    /// top of the pose produced by the other nodes.
+    ///
+    /// Additive blending only affects [`crate::VariableCurve`]s; the
+    /// [`crate::animation_curves::AnimationCurve`]s of additive clips are
+    /// ignored.
+    pub additive: bool,
+
+    /// The blend space that weights the children of this node, if any.
+    ///
+    /// Children not in the blend space get a weight of zero.
+    pub blend_space: Option<BlendSpace>,
 }
 
 /// An [`AssetLoader`] that can load [`AnimationGraph`]s as assets.
@@ -202,6 +232,12 @@ pub struct SerializedAnimationGraphNode {
     pub mask: AnimationMask,
     /// Corresponds to the `weight` field on [`AnimationGraphNode`].
     pub weight: f32,
+    /// Corresponds to the `additive` field on [`AnimationGraphNode`].
+    #[serde(default)]
+    pub additive: bool,
+    /// Corresponds to the `blend_space` field on [`AnimationGraphNode`].
+    #[serde(default)]
+    pub blend_space: Option<BlendSpace>,
 }
 
 /// A version of `Handle<AnimationClip>` suitable for serializing as an asset.
@@ -282,6 +318,7 @@ impl AnimationGraph {
             clip: Some(clip),
             mask: 0,
             weight,
+            ..Default::default()
         });
         self.graph.add_edge(parent, node_index, ());
         node_index
@@ -302,6 +339,7 @@ impl AnimationGraph {
             clip: Some(clip),
             mask,
             weight,
+            ..Default::default()
         });
         self.graph.add_edge(parent, node_index, ());
         node_index
@@ -341,6 +379,7 @@ impl AnimationGraph {
             clip: None,
             mask: 0,
             weight,
+            ..Default::default()
         });
         self.graph.add_edge(parent, node_index, ());
         node_index
@@ -364,11 +403,75 @@ impl AnimationGraph {
             clip: None,
             mask,
             weight,
+            ..Default::default()
         });
         self.graph.add_edge(parent, node_index, ());
         node_index
     }
 
+    /// Adds an additive [`AnimationClip`] to the animation graph with the
+    /// given weight and returns its index.
+    ///
+    /// Rather than being blended with the other clips, the difference between
+    /// each keyframe of this clip and its first keyframe is added to the pose
+    /// that the other clips produce, scaled by the weight. The animation clip
+    /// will be the child of the given parent.
+    ///
+    /// The clip's [`crate::animation_curves::AnimationCurve`]s, which animate
+    /// reflected properties, don't support additive blending and are ignored.
+    pub fn add_additive_clip(
+        &mut self,
+        clip: Handle<AnimationClip>,
+        weight: f32,
+        parent: AnimationNodeIndex,
+    ) -> AnimationNodeIndex {
+        let node_index = self.add_clip(clip, weight, parent);
+        self[node_index].additive = true;
+        node_index
+    }
+
+    /// Adds a blend node with a one-dimensional [`BlendSpace`] to the
+    /// animation graph, with a child clip node for each of the given clips at
+    /// the given positions, and returns the index of the blend node.
+    ///
+    /// The indices of the clip nodes, which must be played for the blend space
+    /// to have an effect, are in the [`BlendSpace::points`].
+    pub fn add_blend_space_1d(
+        &mut self,
+        clips: impl IntoIterator<Item = (Handle<AnimationClip>, f32)>,
+        weight: f32,
+        parent: AnimationNodeIndex,
+    ) -> AnimationNodeIndex {
+        let node_index = self.add_blend(weight, parent);
+        let points: Vec<_> = clips
+            .into_iter()
+            .map(|(clip, x)| (self.add_clip(clip, 1.0, node_index), x))
+            .collect();
+        self[node_index].blend_space = Some(BlendSpace::one_dimensional(points));
+        node_index
+    }
+
+    /// Adds a blend node with a two-dimensional [`BlendSpace`] to the
+    /// animation graph, with a child clip node for each of the given clips at
+    /// the given positions, and returns the index of the blend node.
+    ///
+    /// The indices of the clip nodes, which must be played for the blend space
+    /// to have an effect, are in the [`BlendSpace::points`].
+    pub fn add_blend_space_2d(
+        &mut self,
+        clips: impl IntoIterator<Item = (Handle<AnimationClip>, Vec2)>,
+        weight: f32,
+        parent: AnimationNodeIndex,
+    ) -> AnimationNodeIndex {
+        let node_index = self.add_blend(weight, parent);
+        let points: Vec<_> = clips
+            .into_iter()
+            .map(|(clip, position)| (self.add_clip(clip, 1.0, node_index), position))
+            .collect();
+        self[node_index].blend_space = Some(BlendSpace::two_dimensional(points));
+        node_index
+    }
+
     /// Adds an edge from the edge `from` to `to`, making `to` a child of
     /// `from`.
     ///
@@ -491,6 +594,8 @@ impl Default for AnimationGraphNode {
             clip: None,
             mask: 0,
             weight: 1.0,
+            additive: false,
+            blend_space: None,
         }
     }
 }
@@ -536,6 +641,8 @@ impl AssetLoader for AnimationGraphAssetLoader {
                     }),
                     mask: serialized_node.mask,
                     weight: serialized_node.weight,
+                    additive: serialized_node.additive,
+                    blend_space: serialized_node.blend_space.clone(),
                 },
                 |_, _| (),
             ),
@@ -559,6 +666,8 @@ impl From<AnimationGraph> for SerializedAnimationGraph {
                 |_, node| SerializedAnimationGraphNode {
                     weight: node.weight,
                     mask: node.mask,
+                    additive: node.additive,
+                    blend_space: node.blend_space.clone(),
                     clip: node.clip.as_ref().map(|clip| match clip.path() {
                         Some(path) => SerializedAnimationClip::AssetPath(path.clone()),
                         None => SerializedAnimationClip::AssetId(clip.id()),
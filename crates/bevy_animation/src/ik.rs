@@ -0,0 +1,671 @@
+//! Inverse kinematics, which rotates chains of joints so that they reach
+//! toward targets, such as feet planted on uneven terrain or hands gripping a
+//! weapon.
+//!
+//! Inverse kinematics is applied to the [`Transform`]s of joints after
+//! [`crate::animate_targets`] has posed them, so each solver has a weight
+//! that blends the solved pose with the animated one. The following solvers
+//! are available, each as a component that's placed on the last joint of the
+//! chain it affects:
+//!
+//! * [`TwoBoneIk`] solves chains of exactly two bones, such as arms and legs,
+//!   analytically, with an optional pole that controls the direction the
+//!   middle joint bends in.
+//!
+//! * [`IkChain`] solves chains of any length iteratively, with either the
+//!   [FABRIK] or the [CCD] algorithm.
+//!
+//! * [`LookAt`] rotates a single joint so that it faces a target, such as a
+//!   head following a point of interest.
+//!
+//! The solvers themselves are also available as functions that operate on
+//! joint positions: [`solve_two_bone`], [`solve_fabrik`], and [`solve_ccd`].
+//!
+//! [FABRIK]: http://andreasaristidou.com/FABRIK.html
+//! [CCD]: https://en.wikipedia.org/wiki/Inverse_kinematics#Heuristic_methods
+
+use bevy_ecs::{
+    entity::{Entity, EntityMapper, MapEntities},
+    prelude::Component,
+    reflect::{ReflectComponent, ReflectMapEntities},
+    system::{Local, Query},
+};
+use bevy_hierarchy::Parent;
+use bevy_math::{Quat, Vec3};
+use bevy_reflect::Reflect;
+use bevy_transform::components::{GlobalTransform, Transform};
+
+/// The point that an inverse kinematics solver reaches toward.
+#[derive(Clone, Copy, Debug, PartialEq, Reflect)]
+pub enum IkTarget {
+    /// A fixed position in world space.
+    Position(Vec3),
+    /// The position of an entity.
+    ///
+    /// The position is computed from the [`Transform`]s of the entity and its
+    /// ancestors, so it takes this frame's animation into account.
+    Entity(Entity),
+}
+
+/// Solves a chain of two bones, such as an arm or a leg, so that its end
+/// reaches the target.
+///
+/// Place this component on the joint at the end of the chain, such as a hand
+/// or a foot. Its parent and grandparent are rotated; the joint itself isn't.
+#[derive(Clone, Copy, Debug, Component, Reflect)]
+#[reflect(Component, MapEntities)]
+pub struct TwoBoneIk {
+    /// The point that the end of the chain reaches toward.
+    pub target: IkTarget,
+    /// The point that the middle joint, such as an elbow or a knee, bends
+    /// toward.
+    ///
+    /// If this is `None`, the middle joint bends in the same direction as in
+    /// the animated pose.
+    pub pole: Option<IkTarget>,
+    /// How much the solved pose replaces the animated one, from 0 to 1.
+    pub weight: f32,
+}
+
+/// Solves a chain of any number of bones iteratively so that its end reaches
+/// the target.
+///
+/// Place this component on the joint at the end of the chain. Its ancestors
+/// up to [`IkChain::bone_count`] levels above it are rotated; the joint
+/// itself isn't.
+#[derive(Clone, Copy, Debug, Component, Reflect)]
+#[reflect(Component, MapEntities)]
+pub struct IkChain {
+    /// The algorithm that solves the chain.
+    pub solver: IkChainSolver,
+    /// The point that the end of the chain reaches toward.
+    pub target: IkTarget,
+    /// The number of bones in the chain.
+    pub bone_count: usize,
+    /// The maximum number of iterations of the solver.
+    pub iterations: u32,
+    /// The solver stops once the end of the chain is closer than this to the
+    /// target.
+    pub tolerance: f32,
+    /// How much the solved pose replaces the animated one, from 0 to 1.
+    pub weight: f32,
+}
+
+/// The algorithms that can solve an [`IkChain`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
+pub enum IkChainSolver {
+    /// Forward and backward reaching inverse kinematics, which tends to
+    /// produce natural poses and converges quickly. See [`solve_fabrik`].
+    #[default]
+    Fabrik,
+    /// Cyclic coordinate descent, which favors rotating the joints closest to
+    /// the end of the chain. See [`solve_ccd`].
+    Ccd,
+}
+
+/// Rotates a joint so that one of its axes points toward the target.
+///
+/// The joint is rotated by the smallest rotation that does so, so it doesn't
+/// twist around the axis.
+#[derive(Clone, Copy, Debug, Component, Reflect)]
+#[reflect(Component, MapEntities)]
+pub struct LookAt {
+    /// The point that the joint looks at.
+    pub target: IkTarget,
+    /// The axis of the joint, in its local space, that points toward the
+    /// target.
+    pub forward: Vec3,
+    /// How much the solved pose replaces the animated one, from 0 to 1.
+    pub weight: f32,
+}
+
+impl IkChain {
+    /// Creates a chain with the given number of bones that the given solver
+    /// solves, with full weight and default iterations and tolerance.
+    pub fn new(solver: IkChainSolver, target: IkTarget, bone_count: usize) -> Self {
+        Self {
+            solver,
+            target,
+            bone_count,
+            iterations: 10,
+            tolerance: 0.001,
+            weight: 1.0,
+        }
+    }
+}
+
+impl TwoBoneIk {
+    /// Creates a two-bone solver with full weight and no pole.
+    pub fn new(target: IkTarget) -> Self {
+        Self {
+            target,
+            pole: None,
+            weight: 1.0,
+        }
+    }
+}
+
+impl LookAt {
+    /// Creates a look-at constraint with full weight that points the given
+    /// local axis toward the target.
+    pub fn new(target: IkTarget, forward: Vec3) -> Self {
+        Self {
+            target,
+            forward,
+            weight: 1.0,
+        }
+    }
+}
+
+impl MapEntities for IkTarget {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        if let IkTarget::Entity(ref mut entity) = *self {
+            *entity = entity_mapper.map_entity(*entity);
+        }
+    }
+}
+
+impl MapEntities for TwoBoneIk {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.target.map_entities(entity_mapper);
+        if let Some(ref mut pole) = self.pole {
+            pole.map_entities(entity_mapper);
+        }
+    }
+}
+
+impl MapEntities for IkChain {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.target.map_entities(entity_mapper);
+    }
+}
+
+impl MapEntities for LookAt {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.target.map_entities(entity_mapper);
+    }
+}
+
+/// Moves the middle and end joints of a two-bone chain so that the end
+/// reaches `target`, keeping the lengths of the bones.
+///
+/// The middle joint bends toward `pole`, or toward its current position if
+/// `pole` is `None`. If the target is out of reach, the chain is stretched
+/// toward it.
+pub fn solve_two_bone(positions: &mut [Vec3; 3], target: Vec3, pole: Option<Vec3>) {
+    let [root, middle, end] = *positions;
+    let (upper, lower) = (root.distance(middle), middle.distance(end));
+    let Some(direction) = (target - root).try_normalize() else {
+        return;
+    };
+    if upper == 0.0 || lower == 0.0 {
+        return;
+    }
+
+    // Find the direction that the middle joint bends in, perpendicular to
+    // the direction to the target.
+    let bend = (pole.unwrap_or(middle) - root)
+        .reject_from_normalized(direction)
+        .try_normalize()
+        .unwrap_or_else(|| direction.any_orthonormal_vector());
+
+    // Use the law of cosines to find the angle at the root.
+    let distance = target
+        .distance(root)
+        .clamp((upper - lower).abs(), upper + lower)
+        .max(f32::EPSILON);
+    let cos = ((upper * upper + distance * distance - lower * lower) / (2.0 * upper * distance))
+        .clamp(-1.0, 1.0);
+    let sin = (1.0 - cos * cos).sqrt();
+
+    positions[1] = root + (direction * cos + bend * sin) * upper;
+    positions[2] = root + direction * distance;
+}
+
+/// Moves the joints of a chain so that the last one reaches `target` with the
+/// FABRIK algorithm, keeping the first joint in place and the lengths of the
+/// bones.
+///
+/// The solver stops after `iterations` or once the last joint is within
+/// `tolerance` of the target. If the target is out of reach, the chain is
+/// stretched toward it.
+pub fn solve_fabrik(positions: &mut [Vec3], target: Vec3, iterations: u32, tolerance: f32) {
+    if positions.len() < 2 {
+        return;
+    }
+
+    let lengths: Vec<f32> = positions
+        .windows(2)
+        .map(|bone| bone[0].distance(bone[1]))
+        .collect();
+    let root = positions[0];
+
+    if root.distance(target) >= lengths.iter().sum() {
+        let direction = (target - root).normalize_or_zero();
+        for (index, length) in lengths.iter().enumerate() {
+            positions[index + 1] = positions[index] + direction * *length;
+        }
+        return;
+    }
+
+    let last = positions.len() - 1;
+    for _ in 0..iterations {
+        if positions[last].distance(target) <= tolerance {
+            break;
+        }
+
+        // Reach backward from the target…
+        positions[last] = target;
+        for index in (0..last).rev() {
+            let direction = (positions[index] - positions[index + 1]).normalize_or_zero();
+            positions[index] = positions[index + 1] + direction * lengths[index];
+        }
+
+        // …and then forward from the root.
+        positions[0] = root;
+        for index in 1..=last {
+            let direction = (positions[index] - positions[index - 1]).normalize_or_zero();
+            positions[index] = positions[index - 1] + direction * lengths[index - 1];
+        }
+    }
+}
+
+/// Moves the joints of a chain so that the last one reaches `target` with the
+/// cyclic coordinate descent algorithm, keeping the first joint in place and
+/// the lengths of the bones.
+///
+/// Each iteration rotates the chain around each joint in turn, starting from
+/// the end, so that the last joint points toward the target. The solver stops
+/// after `iterations` or once the last joint is within `tolerance` of the
+/// target.
+pub fn solve_ccd(positions: &mut [Vec3], target: Vec3, iterations: u32, tolerance: f32) {
+    if positions.len() < 2 {
+        return;
+    }
+
+    let last = positions.len() - 1;
+    for _ in 0..iterations {
+        if positions[last].distance(target) <= tolerance {
+            break;
+        }
+
+        for index in (0..last).rev() {
+            let pivot = positions[index];
+            let (Some(to_end), Some(to_target)) = (
+                (positions[last] - pivot).try_normalize(),
+                (target - pivot).try_normalize(),
+            ) else {
+                continue;
+            };
+
+            let rotation = Quat::from_rotation_arc(to_end, to_target);
+            for position in &mut positions[(index + 1)..] {
+                *position = pivot + rotation * (*position - pivot);
+            }
+        }
+    }
+}
+
+/// A system that applies the [`TwoBoneIk`], [`IkChain`], and [`LookAt`]
+/// solvers to the animated poses, in that order.
+///
+/// Because the [`GlobalTransform`]s haven't been propagated yet when this
+/// runs, the world space poses of the joints are computed from the
+/// [`Transform`]s of their ancestors.
+pub fn solve_inverse_kinematics(
+    two_bone_solvers: Query<(Entity, &TwoBoneIk)>,
+    chains: Query<(Entity, &IkChain)>,
+    look_ats: Query<(Entity, &LookAt)>,
+    parents: Query<&Parent>,
+    mut transforms: Query<&mut Transform>,
+    mut joints: Local<Vec<Entity>>,
+    mut positions: Local<Vec<Vec3>>,
+) {
+    for (entity, solver) in &two_bone_solvers {
+        if solver.weight == 0.0
+            || !collect_joints(
+                entity,
+                2,
+                &parents,
+                &transforms,
+                &mut joints,
+                &mut positions,
+            )
+        {
+            continue;
+        }
+        let Some(target) = solver.target.position(&parents, &transforms) else {
+            continue;
+        };
+        let pole = solver
+            .pole
+            .and_then(|pole| pole.position(&parents, &transforms));
+
+        let mut solved = [positions[0], positions[1], positions[2]];
+        solve_two_bone(&mut solved, target, pole);
+        apply_chain(&joints, &solved, solver.weight, &parents, &mut transforms);
+    }
+
+    for (entity, chain) in &chains {
+        if chain.weight == 0.0
+            || chain.bone_count == 0
+            || !collect_joints(
+                entity,
+                chain.bone_count,
+                &parents,
+                &transforms,
+                &mut joints,
+                &mut positions,
+            )
+        {
+            continue;
+        }
+        let Some(target) = chain.target.position(&parents, &transforms) else {
+            continue;
+        };
+
+        match chain.solver {
+            IkChainSolver::Fabrik => {
+                solve_fabrik(&mut positions, target, chain.iterations, chain.tolerance);
+            }
+            IkChainSolver::Ccd => {
+                solve_ccd(&mut positions, target, chain.iterations, chain.tolerance);
+            }
+        }
+        apply_chain(&joints, &positions, chain.weight, &parents, &mut transforms);
+    }
+
+    for (entity, look_at) in &look_ats {
+        if look_at.weight == 0.0 {
+            continue;
+        }
+        let Some(target) = look_at.target.position(&parents, &transforms) else {
+            continue;
+        };
+        let Ok(&local) = transforms.get(entity) else {
+            continue;
+        };
+
+        let parent = parent_transform(entity, &parents, &transforms);
+        let global = parent * local;
+        let (_, parent_rotation, _) = parent.to_scale_rotation_translation();
+        let (_, rotation, _) = global.to_scale_rotation_translation();
+        let (Some(forward), Some(to_target)) = (
+            (rotation * look_at.forward).try_normalize(),
+            (target - global.translation()).try_normalize(),
+        ) else {
+            continue;
+        };
+
+        let solved =
+            (parent_rotation.inverse() * Quat::from_rotation_arc(forward, to_target) * rotation)
+                .normalize();
+        if let Ok(mut transform) = transforms.get_mut(entity) {
+            transform.rotation = local.rotation.slerp(solved, look_at.weight);
+        }
+    }
+}
+
+impl IkTarget {
+    /// Returns the world space position of this target, or `None` if it's an
+    /// entity without a [`Transform`].
+    fn position(
+        &self,
+        parents: &Query<&Parent>,
+        transforms: &Query<&mut Transform>,
+    ) -> Option<Vec3> {
+        match *self {
+            IkTarget::Position(position) => Some(position),
+            IkTarget::Entity(entity) => {
+                let local = transforms.get(entity).ok()?;
+                Some((parent_transform(entity, parents, transforms) * *local).translation())
+            }
+        }
+    }
+}
+
+/// Computes the world space transform of the parent of `entity` from the
+/// [`Transform`]s of its ancestors.
+fn parent_transform(
+    entity: Entity,
+    parents: &Query<&Parent>,
+    transforms: &Query<&mut Transform>,
+) -> GlobalTransform {
+    let mut global = GlobalTransform::IDENTITY;
+    let mut current = entity;
+    while let Ok(parent) = parents.get(current) {
+        current = parent.get();
+        let Ok(transform) = transforms.get(current) else {
+            break;
+        };
+        global = GlobalTransform::from(*transform) * global;
+    }
+    global
+}
+
+/// Collects the `bone_count + 1` joints of the chain that ends at `end` into
+/// `joints`, and their world space positions into `positions`, starting from
+/// the root of the chain.
+///
+/// Returns false if the chain doesn't have enough joints.
+fn collect_joints(
+    end: Entity,
+    bone_count: usize,
+    parents: &Query<&Parent>,
+    transforms: &Query<&mut Transform>,
+    joints: &mut Vec<Entity>,
+    positions: &mut Vec<Vec3>,
+) -> bool {
+    joints.clear();
+    joints.push(end);
+    for _ in 0..bone_count {
+        let Ok(parent) = parents.get(*joints.last().unwrap()) else {
+            return false;
+        };
+        joints.push(parent.get());
+    }
+    joints.reverse();
+
+    positions.clear();
+    let mut global = parent_transform(joints[0], parents, transforms);
+    for &joint in joints.iter() {
+        let Ok(transform) = transforms.get(joint) else {
+            return false;
+        };
+        global = global * *transform;
+        positions.push(global.translation());
+    }
+    true
+}
+
+/// Rotates each joint of a chain except the last so that the next joint
+/// moves to its solved position, blending the rotations with the animated
+/// ones by `weight`.
+fn apply_chain(
+    joints: &[Entity],
+    positions: &[Vec3],
+    weight: f32,
+    parents: &Query<&Parent>,
+    transforms: &mut Query<&mut Transform>,
+) {
+    // Track the fully solved pose of the parent, so that each joint is solved
+    // relative to it regardless of the weight.
+    let mut parent = parent_transform(joints[0], parents, transforms);
+
+    for (index, bone) in joints.windows(2).enumerate() {
+        let (Ok(&local), Ok(&child)) = (transforms.get(bone[0]), transforms.get(bone[1])) else {
+            return;
+        };
+
+        let global = parent * local;
+        let (_, parent_rotation, _) = parent.to_scale_rotation_translation();
+        let (_, rotation, _) = global.to_scale_rotation_translation();
+        let solved_rotation = match (
+            (global.transform_point(child.translation) - global.translation()).try_normalize(),
+            (positions[index + 1] - global.translation()).try_normalize(),
+        ) {
+            (Some(from), Some(to)) => Quat::from_rotation_arc(from, to) * rotation,
+            _ => rotation,
+        };
+
+        let solved = Transform {
+            rotation: (parent_rotation.inverse() * solved_rotation).normalize(),
+            ..local
+        };
+        parent = parent * solved;
+
+        if let Ok(mut transform) = transforms.get_mut(bone[0]) {
+            transform.rotation = local.rotation.slerp(solved.rotation, weight);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::{entity::Entity, system::RunSystemOnce, world::World};
+    use bevy_hierarchy::BuildChildren;
+    use bevy_math::{Quat, Vec3};
+    use bevy_transform::components::{GlobalTransform, Transform};
+
+    use super::{
+        solve_ccd, solve_fabrik, solve_inverse_kinematics, solve_two_bone, IkChain, IkChainSolver,
+        IkTarget, LookAt, TwoBoneIk,
+    };
+
+    fn straight_chain(bone_count: usize) -> Vec<Vec3> {
+        (0..=bone_count)
+            .map(|index| Vec3::Y * index as f32)
+            .collect()
+    }
+
+    fn assert_lengths_preserved(original: &[Vec3], solved: &[Vec3]) {
+        for (original, solved) in original.windows(2).zip(solved.windows(2)) {
+            let (original, solved) = (
+                original[0].distance(original[1]),
+                solved[0].distance(solved[1]),
+            );
+            assert!((original - solved).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn two_bone() {
+        let original = [
+            Vec3::ZERO,
+            Vec3::new(0.0, 1.0, 0.1),
+            Vec3::new(0.0, 2.0, 0.0),
+        ];
+        let target = Vec3::new(1.0, 1.0, 0.0);
+
+        let mut positions = original;
+        solve_two_bone(&mut positions, target, None);
+        assert_lengths_preserved(&original, &positions);
+        assert!(positions[2].distance(target) < 1e-4);
+        // Without a pole, the middle joint keeps bending toward +Z.
+        assert!(positions[1].z > 0.0);
+
+        let mut positions = original;
+        solve_two_bone(&mut positions, target, Some(Vec3::NEG_Z));
+        assert!(positions[2].distance(target) < 1e-4);
+        assert!(positions[1].z < 0.0);
+
+        // Out of reach, the chain is stretched toward the target.
+        let reach = original[0].distance(original[1]) + original[1].distance(original[2]);
+        let mut positions = original;
+        solve_two_bone(&mut positions, Vec3::X * 10.0, None);
+        assert!(positions[2].distance(Vec3::X * reach) < 1e-4);
+    }
+
+    #[test]
+    fn iterative_chains() {
+        let original = straight_chain(4);
+        let target = Vec3::new(2.0, 1.5, 0.5);
+
+        let mut positions = original.clone();
+        solve_fabrik(&mut positions, target, 20, 1e-4);
+        assert_lengths_preserved(&original, &positions);
+        assert_eq!(positions[0], Vec3::ZERO);
+        assert!(positions[4].distance(target) < 1e-3);
+
+        let mut positions = original.clone();
+        solve_ccd(&mut positions, target, 50, 1e-4);
+        assert_lengths_preserved(&original, &positions);
+        assert_eq!(positions[0], Vec3::ZERO);
+        assert!(positions[4].distance(target) < 1e-2);
+
+        let mut positions = original.clone();
+        solve_fabrik(&mut positions, Vec3::X * 10.0, 20, 1e-4);
+        assert!(positions[4].distance(Vec3::X * 4.0) < 1e-4);
+    }
+
+    fn end_position(world: &World, joints: &[Entity]) -> Vec3 {
+        let global = joints
+            .iter()
+            .fold(GlobalTransform::IDENTITY, |global, &joint| {
+                global * *world.get::<Transform>(joint).unwrap()
+            });
+        global.translation()
+    }
+
+    #[test]
+    fn solve_hierarchy() {
+        let mut world = World::new();
+        let target = world.spawn(Transform::from_xyz(1.0, 3.0, 0.0)).id();
+
+        let root = world.spawn(Transform::from_xyz(0.0, 2.0, 0.0)).id();
+        let middle = world.spawn(Transform::from_xyz(0.0, 1.0, 0.0)).id();
+        let end = world
+            .spawn((
+                Transform::from_xyz(0.0, 1.0, 0.0),
+                TwoBoneIk::new(IkTarget::Entity(target)),
+            ))
+            .id();
+        world.entity_mut(root).add_child(middle);
+        world.entity_mut(middle).add_child(end);
+        let joints = [root, middle, end];
+
+        world.run_system_once(solve_inverse_kinematics);
+        assert!(end_position(&world, &joints).distance(Vec3::new(1.0, 3.0, 0.0)) < 1e-4);
+
+        // With half the weight, the end stays between the animated pose and
+        // the target.
+        world.entity_mut(end).insert(IkChain {
+            weight: 0.5,
+            ..IkChain::new(
+                IkChainSolver::Ccd,
+                IkTarget::Position(Vec3::new(0.0, 2.0, 2.0)),
+                2,
+            )
+        });
+        world.entity_mut(end).remove::<TwoBoneIk>();
+        for &joint in &joints {
+            world.get_mut::<Transform>(joint).unwrap().rotation = Quat::IDENTITY;
+        }
+        world.run_system_once(solve_inverse_kinematics);
+        let end = end_position(&world, &joints);
+        assert!(end.distance(Vec3::new(0.0, 2.0, 2.0)) > 0.1);
+        assert!(end.distance(Vec3::new(0.0, 4.0, 0.0)) > 0.1);
+    }
+
+    #[test]
+    fn look_at() {
+        let mut world = World::new();
+        let parent = world
+            .spawn(Transform::from_rotation(Quat::from_rotation_y(1.0)))
+            .id();
+        let head = world
+            .spawn((
+                Transform::from_xyz(0.0, 1.0, 0.0),
+                LookAt::new(IkTarget::Position(Vec3::new(0.0, 1.0, 5.0)), Vec3::NEG_Z),
+            ))
+            .id();
+        world.entity_mut(parent).add_child(head);
+
+        world.run_system_once(solve_inverse_kinematics);
+
+        let global = GlobalTransform::from(*world.get::<Transform>(parent).unwrap())
+            * *world.get::<Transform>(head).unwrap();
+        let (_, rotation, _) = global.to_scale_rotation_translation();
+        assert!((rotation * Vec3::NEG_Z).abs_diff_eq(Vec3::Z, 1e-4));
+    }
+}
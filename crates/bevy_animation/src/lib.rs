@@ -8,21 +8,36 @@
 //! Animation for the game engine Bevy
 
 pub mod animatable;
+pub mod animation_curves;
+pub mod blend_space;
+pub mod compression;
+pub mod event;
 pub mod graph;
+pub mod ik;
+pub mod retarget;
+pub mod root_motion;
+pub mod state_machine;
 pub mod transition;
+pub mod tween;
 mod util;
 
 use std::cell::RefCell;
 use std::collections::BTreeMap;
 use std::hash::{Hash, Hasher};
 use std::iter;
+use std::mem;
 use std::ops::{Add, Mul};
 
 use bevy_app::{App, Plugin, PostUpdate};
-use bevy_asset::{Asset, AssetApp, Assets, Handle};
+use bevy_asset::{processor::LoadTransformAndSave, Asset, AssetApp, AssetId, Assets, Handle};
 use bevy_core::Name;
-use bevy_ecs::{entity::MapEntities, prelude::*, reflect::ReflectMapEntities};
-use bevy_math::{FloatExt, Quat, Vec3};
+use bevy_ecs::{
+    entity::MapEntities,
+    prelude::*,
+    reflect::{AppTypeRegistry, ReflectMapEntities},
+    system::{ParallelCommands, SystemState},
+};
+use bevy_math::{FloatExt, Quat, Vec2, Vec3};
 use bevy_reflect::Reflect;
 use bevy_render::mesh::morph::MorphWeights;
 use bevy_time::Time;
@@ -30,7 +45,7 @@ use bevy_transform::{prelude::Transform, TransformSystem};
 use bevy_utils::{
     hashbrown::HashMap,
     tracing::{error, trace},
-    NoOpHash,
+    warn_once, NoOpHash,
 };
 use fixedbitset::FixedBitSet;
 use graph::AnimationMask;
@@ -43,14 +58,29 @@ use uuid::Uuid;
 pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
-        animatable::*, graph::*, transition::*, AnimationClip, AnimationPlayer, AnimationPlugin,
-        Interpolation, Keyframes, VariableCurve,
+        animatable::*, animation_curves::*, blend_space::*, compression::*, event::*, graph::*,
+        ik::*, retarget::*, root_motion::*, state_machine::*, transition::*, tween::*,
+        AnimationClip, AnimationPlayer, AnimationPlugin, Interpolation, Keyframes, VariableCurve,
     };
 }
 
 use crate::{
+    animation_curves::AnimationCurve,
+    compression::{
+        AnimationClipAssetLoader, AnimationClipCompressionProcessor, AnimationClipSaver,
+        AnimationCompressor, CompressedQuat,
+    },
+    event::{AnimationEvent, AnimationEventTarget, AnimationEvents, TimedAnimationEvent},
     graph::{AnimationGraph, AnimationGraphAssetLoader, AnimationNodeIndex},
+    ik::{solve_inverse_kinematics, IkChain, LookAt, TwoBoneIk},
+    retarget::{AnimationRetarget, AnimationRetargetAssetLoader},
+    root_motion::{extract_root_motion, RootMotion},
+    state_machine::{
+        advance_state_machines, AnimationStateMachine, AnimationStateMachineAssetLoader,
+        AnimationStateMachineController,
+    },
     transition::{advance_transitions, expire_completed_transitions, AnimationTransitions},
+    tween::{advance_tweens, TweenSystem},
 };
 
 /// The [UUID namespace] of animation targets (e.g. bones).
@@ -59,10 +89,18 @@ use crate::{
 pub static ANIMATION_TARGET_NAMESPACE: Uuid = Uuid::from_u128(0x3179f519d9274ff2b5966fd077023911);
 
 /// List of keyframes for one of the attribute of a [`Transform`].
-#[derive(Reflect, Clone, Debug)]
+#[derive(Reflect, Clone, Debug, Serialize, Deserialize)]
 pub enum Keyframes {
     /// Keyframes for rotation.
     Rotation(Vec<Quat>),
+    /// Keyframes for rotation, quantized to save memory.
+    ///
+    /// These are produced by [`compression`]. Only [`Interpolation::Step`]
+    /// and [`Interpolation::Linear`] are fully supported; curves with
+    /// [`Interpolation::CubicSpline`] store their keyframes as in-tangent,
+    /// value, out-tangent triples like [`Keyframes::Rotation`], but only the
+    /// values are used, and they are interpolated linearly.
+    CompressedRotation(Vec<CompressedQuat>),
     /// Keyframes for translation.
     Translation(Vec<Vec3>),
     /// Keyframes for scale.
@@ -85,6 +123,7 @@ impl Keyframes {
             Keyframes::Weights(vec) => vec.len(),
             Keyframes::Translation(vec) | Keyframes::Scale(vec) => vec.len(),
             Keyframes::Rotation(vec) => vec.len(),
+            Keyframes::CompressedRotation(vec) => vec.len(),
         }
     }
 
@@ -97,7 +136,7 @@ impl Keyframes {
 /// Describes how an attribute of a [`Transform`] or [`MorphWeights`] should be animated.
 ///
 /// `keyframe_timestamps` and `keyframes` should have the same length.
-#[derive(Reflect, Clone, Debug)]
+#[derive(Reflect, Clone, Debug, Serialize, Deserialize)]
 pub struct VariableCurve {
     /// Timestamp for each of the keyframes.
     pub keyframe_timestamps: Vec<f32>,
@@ -181,7 +220,7 @@ impl VariableCurve {
 }
 
 /// Interpolation method to use between keyframes.
-#[derive(Reflect, Clone, Debug)]
+#[derive(Reflect, Clone, Debug, Serialize, Deserialize)]
 pub enum Interpolation {
     /// Linear interpolation between the two closest keyframes.
     Linear,
@@ -192,14 +231,17 @@ pub enum Interpolation {
     CubicSpline,
 }
 
-/// A list of [`VariableCurve`]s and the [`AnimationTargetId`]s to which they
-/// apply.
+/// A list of [`VariableCurve`]s and [`AnimationCurve`]s and the
+/// [`AnimationTargetId`]s to which they apply, along with the
+/// [`AnimationEvent`]s fired during playback.
 ///
 /// Because animation clips refer to targets by UUID, they can target any
 /// [`AnimationTarget`] with that ID.
 #[derive(Asset, Reflect, Clone, Debug, Default)]
 pub struct AnimationClip {
     curves: AnimationCurves,
+    property_curves: AnimationPropertyCurves,
+    events: AnimationEvents,
     duration: f32,
 }
 
@@ -207,6 +249,11 @@ pub struct AnimationClip {
 /// animation curves.
 pub type AnimationCurves = HashMap<AnimationTargetId, Vec<VariableCurve>, NoOpHash>;
 
+/// A mapping from [`AnimationTargetId`] to the [`AnimationCurve`]s that
+/// animate the reflected fields of the target.
+pub type AnimationPropertyCurves =
+    HashMap<AnimationTargetId, Vec<Box<dyn AnimationCurve>>, NoOpHash>;
+
 /// A unique [UUID] for an animation target (e.g. bone in a skinned mesh).
 ///
 /// The [`AnimationClip`] asset and the [`AnimationTarget`] component both use
@@ -220,7 +267,8 @@ pub type AnimationCurves = HashMap<AnimationTargetId, Vec<VariableCurve>, NoOpHa
 /// imported animation clip that animates a root bone named `Hips` will
 /// reference the same [`AnimationTargetId`]. Any animation is playable on any
 /// armature as long as the bone names match, which allows for easy animation
-/// retargeting.
+/// retargeting. To play clips on armatures whose bone names or proportions
+/// differ, see [`retarget`].
 ///
 /// Note that asset loaders generally use the *full* path name to generate the
 /// [`AnimationTargetId`]. Thus a bone named `Chest` directly connected to a
@@ -331,6 +379,93 @@ impl AnimationClip {
             .max(*curve.keyframe_timestamps.last().unwrap_or(&0.0));
         self.curves.entry(target_id).or_default().push(curve);
     }
+
+    /// [`AnimationCurve`]s for each animation target. Indexed by the [`AnimationTargetId`].
+    #[inline]
+    pub fn property_curves(&self) -> &AnimationPropertyCurves {
+        &self.property_curves
+    }
+
+    /// Gets the [`AnimationCurve`]s for a single animation target.
+    ///
+    /// Returns `None` if this clip doesn't animate any reflected field of the
+    /// target.
+    #[inline]
+    pub fn property_curves_for_target(
+        &self,
+        target_id: AnimationTargetId,
+    ) -> Option<&'_ Vec<Box<dyn AnimationCurve>>> {
+        self.property_curves.get(&target_id)
+    }
+
+    /// Adds an [`AnimationCurve`] to an [`AnimationTarget`] named by an
+    /// [`AnimationTargetId`].
+    ///
+    /// If the curve extends beyond the current duration of this clip, this
+    /// method lengthens this clip to include the entire time span that the
+    /// curve covers.
+    pub fn add_property_curve_to_target(
+        &mut self,
+        target_id: AnimationTargetId,
+        curve: impl AnimationCurve,
+    ) {
+        let end = curve.domain().end();
+        if end.is_finite() {
+            self.duration = self.duration.max(end);
+        }
+        self.property_curves
+            .entry(target_id)
+            .or_default()
+            .push(Box::new(curve));
+    }
+
+    /// [`TimedAnimationEvent`]s for each [`AnimationEventTarget`], sorted by time.
+    #[inline]
+    pub fn events(&self) -> &AnimationEvents {
+        &self.events
+    }
+
+    /// Adds an [`AnimationEvent`] that is triggered on the entity with the
+    /// [`AnimationPlayer`] when playback reaches `time`, in seconds.
+    ///
+    /// If the event is after the current duration of this clip, this method
+    /// lengthens this clip to include it.
+    pub fn add_event(&mut self, time: f32, event: impl AnimationEvent) {
+        self.add_event_to(AnimationEventTarget::Root, time, Box::new(event));
+    }
+
+    /// Adds an [`AnimationEvent`] that is triggered on the [`AnimationTarget`]
+    /// named by an [`AnimationTargetId`] when playback reaches `time`, in
+    /// seconds.
+    ///
+    /// If the event is after the current duration of this clip, this method
+    /// lengthens this clip to include it.
+    pub fn add_event_to_target(
+        &mut self,
+        target_id: AnimationTargetId,
+        time: f32,
+        event: impl AnimationEvent,
+    ) {
+        self.add_event_to(AnimationEventTarget::Node(target_id), time, Box::new(event));
+    }
+
+    /// Adds a boxed [`AnimationEvent`] that is triggered on the given
+    /// [`AnimationEventTarget`] when playback reaches `time`, in seconds.
+    ///
+    /// If the event is after the current duration of this clip, this method
+    /// lengthens this clip to include it.
+    pub fn add_event_to(
+        &mut self,
+        target: AnimationEventTarget,
+        time: f32,
+        event: Box<dyn AnimationEvent>,
+    ) {
+        self.duration = self.duration.max(time);
+        let events = self.events.entry(target).or_default();
+        // Keep the events sorted, and events at the same time in insertion order.
+        let index = events.partition_point(|event| event.time <= time);
+        events.insert(index, TimedAnimationEvent { time, event });
+    }
 }
 
 /// Repetition behavior of an animation.
@@ -359,6 +494,9 @@ pub struct ActiveAnimation {
     /// The mask groups that are masked out (i.e. won't be animated) this frame,
     /// taking the `AnimationGraph` into account.
     computed_mask: AnimationMask,
+    /// Whether this animation is blended additively this frame, taking the
+    /// `AnimationGraph` into account.
+    computed_additive: bool,
     repeat: RepeatAnimation,
     speed: f32,
     /// Total time the animation has been played.
@@ -369,6 +507,11 @@ pub struct ActiveAnimation {
     ///
     /// Note: This will always be in the range [0.0, animation clip duration]
     seek_time: f32,
+    /// The seek time of the animation at the start of the last frame, if it
+    /// was ticked.
+    ///
+    /// [`AnimationEvent`]s between this and `seek_time` fire this frame.
+    last_seek_time: Option<f32>,
     /// Number of times the animation has completed.
     /// If the animation is playing in reverse, this increments when the animation passes the start.
     completions: u32,
@@ -381,10 +524,12 @@ impl Default for ActiveAnimation {
             weight: 1.0,
             computed_weight: 1.0,
             computed_mask: 0,
+            computed_additive: false,
             repeat: RepeatAnimation::default(),
             speed: 1.0,
             elapsed: 0.0,
             seek_time: 0.0,
+            last_seek_time: None,
             completions: 0,
             paused: false,
         }
@@ -438,6 +583,7 @@ impl ActiveAnimation {
         self.completions = 0;
         self.elapsed = 0.0;
         self.seek_time = 0.0;
+        self.last_seek_time = None;
     }
 
     /// Returns the current weight of this animation.
@@ -544,6 +690,7 @@ pub struct AnimationPlayer {
     /// ordering when applying the animations.
     active_animations: BTreeMap<AnimationNodeIndex, ActiveAnimation>,
     blend_weights: HashMap<AnimationNodeIndex, f32>,
+    blend_space_points: HashMap<AnimationNodeIndex, Vec2>,
 }
 
 // This is needed since `#[derive(Clone)]` does not generate optimized `clone_from`.
@@ -552,12 +699,15 @@ impl Clone for AnimationPlayer {
         Self {
             active_animations: self.active_animations.clone(),
             blend_weights: self.blend_weights.clone(),
+            blend_space_points: self.blend_space_points.clone(),
         }
     }
 
     fn clone_from(&mut self, source: &Self) {
         self.active_animations.clone_from(&source.active_animations);
         self.blend_weights.clone_from(&source.blend_weights);
+        self.blend_space_points
+            .clone_from(&source.blend_space_points);
     }
 }
 
@@ -569,6 +719,9 @@ struct AnimationTargetContext<'a> {
     name: Option<&'a Name>,
     transform: Option<Mut<'a, Transform>>,
     morph_weights: Option<Mut<'a, MorphWeights>>,
+    /// Whether this target is the root bone of a [`RootMotion`], whose
+    /// translation and rotation are held at the start of each clip.
+    is_motion_root: bool,
 }
 
 /// Information needed during the traversal of the animation graph in
@@ -581,6 +734,9 @@ pub struct AnimationGraphEvaluator {
     dfs_visited: FixedBitSet,
     /// Accumulated weights and masks for each node.
     nodes: Vec<EvaluatedAnimationGraphNode>,
+    /// The weight that each node gets from the [`BlendSpace`]s of its
+    /// parents, or 1.0 if none of its parents has one.
+    blend_space_weights: Vec<f32>,
 }
 
 /// The accumulated weight and computed mask for a single node.
@@ -592,6 +748,8 @@ struct EvaluatedAnimationGraphNode {
     /// The mask that has been computed for this node, taking its ancestors'
     /// masks into account.
     mask: AnimationMask,
+    /// Whether this node or any of its ancestors is additive.
+    additive: bool,
 }
 
 thread_local! {
@@ -738,20 +896,44 @@ impl AnimationPlayer {
     pub fn animation_is_playing(&self, animation: AnimationNodeIndex) -> bool {
         self.active_animations.contains_key(&animation)
     }
+
+    /// Sets the point that the [`BlendSpace`] on the given graph node samples
+    /// to weight its children.
+    ///
+    /// One-dimensional blend spaces only use the `x` coordinate. Blend spaces
+    /// whose point hasn't been set sample the origin.
+    pub fn set_blend_space_point(
+        &mut self,
+        blend_space: AnimationNodeIndex,
+        point: Vec2,
+    ) -> &mut Self {
+        self.blend_space_points.insert(blend_space, point);
+        self
+    }
+
+    /// Returns the point that the [`BlendSpace`] on the given graph node
+    /// samples, if one has been set.
+    pub fn blend_space_point(&self, blend_space: AnimationNodeIndex) -> Option<Vec2> {
+        self.blend_space_points.get(&blend_space).copied()
+    }
 }
 
 /// A system that advances the time for all playing animations.
+///
+/// This also triggers the [`AnimationEvent`]s that target the entities with
+/// the [`AnimationPlayer`]s.
 pub fn advance_animations(
     time: Res<Time>,
     animation_clips: Res<Assets<AnimationClip>>,
     animation_graphs: Res<Assets<AnimationGraph>>,
-    mut players: Query<(&mut AnimationPlayer, &Handle<AnimationGraph>)>,
+    mut players: Query<(Entity, &mut AnimationPlayer, &Handle<AnimationGraph>)>,
     animation_graph_evaluator: Local<ThreadLocal<RefCell<AnimationGraphEvaluator>>>,
+    par_commands: ParallelCommands,
 ) {
     let delta_seconds = time.delta_seconds();
     players
         .par_iter_mut()
-        .for_each(|(mut player, graph_handle)| {
+        .for_each(|(entity, mut player, graph_handle)| {
             let Some(animation_graph) = animation_graphs.get(graph_handle) else {
                 return;
             };
@@ -765,7 +947,7 @@ pub fn advance_animations(
             let AnimationPlayer {
                 ref mut active_animations,
                 ref blend_weights,
-                ..
+                ref blend_space_points,
             } = *player;
 
             // Reset our state.
@@ -779,8 +961,8 @@ pub fn advance_animations(
 
                 let node = &animation_graph[node_index];
 
-                // Calculate weight and mask from the graph.
-                let (mut weight, mut mask) = (node.weight, node.mask);
+                // Calculate weight, mask and additivity from the graph.
+                let (mut weight, mut mask, mut additive) = (node.weight, node.mask, node.additive);
                 for parent_index in animation_graph
                     .graph
                     .neighbors_directed(node_index, Direction::Incoming)
@@ -788,15 +970,53 @@ pub fn advance_animations(
                     let evaluated_parent = &evaluator.nodes[parent_index.index()];
                     weight *= evaluated_parent.weight;
                     mask |= evaluated_parent.mask;
+                    additive |= evaluated_parent.additive;
+                }
+                weight *= evaluator.blend_space_weights[node_index.index()];
+                evaluator.nodes[node_index.index()] = EvaluatedAnimationGraphNode {
+                    weight,
+                    mask,
+                    additive,
+                };
+
+                // Weight the children from the blend space, if there is one.
+                if let Some(ref blend_space) = node.blend_space {
+                    for child_index in animation_graph
+                        .graph
+                        .neighbors_directed(node_index, Direction::Outgoing)
+                    {
+                        evaluator.blend_space_weights[child_index.index()] = 0.0;
+                    }
+                    let point = blend_space_points
+                        .get(&node_index)
+                        .copied()
+                        .unwrap_or_default();
+                    blend_space.weights(point, |child_index, child_weight| {
+                        if let Some(blend_space_weight) =
+                            evaluator.blend_space_weights.get_mut(child_index.index())
+                        {
+                            *blend_space_weight = child_weight;
+                        }
+                    });
                 }
-                evaluator.nodes[node_index.index()] = EvaluatedAnimationGraphNode { weight, mask };
 
                 if let Some(active_animation) = active_animations.get_mut(&node_index) {
-                    // Tick the animation if necessary.
+                    active_animation.last_seek_time = Some(active_animation.seek_time);
+
+                    // Tick the animation if necessary, and fire the events it
+                    // passed over.
                     if !active_animation.paused {
                         if let Some(ref clip_handle) = node.clip {
                             if let Some(clip) = animation_clips.get(clip_handle) {
                                 active_animation.update(delta_seconds, clip.duration);
+
+                                if let Some(events) = clip.events.get(&AnimationEventTarget::Root) {
+                                    par_commands.command_scope(|mut commands| {
+                                        active_animation.for_each_crossed_event(events, |event| {
+                                            event.event.trigger(&mut commands, entity);
+                                        });
+                                    });
+                                }
                             }
                         }
                     }
@@ -810,6 +1030,7 @@ pub fn advance_animations(
                 if let Some(active_animation) = active_animations.get_mut(&node_index) {
                     active_animation.computed_weight = weight;
                     active_animation.computed_mask = mask;
+                    active_animation.computed_additive = additive;
                 }
 
                 // Push children.
@@ -824,16 +1045,24 @@ pub fn advance_animations(
 
 /// A system that modifies animation targets (e.g. bones in a skinned mesh)
 /// according to the currently-playing animation.
+///
+/// This also triggers the [`AnimationEvent`]s that target the animation
+/// targets.
 pub fn animate_targets(
     clips: Res<Assets<AnimationClip>>,
     graphs: Res<Assets<AnimationGraph>>,
-    players: Query<(&AnimationPlayer, &Handle<AnimationGraph>)>,
+    players: Query<(
+        &AnimationPlayer,
+        &Handle<AnimationGraph>,
+        Option<&RootMotion>,
+    )>,
     mut targets: Query<(
         Entity,
         &AnimationTarget,
         Option<&Name>,
         AnyOf<(&mut Transform, &mut MorphWeights)>,
     )>,
+    par_commands: ParallelCommands,
 ) {
     // We use two queries here: one read-only query for animation players and
     // one read-write query for animation targets (e.g. bones). The
@@ -844,7 +1073,9 @@ pub fn animate_targets(
     targets
         .par_iter_mut()
         .for_each(|(id, target, name, (transform, morph_weights))| {
-            let Ok((animation_player, animation_graph_handle)) = players.get(target.player) else {
+            let Ok((animation_player, animation_graph_handle, root_motion)) =
+                players.get(target.player)
+            else {
                 trace!(
                     "Either an animation player {:?} or a graph was missing for the target \
                      entity {:?} ({:?}); no animations will play this frame",
@@ -866,6 +1097,8 @@ pub fn animate_targets(
                 name,
                 transform,
                 morph_weights,
+                is_motion_root: root_motion
+                    .is_some_and(|root_motion| root_motion.target == target.id),
             };
 
             // Determine which mask groups this animation target belongs to.
@@ -891,46 +1124,212 @@ pub fn animate_targets(
             //
             // Each step of the following loop corresponds to one of the lerp
             // operations above.
+            //
+            // Additive animations are applied afterward, on top of the blended
+            // result, so they're skipped in the first pass.
             let mut total_weight = 0.0;
-            for (&animation_graph_node_index, active_animation) in
-                animation_player.active_animations.iter()
-            {
-                // If the weight is zero or the current animation target is
-                // masked out, stop here.
-                if active_animation.weight == 0.0
-                    || (target_mask & active_animation.computed_mask) != 0
+            for additive_pass in [false, true] {
+                for (&animation_graph_node_index, active_animation) in
+                    animation_player.active_animations.iter()
                 {
-                    continue;
+                    let Some(clip) = animation_graph
+                        .get(animation_graph_node_index)
+                        .and_then(|animation_graph_node| animation_graph_node.clip.as_ref())
+                        .and_then(|animation_clip_handle| clips.get(animation_clip_handle))
+                    else {
+                        continue;
+                    };
+
+                    // Fire the events for this target that the animation passed
+                    // over, regardless of its weight.
+                    if !additive_pass {
+                        if let Some(events) = clip
+                            .events
+                            .get(&AnimationEventTarget::Node(target_context.target.id))
+                        {
+                            par_commands.command_scope(|mut commands| {
+                                active_animation.for_each_crossed_event(events, |event| {
+                                    event.event.trigger(&mut commands, target_context.entity);
+                                });
+                            });
+                        }
+                    }
+
+                    // If the weight is zero, the animation belongs to the other
+                    // pass, or the current animation target is masked out, stop
+                    // here.
+                    if active_animation.weight == 0.0
+                        || active_animation.computed_additive != additive_pass
+                        || (target_mask & active_animation.computed_mask) != 0
+                    {
+                        continue;
+                    }
+
+                    let Some(curves) = clip.curves_for_target(target_context.target.id) else {
+                        continue;
+                    };
+
+                    let weight = active_animation.computed_weight;
+                    let blend = if additive_pass {
+                        KeyframeBlend::Additive(weight)
+                    } else {
+                        total_weight += weight;
+                        KeyframeBlend::Interpolate(weight / total_weight)
+                    };
+
+                    target_context.apply(curves, blend, active_animation.seek_time);
                 }
+            }
+        });
+}
 
-                let Some(clip) = animation_graph
-                    .get(animation_graph_node_index)
-                    .and_then(|animation_graph_node| animation_graph_node.clip.as_ref())
-                    .and_then(|animation_clip_handle| clips.get(animation_clip_handle))
-                else {
-                    continue;
-                };
+/// An [`AnimationCurve`] evaluation scheduled by [`animate_properties`].
+pub struct PropertyAnimation {
+    entity: Entity,
+    target_id: AnimationTargetId,
+    clip: AssetId<AnimationClip>,
+    seek_time: f32,
+    weight: f32,
+}
+
+/// A system that modifies the reflected fields of animation targets according
+/// to the [`AnimationCurve`]s of the currently-playing animations.
+///
+/// Because these curves can animate any component or asset, this system needs
+/// exclusive access to the world. The animations to apply are gathered first,
+/// and then applied one after another, blending them in the same way as
+/// [`animate_targets`].
+pub fn animate_properties(
+    world: &mut World,
+    state: &mut SystemState<(
+        Query<(Entity, &AnimationTarget)>,
+        Query<(&AnimationPlayer, &Handle<AnimationGraph>)>,
+        Res<Assets<AnimationClip>>,
+        Res<Assets<AnimationGraph>>,
+    )>,
+    mut scratch: Local<Vec<PropertyAnimation>>,
+) {
+    let Some(registry) = world.get_resource::<AppTypeRegistry>().cloned() else {
+        return;
+    };
+
+    // We reuse the allocation across frames.
+    let mut animations = mem::take(&mut *scratch);
+
+    let (targets, players, clips, graphs) = state.get(world);
+    for (entity, target) in &targets {
+        let Ok((animation_player, animation_graph_handle)) = players.get(target.player) else {
+            continue;
+        };
+        let Some(animation_graph) = graphs.get(animation_graph_handle) else {
+            continue;
+        };
+
+        let target_mask = animation_graph
+            .mask_groups
+            .get(&target.id)
+            .cloned()
+            .unwrap_or_default();
 
-                let Some(curves) = clip.curves_for_target(target_context.target.id) else {
+        for (&animation_graph_node_index, active_animation) in
+            animation_player.active_animations.iter()
+        {
+            if active_animation.weight == 0.0 || (target_mask & active_animation.computed_mask) != 0
+            {
+                continue;
+            }
+
+            let Some(clip_handle) = animation_graph
+                .get(animation_graph_node_index)
+                .and_then(|animation_graph_node| animation_graph_node.clip.as_ref())
+            else {
+                continue;
+            };
+            if !clips
+                .get(clip_handle)
+                .is_some_and(|clip| clip.property_curves.contains_key(&target.id))
+            {
+                continue;
+            }
+            if active_animation.computed_additive {
+                warn_once!(
+                    "Additive blending isn't supported for animation curves of reflected \
+                    properties, so they are ignored in additive animation graph nodes"
+                );
+                continue;
+            }
+
+            animations.push(PropertyAnimation {
+                entity,
+                target_id: target.id,
+                clip: clip_handle.id(),
+                seek_time: active_animation.seek_time,
+                weight: active_animation.computed_weight,
+            });
+        }
+    }
+
+    if !animations.is_empty() {
+        world.resource_scope(|world, clips: Mut<Assets<AnimationClip>>| {
+            let registry = registry.read();
+
+            // The animations of each target are contiguous, so we accumulate
+            // the weights per target as in `animate_targets`.
+            let mut current_entity = None;
+            let mut total_weight = 0.0;
+            for animation in animations.drain(..) {
+                if current_entity != Some(animation.entity) {
+                    current_entity = Some(animation.entity);
+                    total_weight = 0.0;
+                }
+
+                let Some(curves) = clips
+                    .get(animation.clip)
+                    .and_then(|clip| clip.property_curves_for_target(animation.target_id))
+                else {
                     continue;
                 };
 
-                let weight = active_animation.computed_weight;
-                total_weight += weight;
-
-                target_context.apply(curves, weight / total_weight, active_animation.seek_time);
+                total_weight += animation.weight;
+                for curve in curves {
+                    if let Err(err) = curve.apply(
+                        world,
+                        animation.entity,
+                        &registry,
+                        animation.seek_time,
+                        animation.weight / total_weight,
+                    ) {
+                        error!("Failed to animate {:?}: {}", animation.entity, err);
+                    }
+                }
             }
         });
+    }
+
+    *scratch = animations;
 }
 
 impl AnimationTargetContext<'_> {
     /// Applies a clip to a single animation target according to the
     /// [`AnimationTargetContext`].
-    fn apply(&mut self, curves: &[VariableCurve], weight: f32, seek_time: f32) {
+    fn apply(&mut self, curves: &[VariableCurve], blend: KeyframeBlend, seek_time: f32) {
         for curve in curves {
+            // The motion of the root bone is extracted by
+            // `extract_root_motion` instead, so hold it at the start.
+            let seek_time = match curve.keyframes {
+                Keyframes::Translation(_)
+                | Keyframes::Rotation(_)
+                | Keyframes::CompressedRotation(_)
+                    if self.is_motion_root =>
+                {
+                    curve.keyframe_timestamps[0]
+                }
+                _ => seek_time,
+            };
+
             // Some curves have only one keyframe used to set a transform
             if curve.keyframe_timestamps.len() == 1 {
-                self.apply_single_keyframe(curve, weight);
+                self.apply_single_keyframe(curve, blend);
                 continue;
             }
 
@@ -946,29 +1345,42 @@ impl AnimationTargetContext<'_> {
                 curve,
                 step_start,
                 lerp,
-                weight,
+                blend,
                 timestamp_end - timestamp_start,
             );
         }
     }
 
-    fn apply_single_keyframe(&mut self, curve: &VariableCurve, weight: f32) {
+    fn apply_single_keyframe(&mut self, curve: &VariableCurve, blend: KeyframeBlend) {
         match &curve.keyframes {
             Keyframes::Rotation(keyframes) => {
                 if let Some(ref mut transform) = self.transform {
-                    transform.rotation = transform.rotation.slerp(keyframes[0], weight);
+                    transform.rotation =
+                        blend.rotation(transform.rotation, keyframes[0], keyframes[0]);
+                }
+            }
+
+            Keyframes::CompressedRotation(keyframes) => {
+                if let Some(ref mut transform) = self.transform {
+                    let value = match curve.interpolation {
+                        Interpolation::CubicSpline => 1,
+                        Interpolation::Step | Interpolation::Linear => 0,
+                    };
+                    let rotation = keyframes[value].to_quat();
+                    transform.rotation = blend.rotation(transform.rotation, rotation, rotation);
                 }
             }
 
             Keyframes::Translation(keyframes) => {
                 if let Some(ref mut transform) = self.transform {
-                    transform.translation = transform.translation.lerp(keyframes[0], weight);
+                    transform.translation =
+                        blend.translation(transform.translation, keyframes[0], keyframes[0]);
                 }
             }
 
             Keyframes::Scale(keyframes) => {
                 if let Some(ref mut transform) = self.transform {
-                    transform.scale = transform.scale.lerp(keyframes[0], weight);
+                    transform.scale = blend.scale(transform.scale, keyframes[0], keyframes[0]);
                 }
             }
 
@@ -982,11 +1394,8 @@ impl AnimationTargetContext<'_> {
                 };
 
                 let target_count = morphs.weights().len();
-                lerp_morph_weights(
-                    morphs.weights_mut(),
-                    get_keyframe(target_count, keyframes, 0).iter().copied(),
-                    weight,
-                );
+                let keyframe = get_keyframe(target_count, keyframes, 0);
+                blend.morph_weights(morphs.weights_mut(), keyframe.iter().copied(), keyframe);
             }
         }
     }
@@ -996,13 +1405,23 @@ impl AnimationTargetContext<'_> {
         curve: &VariableCurve,
         step_start: usize,
         lerp: f32,
-        weight: f32,
+        blend: KeyframeBlend,
         duration: f32,
     ) {
+        // The reference pose of additive animations is the first keyframe.
+        let reference = match curve.interpolation {
+            Interpolation::CubicSpline => 1,
+            Interpolation::Step | Interpolation::Linear => 0,
+        };
+
         match (&curve.interpolation, &curve.keyframes) {
             (Interpolation::Step, Keyframes::Rotation(keyframes)) => {
                 if let Some(ref mut transform) = self.transform {
-                    transform.rotation = transform.rotation.slerp(keyframes[step_start], weight);
+                    transform.rotation = blend.rotation(
+                        transform.rotation,
+                        keyframes[step_start],
+                        keyframes[reference],
+                    );
                 }
             }
 
@@ -1016,7 +1435,38 @@ impl AnimationTargetContext<'_> {
 
                 // Rotations are using a spherical linear interpolation
                 let rot = rot_start.slerp(rot_end, lerp);
-                transform.rotation = transform.rotation.slerp(rot, weight);
+                transform.rotation = blend.rotation(transform.rotation, rot, keyframes[reference]);
+            }
+
+            (Interpolation::Step, Keyframes::CompressedRotation(keyframes)) => {
+                if let Some(ref mut transform) = self.transform {
+                    transform.rotation = blend.rotation(
+                        transform.rotation,
+                        keyframes[step_start].to_quat(),
+                        keyframes[0].to_quat(),
+                    );
+                }
+            }
+
+            (
+                Interpolation::Linear | Interpolation::CubicSpline,
+                Keyframes::CompressedRotation(keyframes),
+            ) => {
+                let Some(ref mut transform) = self.transform else {
+                    return;
+                };
+
+                // Only the values of cubic spline keyframes are used, skipping
+                // their tangents.
+                let (start, end) = match curve.interpolation {
+                    Interpolation::CubicSpline => (step_start * 3 + 1, (step_start + 1) * 3 + 1),
+                    Interpolation::Step | Interpolation::Linear => (step_start, step_start + 1),
+                };
+                let rot_start = keyframes[start].to_quat();
+                let rot_end = keyframes[end].to_quat();
+                let rot = rot_start.slerp(rot_end, lerp);
+                transform.rotation =
+                    blend.rotation(transform.rotation, rot, keyframes[reference].to_quat());
             }
 
             (Interpolation::CubicSpline, Keyframes::Rotation(keyframes)) => {
@@ -1036,13 +1486,17 @@ impl AnimationTargetContext<'_> {
                     lerp,
                     duration,
                 );
-                transform.rotation = transform.rotation.slerp(result.normalize(), weight);
+                transform.rotation =
+                    blend.rotation(transform.rotation, result.normalize(), keyframes[reference]);
             }
 
             (Interpolation::Step, Keyframes::Translation(keyframes)) => {
                 if let Some(ref mut transform) = self.transform {
-                    transform.translation =
-                        transform.translation.lerp(keyframes[step_start], weight);
+                    transform.translation = blend.translation(
+                        transform.translation,
+                        keyframes[step_start],
+                        keyframes[reference],
+                    );
                 }
             }
 
@@ -1054,7 +1508,8 @@ impl AnimationTargetContext<'_> {
                 let translation_start = keyframes[step_start];
                 let translation_end = keyframes[step_start + 1];
                 let result = translation_start.lerp(translation_end, lerp);
-                transform.translation = transform.translation.lerp(result, weight);
+                transform.translation =
+                    blend.translation(transform.translation, result, keyframes[reference]);
             }
 
             (Interpolation::CubicSpline, Keyframes::Translation(keyframes)) => {
@@ -1074,12 +1529,14 @@ impl AnimationTargetContext<'_> {
                     lerp,
                     duration,
                 );
-                transform.translation = transform.translation.lerp(result, weight);
+                transform.translation =
+                    blend.translation(transform.translation, result, keyframes[reference]);
             }
 
             (Interpolation::Step, Keyframes::Scale(keyframes)) => {
                 if let Some(ref mut transform) = self.transform {
-                    transform.scale = transform.scale.lerp(keyframes[step_start], weight);
+                    transform.scale =
+                        blend.scale(transform.scale, keyframes[step_start], keyframes[reference]);
                 }
             }
 
@@ -1091,7 +1548,7 @@ impl AnimationTargetContext<'_> {
                 let scale_start = keyframes[step_start];
                 let scale_end = keyframes[step_start + 1];
                 let result = scale_start.lerp(scale_end, lerp);
-                transform.scale = transform.scale.lerp(result, weight);
+                transform.scale = blend.scale(transform.scale, result, keyframes[reference]);
             }
 
             (Interpolation::CubicSpline, Keyframes::Scale(keyframes)) => {
@@ -1111,7 +1568,7 @@ impl AnimationTargetContext<'_> {
                     lerp,
                     duration,
                 );
-                transform.scale = transform.scale.lerp(result, weight);
+                transform.scale = blend.scale(transform.scale, result, keyframes[reference]);
             }
 
             (Interpolation::Step, Keyframes::Weights(keyframes)) => {
@@ -1121,7 +1578,11 @@ impl AnimationTargetContext<'_> {
 
                 let target_count = morphs.weights().len();
                 let morph_start = get_keyframe(target_count, keyframes, step_start);
-                lerp_morph_weights(morphs.weights_mut(), morph_start.iter().copied(), weight);
+                blend.morph_weights(
+                    morphs.weights_mut(),
+                    morph_start.iter().copied(),
+                    get_keyframe(target_count, keyframes, reference),
+                );
             }
 
             (Interpolation::Linear, Keyframes::Weights(keyframes)) => {
@@ -1136,7 +1597,11 @@ impl AnimationTargetContext<'_> {
                     .iter()
                     .zip(morph_end)
                     .map(|(a, b)| a.lerp(*b, lerp));
-                lerp_morph_weights(morphs.weights_mut(), result, weight);
+                blend.morph_weights(
+                    morphs.weights_mut(),
+                    result,
+                    get_keyframe(target_count, keyframes, reference),
+                );
             }
 
             (Interpolation::CubicSpline, Keyframes::Weights(keyframes)) => {
@@ -1166,18 +1631,71 @@ impl AnimationTargetContext<'_> {
                             )
                         },
                     );
-                lerp_morph_weights(morphs.weights_mut(), result, weight);
+                blend.morph_weights(
+                    morphs.weights_mut(),
+                    result,
+                    get_keyframe(target_count, keyframes, reference),
+                );
             }
         }
     }
 }
 
-/// Update `weights` based on weights in `keyframe` with a linear interpolation
-/// on `key_lerp`.
-fn lerp_morph_weights(weights: &mut [f32], keyframe: impl Iterator<Item = f32>, key_lerp: f32) {
-    let zipped = weights.iter_mut().zip(keyframe);
-    for (morph_weight, keyframe) in zipped {
-        *morph_weight = morph_weight.lerp(keyframe, key_lerp);
+/// How an animation blends its keyframes into the values that the animations
+/// before it have produced.
+#[derive(Clone, Copy)]
+enum KeyframeBlend {
+    /// Interpolates from the current value toward the keyframe by the weight.
+    Interpolate(f32),
+    /// Adds the difference between the keyframe and the reference keyframe,
+    /// scaled by the weight, on top of the current value.
+    Additive(f32),
+}
+
+impl KeyframeBlend {
+    fn rotation(self, current: Quat, value: Quat, reference: Quat) -> Quat {
+        match self {
+            KeyframeBlend::Interpolate(weight) => current.slerp(value, weight),
+            KeyframeBlend::Additive(weight) => {
+                let delta = reference.inverse() * value;
+                (current * Quat::IDENTITY.slerp(delta, weight)).normalize()
+            }
+        }
+    }
+
+    fn translation(self, current: Vec3, value: Vec3, reference: Vec3) -> Vec3 {
+        match self {
+            KeyframeBlend::Interpolate(weight) => current.lerp(value, weight),
+            KeyframeBlend::Additive(weight) => current + (value - reference) * weight,
+        }
+    }
+
+    fn scale(self, current: Vec3, value: Vec3, reference: Vec3) -> Vec3 {
+        match self {
+            KeyframeBlend::Interpolate(weight) => current.lerp(value, weight),
+            KeyframeBlend::Additive(weight) => {
+                // Components whose reference is zero can't be scaled relative
+                // to it, so leave them alone.
+                let ratio = Vec3::select(reference.cmpeq(Vec3::ZERO), Vec3::ONE, value / reference);
+                current * Vec3::ONE.lerp(ratio, weight)
+            }
+        }
+    }
+
+    /// Update `weights` based on weights in `keyframe`.
+    fn morph_weights(
+        self,
+        weights: &mut [f32],
+        keyframe: impl Iterator<Item = f32>,
+        reference: &[f32],
+    ) {
+        let zipped = weights.iter_mut().zip(keyframe).zip(reference);
+        for ((morph_weight, keyframe), &reference) in zipped {
+            *morph_weight = match self {
+                KeyframeBlend::Interpolate(weight) => morph_weight.lerp(keyframe, weight),
+                KeyframeBlend::Additive(weight) => *morph_weight + (keyframe - reference) * weight,
+            };
+        }
     }
 }
 
@@ -1222,19 +1740,39 @@ impl Plugin for AnimationPlugin {
     fn build(&self, app: &mut App) {
         app.init_asset::<AnimationClip>()
             .init_asset::<AnimationGraph>()
+            .init_asset::<AnimationStateMachine>()
+            .init_asset::<AnimationRetarget>()
             .init_asset_loader::<AnimationGraphAssetLoader>()
+            .init_asset_loader::<AnimationStateMachineAssetLoader>()
+            .init_asset_loader::<AnimationRetargetAssetLoader>()
+            .init_asset_loader::<AnimationClipAssetLoader>()
+            .register_asset_processor::<AnimationClipCompressionProcessor>(
+                LoadTransformAndSave::new(AnimationCompressor::default(), AnimationClipSaver),
+            )
             .register_asset_reflect::<AnimationClip>()
             .register_asset_reflect::<AnimationGraph>()
+            .register_asset_reflect::<AnimationStateMachine>()
+            .register_asset_reflect::<AnimationRetarget>()
             .register_type::<AnimationPlayer>()
             .register_type::<AnimationTarget>()
             .register_type::<AnimationTransitions>()
+            .register_type::<AnimationStateMachineController>()
+            .register_type::<TwoBoneIk>()
+            .register_type::<IkChain>()
+            .register_type::<LookAt>()
+            .register_type::<RootMotion>()
             .register_type::<NodeIndex>()
             .add_systems(
                 PostUpdate,
                 (
+                    advance_state_machines,
                     advance_transitions,
                     advance_animations,
+                    extract_root_motion,
                     animate_targets.after(bevy_render::mesh::morph::inherit_weights),
+                    animate_properties,
+                    advance_tweens.in_set(TweenSystem),
+                    solve_inverse_kinematics,
                     expire_completed_transitions,
                 )
                     .chain()
@@ -1288,13 +1826,16 @@ impl AnimationGraphEvaluator {
         self.nodes.clear();
         self.nodes
             .extend(iter::repeat(EvaluatedAnimationGraphNode::default()).take(node_count));
+
+        self.blend_space_weights.clear();
+        self.blend_space_weights.resize(node_count, 1.0);
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::VariableCurve;
-    use bevy_math::Vec3;
+    use crate::{KeyframeBlend, VariableCurve};
+    use bevy_math::{Quat, Vec3};
 
     fn test_variable_curve() -> VariableCurve {
         let keyframe_timestamps = vec![1.0, 2.0, 3.0, 4.0];
@@ -1438,4 +1979,33 @@ mod tests {
             assert!(exact_keyframe == inexact_keyframe);
         }
     }
+
+    #[test]
+    fn additive_keyframe_blend() {
+        let blend = KeyframeBlend::Additive(0.5);
+
+        let translation = blend.translation(Vec3::X, Vec3::new(0.0, 4.0, 0.0), Vec3::Y);
+        assert_eq!(translation, Vec3::new(1.0, 1.5, 0.0));
+
+        let scale = blend.scale(
+            Vec3::splat(2.0),
+            Vec3::new(3.0, 1.0, 5.0),
+            Vec3::new(1.0, 1.0, 0.0),
+        );
+        assert_eq!(scale, Vec3::new(4.0, 2.0, 2.0));
+
+        let rotation = blend.rotation(
+            Quat::from_rotation_y(1.0),
+            Quat::from_rotation_z(1.5),
+            Quat::from_rotation_z(0.5),
+        );
+        assert!(rotation.abs_diff_eq(
+            Quat::from_rotation_y(1.0) * Quat::from_rotation_z(0.5),
+            1e-5
+        ));
+
+        let mut weights = [0.5, 0.5];
+        blend.morph_weights(&mut weights, [1.0, 0.0].into_iter(), &[0.0, 0.0]);
+        assert_eq!(weights, [1.0, 0.5]);
+    }
 }
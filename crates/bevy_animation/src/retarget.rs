@@ -0,0 +1,697 @@
+//! Retargeting, which adapts animation clips authored for one skeleton to
+//! skeletons with different bone names and proportions.
+//!
+//! [`AnimationClip`]s refer to bones by [`AnimationTargetId`], which is derived
+//! from the names of the bones, so a clip only plays on skeletons whose bones
+//! have the same names. An [`AnimationRetarget`] asset maps the bones of a
+//! source skeleton to the bones of a target skeleton, either by name or through
+//! a [`HumanoidProfile`] for each. Given the rest poses of both skeletons as
+//! [`RetargetSkeleton`]s, [`AnimationRetarget::retarget_clip`] then creates a
+//! clip for the target skeleton that compensates for the differences between
+//! the rest poses and the lengths of the bones.
+//!
+//! The rest poses of both skeletons should be similar, for example both in a
+//! T-pose, since the clip is transferred as the motion of each bone relative
+//! to its rest pose.
+
+use std::io::{self, Write};
+
+use bevy_asset::{io::Reader, Asset, AssetLoader, LoadContext};
+use bevy_core::Name;
+use bevy_ecs::{entity::Entity, world::World};
+use bevy_hierarchy::Children;
+use bevy_math::{Quat, Vec3};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect, ReflectDeserialize, ReflectSerialize};
+use bevy_transform::components::Transform;
+use bevy_utils::HashMap;
+use ron::de::SpannedError;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{
+    compression::CompressedQuat, event::AnimationEventTarget, AnimationClip, AnimationTarget,
+    AnimationTargetId, Interpolation, Keyframes, VariableCurve,
+};
+
+/// Maps the bones of a source skeleton to the bones of a target skeleton, so
+/// that animation clips authored for the source can play on the target.
+///
+/// Bones are mapped, in order of priority:
+///
+/// 1. Explicitly by name, with [`AnimationRetarget::bones`].
+///
+/// 2. Through the [`HumanoidBone`] that the source bone has in the
+///    [`AnimationRetarget::source_profile`] and the name that humanoid bone has
+///    in the [`AnimationRetarget::target_profile`].
+///
+/// 3. To the target bone with the same name, if any.
+///
+/// Retargets are assets and can be serialized to and loaded from [RON] files.
+/// Canonically, such files have a `.retarget.ron` extension:
+///
+/// ```ron
+/// (
+///     bones: {
+///         "Hips": "pelvis",
+///         "Spine": "spine_01",
+///     },
+///     source_profile: Some((
+///         bones: {
+///             LeftUpperArm: "Arm.L",
+///             LeftLowerArm: "Forearm.L",
+///         },
+///     )),
+///     target_profile: Some((
+///         bones: {
+///             LeftUpperArm: "upperarm_l",
+///             LeftLowerArm: "lowerarm_l",
+///         },
+///     )),
+/// )
+/// ```
+///
+/// [RON]: https://github.com/ron-rs/ron
+#[derive(Asset, Reflect, Clone, Debug, Default, Serialize, Deserialize)]
+#[reflect(Serialize, Deserialize, Debug, Default)]
+pub struct AnimationRetarget {
+    /// Maps the names of source bones to the names of target bones.
+    #[serde(default)]
+    pub bones: HashMap<String, String>,
+    /// The humanoid profile of the source skeleton.
+    #[serde(default)]
+    pub source_profile: Option<HumanoidProfile>,
+    /// The humanoid profile of the target skeleton.
+    #[serde(default)]
+    pub target_profile: Option<HumanoidProfile>,
+}
+
+/// Names the bones of a humanoid skeleton by their role, so that skeletons
+/// with different naming conventions can be mapped to each other.
+#[derive(Reflect, Clone, Debug, Default, Serialize, Deserialize)]
+#[reflect(Serialize, Deserialize, Debug, Default)]
+pub struct HumanoidProfile {
+    /// The names of the bones of the skeleton, by role.
+    pub bones: HashMap<HumanoidBone, String>,
+}
+
+/// The role of a bone in a humanoid skeleton.
+#[derive(Reflect, Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[reflect(Serialize, Deserialize, Debug, PartialEq, Hash)]
+#[allow(missing_docs)]
+pub enum HumanoidBone {
+    Hips,
+    Spine,
+    Chest,
+    UpperChest,
+    Neck,
+    Head,
+    LeftShoulder,
+    LeftUpperArm,
+    LeftLowerArm,
+    LeftHand,
+    RightShoulder,
+    RightUpperArm,
+    RightLowerArm,
+    RightHand,
+    LeftUpperLeg,
+    LeftLowerLeg,
+    LeftFoot,
+    LeftToes,
+    RightUpperLeg,
+    RightLowerLeg,
+    RightFoot,
+    RightToes,
+}
+
+/// The rest pose of a skeleton that clips are retargeted from or to.
+///
+/// This is usually captured from a spawned scene with
+/// [`RetargetSkeleton::from_hierarchy`] before any animation plays.
+#[derive(Clone, Debug, Default)]
+pub struct RetargetSkeleton {
+    bones: HashMap<String, RetargetBone>,
+}
+
+/// The rest pose of a bone in a [`RetargetSkeleton`].
+#[derive(Clone, Copy, Debug)]
+pub struct RetargetBone {
+    /// The ID that animation clips use to refer to this bone.
+    pub id: AnimationTargetId,
+    /// The local transform of the bone in the rest pose.
+    pub rest: Transform,
+    /// The rotation of the parent of the bone in the rest pose, relative to
+    /// the root of the skeleton.
+    pub parent_rotation: Quat,
+}
+
+/// Various errors that can occur when serializing or deserializing animation
+/// retargets to and from RON, respectively.
+#[derive(Error, Debug)]
+pub enum AnimationRetargetLoadError {
+    /// An I/O error occurred.
+    #[error("I/O")]
+    Io(#[from] io::Error),
+    /// An error occurred in RON serialization or deserialization.
+    #[error("RON serialization")]
+    Ron(#[from] ron::Error),
+    /// An error occurred in RON deserialization, and the location of the error
+    /// is supplied.
+    #[error("RON serialization")]
+    SpannedRon(#[from] SpannedError),
+}
+
+/// An [`AssetLoader`] that can load [`AnimationRetarget`]s as assets.
+///
+/// The canonical extension for [`AnimationRetarget`]s is `.retarget.ron`.
+#[derive(Default)]
+pub struct AnimationRetargetAssetLoader;
+
+impl AnimationRetarget {
+    /// Creates a new retarget that maps bones to the bones with the same name.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a new retarget that maps bones through the given humanoid
+    /// profiles of the source and target skeletons.
+    pub fn from_humanoid_profiles(source: HumanoidProfile, target: HumanoidProfile) -> Self {
+        Self {
+            bones: HashMap::default(),
+            source_profile: Some(source),
+            target_profile: Some(target),
+        }
+    }
+
+    /// Maps the source bone with the given name to the target bone with the
+    /// given name.
+    pub fn map_bone(&mut self, source: impl Into<String>, target: impl Into<String>) -> &mut Self {
+        self.bones.insert(source.into(), target.into());
+        self
+    }
+
+    /// Returns the name of the target bone that the source bone with the given
+    /// name maps to.
+    pub fn target_bone<'a>(&'a self, source: &'a str) -> &'a str {
+        if let Some(target) = self.bones.get(source) {
+            return target;
+        }
+        if let (Some(source_profile), Some(target_profile)) =
+            (&self.source_profile, &self.target_profile)
+        {
+            if let Some(target) = source_profile
+                .humanoid_bone(source)
+                .and_then(|bone| target_profile.bone_name(bone))
+            {
+                return target;
+            }
+        }
+        source
+    }
+
+    /// Creates a clip for the `target` skeleton that plays the given clip
+    /// authored for the `source` skeleton.
+    ///
+    /// Each mapped bone is rotated relative to its rest pose in the same way
+    /// as in the original clip, taking the different orientations of the bones
+    /// in their rest poses into account. Translations relative to the rest
+    /// pose are scaled by the ratio between the lengths of the bones, so that,
+    /// for example, the hips of a taller character move further, and scales
+    /// are applied relative to the rest scale.
+    ///
+    /// Events and [`crate::animation_curves::AnimationCurve`]s of mapped bones
+    /// are copied over as-is. Curves of bones without a target are dropped,
+    /// and target bones without a source stay in their rest pose.
+    pub fn retarget_clip(
+        &self,
+        clip: &AnimationClip,
+        source: &RetargetSkeleton,
+        target: &RetargetSkeleton,
+    ) -> AnimationClip {
+        let mut retargeted = AnimationClip {
+            duration: clip.duration,
+            ..AnimationClip::default()
+        };
+
+        let mut target_ids = HashMap::default();
+        for (name, source_bone) in &source.bones {
+            let Some(target_bone) = target.bone(self.target_bone(name)) else {
+                continue;
+            };
+            target_ids.insert(source_bone.id, target_bone.id);
+
+            let Some(curves) = clip.curves_for_target(source_bone.id) else {
+                continue;
+            };
+            let mapping = BoneMapping::new(source_bone, target_bone);
+            retargeted
+                .curves
+                .entry(target_bone.id)
+                .or_default()
+                .extend(curves.iter().map(|curve| mapping.retarget_curve(curve)));
+        }
+
+        for (id, curves) in &clip.property_curves {
+            if let Some(&target_id) = target_ids.get(id) {
+                retargeted.property_curves.insert(target_id, curves.clone());
+            }
+        }
+
+        for (event_target, events) in &clip.events {
+            let event_target = match *event_target {
+                AnimationEventTarget::Root => AnimationEventTarget::Root,
+                AnimationEventTarget::Node(id) => match target_ids.get(&id) {
+                    Some(&target_id) => AnimationEventTarget::Node(target_id),
+                    None => continue,
+                },
+            };
+            retargeted.events.insert(event_target, events.clone());
+        }
+
+        retargeted
+    }
+
+    /// Serializes the retarget to the given [`Write`]r in RON format.
+    ///
+    /// If writing to a file, it can later be loaded with the
+    /// [`AnimationRetargetAssetLoader`] to reconstruct the retarget.
+    pub fn save<W>(&self, writer: &mut W) -> Result<(), AnimationRetargetLoadError>
+    where
+        W: Write,
+    {
+        let mut ron_serializer = ron::ser::Serializer::new(writer, None)?;
+        Ok(self.serialize(&mut ron_serializer)?)
+    }
+}
+
+impl HumanoidProfile {
+    /// Creates a new humanoid profile with no bones.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The humanoid profile of skeletons exported from [Mixamo].
+    ///
+    /// [Mixamo]: https://www.mixamo.com
+    pub fn mixamo() -> Self {
+        use HumanoidBone::*;
+
+        let mut profile = Self::new();
+        for (bone, name) in [
+            (Hips, "Hips"),
+            (Spine, "Spine"),
+            (Chest, "Spine1"),
+            (UpperChest, "Spine2"),
+            (Neck, "Neck"),
+            (Head, "Head"),
+            (LeftShoulder, "LeftShoulder"),
+            (LeftUpperArm, "LeftArm"),
+            (LeftLowerArm, "LeftForeArm"),
+            (LeftHand, "LeftHand"),
+            (RightShoulder, "RightShoulder"),
+            (RightUpperArm, "RightArm"),
+            (RightLowerArm, "RightForeArm"),
+            (RightHand, "RightHand"),
+            (LeftUpperLeg, "LeftUpLeg"),
+            (LeftLowerLeg, "LeftLeg"),
+            (LeftFoot, "LeftFoot"),
+            (LeftToes, "LeftToeBase"),
+            (RightUpperLeg, "RightUpLeg"),
+            (RightLowerLeg, "RightLeg"),
+            (RightFoot, "RightFoot"),
+            (RightToes, "RightToeBase"),
+        ] {
+            profile.set_bone(bone, format!("mixamorig:{name}"));
+        }
+        profile
+    }
+
+    /// Sets the name of the bone with the given role.
+    pub fn set_bone(&mut self, bone: HumanoidBone, name: impl Into<String>) -> &mut Self {
+        self.bones.insert(bone, name.into());
+        self
+    }
+
+    /// Returns the name of the bone with the given role, if any.
+    pub fn bone_name(&self, bone: HumanoidBone) -> Option<&str> {
+        self.bones.get(&bone).map(String::as_str)
+    }
+
+    /// Returns the role of the bone with the given name, if any.
+    pub fn humanoid_bone(&self, name: &str) -> Option<HumanoidBone> {
+        self.bones
+            .iter()
+            .find(|(_, bone_name)| *bone_name == name)
+            .map(|(&bone, _)| bone)
+    }
+}
+
+impl RetargetSkeleton {
+    /// Creates a new skeleton with no bones.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Captures the skeleton below `root` in its current pose, which should be
+    /// the rest pose.
+    ///
+    /// Every descendant of `root` with an [`AnimationTarget`] and a [`Name`]
+    /// becomes a bone. Rotations are relative to `root`.
+    pub fn from_hierarchy(world: &World, root: Entity) -> Self {
+        let mut skeleton = Self::new();
+        let mut stack = vec![];
+        let push_children = |stack: &mut Vec<(Entity, Quat)>, entity: Entity, rotation: Quat| {
+            if let Some(children) = world.get::<Children>(entity) {
+                stack.extend(children.iter().map(|&child| (child, rotation)));
+            }
+        };
+
+        push_children(&mut stack, root, Quat::IDENTITY);
+        while let Some((entity, parent_rotation)) = stack.pop() {
+            let rest = world.get::<Transform>(entity).copied().unwrap_or_default();
+            if let (Some(target), Some(name)) = (
+                world.get::<AnimationTarget>(entity),
+                world.get::<Name>(entity),
+            ) {
+                skeleton.add_bone(
+                    name.as_str(),
+                    RetargetBone {
+                        id: target.id,
+                        rest,
+                        parent_rotation,
+                    },
+                );
+            }
+            push_children(&mut stack, entity, parent_rotation * rest.rotation);
+        }
+        skeleton
+    }
+
+    /// Adds a bone with the given name.
+    pub fn add_bone(&mut self, name: impl Into<String>, bone: RetargetBone) -> &mut Self {
+        self.bones.insert(name.into(), bone);
+        self
+    }
+
+    /// Returns the bone with the given name, if any.
+    pub fn bone(&self, name: &str) -> Option<&RetargetBone> {
+        self.bones.get(name)
+    }
+}
+
+/// How the keyframes of one source bone map to a target bone.
+///
+/// A source rotation `q` maps to `rotation_before * q * rotation_after`, which
+/// applies the rotation that `q` makes relative to the rest pose of the source
+/// bone, in the space of the skeleton, to the rest pose of the target bone.
+/// Since this is linear in `q`, tangents map in the same way.
+struct BoneMapping {
+    rotation_before: Quat,
+    rotation_after: Quat,
+    source_translation: Vec3,
+    target_translation: Vec3,
+    translation_scale: f32,
+    scale_factor: Vec3,
+}
+
+impl BoneMapping {
+    fn new(source: &RetargetBone, target: &RetargetBone) -> Self {
+        let rotation_before = target.parent_rotation.inverse() * source.parent_rotation;
+        let rotation_after =
+            source.rest.rotation.inverse() * rotation_before.inverse() * target.rest.rotation;
+
+        let source_length = source.rest.translation.length();
+        let translation_scale = if source_length > f32::EPSILON {
+            target.rest.translation.length() / source_length
+        } else {
+            1.0
+        };
+
+        let scale_factor = Vec3::select(
+            source.rest.scale.cmpeq(Vec3::ZERO),
+            Vec3::ONE,
+            target.rest.scale / source.rest.scale,
+        );
+
+        Self {
+            rotation_before,
+            rotation_after,
+            source_translation: source.rest.translation,
+            target_translation: target.rest.translation,
+            translation_scale,
+            scale_factor,
+        }
+    }
+
+    fn retarget_curve(&self, curve: &VariableCurve) -> VariableCurve {
+        // Cubic spline keyframes are triples of in-tangent, value, and
+        // out-tangent. Tangents are differences, so they aren't offset.
+        let is_value = |index: usize| match curve.interpolation {
+            Interpolation::CubicSpline => index % 3 == 1,
+            Interpolation::Step | Interpolation::Linear => true,
+        };
+
+        let keyframes = match curve.keyframes {
+            Keyframes::Rotation(ref keyframes) => Keyframes::Rotation(
+                keyframes
+                    .iter()
+                    .enumerate()
+                    .map(|(index, &rotation)| {
+                        let rotation = self.rotation_before * rotation * self.rotation_after;
+                        if is_value(index) {
+                            rotation.normalize()
+                        } else {
+                            rotation
+                        }
+                    })
+                    .collect(),
+            ),
+            Keyframes::CompressedRotation(ref keyframes) => Keyframes::CompressedRotation(
+                keyframes
+                    .iter()
+                    .map(|rotation| {
+                        CompressedQuat::from_quat(
+                            self.rotation_before * rotation.to_quat() * self.rotation_after,
+                        )
+                    })
+                    .collect(),
+            ),
+            Keyframes::Translation(ref keyframes) => Keyframes::Translation(
+                keyframes
+                    .iter()
+                    .enumerate()
+                    .map(|(index, &translation)| {
+                        if is_value(index) {
+                            self.target_translation
+                                + self.rotation_before
+                                    * (translation - self.source_translation)
+                                    * self.translation_scale
+                        } else {
+                            self.rotation_before * translation * self.translation_scale
+                        }
+                    })
+                    .collect(),
+            ),
+            Keyframes::Scale(ref keyframes) => Keyframes::Scale(
+                keyframes
+                    .iter()
+                    .map(|&scale| scale * self.scale_factor)
+                    .collect(),
+            ),
+            Keyframes::Weights(ref weights) => Keyframes::Weights(weights.clone()),
+        };
+
+        VariableCurve {
+            keyframe_timestamps: curve.keyframe_timestamps.clone(),
+            keyframes,
+            interpolation: curve.interpolation.clone(),
+        }
+    }
+}
+
+impl AssetLoader for AnimationRetargetAssetLoader {
+    type Asset = AnimationRetarget;
+
+    type Settings = ();
+
+    type Error = AnimationRetargetLoadError;
+
+    async fn load<'a>(
+        &'a self,
+        reader: &'a mut dyn Reader,
+        _: &'a Self::Settings,
+        _: &'a mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+
+        let mut deserializer = ron::de::Deserializer::from_bytes(&bytes)?;
+        AnimationRetarget::deserialize(&mut deserializer)
+            .map_err(|err| deserializer.span_error(err).into())
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["retarget.ron"]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_core::Name;
+    use bevy_ecs::{entity::Entity, world::World};
+    use bevy_hierarchy::BuildChildren;
+    use bevy_math::{Quat, Vec3};
+    use bevy_transform::components::Transform;
+
+    use super::{AnimationRetarget, HumanoidBone, HumanoidProfile, RetargetBone, RetargetSkeleton};
+    use crate::{
+        AnimationClip, AnimationTarget, AnimationTargetId, Interpolation, Keyframes, VariableCurve,
+    };
+
+    fn id(name: &str) -> AnimationTargetId {
+        AnimationTargetId::from_name(&Name::new(name.to_owned()))
+    }
+
+    fn skeleton(bones: &[(&str, Transform, Quat)]) -> RetargetSkeleton {
+        let mut skeleton = RetargetSkeleton::new();
+        for &(name, rest, parent_rotation) in bones {
+            skeleton.add_bone(
+                name,
+                RetargetBone {
+                    id: id(name),
+                    rest,
+                    parent_rotation,
+                },
+            );
+        }
+        skeleton
+    }
+
+    #[test]
+    fn bone_mapping() {
+        let mut retarget = AnimationRetarget::from_humanoid_profiles(HumanoidProfile::mixamo(), {
+            let mut profile = HumanoidProfile::new();
+            profile
+                .set_bone(HumanoidBone::Hips, "pelvis")
+                .set_bone(HumanoidBone::Head, "head");
+            profile
+        });
+        retarget.map_bone("mixamorig:Head", "skull");
+
+        assert_eq!(retarget.target_bone("mixamorig:Hips"), "pelvis");
+        assert_eq!(retarget.target_bone("mixamorig:Head"), "skull");
+        assert_eq!(retarget.target_bone("mixamorig:Neck"), "mixamorig:Neck");
+
+        let mut serialized = vec![];
+        retarget.save(&mut serialized).unwrap();
+        let deserialized: AnimationRetarget = ron::de::from_bytes(&serialized).unwrap();
+        assert_eq!(deserialized.target_bone("mixamorig:Hips"), "pelvis");
+    }
+
+    #[test]
+    fn retarget_clip() {
+        // The target's hips are twice as high, and its arm bone points along
+        // a different axis in the rest pose.
+        let source = skeleton(&[
+            ("Hips", Transform::from_xyz(0.0, 1.0, 0.0), Quat::IDENTITY),
+            ("Arm", Transform::IDENTITY, Quat::IDENTITY),
+        ]);
+        let target_arm_rest = Quat::from_rotation_z(1.0);
+        let target_parent_rotation = Quat::from_rotation_x(0.5);
+        let target = skeleton(&[
+            ("pelvis", Transform::from_xyz(0.0, 2.0, 0.0), Quat::IDENTITY),
+            (
+                "arm",
+                Transform::from_rotation(target_arm_rest),
+                target_parent_rotation,
+            ),
+        ]);
+
+        let source_arm_rotation = Quat::from_rotation_y(0.7);
+        let mut clip = AnimationClip::default();
+        clip.add_curve_to_target(
+            id("Hips"),
+            VariableCurve {
+                keyframe_timestamps: vec![0.0, 1.0],
+                keyframes: Keyframes::Translation(vec![
+                    Vec3::new(0.0, 1.0, 0.0),
+                    Vec3::new(0.0, 1.0, 1.0),
+                ]),
+                interpolation: Interpolation::Linear,
+            },
+        );
+        clip.add_curve_to_target(
+            id("Arm"),
+            VariableCurve {
+                keyframe_timestamps: vec![0.0],
+                keyframes: Keyframes::Rotation(vec![source_arm_rotation]),
+                interpolation: Interpolation::Step,
+            },
+        );
+
+        let mut retarget = AnimationRetarget::new();
+        retarget.map_bone("Hips", "pelvis").map_bone("Arm", "arm");
+        let retargeted = retarget.retarget_clip(&clip, &source, &target);
+        assert_eq!(retargeted.duration(), 1.0);
+        assert!(retargeted.curves_for_target(id("Hips")).is_none());
+
+        let Keyframes::Translation(ref translations) =
+            retargeted.curves_for_target(id("pelvis")).unwrap()[0].keyframes
+        else {
+            panic!("expected translation keyframes");
+        };
+        assert!(translations[0].abs_diff_eq(Vec3::new(0.0, 2.0, 0.0), 1e-5));
+        assert!(translations[1].abs_diff_eq(Vec3::new(0.0, 2.0, 2.0), 1e-5));
+
+        // The arm rotates by the same amount relative to its rest pose, in
+        // the space of the skeleton.
+        let Keyframes::Rotation(ref rotations) =
+            retargeted.curves_for_target(id("arm")).unwrap()[0].keyframes
+        else {
+            panic!("expected rotation keyframes");
+        };
+        let target_delta = target_parent_rotation
+            * rotations[0]
+            * target_arm_rest.inverse()
+            * target_parent_rotation.inverse();
+        assert!(target_delta.abs_diff_eq(source_arm_rotation, 1e-5));
+    }
+
+    #[test]
+    fn from_hierarchy() {
+        let mut world = World::new();
+        let target = |name: &str| AnimationTarget {
+            id: id(name),
+            player: Entity::PLACEHOLDER,
+        };
+
+        let hips = world
+            .spawn((
+                Name::new("Hips"),
+                target("Hips"),
+                Transform::from_rotation(Quat::from_rotation_y(1.0)),
+            ))
+            .id();
+        // Entities without an `AnimationTarget` still contribute rotation.
+        let offset = world
+            .spawn(Transform::from_rotation(Quat::from_rotation_x(0.5)))
+            .id();
+        let spine = world
+            .spawn((Name::new("Spine"), target("Spine"), Transform::IDENTITY))
+            .id();
+        let root = world.spawn(Transform::from_xyz(5.0, 0.0, 0.0)).id();
+        world.entity_mut(root).add_child(hips);
+        world.entity_mut(hips).add_child(offset);
+        world.entity_mut(offset).add_child(spine);
+
+        let skeleton = RetargetSkeleton::from_hierarchy(&world, root);
+        assert_eq!(
+            skeleton.bone("Hips").unwrap().parent_rotation,
+            Quat::IDENTITY
+        );
+        assert!(skeleton.bone("Spine").unwrap().parent_rotation.abs_diff_eq(
+            Quat::from_rotation_y(1.0) * Quat::from_rotation_x(0.5),
+            1e-6
+        ));
+        assert_eq!(skeleton.bone("Spine").unwrap().id, id("Spine"));
+    }
+}
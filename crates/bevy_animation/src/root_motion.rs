@@ -0,0 +1,393 @@
+//! Root motion, which extracts the movement of the root bone from animations
+//! so that it can move the character instead.
+//!
+//! Animations of characters walking or turning are often authored with the
+//! root bone moving through space. Playing such an animation as-is moves the
+//! skeleton away from the entity that represents the character, so the
+//! character's collider and game logic drift away from what's displayed.
+//! Adding a [`RootMotion`] component to the entity with the
+//! [`AnimationPlayer`] pins the translation and rotation of the root bone to
+//! the start of each clip, and instead exposes how much the root bone would
+//! have moved each frame, which a character controller can then apply.
+
+use bevy_asset::{Assets, Handle};
+use bevy_ecs::{
+    component::Component,
+    reflect::ReflectComponent,
+    system::{Query, Res},
+};
+use bevy_math::{Isometry3d, Quat, Vec3};
+use bevy_reflect::Reflect;
+use std::ops::{Add, Mul};
+
+use crate::{
+    compression::CompressedQuat, cubic_spline_interpolation, graph::AnimationGraph,
+    ActiveAnimation, AnimationClip, AnimationPlayer, AnimationTargetId, Interpolation, Keyframes,
+    VariableCurve,
+};
+
+/// Extracts the motion of the root bone from the animations that an
+/// [`AnimationPlayer`] plays.
+///
+/// Place this component on the same entity as the [`AnimationPlayer`]. The
+/// translation and rotation of the [`crate::AnimationTarget`] with the
+/// [`RootMotion::target`] ID are then held at their values at the start of
+/// each clip, and the motion they would have had this frame is stored in
+/// [`RootMotion::translation`] and [`RootMotion::rotation`] instead.
+///
+/// The motion is relative to the parent of the root bone, which is usually
+/// the entity that represents the character. To move the character by it,
+/// apply it to the character's [`bevy_transform::components::Transform`]
+/// every frame after the animations are advanced:
+///
+/// ```
+/// # use bevy_animation::root_motion::RootMotion;
+/// # use bevy_transform::components::Transform;
+/// fn apply_root_motion(transform: &mut Transform, root_motion: &RootMotion) {
+///     transform.translation += transform.rotation * root_motion.translation;
+///     transform.rotation *= root_motion.rotation;
+/// }
+/// ```
+///
+/// The motion takes looping, the weights of blended animations, and
+/// transitions into account. Additive animations add their motion on top of
+/// the others.
+#[derive(Clone, Copy, Debug, Component, Reflect)]
+#[reflect(Component)]
+pub struct RootMotion {
+    /// The ID of the root bone whose motion is extracted.
+    pub target: AnimationTargetId,
+    /// How far the root bone moved this frame.
+    pub translation: Vec3,
+    /// How much the root bone rotated this frame.
+    pub rotation: Quat,
+}
+
+impl RootMotion {
+    /// Creates a [`RootMotion`] component that extracts the motion of the
+    /// given root bone.
+    pub fn new(target: AnimationTargetId) -> Self {
+        Self {
+            target,
+            translation: Vec3::ZERO,
+            rotation: Quat::IDENTITY,
+        }
+    }
+}
+
+/// A system that computes the [`RootMotion`] of each [`AnimationPlayer`] for
+/// this frame.
+///
+/// This runs after [`crate::advance_animations`], so the motion is the one
+/// between the seek times of the previous and current frames.
+pub fn extract_root_motion(
+    mut players: Query<(&AnimationPlayer, &Handle<AnimationGraph>, &mut RootMotion)>,
+    clips: Res<Assets<AnimationClip>>,
+    graphs: Res<Assets<AnimationGraph>>,
+) {
+    for (player, graph_handle, mut root_motion) in &mut players {
+        let mut translation = Vec3::ZERO;
+        let mut rotation = Quat::IDENTITY;
+
+        let Some(graph) = graphs.get(graph_handle) else {
+            root_motion.translation = translation;
+            root_motion.rotation = rotation;
+            continue;
+        };
+        let target_mask = graph
+            .mask_groups
+            .get(&root_motion.target)
+            .cloned()
+            .unwrap_or_default();
+
+        // Blend the motion of the animations in the same way that
+        // `animate_targets` blends their poses, additive animations last.
+        let mut total_weight = 0.0;
+        for additive_pass in [false, true] {
+            for (&node_index, active_animation) in player.playing_animations() {
+                if active_animation.weight() == 0.0
+                    || active_animation.computed_additive != additive_pass
+                    || (target_mask & active_animation.computed_mask) != 0
+                {
+                    continue;
+                }
+                let Some(clip) = graph
+                    .get(node_index)
+                    .and_then(|node| node.clip.as_ref())
+                    .and_then(|clip_handle| clips.get(clip_handle))
+                else {
+                    continue;
+                };
+                let Some(curves) = clip.curves_for_target(root_motion.target) else {
+                    continue;
+                };
+                let Some(motion) = animation_motion(active_animation, curves, clip.duration) else {
+                    continue;
+                };
+
+                let weight = active_animation.computed_weight;
+                let motion_translation = Vec3::from(motion.translation);
+                if additive_pass {
+                    translation += motion_translation * weight;
+                    rotation =
+                        (rotation * Quat::IDENTITY.slerp(motion.rotation, weight)).normalize();
+                } else {
+                    total_weight += weight;
+                    translation = translation.lerp(motion_translation, weight / total_weight);
+                    rotation = rotation.slerp(motion.rotation, weight / total_weight);
+                }
+            }
+        }
+
+        root_motion.translation = translation;
+        root_motion.rotation = rotation;
+    }
+}
+
+/// Computes the motion of the root bone that the given animation produced
+/// this frame, or `None` if it hasn't been ticked.
+fn animation_motion(
+    animation: &ActiveAnimation,
+    curves: &[VariableCurve],
+    duration: f32,
+) -> Option<Isometry3d> {
+    let from = animation.last_seek_time?;
+    let to = animation.seek_time;
+    let start = root_pose(curves, 0.0);
+
+    // If playback wrapped around the end of the clip, add the motion up to
+    // the end to the motion from the start.
+    let reverse = animation.is_playback_reversed();
+    let motion = match (reverse, if reverse { to > from } else { to < from }) {
+        (_, false) => segment_motion(curves, start, from, to),
+        (false, true) => {
+            segment_motion(curves, start, from, duration) * segment_motion(curves, start, 0.0, to)
+        }
+        (true, true) => {
+            segment_motion(curves, start, from, 0.0) * segment_motion(curves, start, duration, to)
+        }
+    };
+    Some(motion)
+}
+
+/// Computes the motion between the times `from` and `to` of a clip, relative
+/// to the parent of the root bone when the root bone is held at `start`.
+fn segment_motion(curves: &[VariableCurve], start: Isometry3d, from: f32, to: f32) -> Isometry3d {
+    start * root_pose(curves, from).inverse() * root_pose(curves, to) * start.inverse()
+}
+
+/// Samples the translation and rotation of the root bone at `time`.
+fn root_pose(curves: &[VariableCurve], time: f32) -> Isometry3d {
+    let mut pose = Isometry3d::IDENTITY;
+    for curve in curves {
+        match curve.keyframes {
+            Keyframes::Translation(ref keyframes) => {
+                pose.translation =
+                    sample(curve, &curve.interpolation, keyframes, time, Vec3::lerp).into();
+            }
+            Keyframes::Rotation(ref keyframes) => {
+                pose.rotation =
+                    sample(curve, &curve.interpolation, keyframes, time, Quat::slerp).normalize();
+            }
+            Keyframes::CompressedRotation(ref keyframes) => {
+                pose.rotation = sample_compressed_rotation(curve, keyframes, time).normalize();
+            }
+            Keyframes::Scale(_) | Keyframes::Weights(_) => {}
+        }
+    }
+    pose
+}
+
+/// Samples the value of `keyframes` at `time`, interpolating with `lerp`.
+fn sample<T>(
+    curve: &VariableCurve,
+    interpolation: &Interpolation,
+    keyframes: &[T],
+    time: f32,
+    lerp: fn(T, T, f32) -> T,
+) -> T
+where
+    T: Copy + Mul<f32, Output = T> + Add<Output = T>,
+{
+    let value_offset = match interpolation {
+        Interpolation::CubicSpline => 1,
+        Interpolation::Step | Interpolation::Linear => 0,
+    };
+    if curve.keyframe_timestamps.len() == 1 {
+        return keyframes[value_offset];
+    }
+
+    let (step_start, t, duration) = interpolation_segment(curve, time);
+
+    match interpolation {
+        Interpolation::Step => keyframes[step_start],
+        Interpolation::Linear => lerp(keyframes[step_start], keyframes[step_start + 1], t),
+        Interpolation::CubicSpline => cubic_spline_interpolation(
+            keyframes[step_start * 3 + 1],
+            keyframes[step_start * 3 + 2],
+            keyframes[(step_start + 1) * 3],
+            keyframes[(step_start + 1) * 3 + 1],
+            t,
+            duration,
+        ),
+    }
+}
+
+/// Samples a compressed rotation track at `time`, decoding only the keyframes
+/// that are interpolated between.
+fn sample_compressed_rotation(
+    curve: &VariableCurve,
+    keyframes: &[CompressedQuat],
+    time: f32,
+) -> Quat {
+    // Only the values of cubic spline keyframes are used, skipping their
+    // tangents.
+    let (stride, value_offset) = match curve.interpolation {
+        Interpolation::CubicSpline => (3, 1),
+        Interpolation::Step | Interpolation::Linear => (1, 0),
+    };
+    if curve.keyframe_timestamps.len() == 1 {
+        return keyframes[value_offset].to_quat();
+    }
+
+    let (step_start, t, _) = interpolation_segment(curve, time);
+    let start = keyframes[step_start * stride + value_offset].to_quat();
+    match curve.interpolation {
+        Interpolation::Step => start,
+        Interpolation::Linear | Interpolation::CubicSpline => start.slerp(
+            keyframes[(step_start + 1) * stride + value_offset].to_quat(),
+            t,
+        ),
+    }
+}
+
+/// Finds the keyframe that starts the interpolation at `time`, the
+/// interpolation factor within that keyframe, and the keyframe's duration.
+fn interpolation_segment(curve: &VariableCurve, time: f32) -> (usize, f32, f32) {
+    let step_start = curve.find_interpolation_start_keyframe(time);
+    let timestamp_start = curve.keyframe_timestamps[step_start];
+    let timestamp_end = curve.keyframe_timestamps[step_start + 1];
+    let duration = timestamp_end - timestamp_start;
+    let t = ((time - timestamp_start) / duration).clamp(0.0, 1.0);
+    (step_start, t, duration)
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_math::{Quat, Vec3};
+
+    use super::{animation_motion, root_pose};
+    use crate::{
+        compression::CompressedQuat, ActiveAnimation, Interpolation, Keyframes, RepeatAnimation,
+        VariableCurve,
+    };
+
+    fn walk_forward() -> Vec<VariableCurve> {
+        vec![VariableCurve {
+            keyframe_timestamps: vec![0.0, 1.0],
+            keyframes: Keyframes::Translation(vec![Vec3::ZERO, Vec3::new(0.0, 0.0, 2.0)]),
+            interpolation: Interpolation::Linear,
+        }]
+    }
+
+    fn ticked(from: f32, to: f32) -> ActiveAnimation {
+        ActiveAnimation {
+            last_seek_time: Some(from),
+            seek_time: to,
+            repeat: RepeatAnimation::Forever,
+            ..ActiveAnimation::default()
+        }
+    }
+
+    #[test]
+    fn translation() {
+        let curves = walk_forward();
+
+        let motion = animation_motion(&ticked(0.25, 0.5), &curves, 1.0).unwrap();
+        assert!(Vec3::from(motion.translation).abs_diff_eq(Vec3::new(0.0, 0.0, 0.5), 1e-5));
+
+        // Wrapping around the end of the clip keeps moving forward.
+        let motion = animation_motion(&ticked(0.75, 0.25), &curves, 1.0).unwrap();
+        assert!(Vec3::from(motion.translation).abs_diff_eq(Vec3::new(0.0, 0.0, 1.0), 1e-5));
+
+        // Without a previous seek time, there's no motion.
+        assert!(animation_motion(&ActiveAnimation::default(), &curves, 1.0).is_none());
+    }
+
+    #[test]
+    fn turning() {
+        // The root bone walks forward while turning a quarter turn to the left.
+        let curves = vec![
+            VariableCurve {
+                keyframe_timestamps: vec![0.0, 1.0],
+                keyframes: Keyframes::Translation(vec![Vec3::ZERO, Vec3::new(-1.0, 0.0, 1.0)]),
+                interpolation: Interpolation::Linear,
+            },
+            VariableCurve {
+                keyframe_timestamps: vec![0.0, 1.0],
+                keyframes: Keyframes::Rotation(vec![
+                    Quat::IDENTITY,
+                    Quat::from_rotation_y(std::f32::consts::FRAC_PI_2),
+                ]),
+                interpolation: Interpolation::Linear,
+            },
+        ];
+
+        // Applying the motion of each frame in turn ends up at the same pose
+        // as the end of the clip, even across a loop.
+        let (mut translation, mut rotation) = (Vec3::ZERO, Quat::IDENTITY);
+        for frame in 0..8 {
+            let from = (frame as f32 * 0.25) % 1.0;
+            let to = ((frame + 1) as f32 * 0.25) % 1.0;
+            let motion = animation_motion(&ticked(from, to), &curves, 1.0).unwrap();
+            translation += rotation * Vec3::from(motion.translation);
+            rotation *= motion.rotation;
+        }
+        assert!(translation.abs_diff_eq(Vec3::new(0.0, 0.0, 2.0), 1e-4));
+        assert!(rotation.abs_diff_eq(Quat::from_rotation_y(std::f32::consts::PI), 1e-4));
+    }
+
+    #[test]
+    fn compressed_rotation() {
+        let rotations = [
+            Quat::IDENTITY,
+            Quat::from_rotation_y(1.0),
+            Quat::from_rotation_x(0.5),
+        ];
+        let curve = |keyframes| VariableCurve {
+            keyframe_timestamps: vec![0.0, 1.0, 2.0],
+            keyframes,
+            interpolation: Interpolation::Linear,
+        };
+        let uncompressed = [curve(Keyframes::Rotation(rotations.to_vec()))];
+        let compressed = [curve(Keyframes::CompressedRotation(
+            rotations
+                .iter()
+                .copied()
+                .map(CompressedQuat::from)
+                .collect(),
+        ))];
+
+        // Cubic spline keyframes are interpolated linearly between their
+        // values, ignoring their tangents.
+        let tangent = CompressedQuat::from(Quat::from_rotation_z(1.0));
+        let cubic_spline = [VariableCurve {
+            interpolation: Interpolation::CubicSpline,
+            ..curve(Keyframes::CompressedRotation(
+                rotations
+                    .iter()
+                    .flat_map(|&rotation| [tangent, rotation.into(), tangent])
+                    .collect(),
+            ))
+        }];
+
+        for time in [0.0, 0.5, 1.25, 2.0] {
+            let expected = root_pose(&uncompressed, time).rotation;
+            for curves in [&compressed, &cubic_spline] {
+                let rotation = root_pose(curves, time).rotation;
+                assert!(rotation.is_normalized());
+                assert!(rotation.abs_diff_eq(expected, 1e-3));
+            }
+        }
+    }
+}
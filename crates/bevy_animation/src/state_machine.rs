@@ -0,0 +1,763 @@
+//! Animation state machines, which drive an [`AnimationPlayer`] from named
+//! parameters.
+//!
+//! An [`AnimationStateMachine`] is an asset describing a set of states, each of
+//! which plays a node of the entity's [`AnimationGraph`], and the transitions
+//! between them. Every frame, the [`AnimationStateMachineController`] component
+//! checks the transitions out of the current state against its parameters, and
+//! cross-fades to the target state of the first transition whose conditions
+//! hold, using [`AnimationTransitions`].
+//!
+//! [`AnimationGraph`]: crate::graph::AnimationGraph
+
+use std::io::{self, Write};
+
+use bevy_asset::{io::Reader, Asset, AssetLoader, Assets, Handle, LoadContext};
+use bevy_ecs::{
+    component::Component,
+    reflect::ReflectComponent,
+    system::{Query, Res},
+};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect, ReflectDeserialize, ReflectSerialize};
+use bevy_time::Time;
+use bevy_utils::{tracing::warn, Duration, HashMap};
+use ron::de::SpannedError;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{
+    graph::AnimationNodeIndex, transition::AnimationTransitions, ActiveAnimation, AnimationPlayer,
+};
+
+/// A state machine that decides which node of an
+/// [`AnimationGraph`](crate::graph::AnimationGraph) plays, based on the
+/// parameters of an [`AnimationStateMachineController`].
+///
+/// State machines are assets and can be serialized to and loaded from [RON]
+/// files. Canonically, such files have an `.animsm.ron` extension:
+///
+/// ```ron
+/// (
+///     states: [
+///         (name: "idle", node: 1),
+///         (name: "run", node: 2),
+///         (name: "jump", node: 3, looping: false),
+///     ],
+///     transitions: [
+///         (from: Some("idle"), to: "run", conditions: [Greater("speed", 0.1)], blend: 0.2),
+///         (from: Some("run"), to: "idle", conditions: [Less("speed", 0.1)], blend: 0.2),
+///         (from: None, to: "jump", conditions: [Trigger("jump")], blend: 0.1),
+///         (from: Some("jump"), to: "idle", exit_time: Some(0.8), blend: 0.3),
+///     ],
+///     parameters: {
+///         "speed": Float(0.0),
+///         "jump": Trigger(false),
+///     },
+/// )
+/// ```
+///
+/// The first state is the initial state.
+///
+/// [RON]: https://github.com/ron-rs/ron
+#[derive(Asset, Reflect, Clone, Debug, Default, Serialize, Deserialize)]
+#[reflect(Serialize, Deserialize, Debug, Default)]
+pub struct AnimationStateMachine {
+    /// The states of this state machine. The first one is the initial state.
+    pub states: Vec<AnimationState>,
+    /// The transitions between states.
+    ///
+    /// When several transitions could be taken in the same frame, the first
+    /// one in this list wins.
+    #[serde(default)]
+    pub transitions: Vec<AnimationStateTransition>,
+    /// The parameters that conditions refer to, along with their initial
+    /// values.
+    #[serde(default)]
+    pub parameters: HashMap<String, AnimationParameter>,
+}
+
+/// A state of an [`AnimationStateMachine`].
+#[derive(Reflect, Clone, Debug, Serialize, Deserialize)]
+pub struct AnimationState {
+    /// The name of this state, which transitions use to refer to it.
+    pub name: String,
+    /// The animation graph node that plays while in this state.
+    pub node: AnimationNodeIndex,
+    /// The playback speed of the node.
+    #[serde(default = "default_speed")]
+    pub speed: f32,
+    /// Whether the node repeats forever, rather than playing once.
+    #[serde(default = "default_looping")]
+    pub looping: bool,
+}
+
+fn default_speed() -> f32 {
+    1.0
+}
+
+fn default_looping() -> bool {
+    true
+}
+
+/// A transition between two states of an [`AnimationStateMachine`].
+#[derive(Reflect, Clone, Debug, Serialize, Deserialize)]
+pub struct AnimationStateTransition {
+    /// The name of the state that this transition leaves, or `None` if it can
+    /// be taken from any state other than its target.
+    pub from: Option<String>,
+    /// The name of the state that this transition enters.
+    pub to: String,
+    /// Conditions on the parameters that must all hold for this transition to
+    /// be taken.
+    #[serde(default)]
+    pub conditions: Vec<AnimationCondition>,
+    /// If present, the transition can't be taken until the node of the current
+    /// state has played for this many seconds.
+    ///
+    /// A transition without conditions but with an exit time is taken as soon
+    /// as the exit time is reached.
+    #[serde(default)]
+    pub exit_time: Option<f32>,
+    /// The duration of the cross-fade to the target state, in seconds.
+    #[serde(default)]
+    pub blend: f32,
+    /// Which transitions can interrupt this one while it's blending.
+    #[serde(default)]
+    pub interruption: AnimationInterruption,
+}
+
+/// Which transitions can interrupt an [`AnimationStateTransition`] while it's
+/// blending.
+///
+/// Once a transition is taken, the state machine is in its target state, so
+/// the transitions that may interrupt it leave that state.
+#[derive(Reflect, Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AnimationInterruption {
+    /// The transition always blends to completion.
+    #[default]
+    Never,
+    /// Only transitions from any state (those with no `from` state) can
+    /// interrupt the transition.
+    AnyState,
+    /// Any transition out of the target state can interrupt the transition.
+    Always,
+}
+
+/// A condition on a named parameter of an [`AnimationStateMachineController`].
+#[derive(Reflect, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum AnimationCondition {
+    /// The float parameter is greater than the value.
+    Greater(String, f32),
+    /// The float parameter is less than the value.
+    Less(String, f32),
+    /// The bool parameter equals the value.
+    Bool(String, bool),
+    /// The trigger parameter is set. Taking the transition resets it.
+    Trigger(String),
+}
+
+/// The value of a named parameter of an [`AnimationStateMachineController`].
+#[derive(Reflect, Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum AnimationParameter {
+    /// A float parameter, compared with [`AnimationCondition::Greater`] and
+    /// [`AnimationCondition::Less`].
+    Float(f32),
+    /// A bool parameter, compared with [`AnimationCondition::Bool`].
+    Bool(bool),
+    /// A trigger parameter, which stays set until a transition with an
+    /// [`AnimationCondition::Trigger`] on it is taken.
+    Trigger(bool),
+}
+
+/// Drives an [`AnimationPlayer`] according to an [`AnimationStateMachine`].
+///
+/// To use this component, place it on the same entity as the
+/// [`AnimationPlayer`], the [`Handle<AnimationGraph>`], the
+/// [`Handle<AnimationStateMachine>`] and an [`AnimationTransitions`]
+/// component. Then set parameters on it to change states; the state machine
+/// takes care of playing the animations.
+///
+/// [`Handle<AnimationGraph>`]: crate::graph::AnimationGraph
+#[derive(Component, Default, Reflect, Clone, Debug)]
+#[reflect(Component, Default)]
+pub struct AnimationStateMachineController {
+    parameters: HashMap<String, AnimationParameter>,
+    current_state: Option<usize>,
+    blend: Option<ActiveBlend>,
+}
+
+/// A transition that is still blending.
+#[derive(Reflect, Clone, Copy, Debug)]
+struct ActiveBlend {
+    remaining: f32,
+    interruption: AnimationInterruption,
+}
+
+/// Various errors that can occur when serializing or deserializing animation
+/// state machines to and from RON, respectively.
+#[derive(Error, Debug)]
+pub enum AnimationStateMachineLoadError {
+    /// An I/O error occurred.
+    #[error("I/O")]
+    Io(#[from] io::Error),
+    /// An error occurred in RON serialization or deserialization.
+    #[error("RON serialization")]
+    Ron(#[from] ron::Error),
+    /// An error occurred in RON deserialization, and the location of the error
+    /// is supplied.
+    #[error("RON serialization")]
+    SpannedRon(#[from] SpannedError),
+    /// The state machine has no states.
+    #[error("the state machine has no states")]
+    NoStates,
+    /// A transition refers to a state that doesn't exist.
+    #[error("a transition refers to the unknown state `{0}`")]
+    UnknownState(String),
+    /// A transition's blend duration is negative or not finite.
+    #[error("a transition to `{0}` has an invalid blend duration")]
+    InvalidBlend(String),
+}
+
+/// An [`AssetLoader`] that can load [`AnimationStateMachine`]s as assets.
+///
+/// The canonical extension for [`AnimationStateMachine`]s is `.animsm.ron`.
+/// Plain `.animsm` is supported as well.
+#[derive(Default)]
+pub struct AnimationStateMachineAssetLoader;
+
+impl AnimationStateMachine {
+    /// Creates a new state machine with no states.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a state that plays the given animation graph node, looping, and
+    /// returns its index.
+    ///
+    /// The first state added is the initial state.
+    pub fn add_state(&mut self, name: impl Into<String>, node: AnimationNodeIndex) -> usize {
+        self.states.push(AnimationState {
+            name: name.into(),
+            node,
+            speed: 1.0,
+            looping: true,
+        });
+        self.states.len() - 1
+    }
+
+    /// Adds a transition from the state named `from` (or from any state, if
+    /// `None`) to the state named `to`, with the given conditions and blend
+    /// duration, and returns it for further configuration.
+    pub fn add_transition(
+        &mut self,
+        from: Option<&str>,
+        to: impl Into<String>,
+        conditions: impl IntoIterator<Item = AnimationCondition>,
+        blend: Duration,
+    ) -> &mut AnimationStateTransition {
+        self.transitions.push(AnimationStateTransition {
+            from: from.map(ToOwned::to_owned),
+            to: to.into(),
+            conditions: conditions.into_iter().collect(),
+            exit_time: None,
+            blend: blend.as_secs_f32(),
+            interruption: AnimationInterruption::Never,
+        });
+        self.transitions.last_mut().unwrap()
+    }
+
+    /// Declares a parameter with its initial value.
+    pub fn add_parameter(&mut self, name: impl Into<String>, initial: AnimationParameter) {
+        self.parameters.insert(name.into(), initial);
+    }
+
+    /// Returns the index of the state with the given name, if any.
+    pub fn state_index(&self, name: &str) -> Option<usize> {
+        self.states.iter().position(|state| state.name == name)
+    }
+
+    /// Checks that this state machine has an initial state, that every
+    /// transition refers to existing states, and that every blend duration is
+    /// a finite, non-negative number of seconds.
+    pub fn validate(&self) -> Result<(), AnimationStateMachineLoadError> {
+        if self.states.is_empty() {
+            return Err(AnimationStateMachineLoadError::NoStates);
+        }
+        for transition in &self.transitions {
+            for name in transition.from.iter().chain(Some(&transition.to)) {
+                if self.state_index(name).is_none() {
+                    return Err(AnimationStateMachineLoadError::UnknownState(name.clone()));
+                }
+            }
+            if !transition.blend.is_finite() || transition.blend < 0.0 {
+                return Err(AnimationStateMachineLoadError::InvalidBlend(
+                    transition.to.clone(),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Serializes the state machine to the given [`Write`]r in RON format.
+    ///
+    /// If writing to a file, it can later be loaded with the
+    /// [`AnimationStateMachineAssetLoader`] to reconstruct the state machine.
+    pub fn save<W>(&self, writer: &mut W) -> Result<(), AnimationStateMachineLoadError>
+    where
+        W: Write,
+    {
+        let mut ron_serializer = ron::ser::Serializer::new(writer, None)?;
+        Ok(self.serialize(&mut ron_serializer)?)
+    }
+}
+
+impl AnimationStateTransition {
+    /// Prevents this transition from being taken until the node of the current
+    /// state has played for `exit_time`.
+    pub fn with_exit_time(&mut self, exit_time: Duration) -> &mut Self {
+        self.exit_time = Some(exit_time.as_secs_f32());
+        self
+    }
+
+    /// Sets which transitions can interrupt this one while it's blending.
+    pub fn with_interruption(&mut self, interruption: AnimationInterruption) -> &mut Self {
+        self.interruption = interruption;
+        self
+    }
+}
+
+impl AnimationStateMachineController {
+    /// Creates a new [`AnimationStateMachineController`], ready to be added to
+    /// an entity with an [`AnimationPlayer`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets a float parameter.
+    pub fn set_float(&mut self, name: impl Into<String>, value: f32) -> &mut Self {
+        self.parameters
+            .insert(name.into(), AnimationParameter::Float(value));
+        self
+    }
+
+    /// Sets a bool parameter.
+    pub fn set_bool(&mut self, name: impl Into<String>, value: bool) -> &mut Self {
+        self.parameters
+            .insert(name.into(), AnimationParameter::Bool(value));
+        self
+    }
+
+    /// Sets a trigger parameter. It stays set until a transition that
+    /// requires it is taken.
+    pub fn trigger(&mut self, name: impl Into<String>) -> &mut Self {
+        self.parameters
+            .insert(name.into(), AnimationParameter::Trigger(true));
+        self
+    }
+
+    /// Resets a trigger parameter without taking a transition.
+    pub fn reset_trigger(&mut self, name: impl Into<String>) -> &mut Self {
+        self.parameters
+            .insert(name.into(), AnimationParameter::Trigger(false));
+        self
+    }
+
+    /// Returns the value of a parameter, if it's been set or declared by the
+    /// state machine.
+    pub fn parameter(&self, name: &str) -> Option<AnimationParameter> {
+        self.parameters.get(name).copied()
+    }
+
+    /// Returns the index of the current state in the
+    /// [`AnimationStateMachine`], or `None` if the state machine hasn't
+    /// started yet.
+    pub fn current_state(&self) -> Option<usize> {
+        self.current_state
+    }
+
+    /// Returns true if a transition is still blending.
+    pub fn is_blending(&self) -> bool {
+        self.blend.is_some()
+    }
+
+    /// Returns true if the condition holds for the current parameters.
+    fn check(&self, condition: &AnimationCondition) -> bool {
+        match (condition, self.parameters.get(condition.parameter())) {
+            (AnimationCondition::Greater(_, value), Some(AnimationParameter::Float(parameter))) => {
+                parameter > value
+            }
+            (AnimationCondition::Less(_, value), Some(AnimationParameter::Float(parameter))) => {
+                parameter < value
+            }
+            (AnimationCondition::Bool(_, value), Some(AnimationParameter::Bool(parameter))) => {
+                parameter == value
+            }
+            (AnimationCondition::Trigger(_), Some(AnimationParameter::Trigger(parameter))) => {
+                *parameter
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns true if `transition` may be taken now, given the blend in
+    /// progress.
+    fn can_interrupt(&self, transition: &AnimationStateTransition) -> bool {
+        match self.blend {
+            None => true,
+            Some(blend) => match blend.interruption {
+                AnimationInterruption::Never => false,
+                AnimationInterruption::AnyState => transition.from.is_none(),
+                AnimationInterruption::Always => true,
+            },
+        }
+    }
+}
+
+impl AnimationCondition {
+    /// The name of the parameter that this condition refers to.
+    pub fn parameter(&self) -> &str {
+        match self {
+            AnimationCondition::Greater(name, _)
+            | AnimationCondition::Less(name, _)
+            | AnimationCondition::Bool(name, _)
+            | AnimationCondition::Trigger(name) => name,
+        }
+    }
+}
+
+/// A system that evaluates the transitions of each
+/// [`AnimationStateMachineController`] and plays the animations of the states
+/// it enters.
+pub fn advance_state_machines(
+    mut query: Query<(
+        &mut AnimationStateMachineController,
+        &Handle<AnimationStateMachine>,
+        &mut AnimationTransitions,
+        &mut AnimationPlayer,
+    )>,
+    state_machines: Res<Assets<AnimationStateMachine>>,
+    time: Res<Time>,
+) {
+    for (mut controller, state_machine_handle, mut transitions, mut player) in query.iter_mut() {
+        // The state machine might not have loaded yet.
+        let Some(state_machine) = state_machines.get(state_machine_handle) else {
+            continue;
+        };
+
+        let controller = &mut *controller;
+        let current_state = match controller.current_state {
+            Some(current_state) if current_state < state_machine.states.len() => current_state,
+            _ => {
+                // Start the state machine.
+                let Some(initial_state) = state_machine.states.first() else {
+                    continue;
+                };
+                for (name, &initial) in &state_machine.parameters {
+                    controller.parameters.entry(name.clone()).or_insert(initial);
+                }
+                enter_state(&mut transitions, &mut player, initial_state, Duration::ZERO);
+                controller.current_state = Some(0);
+                controller.blend = None;
+                continue;
+            }
+        };
+
+        if let Some(ref mut blend) = controller.blend {
+            blend.remaining -= time.delta_seconds();
+            if blend.remaining <= 0.0 {
+                controller.blend = None;
+            }
+        }
+
+        let elapsed = player
+            .animation(state_machine.states[current_state].node)
+            .map_or(0.0, ActiveAnimation::elapsed);
+
+        let current_name = &state_machine.states[current_state].name;
+        let Some((transition, target_state)) = state_machine
+            .transitions
+            .iter()
+            .filter(|transition| match transition.from {
+                Some(ref from) => from == current_name,
+                None => transition.to != *current_name,
+            })
+            .filter(|transition| controller.can_interrupt(transition))
+            .filter(|transition| !transition.exit_time.is_some_and(|exit| elapsed < exit))
+            .filter(|transition| {
+                transition
+                    .conditions
+                    .iter()
+                    .all(|condition| controller.check(condition))
+            })
+            .find_map(|transition| {
+                let target_state = state_machine.state_index(&transition.to);
+                if target_state.is_none() {
+                    warn!(
+                        "Animation state transition to unknown state `{}`",
+                        transition.to
+                    );
+                }
+                target_state.map(|target_state| (transition, target_state))
+            })
+        else {
+            continue;
+        };
+
+        // Consume the triggers that the transition required.
+        for condition in &transition.conditions {
+            if let AnimationCondition::Trigger(name) = condition {
+                controller
+                    .parameters
+                    .insert(name.clone(), AnimationParameter::Trigger(false));
+            }
+        }
+
+        // Blends that can't be represented as a duration switch immediately.
+        let blend = Duration::try_from_secs_f32(transition.blend).unwrap_or_default();
+        enter_state(
+            &mut transitions,
+            &mut player,
+            &state_machine.states[target_state],
+            blend,
+        );
+        controller.current_state = Some(target_state);
+        controller.blend = (!blend.is_zero()).then_some(ActiveBlend {
+            remaining: blend.as_secs_f32(),
+            interruption: transition.interruption,
+        });
+    }
+}
+
+/// Cross-fades to the node of `state` over `blend`.
+fn enter_state(
+    transitions: &mut AnimationTransitions,
+    player: &mut AnimationPlayer,
+    state: &AnimationState,
+    blend: Duration,
+) {
+    let animation = transitions.play(player, state.node, blend);
+    animation.set_speed(state.speed);
+    if state.looping {
+        animation.repeat();
+    }
+}
+
+impl AssetLoader for AnimationStateMachineAssetLoader {
+    type Asset = AnimationStateMachine;
+
+    type Settings = ();
+
+    type Error = AnimationStateMachineLoadError;
+
+    async fn load<'a>(
+        &'a self,
+        reader: &'a mut dyn Reader,
+        _: &'a Self::Settings,
+        _: &'a mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+
+        let mut deserializer = ron::de::Deserializer::from_bytes(&bytes)?;
+        let state_machine = AnimationStateMachine::deserialize(&mut deserializer)
+            .map_err(|err| deserializer.span_error(err))?;
+        state_machine.validate()?;
+        Ok(state_machine)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["animsm", "animsm.ron"]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_asset::Assets;
+    use bevy_ecs::{entity::Entity, system::RunSystemOnce, world::World};
+    use bevy_time::Time;
+    use bevy_utils::Duration;
+
+    use super::{
+        advance_state_machines, AnimationCondition, AnimationInterruption, AnimationParameter,
+        AnimationStateMachine, AnimationStateMachineController, AnimationStateMachineLoadError,
+    };
+    use crate::{graph::AnimationNodeIndex, transition::AnimationTransitions, AnimationPlayer};
+
+    const LOCOMOTION: &str = r#"(
+        states: [
+            (name: "idle", node: 1),
+            (name: "run", node: 2),
+            (name: "jump", node: 3, looping: false),
+        ],
+        transitions: [
+            (from: Some("idle"), to: "run", conditions: [Greater("speed", 0.1)]),
+            (from: Some("run"), to: "idle", conditions: [Less("speed", 0.1)], blend: 0.2),
+            (from: None, to: "jump", conditions: [Trigger("jump")]),
+            (from: Some("jump"), to: "idle", exit_time: Some(0.8)),
+        ],
+        parameters: {
+            "speed": Float(0.0),
+            "jump": Trigger(false),
+        },
+    )"#;
+
+    fn node(index: u32) -> AnimationNodeIndex {
+        AnimationNodeIndex::new(index as usize)
+    }
+
+    fn setup(state_machine: AnimationStateMachine) -> (World, Entity) {
+        let mut world = World::new();
+        world.init_resource::<Time>();
+        let mut state_machines = Assets::<AnimationStateMachine>::default();
+        let handle = state_machines.add(state_machine);
+        world.insert_resource(state_machines);
+        let entity = world
+            .spawn((
+                AnimationStateMachineController::new(),
+                handle,
+                AnimationTransitions::new(),
+                AnimationPlayer::default(),
+            ))
+            .id();
+        world.run_system_once(advance_state_machines);
+        (world, entity)
+    }
+
+    fn controller(world: &mut World, entity: Entity) -> &mut AnimationStateMachineController {
+        world
+            .get_mut::<AnimationStateMachineController>(entity)
+            .unwrap()
+            .into_inner()
+    }
+
+    fn main_animation(world: &World, entity: Entity) -> Option<AnimationNodeIndex> {
+        world
+            .get::<AnimationTransitions>(entity)
+            .unwrap()
+            .get_main_animation()
+    }
+
+    #[test]
+    fn load_and_validate() {
+        let state_machine: AnimationStateMachine = ron::from_str(LOCOMOTION).unwrap();
+        state_machine.validate().unwrap();
+        assert_eq!(state_machine.states[2].node, node(3));
+        assert!(!state_machine.states[2].looping);
+        assert_eq!(state_machine.transitions[1].blend, 0.2);
+
+        let mut state_machine = AnimationStateMachine::new();
+        assert!(matches!(
+            state_machine.validate(),
+            Err(AnimationStateMachineLoadError::NoStates)
+        ));
+        state_machine.add_state("idle", node(1));
+        state_machine.add_transition(Some("idle"), "walk", [], Duration::ZERO);
+        assert!(matches!(
+            state_machine.validate(),
+            Err(AnimationStateMachineLoadError::UnknownState(name)) if name == "walk"
+        ));
+
+        state_machine.add_state("walk", node(2));
+        state_machine.validate().unwrap();
+        for blend in [f32::INFINITY, f32::NAN, -1.0] {
+            state_machine.transitions[0].blend = blend;
+            assert!(matches!(
+                state_machine.validate(),
+                Err(AnimationStateMachineLoadError::InvalidBlend(name)) if name == "walk"
+            ));
+        }
+    }
+
+    #[test]
+    fn transitions() {
+        let (mut world, entity) = setup(ron::from_str(LOCOMOTION).unwrap());
+
+        // The state machine starts in the first state, with the declared
+        // parameters.
+        assert_eq!(controller(&mut world, entity).current_state(), Some(0));
+        assert_eq!(
+            controller(&mut world, entity).parameter("speed"),
+            Some(AnimationParameter::Float(0.0))
+        );
+        assert_eq!(main_animation(&world, entity), Some(node(1)));
+
+        controller(&mut world, entity).set_float("speed", 1.0);
+        world.run_system_once(advance_state_machines);
+        assert_eq!(controller(&mut world, entity).current_state(), Some(1));
+        assert_eq!(main_animation(&world, entity), Some(node(2)));
+
+        // Transitions from any state are taken, and consume their trigger.
+        controller(&mut world, entity).trigger("jump");
+        world.run_system_once(advance_state_machines);
+        assert_eq!(controller(&mut world, entity).current_state(), Some(2));
+        assert_eq!(
+            controller(&mut world, entity).parameter("jump"),
+            Some(AnimationParameter::Trigger(false))
+        );
+
+        // The exit time hasn't been reached yet.
+        world.run_system_once(advance_state_machines);
+        assert_eq!(controller(&mut world, entity).current_state(), Some(2));
+
+        world
+            .get_mut::<AnimationPlayer>(entity)
+            .unwrap()
+            .animation_mut(node(3))
+            .unwrap()
+            .update(1.0, 2.0);
+        world.run_system_once(advance_state_machines);
+        assert_eq!(controller(&mut world, entity).current_state(), Some(0));
+    }
+
+    fn interrupt(interruption: AnimationInterruption, trigger_fall: bool) -> Option<usize> {
+        let mut state_machine = AnimationStateMachine::new();
+        state_machine.add_state("idle", node(1));
+        state_machine.add_state("run", node(2));
+        state_machine.add_state("fall", node(3));
+        state_machine
+            .add_transition(
+                Some("idle"),
+                "run",
+                [AnimationCondition::Bool("moving".into(), true)],
+                Duration::from_secs(1),
+            )
+            .with_interruption(interruption);
+        state_machine.add_transition(
+            Some("run"),
+            "idle",
+            [AnimationCondition::Bool("moving".into(), false)],
+            Duration::ZERO,
+        );
+        state_machine.add_transition(
+            None,
+            "fall",
+            [AnimationCondition::Trigger("fall".into())],
+            Duration::ZERO,
+        );
+        let (mut world, entity) = setup(state_machine);
+
+        // Time doesn't advance, so the blend to "run" never finishes.
+        controller(&mut world, entity).set_bool("moving", true);
+        world.run_system_once(advance_state_machines);
+        assert_eq!(controller(&mut world, entity).current_state(), Some(1));
+        assert!(controller(&mut world, entity).is_blending());
+
+        if trigger_fall {
+            controller(&mut world, entity).trigger("fall");
+        } else {
+            controller(&mut world, entity).set_bool("moving", false);
+        }
+        world.run_system_once(advance_state_machines);
+        controller(&mut world, entity).current_state()
+    }
+
+    #[test]
+    fn interruption() {
+        assert_eq!(interrupt(AnimationInterruption::Never, false), Some(1));
+        assert_eq!(interrupt(AnimationInterruption::Never, true), Some(1));
+        assert_eq!(interrupt(AnimationInterruption::AnyState, false), Some(1));
+        assert_eq!(interrupt(AnimationInterruption::AnyState, true), Some(2));
+        assert_eq!(interrupt(AnimationInterruption::Always, false), Some(0));
+        assert_eq!(interrupt(AnimationInterruption::Always, true), Some(2));
+    }
+}
@@ -1,7 +1,8 @@
 //! Animation transitions.
 //!
-//! Please note that this is an unstable temporary API. It may be replaced by a
-//! state machine in the future.
+//! Please note that this is an unstable temporary API. For a declarative way to
+//! choose which animations play, see [`crate::state_machine`], which uses this
+//! API internally.
 
 use bevy_ecs::{
     component::Component,
@@ -0,0 +1,741 @@
+//! Tweens, which animate a value from a start to an end over time, shaped by
+//! an easing curve.
+//!
+//! Unlike [`AnimationClip`]s, tweens don't need assets, animation graphs, or
+//! [`AnimationTarget`]s, which makes them convenient for one-off UI and
+//! gameplay effects. A [`Tween`] animates a single reflected field of a
+//! component or asset of the entity that plays it. Tweens can be played one
+//! after the other with a [`TweenSequence`], or at the same time with a
+//! [`TweenGroup`], and these can be nested. A [`TweenPlayer`] component plays
+//! the resulting [`Tweenable`] on its entity, and triggers [`TweenCompleted`]
+//! on that entity when it finishes.
+//!
+//! ```
+//! # use bevy_animation::tween::*;
+//! # use bevy_ecs::prelude::*;
+//! # use bevy_math::{curve::EaseFunction, Vec3};
+//! # use bevy_transform::components::Transform;
+//! fn pop_in(mut commands: Commands) {
+//!     let pop = TweenSequence::new()
+//!         .then(
+//!             Tween::scale(Vec3::ZERO, Vec3::ONE, 0.3).with_ease(EaseFunction::BackOut),
+//!         )
+//!         .then(
+//!             Tween::translation(Vec3::ZERO, Vec3::Y * 10.0, 0.5)
+//!                 .with_ease(EaseFunction::BounceOut),
+//!         );
+//!
+//!     commands
+//!         .spawn((Transform::default(), TweenPlayer::new(pop)))
+//!         .observe(|trigger: Trigger<TweenCompleted>, mut commands: Commands| {
+//!             commands.entity(trigger.entity()).remove::<TweenPlayer>();
+//!         });
+//! }
+//! ```
+//!
+//! [`AnimationClip`]: crate::AnimationClip
+//! [`AnimationTarget`]: crate::AnimationTarget
+
+use std::{
+    fmt::{self, Debug, Formatter},
+    mem,
+    sync::Arc,
+};
+
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    event::Event,
+    reflect::AppTypeRegistry,
+    schedule::SystemSet,
+    system::{Local, Query, Res, SystemState},
+    world::World,
+};
+use bevy_log::error;
+use bevy_math::{
+    curve::{Curve, EaseFunction},
+    Quat, Vec3,
+};
+use bevy_reflect::{TypePath, TypeRegistry};
+use bevy_time::Time;
+use bevy_transform::components::Transform;
+
+use crate::{
+    animatable::Animatable,
+    animation_curves::{AnimatedField, AnimationEvaluationError},
+    RepeatAnimation,
+};
+
+/// Something that a [`TweenPlayer`] can play: a [`Tween`], or a combination
+/// of tweens.
+pub trait Tweenable: Send + Sync + 'static {
+    /// How long this takes to play, in seconds.
+    fn duration(&self) -> f32;
+
+    /// Applies the values at `time` to `entity`, where `last_time` is the
+    /// time that they were last applied at.
+    ///
+    /// Both times lie within `0.0..=duration`. Playback can go backwards, in
+    /// which case `time` is less than `last_time`.
+    fn apply(
+        &self,
+        world: &mut World,
+        entity: Entity,
+        registry: &TypeRegistry,
+        time: f32,
+        last_time: f32,
+    ) -> Result<(), AnimationEvaluationError>;
+}
+
+/// Animates an [`AnimatedField`] from a `start` value to an `end` value.
+///
+/// The progress through the tween is shaped by an easing curve, which maps
+/// linear progress over the unit interval to eased progress; it's linear by
+/// default. Any [`Curve<f32>`] can be used, such as an [`EaseFunction`] or a
+/// cubic Bézier [`CubicSegment`](bevy_math::cubic_splines::CubicSegment).
+pub struct Tween<T> {
+    /// The field that this tween animates.
+    pub field: AnimatedField,
+    /// The value at the start of the tween.
+    pub start: T,
+    /// The value at the end of the tween.
+    pub end: T,
+    /// How long the tween takes, in seconds.
+    pub duration: f32,
+    ease: Box<dyn Curve<f32> + Send + Sync>,
+}
+
+/// Plays [`Tweenable`]s one after the other.
+#[derive(Default)]
+pub struct TweenSequence {
+    tweens: Vec<Box<dyn Tweenable>>,
+}
+
+/// Plays [`Tweenable`]s at the same time.
+///
+/// The group lasts as long as its longest member.
+#[derive(Default)]
+pub struct TweenGroup {
+    tweens: Vec<Box<dyn Tweenable>>,
+}
+
+/// Plays a [`Tweenable`] on this entity.
+///
+/// The tweened fields are relative to this entity. When the tween finishes,
+/// including all of its repetitions, a [`TweenCompleted`] event is triggered
+/// on this entity.
+#[derive(Component)]
+pub struct TweenPlayer {
+    tween: Arc<dyn Tweenable>,
+    time: f32,
+    backwards: bool,
+    repeat: RepeatAnimation,
+    ping_pong: bool,
+    speed: f32,
+    paused: bool,
+    completions: u32,
+}
+
+/// The [`SystemSet`] in which [`advance_tweens`] runs.
+///
+/// Tweens are applied after animation clips, so they override the fields
+/// that both animate.
+#[derive(Debug, Hash, PartialEq, Eq, Clone, SystemSet)]
+pub struct TweenSystem;
+
+/// The [observer] event that a [`TweenPlayer`] triggers on its entity when
+/// its tween finishes.
+///
+/// [observer]: bevy_ecs::observer::Observer
+#[derive(Event, Clone, Copy, Debug)]
+pub struct TweenCompleted;
+
+impl<T: Animatable + Clone> Tween<T> {
+    /// Creates a tween that linearly animates `field` from `start` to `end`
+    /// over `duration` seconds.
+    pub fn new(field: AnimatedField, start: T, end: T, duration: f32) -> Self {
+        Self {
+            field,
+            start,
+            end,
+            duration,
+            ease: Box::new(EaseFunction::Linear),
+        }
+    }
+
+    /// Shapes the progress of this tween with the given easing curve, which
+    /// is sampled over the unit interval.
+    pub fn with_ease(mut self, ease: impl Curve<f32> + Send + Sync + 'static) -> Self {
+        self.ease = Box::new(ease);
+        self
+    }
+
+    /// Returns the value of this tween at `time`, in seconds.
+    pub fn sample(&self, time: f32) -> T {
+        let progress = if self.duration > 0.0 {
+            (time / self.duration).clamp(0.0, 1.0)
+        } else {
+            1.0
+        };
+        T::interpolate(&self.start, &self.end, self.ease.sample_clamped(progress))
+    }
+}
+
+impl Tween<Vec3> {
+    /// Creates a tween that animates [`Transform::translation`].
+    pub fn translation(start: Vec3, end: Vec3, duration: f32) -> Self {
+        Self::new(
+            AnimatedField::component::<Transform>("translation"),
+            start,
+            end,
+            duration,
+        )
+    }
+
+    /// Creates a tween that animates [`Transform::scale`].
+    pub fn scale(start: Vec3, end: Vec3, duration: f32) -> Self {
+        Self::new(
+            AnimatedField::component::<Transform>("scale"),
+            start,
+            end,
+            duration,
+        )
+    }
+}
+
+impl Tween<Quat> {
+    /// Creates a tween that animates [`Transform::rotation`].
+    pub fn rotation(start: Quat, end: Quat, duration: f32) -> Self {
+        Self::new(
+            AnimatedField::component::<Transform>("rotation"),
+            start,
+            end,
+            duration,
+        )
+    }
+}
+
+impl Tween<Transform> {
+    /// Creates a tween that animates the whole [`Transform`].
+    pub fn transform(start: Transform, end: Transform, duration: f32) -> Self {
+        Self::new(
+            AnimatedField::component::<Transform>(""),
+            start,
+            end,
+            duration,
+        )
+    }
+}
+
+impl<T: Debug> Debug for Tween<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Tween")
+            .field("field", &self.field)
+            .field("start", &self.start)
+            .field("end", &self.end)
+            .field("duration", &self.duration)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> Tweenable for Tween<T>
+where
+    T: Animatable + TypePath + Clone,
+{
+    fn duration(&self) -> f32 {
+        self.duration
+    }
+
+    fn apply(
+        &self,
+        world: &mut World,
+        entity: Entity,
+        registry: &TypeRegistry,
+        time: f32,
+        _: f32,
+    ) -> Result<(), AnimationEvaluationError> {
+        let sample = self.sample(time);
+        self.field.with_field_mut(world, entity, registry, |field| {
+            let found = field.reflect_type_path().to_owned();
+            let value = field.try_downcast_mut::<T>().ok_or_else(|| {
+                AnimationEvaluationError::MismatchedType {
+                    path: self.field.path().to_owned(),
+                    expected: T::type_path(),
+                    found,
+                }
+            })?;
+            *value = sample;
+            Ok(())
+        })
+    }
+}
+
+impl TweenSequence {
+    /// Creates an empty sequence.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a tween that plays after the ones already in this sequence.
+    pub fn then(mut self, tween: impl Tweenable) -> Self {
+        self.tweens.push(Box::new(tween));
+        self
+    }
+}
+
+impl Tweenable for TweenSequence {
+    fn duration(&self) -> f32 {
+        self.tweens.iter().map(|tween| tween.duration()).sum()
+    }
+
+    fn apply(
+        &self,
+        world: &mut World,
+        entity: Entity,
+        registry: &TypeRegistry,
+        time: f32,
+        last_time: f32,
+    ) -> Result<(), AnimationEvaluationError> {
+        // Every tween that was passed through since the last update is
+        // applied, so that none of them is left unfinished. They're applied in
+        // the direction of playback, so that the current one ends up on top.
+        let (low, high) = (time.min(last_time), time.max(last_time));
+        let mut apply = |tween: &dyn Tweenable, start: f32, duration: f32| {
+            if start > high || start + duration < low {
+                return Ok(());
+            }
+            let local = |time: f32| (time - start).clamp(0.0, duration);
+            tween.apply(world, entity, registry, local(time), local(last_time))
+        };
+
+        if time >= last_time {
+            let mut start = 0.0;
+            for tween in &self.tweens {
+                let duration = tween.duration();
+                apply(&**tween, start, duration)?;
+                start += duration;
+            }
+        } else {
+            let mut end = self.duration();
+            for tween in self.tweens.iter().rev() {
+                let duration = tween.duration();
+                apply(&**tween, end - duration, duration)?;
+                end -= duration;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl TweenGroup {
+    /// Creates an empty group.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a tween that plays at the same time as the ones already in this
+    /// group.
+    pub fn with(mut self, tween: impl Tweenable) -> Self {
+        self.tweens.push(Box::new(tween));
+        self
+    }
+}
+
+impl Tweenable for TweenGroup {
+    fn duration(&self) -> f32 {
+        self.tweens
+            .iter()
+            .map(|tween| tween.duration())
+            .fold(0.0, f32::max)
+    }
+
+    fn apply(
+        &self,
+        world: &mut World,
+        entity: Entity,
+        registry: &TypeRegistry,
+        time: f32,
+        last_time: f32,
+    ) -> Result<(), AnimationEvaluationError> {
+        for tween in &self.tweens {
+            let duration = tween.duration();
+            // Leave tweens that have already finished alone.
+            if time.min(last_time) > duration {
+                continue;
+            }
+            tween.apply(
+                world,
+                entity,
+                registry,
+                time.min(duration),
+                last_time.min(duration),
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl TweenPlayer {
+    /// Creates a player that plays `tween` once.
+    pub fn new(tween: impl Tweenable) -> Self {
+        Self {
+            tween: Arc::new(tween),
+            time: 0.0,
+            backwards: false,
+            repeat: RepeatAnimation::Never,
+            ping_pong: false,
+            speed: 1.0,
+            paused: false,
+            completions: 0,
+        }
+    }
+
+    /// Sets how many times the tween plays.
+    pub fn with_repeat(mut self, repeat: RepeatAnimation) -> Self {
+        self.repeat = repeat;
+        self
+    }
+
+    /// Sets whether the tween plays backwards after playing forwards, in
+    /// which case one repetition consists of both directions.
+    pub fn with_ping_pong(mut self, ping_pong: bool) -> Self {
+        self.ping_pong = ping_pong;
+        self
+    }
+
+    /// Sets the speed of playback, where 1.0 is normal speed.
+    ///
+    /// A negative speed plays the tween in reverse, from its end to its
+    /// start.
+    pub fn with_speed(mut self, speed: f32) -> Self {
+        self.speed = speed;
+        self.time = if speed < 0.0 {
+            self.tween.duration().max(0.0)
+        } else {
+            0.0
+        };
+        self
+    }
+
+    /// The time within the tween, in seconds.
+    pub fn elapsed(&self) -> f32 {
+        self.time
+    }
+
+    /// The number of times the tween has completed.
+    pub fn completions(&self) -> u32 {
+        self.completions
+    }
+
+    /// Whether the tween has finished all of its repetitions.
+    pub fn is_finished(&self) -> bool {
+        match self.repeat {
+            RepeatAnimation::Forever => false,
+            RepeatAnimation::Never => self.completions >= 1,
+            RepeatAnimation::Count(n) => self.completions >= n,
+        }
+    }
+
+    /// Pauses playback.
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    /// Resumes playback.
+    pub fn resume(&mut self) {
+        self.paused = false;
+    }
+
+    /// Whether playback is paused.
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Advances playback by `delta` seconds, pushing the spans of time that
+    /// were played, as `(last_time, time)` pairs, to `spans`.
+    fn advance(&mut self, delta: f32, spans: &mut Vec<(f32, f32)>) {
+        let duration = self.tween.duration().max(0.0);
+        let mut remaining = delta * self.speed.abs();
+        // Each repetition of a reversed tween starts at the end.
+        let reversed = self.speed < 0.0;
+        let start = if reversed { duration } else { 0.0 };
+        while !self.is_finished() {
+            let last_time = self.time;
+            let towards_start = self.backwards != reversed;
+            let end = if towards_start { 0.0 } else { duration };
+            let step = remaining.min((end - last_time).abs());
+            remaining -= step;
+            self.time = if towards_start {
+                last_time - step
+            } else {
+                last_time + step
+            };
+            spans.push((last_time, self.time));
+            if self.time != end {
+                break;
+            }
+
+            if self.ping_pong && !self.backwards {
+                self.backwards = true;
+            } else {
+                self.completions += 1;
+                self.backwards = false;
+                self.time = start;
+                if self.is_finished() {
+                    // Leave the player where it finished.
+                    self.time = end;
+                }
+            }
+
+            // Don't loop tweens with no duration within a single update.
+            if remaining <= 0.0 || duration == 0.0 {
+                break;
+            }
+        }
+    }
+}
+
+impl Debug for TweenPlayer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TweenPlayer")
+            .field("time", &self.time)
+            .field("backwards", &self.backwards)
+            .field("repeat", &self.repeat)
+            .field("ping_pong", &self.ping_pong)
+            .field("speed", &self.speed)
+            .field("paused", &self.paused)
+            .field("completions", &self.completions)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A span of a tween's playback, scheduled by [`advance_tweens`].
+pub struct TweenSpan {
+    entity: Entity,
+    tween: Arc<dyn Tweenable>,
+    last_time: f32,
+    time: f32,
+}
+
+/// A system that advances all [`TweenPlayer`]s and applies their tweens.
+pub fn advance_tweens(
+    world: &mut World,
+    state: &mut SystemState<(Res<Time>, Query<(Entity, &mut TweenPlayer)>)>,
+    mut scratch: Local<(Vec<TweenSpan>, Vec<(f32, f32)>, Vec<Entity>)>,
+) {
+    let Some(registry) = world.get_resource::<AppTypeRegistry>().cloned() else {
+        return;
+    };
+
+    // We reuse the allocations across frames.
+    let (mut spans, mut times, mut completed) = mem::take(&mut *scratch);
+
+    let (time, mut players) = state.get_mut(world);
+    let delta = time.delta_seconds();
+    for (entity, mut player) in &mut players {
+        if player.paused || player.is_finished() {
+            continue;
+        }
+        player.advance(delta, &mut times);
+        spans.extend(times.drain(..).map(|(last_time, time)| TweenSpan {
+            entity,
+            tween: player.tween.clone(),
+            last_time,
+            time,
+        }));
+        if player.is_finished() {
+            completed.push(entity);
+        }
+    }
+
+    let registry = registry.read();
+    for span in spans.drain(..) {
+        if let Err(err) = span
+            .tween
+            .apply(world, span.entity, &registry, span.time, span.last_time)
+        {
+            error!("Failed to tween {:?}: {}", span.entity, err);
+        }
+    }
+    drop(registry);
+
+    for entity in completed.drain(..) {
+        world.trigger_targets(TweenCompleted, entity);
+    }
+
+    *scratch = (spans, times, completed);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy_ecs::{
+        component::Component,
+        observer::Trigger,
+        reflect::{AppTypeRegistry, ReflectComponent},
+        system::{ResMut, Resource, RunSystemOnce},
+        world::World,
+    };
+    use bevy_math::{curve::EaseFunction, Vec3};
+    use bevy_reflect::Reflect;
+    use bevy_time::Time;
+    use bevy_transform::components::Transform;
+
+    use super::{
+        advance_tweens, Tween, TweenCompleted, TweenGroup, TweenPlayer, TweenSequence, Tweenable,
+    };
+    use crate::{animation_curves::AnimatedField, RepeatAnimation};
+
+    #[derive(Component, Reflect, Default)]
+    #[reflect(Component)]
+    struct Lamp {
+        intensity: f32,
+    }
+
+    #[derive(Resource, Default)]
+    struct Completions(u32);
+
+    fn world() -> World {
+        let mut world = World::new();
+        world.init_resource::<AppTypeRegistry>();
+        {
+            let registry = world.resource::<AppTypeRegistry>();
+            let mut registry = registry.write();
+            registry.register::<Transform>();
+            registry.register::<Lamp>();
+        }
+        world.init_resource::<Time>();
+        world.init_resource::<Completions>();
+        world.observe(
+            |_: Trigger<TweenCompleted>, mut completions: ResMut<Completions>| {
+                completions.0 += 1;
+            },
+        );
+        world
+    }
+
+    fn tick(world: &mut World, seconds: f32) {
+        world
+            .resource_mut::<Time>()
+            .advance_by(Duration::from_secs_f32(seconds));
+        world.run_system_once(advance_tweens);
+    }
+
+    #[test]
+    fn sequence_and_group() {
+        let mut world = world();
+        let tween = TweenSequence::new()
+            .then(Tween::translation(Vec3::ZERO, Vec3::X, 1.0))
+            .then(
+                TweenGroup::new()
+                    .with(Tween::scale(Vec3::ONE, Vec3::splat(2.0), 1.0))
+                    .with(Tween::new(
+                        AnimatedField::component::<Lamp>("intensity"),
+                        0.0f32,
+                        10.0,
+                        0.5,
+                    )),
+            );
+        assert_eq!(tween.duration(), 2.0);
+        let entity = world
+            .spawn((
+                Transform::default(),
+                Lamp::default(),
+                TweenPlayer::new(tween),
+            ))
+            .id();
+
+        tick(&mut world, 0.5);
+        let transform = world.get::<Transform>(entity).unwrap();
+        assert_eq!(transform.translation, Vec3::new(0.5, 0.0, 0.0));
+        assert_eq!(transform.scale, Vec3::ONE);
+
+        // Skipping past the end of the translation still finishes it.
+        tick(&mut world, 1.0);
+        let transform = world.get::<Transform>(entity).unwrap();
+        assert_eq!(transform.translation, Vec3::X);
+        assert_eq!(transform.scale, Vec3::splat(1.5));
+        assert_eq!(world.get::<Lamp>(entity).unwrap().intensity, 10.0);
+        assert_eq!(world.resource::<Completions>().0, 0);
+
+        tick(&mut world, 1.0);
+        assert_eq!(
+            world.get::<Transform>(entity).unwrap().scale,
+            Vec3::splat(2.0)
+        );
+        assert!(world.get::<TweenPlayer>(entity).unwrap().is_finished());
+        assert_eq!(world.resource::<Completions>().0, 1);
+
+        // Finished tweens no longer trigger.
+        tick(&mut world, 1.0);
+        assert_eq!(world.resource::<Completions>().0, 1);
+    }
+
+    #[test]
+    fn ping_pong() {
+        let mut world = world();
+        let tween = Tween::new(
+            AnimatedField::component::<Lamp>("intensity"),
+            0.0f32,
+            1.0,
+            1.0,
+        );
+        let player = TweenPlayer::new(tween)
+            .with_ping_pong(true)
+            .with_repeat(RepeatAnimation::Count(2));
+        let entity = world.spawn((Lamp::default(), player)).id();
+        let intensity = |world: &World| world.get::<Lamp>(entity).unwrap().intensity;
+
+        tick(&mut world, 0.75);
+        assert_eq!(intensity(&world), 0.75);
+        tick(&mut world, 0.5);
+        assert_eq!(intensity(&world), 0.75);
+        tick(&mut world, 1.0);
+        assert_eq!(intensity(&world), 0.25);
+        assert_eq!(world.get::<TweenPlayer>(entity).unwrap().completions(), 1);
+
+        tick(&mut world, 2.0);
+        assert_eq!(intensity(&world), 0.0);
+        assert!(world.get::<TweenPlayer>(entity).unwrap().is_finished());
+        assert_eq!(world.resource::<Completions>().0, 1);
+    }
+
+    #[test]
+    fn reversed() {
+        let mut world = world();
+        let tween = Tween::new(
+            AnimatedField::component::<Lamp>("intensity"),
+            0.0f32,
+            1.0,
+            1.0,
+        );
+        let player = TweenPlayer::new(tween)
+            .with_speed(-0.5)
+            .with_repeat(RepeatAnimation::Count(2));
+        let entity = world.spawn((Lamp::default(), player)).id();
+        let intensity = |world: &World| world.get::<Lamp>(entity).unwrap().intensity;
+
+        tick(&mut world, 1.0);
+        assert_eq!(intensity(&world), 0.5);
+        tick(&mut world, 1.5);
+        assert_eq!(intensity(&world), 0.75);
+        assert_eq!(world.get::<TweenPlayer>(entity).unwrap().completions(), 1);
+
+        tick(&mut world, 2.0);
+        assert_eq!(intensity(&world), 0.0);
+        assert!(world.get::<TweenPlayer>(entity).unwrap().is_finished());
+        assert_eq!(world.resource::<Completions>().0, 1);
+    }
+
+    #[test]
+    fn easing() {
+        let tween = Tween::translation(Vec3::ZERO, Vec3::X, 2.0).with_ease(EaseFunction::Steps(2));
+        assert_eq!(tween.sample(0.9), Vec3::ZERO);
+        assert_eq!(tween.sample(1.0), Vec3::X * 0.5);
+        assert_eq!(tween.sample(3.0), Vec3::X);
+
+        let tween = Tween::translation(Vec3::ZERO, Vec3::X, 1.0).with_ease(EaseFunction::CubicIn);
+        assert_eq!(tween.sample(0.5), Vec3::X * 0.125);
+    }
+}
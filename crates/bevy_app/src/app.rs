@@ -753,6 +753,26 @@ impl App {
         self
     }
 
+    /// Registers the given system so it can be run by name with [`World::call_function`].
+    ///
+    /// The system's input is converted from the reflected arguments passed to the call,
+    /// and its output is returned as a reflected value.
+    ///
+    /// See [`World::register_system_function`] for more information.
+    #[cfg(feature = "reflect_functions")]
+    pub fn register_system_function<I, O, M>(
+        &mut self,
+        name: impl Into<std::borrow::Cow<'static, str>>,
+        system: impl IntoSystem<I, O, M> + 'static,
+    ) -> &mut Self
+    where
+        I: bevy_reflect::FromReflect + bevy_reflect::TypePath,
+        O: bevy_reflect::PartialReflect,
+    {
+        self.main_mut().register_system_function(name, system);
+        self
+    }
+
     /// Returns a reference to the [`World`].
     pub fn world(&self) -> &World {
         self.main().world()
@@ -434,6 +434,21 @@ impl SubApp {
         registry.write().register_with_name(name, function).unwrap();
         self
     }
+
+    /// See [`App::register_system_function`].
+    #[cfg(feature = "reflect_functions")]
+    pub fn register_system_function<I, O, M>(
+        &mut self,
+        name: impl Into<std::borrow::Cow<'static, str>>,
+        system: impl IntoSystem<I, O, M> + 'static,
+    ) -> &mut Self
+    where
+        I: bevy_reflect::FromReflect + bevy_reflect::TypePath,
+        O: bevy_reflect::PartialReflect,
+    {
+        self.world.register_system_function(name, system);
+        self
+    }
 }
 
 /// The collection of sub-apps that belong to an [`App`].
@@ -0,0 +1,321 @@
+use crate::{io::Reader, Asset, AssetLoader, LoadContext, ReflectHandle};
+use bevy_ecs::{reflect::AppTypeRegistry, world::FromWorld};
+use bevy_reflect::{PartialReflect, Struct, TypeRegistryArc};
+use bevy_utils::HashMap;
+use serde::{
+    de::{EnumAccess, VariantAccess, Visitor},
+    Deserialize, Deserializer,
+};
+use std::{fmt, marker::PhantomData};
+use thiserror::Error;
+
+/// A reflected struct of [`Handle`](crate::Handle) fields that can be loaded from a manifest file using an [`AssetCollectionLoader`].
+///
+/// This is implemented for every [`Asset`] that derives [`Reflect`](bevy_reflect::Reflect) on a struct and implements [`Default`].
+///
+/// ```
+/// # use bevy_asset::{Asset, AssetApp, Handle};
+/// # use bevy_reflect::Reflect;
+/// # #[derive(Asset, Reflect)]
+/// # struct Image;
+/// # #[derive(Asset, Reflect)]
+/// # struct Font;
+/// #[derive(Asset, Reflect, Default)]
+/// struct GameAssets {
+///     #[dependency]
+///     player_sprite: Handle<Image>,
+///     #[dependency]
+///     font: Handle<Font>,
+/// }
+/// ```
+///
+/// The `GameAssets` above can then be loaded from a `game.assets.ron` manifest like this one:
+///
+/// ```ron
+/// {
+///     "player_sprite": Image("sprites/player.png"),
+///     "font": Font("fonts/FiraSans-Bold.ttf"),
+/// }
+/// ```
+///
+/// Every entry of the manifest names a field of the collection, the short type path of the asset
+/// (which must match the type of the field) and the asset path to load it from.
+/// Because every entry is a dependency of the collection, the collection is only reported as loaded with dependencies
+/// (see [`AssetServer::is_loaded_with_dependencies`](crate::AssetServer::is_loaded_with_dependencies)) once all of its entries are.
+pub trait AssetCollection: Asset + Struct + Default {}
+
+impl<C: Asset + Struct + Default> AssetCollection for C {}
+
+/// A single entry of an [`AssetCollectionManifest`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AssetCollectionEntry {
+    /// The [`TypePath::short_type_path`](bevy_reflect::TypePath::short_type_path) or
+    /// [`TypePath::type_path`](bevy_reflect::TypePath::type_path) of the asset.
+    pub asset_type: String,
+    /// The asset path to load the asset from.
+    pub path: String,
+}
+
+impl<'de> Deserialize<'de> for AssetCollectionEntry {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct EntryVisitor;
+
+        impl<'de> Visitor<'de> for EntryVisitor {
+            type Value = AssetCollectionEntry;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str(
+                    "an asset type with an asset path, such as `Image(\"sprites/player.png\")`",
+                )
+            }
+
+            fn visit_enum<A: EnumAccess<'de>>(self, data: A) -> Result<Self::Value, A::Error> {
+                let (AssetType(asset_type), variant) = data.variant::<AssetType>()?;
+                let path = variant.newtype_variant::<String>()?;
+                Ok(AssetCollectionEntry { asset_type, path })
+            }
+        }
+
+        /// The variant name of an entry, which is deserialized as an identifier rather than a string.
+        struct AssetType(String);
+
+        impl<'de> Deserialize<'de> for AssetType {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                struct AssetTypeVisitor;
+
+                impl<'de> Visitor<'de> for AssetTypeVisitor {
+                    type Value = AssetType;
+
+                    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                        formatter.write_str("an asset type")
+                    }
+
+                    fn visit_str<E: serde::de::Error>(self, value: &str) -> Result<AssetType, E> {
+                        Ok(AssetType(value.to_string()))
+                    }
+                }
+
+                deserializer.deserialize_identifier(AssetTypeVisitor)
+            }
+        }
+
+        // The asset type is not known ahead of time, so any variant name is accepted
+        deserializer.deserialize_enum("AssetCollectionEntry", &[], EntryVisitor)
+    }
+}
+
+/// The contents of an asset collection manifest: a map from field names to [`AssetCollectionEntry`].
+pub type AssetCollectionManifest = HashMap<String, AssetCollectionEntry>;
+
+/// Loads an [`AssetCollection`] from an `.assets.ron` manifest. See [`AssetCollection`] for the manifest format.
+///
+/// Several collection types can share the `.assets.ron` extension: the loader is picked based on the requested asset type,
+/// so collections should be loaded with a typed [`AssetServer::load`](crate::AssetServer::load).
+///
+/// This is registered by [`AssetApp::init_asset_collection`](crate::AssetApp::init_asset_collection).
+pub struct AssetCollectionLoader<C: AssetCollection> {
+    type_registry: TypeRegistryArc,
+    marker: PhantomData<fn() -> C>,
+}
+
+impl<C: AssetCollection> FromWorld for AssetCollectionLoader<C> {
+    fn from_world(world: &mut bevy_ecs::world::World) -> Self {
+        let type_registry = world.resource::<AppTypeRegistry>();
+        AssetCollectionLoader {
+            type_registry: type_registry.0.clone(),
+            marker: PhantomData,
+        }
+    }
+}
+
+/// An error that occurs when loading an [`AssetCollection`] with an [`AssetCollectionLoader`].
+#[derive(Debug, Error)]
+pub enum AssetCollectionLoaderError {
+    #[error("Could not read the manifest: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Could not parse the manifest RON: {0}")]
+    RonSpannedError(#[from] ron::error::SpannedError),
+    #[error("The manifest entry '{field}' does not match a field of the asset collection")]
+    UnknownField { field: String },
+    #[error("The field '{field}' of the asset collection is not a registered `Handle` type. Make sure its asset type has been initialized with `init_asset`")]
+    NotAHandle { field: String },
+    #[error("The manifest entry '{field}' is declared as a '{found}', but the field is a handle to a '{expected}'")]
+    MismatchedType {
+        field: String,
+        expected: &'static str,
+        found: String,
+    },
+    #[error("The manifest has no entry for the field '{field}' of the asset collection")]
+    MissingEntry { field: String },
+}
+
+impl<C: AssetCollection> AssetLoader for AssetCollectionLoader<C> {
+    type Asset = C;
+    type Settings = ();
+    type Error = AssetCollectionLoaderError;
+
+    async fn load<'a>(
+        &'a self,
+        reader: &'a mut dyn Reader,
+        _settings: &'a (),
+        load_context: &'a mut LoadContext<'_>,
+    ) -> Result<C, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let manifest: AssetCollectionManifest = ron::de::from_bytes(&bytes)?;
+
+        let type_registry = self.type_registry.read();
+        let mut collection = C::default();
+        for index in 0..collection.field_len() {
+            let field_name = collection.name_at(index).unwrap_or_default();
+            let reflect_handle = collection
+                .field_at(index)
+                .and_then(PartialReflect::get_represented_type_info)
+                .and_then(|info| type_registry.get_type_data::<ReflectHandle>(info.type_id()));
+            if reflect_handle.is_some() && !manifest.contains_key(field_name) {
+                return Err(AssetCollectionLoaderError::MissingEntry {
+                    field: field_name.to_string(),
+                });
+            }
+        }
+
+        for (field_name, entry) in manifest {
+            let Some(field) = collection.field_mut(&field_name) else {
+                return Err(AssetCollectionLoaderError::UnknownField { field: field_name });
+            };
+            let Some(reflect_handle) = field
+                .get_represented_type_info()
+                .and_then(|info| type_registry.get_type_data::<ReflectHandle>(info.type_id()))
+            else {
+                return Err(AssetCollectionLoaderError::NotAHandle { field: field_name });
+            };
+            if entry.asset_type != reflect_handle.asset_short_type_path()
+                && entry.asset_type != reflect_handle.asset_type_path()
+            {
+                return Err(AssetCollectionLoaderError::MismatchedType {
+                    field: field_name,
+                    expected: reflect_handle.asset_short_type_path(),
+                    found: entry.asset_type,
+                });
+            }
+
+            let handle = load_context.loader().load_erased(
+                reflect_handle.asset_type_id(),
+                reflect_handle.asset_type_path(),
+                entry.path,
+            );
+            let Some(field) = field.try_as_reflect_mut() else {
+                return Err(AssetCollectionLoaderError::NotAHandle { field: field_name });
+            };
+            if field.set(reflect_handle.typed(handle)).is_err() {
+                return Err(AssetCollectionLoaderError::NotAHandle { field: field_name });
+            }
+        }
+
+        Ok(collection)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["assets.ron"]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate as bevy_asset;
+    use crate::{
+        io::{
+            memory::{Dir, MemoryAssetReader},
+            AssetSource, AssetSourceId,
+        },
+        tests::{run_app_until, CoolText, CoolTextLoader},
+        Asset, AssetApp, AssetCollectionEntry, AssetCollectionManifest, AssetPlugin, AssetServer,
+        Assets, Handle, LoadState,
+    };
+    use bevy_app::App;
+    use bevy_core::TaskPoolPlugin;
+    use bevy_log::LogPlugin;
+    use bevy_reflect::Reflect;
+    use std::path::Path;
+
+    #[derive(Asset, Reflect, Default)]
+    struct TextCollection {
+        #[dependency]
+        greeting: Handle<CoolText>,
+        #[dependency]
+        farewell: Handle<CoolText>,
+        volume: f32,
+    }
+
+    #[test]
+    fn parse_manifest() {
+        let manifest: AssetCollectionManifest =
+            ron::de::from_str(r#"{ "player_sprite": Image("sprites/player.png") }"#).unwrap();
+        assert_eq!(
+            manifest["player_sprite"],
+            AssetCollectionEntry {
+                asset_type: "Image".to_string(),
+                path: "sprites/player.png".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn load_collection() {
+        // Loading the entries of the collection will cause deadlocking if running single-threaded
+        #[cfg(not(feature = "multi_threaded"))]
+        panic!("This test requires the \"multi_threaded\" feature, otherwise it will deadlock.\ncargo test --package bevy_asset --features multi_threaded");
+
+        let text = |text: &str| {
+            format!("(text: {text:?}, dependencies: [], embedded_dependencies: [], sub_texts: [])")
+        };
+        let dir = Dir::default();
+        dir.insert_asset_text(Path::new("hello.cool.ron"), &text("hello"));
+        dir.insert_asset_text(Path::new("bye.cool.ron"), &text("bye"));
+        dir.insert_asset_text(
+            Path::new("texts.assets.ron"),
+            r#"{ "greeting": CoolText("hello.cool.ron"), "farewell": CoolText("bye.cool.ron") }"#,
+        );
+        dir.insert_asset_text(
+            Path::new("missing.assets.ron"),
+            r#"{ "greeting": CoolText("hello.cool.ron") }"#,
+        );
+        dir.insert_asset_text(
+            Path::new("mismatched.assets.ron"),
+            r#"{ "greeting": SubText("hello.cool.ron"), "farewell": CoolText("bye.cool.ron") }"#,
+        );
+
+        let mut app = App::new();
+        let reader = MemoryAssetReader { root: dir };
+        app.register_asset_source(
+            AssetSourceId::Default,
+            AssetSource::build().with_reader(move || Box::new(reader.clone())),
+        )
+        .add_plugins((
+            TaskPoolPlugin::default(),
+            LogPlugin::default(),
+            AssetPlugin::default(),
+        ))
+        .init_asset::<CoolText>()
+        .register_asset_loader(CoolTextLoader)
+        .init_asset_collection::<TextCollection>();
+
+        let asset_server = app.world().resource::<AssetServer>().clone();
+        let handle: Handle<TextCollection> = asset_server.load("texts.assets.ron");
+        let missing: Handle<TextCollection> = asset_server.load("missing.assets.ron");
+        let mismatched: Handle<TextCollection> = asset_server.load("mismatched.assets.ron");
+        run_app_until(&mut app, |_| {
+            (asset_server.is_loaded_with_dependencies(&handle)
+                && matches!(asset_server.load_state(&missing), LoadState::Failed(_))
+                && matches!(asset_server.load_state(&mismatched), LoadState::Failed(_)))
+            .then_some(())
+        });
+
+        let collections = app.world().resource::<Assets<TextCollection>>();
+        let texts = app.world().resource::<Assets<CoolText>>();
+        let collection = collections.get(&handle).unwrap();
+        assert_eq!(texts.get(&collection.greeting).unwrap().text, "hello");
+        assert_eq!(texts.get(&collection.farewell).unwrap().text, "bye");
+        assert_eq!(collection.volume, 0.0);
+    }
+}
@@ -41,6 +41,25 @@ impl<A: Asset> From<&AssetLoadFailedEvent<A>> for UntypedAssetLoadFailedEvent {
     }
 }
 
+/// An event emitted once every reload in a "reload batch" has finished. A reload batch starts when an asset is reloaded
+/// (either because it changed on disk while watching for changes, or because of a call to [`AssetServer::reload`]) and
+/// collects every reload that starts before all of the batch's reloads have finished.
+///
+/// By the time this is sent, all reloaded asset values have been updated, so systems that derive data from many assets can
+/// rebuild once per batch instead of once per modified file.
+///
+/// [`AssetServer::reload`]: crate::AssetServer::reload
+#[derive(Event, Clone, Debug)]
+pub struct AssetReloadBatchCompleteEvent {
+    /// The asset paths that were reloaded in this batch.
+    pub reloaded: Vec<AssetPath<'static>>,
+    /// The assets (including labeled assets) whose values were replaced by this batch.
+    pub modified: Vec<UntypedAssetId>,
+    /// The assets that had one of their (recursive) dependencies modified by this batch, in topological order
+    /// (an asset always comes after its dependencies). An [`AssetEvent::DependencyModified`] was emitted for each of these.
+    pub dependency_modified: Vec<UntypedAssetId>,
+}
+
 /// Events that occur for a specific loaded [`Asset`], such as "value changed" events and "dependency" events.
 #[derive(Event)]
 pub enum AssetEvent<A: Asset> {
@@ -54,6 +73,13 @@ pub enum AssetEvent<A: Asset> {
     Unused { id: AssetId<A> },
     /// Emitted whenever an [`Asset`] has been fully loaded (including its dependencies and all "recursive dependencies").
     LoadedWithDependencies { id: AssetId<A> },
+    /// Emitted when one of the (recursive) dependencies of an [`Asset`] was modified, such as when a texture used by a material is hot-reloaded.
+    /// Within a reload batch, these events are emitted in topological order: an asset is always notified after its modified dependencies.
+    /// See [`AssetReloadBatchCompleteEvent`].
+    DependencyModified { id: AssetId<A> },
+    /// Emitted after an [`Asset`] was loaded if one of its [`AssetValidator`](crate::AssetValidator)s reported an error.
+    /// The full report can be retrieved using [`AssetServer::get_validation_report`](crate::AssetServer::get_validation_report).
+    ValidationFailed { id: AssetId<A> },
 }
 
 impl<A: Asset> AssetEvent<A> {
@@ -81,6 +107,16 @@ impl<A: Asset> AssetEvent<A> {
     pub fn is_unused(&self, asset_id: impl Into<AssetId<A>>) -> bool {
         matches!(self, AssetEvent::Unused { id } if *id == asset_id.into())
     }
+
+    /// Returns `true` if this event is [`AssetEvent::DependencyModified`] and matches the given `id`.
+    pub fn is_dependency_modified(&self, asset_id: impl Into<AssetId<A>>) -> bool {
+        matches!(self, AssetEvent::DependencyModified { id } if *id == asset_id.into())
+    }
+
+    /// Returns `true` if this event is [`AssetEvent::ValidationFailed`] and matches the given `id`.
+    pub fn is_validation_failed(&self, asset_id: impl Into<AssetId<A>>) -> bool {
+        matches!(self, AssetEvent::ValidationFailed { id } if *id == asset_id.into())
+    }
 }
 
 impl<A: Asset> Clone for AssetEvent<A> {
@@ -102,6 +138,13 @@ impl<A: Asset> Debug for AssetEvent<A> {
                 .debug_struct("LoadedWithDependencies")
                 .field("id", id)
                 .finish(),
+            Self::DependencyModified { id } => f
+                .debug_struct("DependencyModified")
+                .field("id", id)
+                .finish(),
+            Self::ValidationFailed { id } => {
+                f.debug_struct("ValidationFailed").field("id", id).finish()
+            }
         }
     }
 }
@@ -116,7 +159,11 @@ impl<A: Asset> PartialEq for AssetEvent<A> {
             | (
                 Self::LoadedWithDependencies { id: l_id },
                 Self::LoadedWithDependencies { id: r_id },
-            ) => l_id == r_id,
+            )
+            | (Self::DependencyModified { id: l_id }, Self::DependencyModified { id: r_id })
+            | (Self::ValidationFailed { id: l_id }, Self::ValidationFailed { id: r_id }) => {
+                l_id == r_id
+            }
             _ => false,
         }
     }
@@ -124,6 +124,7 @@ pub mod prelude {
 }
 
 mod assets;
+mod collection;
 mod direct_access_ext;
 mod event;
 mod folder;
@@ -134,9 +135,11 @@ mod loader_builders;
 mod path;
 mod reflect;
 mod server;
+mod validation;
 
 pub use assets::*;
 pub use bevy_asset_macros::Asset;
+pub use collection::*;
 pub use direct_access_ext::DirectAssetAccessExt;
 pub use event::*;
 pub use folder::*;
@@ -150,6 +153,7 @@ pub use loader_builders::{
 pub use path::*;
 pub use reflect::*;
 pub use server::*;
+pub use validation::*;
 
 /// Rusty Object Notation, a crate used to serialize and deserialize bevy assets.
 pub use ron;
@@ -300,10 +304,12 @@ impl Plugin for AssetPlugin {
                         let processor = AssetProcessor::new(&mut builders);
                         let mut sources = builders.build_sources(false, watch);
                         sources.gate_on_processor(processor.data.clone());
-                        // the main asset server shares loaders with the processor asset server
+                        // the main asset server shares loaders (and validators) with the processor asset server
                         app.insert_resource(AssetServer::new_with_loaders(
                             sources,
                             processor.server().data.loaders.clone(),
+                            processor.server().data.meta_migrations.clone(),
+                            processor.server().data.validators.clone(),
                             AssetServerMode::Processed,
                             AssetMetaCheck::Always,
                             watch,
@@ -330,6 +336,7 @@ impl Plugin for AssetPlugin {
             .init_asset::<LoadedUntypedAsset>()
             .init_asset::<()>()
             .add_event::<UntypedAssetLoadFailedEvent>()
+            .add_event::<AssetReloadBatchCompleteEvent>()
             .configure_sets(PreUpdate, TrackAssets.after(handle_internal_asset_events))
             // `handle_internal_asset_events` requires the use of `&mut World`,
             // and as a result has ambiguous system ordering with all other systems in `PreUpdate`.
@@ -412,6 +419,31 @@ pub trait AssetApp {
     fn register_asset_loader<L: AssetLoader>(&mut self, loader: L) -> &mut Self;
     /// Registers the given `processor` in the [`App`]'s [`AssetProcessor`].
     fn register_asset_processor<P: Process>(&mut self, processor: P) -> &mut Self;
+    /// Registers a migration of the [`AssetLoader::Settings`] of `L` from settings version `from_version` (stored as `Old`)
+    /// to `from_version + 1` (stored as `New`). Meta files written with an older [`AssetLoader::SETTINGS_VERSION`]
+    /// will be upgraded using these migrations when they are loaded (and rewritten in place by the [`AssetProcessor`]).
+    fn register_asset_loader_settings_migration<L: AssetLoader, Old, New>(
+        &mut self,
+        from_version: u32,
+        migrate: impl Fn(Old) -> New + Send + Sync + 'static,
+    ) -> &mut Self
+    where
+        Old: for<'de> serde::Deserialize<'de>,
+        New: serde::Serialize;
+    /// Registers a migration of the [`Process::Settings`] of `P` from settings version `from_version` (stored as `Old`)
+    /// to `from_version + 1` (stored as `New`). Meta files written with an older [`Process::SETTINGS_VERSION`]
+    /// will be upgraded in place by the [`AssetProcessor`] using these migrations.
+    fn register_asset_processor_settings_migration<P: Process, Old, New>(
+        &mut self,
+        from_version: u32,
+        migrate: impl Fn(Old) -> New + Send + Sync + 'static,
+    ) -> &mut Self
+    where
+        Old: for<'de> serde::Deserialize<'de>,
+        New: serde::Serialize;
+    /// Registers the given [`AssetValidator`]. It runs for every asset of type [`AssetValidator::Asset`] loaded by the
+    /// [`AssetServer`] and for every such asset written by the [`AssetProcessor`].
+    fn register_asset_validator<V: AssetValidator>(&mut self, validator: V) -> &mut Self;
     /// Registers the given [`AssetSourceBuilder`] with the given `id`.
     ///
     /// Note that asset sources must be registered before adding [`AssetPlugin`] to your application,
@@ -429,10 +461,14 @@ pub trait AssetApp {
     /// * Registering the [`Asset`] in the [`AssetServer`]
     /// * Initializing the [`AssetEvent`] resource for the [`Asset`]
     /// * Adding other relevant systems and resources for the [`Asset`]
+    /// * Adding [`ReflectHandle`] type data to [`Handle<A>`] in the type registry
     /// * Ignoring schedule ambiguities in [`Assets`] resource. Any time a system takes
     ///     mutable access to this resource this causes a conflict, but they rarely actually
     ///     modify the same underlying asset.
     fn init_asset<A: Asset>(&mut self) -> &mut Self;
+    /// Initializes the given [`AssetCollection`] as an [`Asset`] (see [`AssetApp::init_asset`]) and registers an
+    /// [`AssetCollectionLoader`] for it, which loads it from `.assets.ron` manifests.
+    fn init_asset_collection<C: AssetCollection>(&mut self) -> &mut Self;
     /// Registers the asset type `T` using `[App::register]`,
     /// and adds [`ReflectAsset`] type data to `T` and [`ReflectHandle`] type data to [`Handle<T>`] in the type registry.
     ///
@@ -460,6 +496,43 @@ impl AssetApp for App {
         self
     }
 
+    fn register_asset_loader_settings_migration<L: AssetLoader, Old, New>(
+        &mut self,
+        from_version: u32,
+        migrate: impl Fn(Old) -> New + Send + Sync + 'static,
+    ) -> &mut Self
+    where
+        Old: for<'de> serde::Deserialize<'de>,
+        New: serde::Serialize,
+    {
+        self.world()
+            .resource::<AssetServer>()
+            .register_loader_settings_migration::<L, Old, New>(from_version, migrate);
+        self
+    }
+
+    fn register_asset_processor_settings_migration<P: Process, Old, New>(
+        &mut self,
+        from_version: u32,
+        migrate: impl Fn(Old) -> New + Send + Sync + 'static,
+    ) -> &mut Self
+    where
+        Old: for<'de> serde::Deserialize<'de>,
+        New: serde::Serialize,
+    {
+        if let Some(asset_processor) = self.world().get_resource::<AssetProcessor>() {
+            asset_processor.register_settings_migration::<P, Old, New>(from_version, migrate);
+        }
+        self
+    }
+
+    fn register_asset_validator<V: AssetValidator>(&mut self, validator: V) -> &mut Self {
+        self.world()
+            .resource::<AssetServer>()
+            .register_validator(validator);
+        self
+    }
+
     fn register_asset_source(
         &mut self,
         id: impl Into<AssetSourceId<'static>>,
@@ -514,6 +587,7 @@ impl AssetApp for App {
             .add_event::<AssetEvent<A>>()
             .add_event::<AssetLoadFailedEvent<A>>()
             .register_type::<Handle<A>>()
+            .register_type_data::<Handle<A>, ReflectHandle>()
             .add_systems(
                 Last,
                 Assets::<A>::asset_events
@@ -523,6 +597,11 @@ impl AssetApp for App {
             .add_systems(PreUpdate, Assets::<A>::track_assets.in_set(TrackAssets))
     }
 
+    fn init_asset_collection<C: AssetCollection>(&mut self) -> &mut Self {
+        self.init_asset::<C>()
+            .init_asset_loader::<AssetCollectionLoader<C>>()
+    }
+
     fn register_asset_reflect<A>(&mut self) -> &mut Self
     where
         A: Asset + Reflect + FromReflect + GetTypeRegistration,
@@ -571,8 +650,8 @@ mod tests {
         },
         loader::{AssetLoader, LoadContext},
         Asset, AssetApp, AssetEvent, AssetId, AssetLoadError, AssetLoadFailedEvent, AssetPath,
-        AssetPlugin, AssetServer, Assets, DependencyLoadState, LoadState,
-        RecursiveDependencyLoadState,
+        AssetPlugin, AssetReloadBatchCompleteEvent, AssetServer, AssetValidator, Assets,
+        DependencyLoadState, LoadState, RecursiveDependencyLoadState, ValidationContext,
     };
     use bevy_app::{App, Update};
     use bevy_core::TaskPoolPlugin;
@@ -1592,6 +1671,180 @@ mod tests {
         });
     }
 
+    /// Tests that reloading an asset notifies its (recursive) dependants in topological order.
+    #[test]
+    fn reload_notifies_dependants() {
+        // Loading the dependencies of an asset in this test will cause deadlocking if running single-threaded
+        #[cfg(not(feature = "multi_threaded"))]
+        panic!("This test requires the \"multi_threaded\" feature, otherwise it will deadlock.\ncargo test --package bevy_asset --features multi_threaded");
+
+        fn cool_ron(text: &str, dependencies: &[&str]) -> String {
+            format!(
+                "(text: {text:?}, dependencies: {dependencies:?}, embedded_dependencies: [], sub_texts: [])"
+            )
+        }
+
+        #[derive(Resource, Default)]
+        struct StoredBatches(Vec<AssetReloadBatchCompleteEvent>);
+
+        fn store_batches(
+            mut reader: EventReader<AssetReloadBatchCompleteEvent>,
+            mut storage: ResMut<StoredBatches>,
+        ) {
+            storage.0.extend(reader.read().cloned());
+        }
+
+        let dir = Dir::default();
+        dir.insert_asset_text(
+            Path::new("a.cool.ron"),
+            &cool_ron("a", &["b.cool.ron", "c.cool.ron"]),
+        );
+        dir.insert_asset_text(Path::new("b.cool.ron"), &cool_ron("b", &["d.cool.ron"]));
+        dir.insert_asset_text(Path::new("c.cool.ron"), &cool_ron("c", &["d.cool.ron"]));
+        dir.insert_asset_text(Path::new("d.cool.ron"), &cool_ron("d", &[]));
+
+        let mut app = App::new();
+        let reader = MemoryAssetReader { root: dir.clone() };
+        app.register_asset_source(
+            AssetSourceId::Default,
+            AssetSource::build().with_reader(move || Box::new(reader.clone())),
+        )
+        .add_plugins((
+            TaskPoolPlugin::default(),
+            LogPlugin::default(),
+            AssetPlugin {
+                watch_for_changes_override: Some(true),
+                ..Default::default()
+            },
+        ))
+        .init_asset::<CoolText>()
+        .init_asset::<SubText>()
+        .init_resource::<StoredEvents>()
+        .init_resource::<StoredBatches>()
+        .register_asset_loader(CoolTextLoader)
+        .add_systems(Update, (store_asset_events, store_batches));
+
+        let asset_server = app.world().resource::<AssetServer>().clone();
+        let a_handle: Handle<CoolText> = asset_server.load("a.cool.ron");
+        let a_id = a_handle.id();
+        app.world_mut().spawn(a_handle);
+        run_app_until(&mut app, |_| {
+            asset_server.is_loaded_with_dependencies(a_id).then_some(())
+        });
+        let id = |path: &str| {
+            asset_server
+                .get_handle::<CoolText>(path)
+                .unwrap()
+                .id()
+                .untyped()
+        };
+        let (b_id, c_id, d_id) = (id("b.cool.ron"), id("c.cool.ron"), id("d.cool.ron"));
+
+        dir.insert_asset_text(Path::new("d.cool.ron"), &cool_ron("d2", &[]));
+        asset_server.reload("d.cool.ron");
+        run_app_until(&mut app, |world| {
+            (!world.resource::<StoredBatches>().0.is_empty()).then_some(())
+        });
+
+        let batches = &app.world().resource::<StoredBatches>().0;
+        assert_eq!(batches.len(), 1);
+        let batch = &batches[0];
+        assert_eq!(batch.reloaded, vec![AssetPath::from("d.cool.ron")]);
+        assert_eq!(batch.modified, vec![d_id]);
+        assert_eq!(batch.dependency_modified.len(), 3);
+        assert_eq!(batch.dependency_modified[2], a_id.untyped());
+        assert!(batch.dependency_modified.contains(&b_id));
+        assert!(batch.dependency_modified.contains(&c_id));
+        assert_eq!(
+            get::<CoolText>(app.world(), d_id.typed()).unwrap().text,
+            "d2"
+        );
+
+        // Typed events are flushed at the end of the frame
+        app.update();
+        let events = &app.world().resource::<StoredEvents>().0;
+        for id in [a_id, b_id.typed(), c_id.typed()] {
+            assert!(events.iter().any(|event| event.is_dependency_modified(id)));
+        }
+        assert!(!events
+            .iter()
+            .any(|event| event.is_dependency_modified(d_id.typed())));
+    }
+
+    #[test]
+    fn validators_report_issues() {
+        struct NotEmpty;
+
+        impl AssetValidator for NotEmpty {
+            type Asset = CoolText;
+
+            fn validate(&self, asset: &CoolText, context: &mut ValidationContext) {
+                if asset.text.is_empty() {
+                    context.error("text is empty");
+                } else if asset.text.len() < 3 {
+                    context.warning("text is very short");
+                }
+            }
+        }
+
+        let dir = Dir::default();
+        for (path, text) in [("a.cool.ron", "hello"), ("b.cool.ron", "")] {
+            dir.insert_asset_text(
+                Path::new(path),
+                &format!(
+                    "(text: {text:?}, dependencies: [], embedded_dependencies: [], sub_texts: [])"
+                ),
+            );
+        }
+
+        let mut app = App::new();
+        let reader = MemoryAssetReader { root: dir.clone() };
+        app.register_asset_source(
+            AssetSourceId::Default,
+            AssetSource::build().with_reader(move || Box::new(reader.clone())),
+        )
+        .add_plugins((
+            TaskPoolPlugin::default(),
+            LogPlugin::default(),
+            AssetPlugin::default(),
+        ))
+        .init_asset::<CoolText>()
+        .init_asset::<SubText>()
+        .init_resource::<StoredEvents>()
+        .register_asset_loader(CoolTextLoader)
+        .register_asset_validator(NotEmpty)
+        .add_systems(Update, store_asset_events);
+
+        let asset_server = app.world().resource::<AssetServer>().clone();
+        let a_handle: Handle<CoolText> = asset_server.load("a.cool.ron");
+        let b_handle: Handle<CoolText> = asset_server.load("b.cool.ron");
+        run_app_until(&mut app, |world| {
+            let events = &world.resource::<StoredEvents>().0;
+            (events
+                .iter()
+                .any(|event| event.is_validation_failed(&b_handle))
+                && asset_server.is_loaded_with_dependencies(&a_handle))
+            .then_some(())
+        });
+
+        // Validation errors do not prevent the asset from being added
+        assert!(app
+            .world()
+            .resource::<Assets<CoolText>>()
+            .contains(&b_handle));
+        assert!(asset_server.get_validation_report(&a_handle).is_none());
+        let report = asset_server.get_validation_report(&b_handle).unwrap();
+        assert_eq!(report.path, AssetPath::from("b.cool.ron"));
+        assert!(report.has_errors());
+        assert_eq!(report.issues[0].message, "text is empty");
+        assert!(!app
+            .world()
+            .resource::<StoredEvents>()
+            .0
+            .iter()
+            .any(|event| event.is_validation_failed(&a_handle)));
+    }
+
     #[test]
     fn ignore_system_ambiguities_on_assets() {
         let mut app = App::new();
@@ -1,7 +1,7 @@
 use crate::{
     io::{AssetReaderError, MissingAssetSourceError, MissingProcessedAssetReaderError, Reader},
     loader_builders::NestedLoader,
-    meta::{AssetHash, AssetMeta, AssetMetaDyn, ProcessedInfoMinimal, Settings},
+    meta::{AssetHash, AssetMeta, AssetMetaDyn, MigrateMetaError, ProcessedInfoMinimal, Settings},
     path::AssetPath,
     Asset, AssetLoadError, AssetServer, AssetServerMode, Assets, Handle, UntypedAssetId,
     UntypedHandle,
@@ -32,6 +32,10 @@ pub trait AssetLoader: Send + Sync + 'static {
     type Settings: Settings + Default + Serialize + for<'a> Deserialize<'a>;
     /// The type of [error](`std::error::Error`) which could be encountered by this loader.
     type Error: Into<Box<dyn std::error::Error + Send + Sync + 'static>>;
+    /// The version of [`AssetLoader::Settings`]. Increment this whenever a change to [`AssetLoader::Settings`] would break
+    /// deserializing existing meta files, and register a migration from the previous version with
+    /// [`AssetApp::register_asset_loader_settings_migration`](crate::AssetApp::register_asset_loader_settings_migration).
+    const SETTINGS_VERSION: u32 = 0;
     /// Asynchronously loads [`AssetLoader::Asset`] (and any other labeled assets) from the bytes provided by [`Reader`].
     fn load<'a>(
         &'a self,
@@ -66,6 +70,8 @@ pub trait ErasedAssetLoader: Send + Sync + 'static {
     fn deserialize_meta(&self, meta: &[u8]) -> Result<Box<dyn AssetMetaDyn>, DeserializeMetaError>;
     /// Returns the default meta value for the [`AssetLoader`] (erased as [`Box<dyn AssetMetaDyn>`]).
     fn default_meta(&self) -> Box<dyn AssetMetaDyn>;
+    /// Returns the [`AssetLoader::SETTINGS_VERSION`] of the [`AssetLoader`].
+    fn settings_version(&self) -> u32;
     /// Returns the type name of the [`AssetLoader`].
     fn type_name(&self) -> &'static str;
     /// Returns the [`TypeId`] of the [`AssetLoader`].
@@ -119,6 +125,10 @@ where
         }))
     }
 
+    fn settings_version(&self) -> u32 {
+        L::SETTINGS_VERSION
+    }
+
     fn type_name(&self) -> &'static str {
         std::any::type_name::<L>()
     }
@@ -308,6 +318,8 @@ pub enum DeserializeMetaError {
     DeserializeSettings(#[from] SpannedError),
     #[error("Failed to deserialize minimal asset meta: {0:?}")]
     DeserializeMinimal(SpannedError),
+    #[error("Failed to migrate asset meta: {0}")]
+    Migrate(#[from] MigrateMetaError),
 }
 
 /// A context that provides access to assets in [`AssetLoader`]s, tracks dependencies, and collects asset load state.
@@ -5,7 +5,7 @@ use crate::{
     io::Reader,
     meta::{meta_transform_settings, AssetMetaDyn, MetaTransform, Settings},
     Asset, AssetLoadError, AssetPath, ErasedAssetLoader, ErasedLoadedAsset, Handle, LoadContext,
-    LoadDirectError, LoadedAsset, LoadedUntypedAsset,
+    LoadDirectError, LoadedAsset, LoadedUntypedAsset, UntypedHandle,
 };
 use std::any::TypeId;
 use std::sync::Arc;
@@ -129,6 +129,27 @@ impl<'ctx, 'builder> NestedLoader<'ctx, 'builder> {
         self.load_context.dependencies.insert(handle.id().untyped());
         handle
     }
+
+    /// Same as [`NestedLoader::load`], but for an asset type that is only known at runtime.
+    pub(crate) fn load_erased<'c>(
+        self,
+        type_id: TypeId,
+        type_name: &'static str,
+        path: impl Into<AssetPath<'c>>,
+    ) -> UntypedHandle {
+        let path = path.into().to_owned();
+        let handle = if self.load_context.should_load_dependencies {
+            self.load_context
+                .asset_server
+                .load_erased_with_meta_transform(path, type_id, type_name, self.meta_transform, ())
+        } else {
+            self.load_context
+                .asset_server
+                .get_or_create_path_handle_untyped(path, type_id, type_name, None)
+        };
+        self.load_context.dependencies.insert(handle.id());
+        handle
+    }
 }
 
 /// A builder for loading untyped nested assets inside a [`LoadContext`].
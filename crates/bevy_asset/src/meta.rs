@@ -1,9 +1,10 @@
 use crate::{self as bevy_asset, DeserializeMetaError, VisitAssetDependencies};
 use crate::{loader::AssetLoader, processor::Process, Asset, AssetPath};
-use bevy_utils::tracing::error;
+use bevy_utils::{tracing::error, HashMap};
 use downcast_rs::{impl_downcast, Downcast};
-use ron::ser::PrettyConfig;
-use serde::{Deserialize, Serialize};
+use ron::{error::SpannedError, ser::PrettyConfig};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use thiserror::Error;
 
 pub const META_FORMAT_VERSION: &str = "1.0";
 pub type MetaTransform = Box<dyn Fn(&mut dyn AssetMetaDyn) + Send + Sync>;
@@ -17,6 +18,12 @@ pub struct AssetMeta<L: AssetLoader, P: Process> {
     /// The version of the meta format being used. This will change whenever a breaking change is made to
     /// the meta format.
     pub meta_format_version: String,
+    /// The version of the settings stored in [`AssetMeta::asset`]. This is [`AssetLoader::SETTINGS_VERSION`] for
+    /// [`AssetAction::Load`] and [`Process::SETTINGS_VERSION`] for [`AssetAction::Process`].
+    ///
+    /// Meta written with an older settings version can be upgraded using [`MetaMigrations`].
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub settings_version: u32,
     /// Information produced by the [`AssetProcessor`] _after_ processing this asset.
     /// This will only exist alongside processed versions of assets. You should not manually set it in your asset source files.
     ///
@@ -29,8 +36,14 @@ pub struct AssetMeta<L: AssetLoader, P: Process> {
 
 impl<L: AssetLoader, P: Process> AssetMeta<L, P> {
     pub fn new(asset: AssetAction<L::Settings, P::Settings>) -> Self {
+        let settings_version = match &asset {
+            AssetAction::Load { .. } => L::SETTINGS_VERSION,
+            AssetAction::Process { .. } => P::SETTINGS_VERSION,
+            AssetAction::Ignore => 0,
+        };
         Self {
             meta_format_version: META_FORMAT_VERSION.to_string(),
+            settings_version,
             processed_info: None,
             asset,
         }
@@ -93,6 +106,8 @@ pub struct ProcessDependencyInfo {
 // using a type registry.
 #[derive(Serialize, Deserialize)]
 pub struct AssetMetaMinimal {
+    #[serde(default)]
+    pub settings_version: u32,
     pub asset: AssetActionMinimal,
 }
 
@@ -166,6 +181,133 @@ impl<T: 'static> Settings for T where T: Send + Sync {}
 
 impl_downcast!(Settings);
 
+fn is_zero(value: &u32) -> bool {
+    *value == 0
+}
+
+/// A mirror of [`AssetMeta`] that uses the same settings type for both loaders and processors.
+/// This is used to (de)serialize meta in [`MetaMigrations`] without knowing the [`AssetLoader`] / [`Process`] type.
+#[derive(Serialize, Deserialize)]
+struct VersionedAssetMeta<S> {
+    meta_format_version: String,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    settings_version: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    processed_info: Option<ProcessedInfo>,
+    asset: AssetAction<S, S>,
+}
+
+type ErasedMetaMigration = Box<dyn Fn(&[u8]) -> Result<Vec<u8>, MigrateMetaError> + Send + Sync>;
+
+/// Upgrades serialized [`AssetMeta`] written with an older [`AssetLoader::SETTINGS_VERSION`] or [`Process::SETTINGS_VERSION`]
+/// to the current version.
+///
+/// Each migration converts the settings of a given [`AssetLoader`] / [`Process`] from one version to the next. When meta with an
+/// older version is encountered, migrations are chained until the current version is reached.
+#[derive(Default)]
+pub struct MetaMigrations {
+    migrations: HashMap<String, HashMap<u32, ErasedMetaMigration>>,
+}
+
+impl MetaMigrations {
+    /// Registers a migration of the settings used by the [`AssetLoader`] or [`Process`] with the given `type_name`, from settings
+    /// version `from_version` (stored as `Old`) to `from_version + 1` (stored as `New`).
+    pub fn add<Old, New>(
+        &mut self,
+        type_name: &str,
+        from_version: u32,
+        migrate: impl Fn(Old) -> New + Send + Sync + 'static,
+    ) where
+        Old: DeserializeOwned,
+        New: Serialize,
+    {
+        let migration = move |bytes: &[u8]| {
+            let old: VersionedAssetMeta<Old> =
+                ron::de::from_bytes(bytes).map_err(|error| MigrateMetaError::Deserialize {
+                    version: from_version,
+                    error,
+                })?;
+            let asset = match old.asset {
+                AssetAction::Load { loader, settings } => AssetAction::Load {
+                    loader,
+                    settings: migrate(settings),
+                },
+                AssetAction::Process {
+                    processor,
+                    settings,
+                } => AssetAction::Process {
+                    processor,
+                    settings: migrate(settings),
+                },
+                AssetAction::Ignore => AssetAction::Ignore,
+            };
+            let new = VersionedAssetMeta {
+                meta_format_version: old.meta_format_version,
+                settings_version: from_version + 1,
+                processed_info: old.processed_info,
+                asset,
+            };
+            ron::ser::to_string_pretty(&new, PrettyConfig::default())
+                .map(String::into_bytes)
+                .map_err(MigrateMetaError::Serialize)
+        };
+        self.migrations
+            .entry(type_name.to_string())
+            .or_default()
+            .insert(from_version, Box::new(migration));
+    }
+
+    /// Upgrades the given serialized `meta` for the [`AssetLoader`] or [`Process`] with the given `type_name` from
+    /// `from_version` to `to_version`, returning the upgraded serialized meta.
+    pub fn migrate(
+        &self,
+        type_name: &str,
+        meta: &[u8],
+        from_version: u32,
+        to_version: u32,
+    ) -> Result<Vec<u8>, MigrateMetaError> {
+        if from_version > to_version {
+            return Err(MigrateMetaError::UnsupportedVersion {
+                type_name: type_name.to_string(),
+                found: from_version,
+                current: to_version,
+            });
+        }
+        let mut meta = meta.to_vec();
+        for version in from_version..to_version {
+            let migration = self
+                .migrations
+                .get(type_name)
+                .and_then(|migrations| migrations.get(&version))
+                .ok_or_else(|| MigrateMetaError::MissingMigration {
+                    type_name: type_name.to_string(),
+                    version,
+                })?;
+            meta = migration(&meta)?;
+        }
+        Ok(meta)
+    }
+}
+
+/// An error that occurs while upgrading [`AssetMeta`] with [`MetaMigrations`].
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum MigrateMetaError {
+    #[error(
+        "Settings version {found} of '{type_name}' is newer than the current version {current}"
+    )]
+    UnsupportedVersion {
+        type_name: String,
+        found: u32,
+        current: u32,
+    },
+    #[error("There is no migration for the settings of '{type_name}' from version {version}")]
+    MissingMigration { type_name: String, version: u32 },
+    #[error("Failed to deserialize asset meta with settings version {version}: {error}")]
+    Deserialize { version: u32, error: SpannedError },
+    #[error("Failed to serialize migrated asset meta: {0}")]
+    Serialize(ron::Error),
+}
+
 /// The () processor should never be called. This implementation exists to make the meta format nicer to work with.
 impl Process for () {
     type Settings = ();
@@ -252,3 +394,98 @@ pub(crate) fn get_full_asset_hash(
     }
     *hasher.finalize().as_bytes()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Serialize, Deserialize)]
+    struct SettingsV0 {
+        scale: f32,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    struct SettingsV1 {
+        scale: f32,
+        flip: bool,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct SettingsV2 {
+        scale_x: f32,
+        scale_y: f32,
+        flip: bool,
+    }
+
+    const LOADER: &str = "test::Loader";
+
+    fn migrations() -> MetaMigrations {
+        let mut migrations = MetaMigrations::default();
+        migrations.add(LOADER, 0, |old: SettingsV0| SettingsV1 {
+            scale: old.scale,
+            flip: false,
+        });
+        migrations.add(LOADER, 1, |old: SettingsV1| SettingsV2 {
+            scale_x: old.scale,
+            scale_y: old.scale,
+            flip: old.flip,
+        });
+        migrations
+    }
+
+    #[test]
+    fn migrate_settings_chain() {
+        let meta = r#"(
+    meta_format_version: "1.0",
+    asset: Load(
+        loader: "test::Loader",
+        settings: (
+            scale: 2.0,
+        ),
+    ),
+)"#;
+        let minimal: AssetMetaMinimal = ron::de::from_str(meta).unwrap();
+        assert_eq!(minimal.settings_version, 0);
+
+        let migrated = migrations().migrate(LOADER, meta.as_bytes(), 0, 2).unwrap();
+        let minimal: AssetMetaMinimal = ron::de::from_bytes(&migrated).unwrap();
+        assert_eq!(minimal.settings_version, 2);
+        let migrated: VersionedAssetMeta<SettingsV2> = ron::de::from_bytes(&migrated).unwrap();
+        let AssetAction::Load { loader, settings } = migrated.asset else {
+            panic!("expected a load action");
+        };
+        assert_eq!(loader, LOADER);
+        assert_eq!(
+            settings,
+            SettingsV2 {
+                scale_x: 2.0,
+                scale_y: 2.0,
+                flip: false,
+            }
+        );
+    }
+
+    #[test]
+    fn migrate_settings_errors() {
+        let meta = br#"(meta_format_version: "1.0", asset: Load(loader: "test::Loader", settings: (scale: 1.0)))"#;
+        assert_eq!(
+            migrations().migrate(LOADER, meta, 0, 3),
+            Err(MigrateMetaError::MissingMigration {
+                type_name: LOADER.to_string(),
+                version: 2,
+            })
+        );
+        assert_eq!(
+            migrations().migrate(LOADER, meta, 3, 2),
+            Err(MigrateMetaError::UnsupportedVersion {
+                type_name: LOADER.to_string(),
+                found: 3,
+                current: 2,
+            })
+        );
+        assert!(matches!(
+            migrations().migrate(LOADER, meta, 1, 2),
+            Err(MigrateMetaError::Deserialize { version: 1, .. })
+        ));
+    }
+}
@@ -0,0 +1,303 @@
+use crate::{
+    processor::{AssetProcessor, ProcessError, ProcessResult},
+    AssetPath,
+};
+use bevy_app::{App, AppExit, Plugin, PluginsState};
+use bevy_utils::{tracing::error, HashMap};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Configures a single "batch" run of the [`AssetProcessor`]. See [`AssetProcessor::process_assets_batch`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ProcessorBatchSettings {
+    /// Verify that every processed asset is up to date, without writing anything. Assets that would be
+    /// (re)processed are reported as [`ProcessOutcome::Stale`] and cause the run to fail.
+    pub check: bool,
+    /// Remove all previously processed assets before processing, forcing every asset to be processed from scratch.
+    pub clean: bool,
+}
+
+impl ProcessorBatchSettings {
+    /// Parses [`ProcessorBatchSettings`] from command line style arguments. Supported arguments are `--check` and `--clean`.
+    pub fn from_args<I, S>(args: I) -> Result<Self, ProcessorArgsError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut settings = ProcessorBatchSettings::default();
+        for arg in args {
+            match arg.as_ref() {
+                "--check" => settings.check = true,
+                "--clean" => settings.clean = true,
+                other => return Err(ProcessorArgsError::UnknownArgument(other.to_string())),
+            }
+        }
+        if settings.check && settings.clean {
+            return Err(ProcessorArgsError::CheckAndClean);
+        }
+        Ok(settings)
+    }
+
+    /// Parses [`ProcessorBatchSettings`] from the arguments the current process was started with.
+    /// See [`ProcessorBatchSettings::from_args`].
+    pub fn from_env() -> Result<Self, ProcessorArgsError> {
+        Self::from_args(std::env::args().skip(1))
+    }
+}
+
+/// An error that occurs when parsing [`ProcessorBatchSettings`] from arguments.
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum ProcessorArgsError {
+    #[error("Unknown argument '{0}'. Expected '--check' or '--clean'")]
+    UnknownArgument(String),
+    #[error(
+        "'--check' cannot be combined with '--clean', as cleaning writes to the processed assets"
+    )]
+    CheckAndClean,
+}
+
+/// The outcome of processing a single asset during a batch run of the [`AssetProcessor`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ProcessOutcome {
+    /// The asset was processed and written to its processed [`AssetSource`](crate::io::AssetSource).
+    Processed,
+    /// The asset (and its process dependencies) did not change since it was last processed.
+    Skipped,
+    /// The asset is not processed, either because its meta says so or because there is nothing that can load it.
+    Ignored,
+    /// The asset would have been processed, but the run was a [`ProcessorBatchSettings::check`] run.
+    Stale,
+    /// Processing the asset failed with the given [`ProcessError`].
+    Failed(String),
+}
+
+impl ProcessOutcome {
+    pub(crate) fn from_result(result: &Result<ProcessResult, ProcessError>) -> Self {
+        match result {
+            Ok(ProcessResult::Processed(_)) => ProcessOutcome::Processed,
+            Ok(ProcessResult::SkippedNotChanged) => ProcessOutcome::Skipped,
+            Ok(ProcessResult::Stale) => ProcessOutcome::Stale,
+            // The errors mirror the "non-errors" in `ProcessorAssetInfos::finish_processing`
+            Ok(ProcessResult::Ignored)
+            | Err(
+                ProcessError::ExtensionRequired
+                | ProcessError::MissingAssetLoaderForExtension(_)
+                | ProcessError::AssetReaderError {
+                    err: crate::io::AssetReaderError::NotFound(_),
+                    ..
+                },
+            ) => ProcessOutcome::Ignored,
+            Err(err) => ProcessOutcome::Failed(err.to_string()),
+        }
+    }
+}
+
+/// A single entry in a [`ProcessorReport`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProcessorReportEntry {
+    /// The path of the source asset.
+    pub path: AssetPath<'static>,
+    /// The final outcome of processing the asset.
+    pub outcome: ProcessOutcome,
+}
+
+/// A machine-readable report produced by [`AssetProcessor::process_assets_batch`], containing the outcome of every
+/// asset the processor encountered, sorted by path.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProcessorReport {
+    /// Whether or not this report was produced by a [`ProcessorBatchSettings::check`] run.
+    pub check: bool,
+    /// The outcome of each asset.
+    pub entries: Vec<ProcessorReportEntry>,
+}
+
+impl ProcessorReport {
+    pub(crate) fn from_outcomes(
+        check: bool,
+        outcomes: HashMap<AssetPath<'static>, ProcessOutcome>,
+    ) -> Self {
+        let mut entries = outcomes
+            .into_iter()
+            .map(|(path, outcome)| ProcessorReportEntry { path, outcome })
+            .collect::<Vec<_>>();
+        entries.sort_by_key(|entry| entry.path.to_string());
+        Self { check, entries }
+    }
+
+    /// Iterates over the entries of assets that failed to process.
+    pub fn failed(&self) -> impl Iterator<Item = &ProcessorReportEntry> {
+        self.entries
+            .iter()
+            .filter(|entry| matches!(entry.outcome, ProcessOutcome::Failed(_)))
+    }
+
+    /// Iterates over the entries of assets that are out of date. This is only populated by [`ProcessorBatchSettings::check`] runs.
+    pub fn stale(&self) -> impl Iterator<Item = &ProcessorReportEntry> {
+        self.entries
+            .iter()
+            .filter(|entry| entry.outcome == ProcessOutcome::Stale)
+    }
+
+    /// Returns `true` if no asset failed to process and no asset is [`ProcessOutcome::Stale`].
+    pub fn is_success(&self) -> bool {
+        self.failed().next().is_none() && self.stale().next().is_none()
+    }
+
+    /// Returns the [`AppExit`] corresponding to this report: [`AppExit::Success`] if [`ProcessorReport::is_success`],
+    /// otherwise [`AppExit::error`].
+    pub fn app_exit(&self) -> AppExit {
+        if self.is_success() {
+            AppExit::Success
+        } else {
+            AppExit::error()
+        }
+    }
+
+    /// Serializes this report as pretty-printed RON.
+    pub fn to_ron(&self) -> Result<String, ron::Error> {
+        ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+    }
+}
+
+/// Replaces the [`App`] runner with a "headless" runner that processes every asset once using the [`AssetProcessor`],
+/// prints a [`ProcessorReport`] (as RON) to stdout, and then exits. The app exits with a non-zero exit code if any asset failed to process
+/// (or, when [`ProcessorBatchSettings::check`] is set, if any asset is out of date).
+///
+/// This enables running asset processing as a separate build step (ex: in CI), without running the app itself. It requires
+/// [`AssetPlugin::mode`](crate::AssetPlugin::mode) to be [`AssetMode::Processed`](crate::AssetMode::Processed) and the
+/// `asset_processor` and `multi_threaded` cargo features.
+///
+/// Add this plugin _after_ any plugin that sets its own runner (such as `ScheduleRunnerPlugin`).
+///
+/// ```no_run
+/// # use bevy_app::{App, AppExit};
+/// # use bevy_asset::{AssetMode, AssetPlugin, processor::{ProcessorBatchPlugin, ProcessorBatchSettings}};
+/// fn main() -> AppExit {
+///     let settings = match ProcessorBatchSettings::from_env() {
+///         Ok(settings) => settings,
+///         Err(err) => {
+///             eprintln!("{err}");
+///             return AppExit::error();
+///         }
+///     };
+///     App::new()
+///         .add_plugins(AssetPlugin {
+///             mode: AssetMode::Processed,
+///             ..Default::default()
+///         })
+///         // Register your asset loaders and processors here
+///         .add_plugins(ProcessorBatchPlugin { settings })
+///         .run()
+/// }
+/// ```
+#[derive(Default)]
+pub struct ProcessorBatchPlugin {
+    /// The settings used for the batch run.
+    pub settings: ProcessorBatchSettings,
+}
+
+impl Plugin for ProcessorBatchPlugin {
+    fn build(&self, app: &mut App) {
+        let settings = self.settings.clone();
+        app.set_runner(move |app| run_batch(app, &settings));
+    }
+}
+
+fn run_batch(mut app: App, settings: &ProcessorBatchSettings) -> AppExit {
+    if app.plugins_state() != PluginsState::Cleaned {
+        while app.plugins_state() == PluginsState::Adding {
+            #[cfg(not(target_arch = "wasm32"))]
+            bevy_tasks::tick_global_task_pools_on_main_thread();
+        }
+        app.finish();
+        app.cleanup();
+    }
+
+    let Some(processor) = app.world().get_resource::<AssetProcessor>().cloned() else {
+        error!("Batch asset processing requires an AssetProcessor. Set AssetPlugin::mode to AssetMode::Processed and enable the `asset_processor` cargo feature.");
+        return AppExit::error();
+    };
+
+    #[cfg(any(target_arch = "wasm32", not(feature = "multi_threaded")))]
+    {
+        let _ = (processor, settings);
+        error!("Cannot run AssetProcessor in single threaded mode (or Wasm) yet.");
+        AppExit::error()
+    }
+
+    #[cfg(all(not(target_arch = "wasm32"), feature = "multi_threaded"))]
+    {
+        let report = processor.process_assets_batch(settings);
+        match report.to_ron() {
+            Ok(ron) => println!("{ron}"),
+            Err(err) => {
+                error!("Failed to serialize processor report: {err}");
+                return AppExit::error();
+            }
+        }
+        report.app_exit()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_args() {
+        assert_eq!(
+            ProcessorBatchSettings::from_args(Vec::<String>::new()),
+            Ok(ProcessorBatchSettings::default())
+        );
+        assert_eq!(
+            ProcessorBatchSettings::from_args(["--check"]),
+            Ok(ProcessorBatchSettings {
+                check: true,
+                clean: false
+            })
+        );
+        assert_eq!(
+            ProcessorBatchSettings::from_args(["--clean"]),
+            Ok(ProcessorBatchSettings {
+                check: false,
+                clean: true
+            })
+        );
+        assert_eq!(
+            ProcessorBatchSettings::from_args(["--check", "--clean"]),
+            Err(ProcessorArgsError::CheckAndClean)
+        );
+        assert_eq!(
+            ProcessorBatchSettings::from_args(["--fast"]),
+            Err(ProcessorArgsError::UnknownArgument("--fast".to_string()))
+        );
+    }
+
+    #[test]
+    fn report_exit_code() {
+        let mut outcomes = HashMap::default();
+        outcomes.insert(AssetPath::from("b.png"), ProcessOutcome::Processed);
+        outcomes.insert(AssetPath::from("a.png"), ProcessOutcome::Skipped);
+        let report = ProcessorReport::from_outcomes(false, outcomes.clone());
+        assert_eq!(report.entries[0].path, AssetPath::from("a.png"));
+        assert!(report.is_success());
+        assert_eq!(report.app_exit(), AppExit::Success);
+
+        outcomes.insert(AssetPath::from("c.png"), ProcessOutcome::Stale);
+        let report = ProcessorReport::from_outcomes(true, outcomes.clone());
+        assert_eq!(report.stale().count(), 1);
+        assert!(report.app_exit().is_error());
+
+        outcomes.insert(
+            AssetPath::from("c.png"),
+            ProcessOutcome::Failed("bad".to_string()),
+        );
+        let report = ProcessorReport::from_outcomes(false, outcomes);
+        assert_eq!(report.failed().count(), 1);
+        assert!(!report.is_success());
+
+        let ron = report.to_ron().unwrap();
+        let parsed: ProcessorReport = ron::de::from_str(&ron).unwrap();
+        assert_eq!(parsed, report);
+    }
+}
@@ -0,0 +1,114 @@
+use crate::{
+    io::AssetSource,
+    meta::{AssetActionMinimal, AssetMetaMinimal},
+    processor::{get_asset_paths, AssetProcessor},
+    AssetPath,
+};
+use bevy_utils::tracing::debug;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// A single meta file that was (or, for dry runs, would be) upgraded by [`AssetProcessor::migrate_meta_files`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MetaMigrationReportEntry {
+    /// The path of the asset the meta file belongs to.
+    pub path: AssetPath<'static>,
+    /// The settings version the meta file was written with.
+    pub from_version: u32,
+    /// The current settings version of the meta's loader or processor.
+    pub to_version: u32,
+    /// The error encountered while upgrading, if any.
+    pub error: Option<String>,
+}
+
+/// A report of every meta file that needs to be upgraded, produced by [`AssetProcessor::migrate_meta_files`].
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MetaMigrationReport {
+    /// If true, no meta files were written.
+    pub dry_run: bool,
+    /// The meta files that were (or would be) upgraded, sorted by path.
+    pub entries: Vec<MetaMigrationReportEntry>,
+}
+
+impl MetaMigrationReport {
+    /// Iterates over the entries of meta files that could not be upgraded.
+    pub fn failed(&self) -> impl Iterator<Item = &MetaMigrationReportEntry> {
+        self.entries.iter().filter(|entry| entry.error.is_some())
+    }
+}
+
+impl AssetProcessor {
+    /// Upgrades every meta file in every processed [`AssetSource`] that was written with an older settings version than
+    /// its loader or processor's current version, using the registered [`MetaMigrations`]. If `dry_run` is true, nothing is
+    /// written, but the returned report still lists every meta file that would be upgraded.
+    ///
+    /// [`MetaMigrations`]: crate::meta::MetaMigrations
+    pub async fn migrate_meta_files(&self, dry_run: bool) -> MetaMigrationReport {
+        let mut entries = Vec::new();
+        for source in self.sources().iter_processed() {
+            let mut paths = Vec::new();
+            if let Err(err) =
+                get_asset_paths(source.reader(), None, PathBuf::from(""), &mut paths).await
+            {
+                debug!("Failed to read asset paths for meta migration: {err}");
+                continue;
+            }
+            for path in paths {
+                if let Some(entry) = self.migrate_meta_file(source, path, dry_run).await {
+                    entries.push(entry);
+                }
+            }
+        }
+        entries.sort_by_key(|entry| entry.path.to_string());
+        MetaMigrationReport { dry_run, entries }
+    }
+
+    async fn migrate_meta_file(
+        &self,
+        source: &AssetSource,
+        path: PathBuf,
+        dry_run: bool,
+    ) -> Option<MetaMigrationReportEntry> {
+        let meta_bytes = source.reader().read_meta_bytes(&path).await.ok()?;
+        // meta files that can't be parsed at all are reported by the processor itself
+        let minimal: AssetMetaMinimal = ron::de::from_bytes(&meta_bytes).ok()?;
+        let (type_name, current_version) = match &minimal.asset {
+            AssetActionMinimal::Load { loader } => {
+                let loader_version = self
+                    .server
+                    .get_asset_loader_with_type_name(loader)
+                    .await
+                    .ok()?
+                    .settings_version();
+                (loader, loader_version)
+            }
+            AssetActionMinimal::Process { processor } => {
+                (processor, self.get_processor(processor)?.settings_version())
+            }
+            AssetActionMinimal::Ignore => return None,
+        };
+        if minimal.settings_version == current_version {
+            return None;
+        }
+        let asset_path = AssetPath::from(path).with_source(source.id());
+        let result = async {
+            let meta_bytes =
+                self.server
+                    .migrate_meta_bytes(type_name, current_version, &minimal, meta_bytes)?;
+            if !dry_run {
+                source
+                    .writer()?
+                    .write_meta_bytes(asset_path.path(), &meta_bytes)
+                    .await?;
+            }
+            Ok::<_, Box<dyn std::error::Error + Send + Sync>>(())
+        }
+        .await;
+        Some(MetaMigrationReportEntry {
+            from_version: minimal.settings_version,
+            to_version: current_version,
+            error: result.err().map(|err| err.to_string()),
+            path: asset_path,
+        })
+    }
+}
@@ -1,7 +1,11 @@
+mod batch;
 mod log;
+mod migrate;
 mod process;
 
+pub use batch::*;
 pub use log::*;
+pub use migrate::*;
 pub use process::*;
 
 use crate::{
@@ -14,8 +18,8 @@ use crate::{
         get_asset_hash, get_full_asset_hash, AssetAction, AssetActionMinimal, AssetHash, AssetMeta,
         AssetMetaDyn, AssetMetaMinimal, ProcessedInfo, ProcessedInfoMinimal,
     },
-    AssetLoadError, AssetMetaCheck, AssetPath, AssetServer, AssetServerMode, DeserializeMetaError,
-    MissingAssetLoaderForExtensionError,
+    AssetLoadError, AssetMetaCheck, AssetPath, AssetServer, AssetServerMode, AssetValidationReport,
+    DeserializeMetaError, MissingAssetLoaderForExtensionError,
 };
 use bevy_ecs::prelude::*;
 use bevy_tasks::IoTaskPool;
@@ -28,11 +32,14 @@ use bevy_utils::{
 use bevy_utils::{HashMap, HashSet};
 use futures_io::ErrorKind;
 use futures_lite::{AsyncReadExt, AsyncWriteExt, StreamExt};
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
 use std::{
     collections::VecDeque,
     path::{Path, PathBuf},
-    sync::Arc,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
 };
 use thiserror::Error;
 
@@ -73,6 +80,10 @@ pub struct AssetProcessorData {
     initialized_receiver: async_broadcast::Receiver<()>,
     finished_sender: async_broadcast::Sender<()>,
     finished_receiver: async_broadcast::Receiver<()>,
+    /// If true, the processor will not write to any processed or source assets. See [`ProcessorBatchSettings::check`].
+    check_only: AtomicBool,
+    /// The outcome of each asset processed during a batch run. This is `None` when no batch run is active.
+    batch_outcomes: Mutex<Option<HashMap<AssetPath<'static>, ProcessOutcome>>>,
 }
 
 impl AssetProcessor {
@@ -196,6 +207,52 @@ impl AssetProcessor {
         debug!("Processing finished in {:?}", end_time - start_time);
     }
 
+    /// Processes all assets once (see [`AssetProcessor::process_assets`]) and returns a [`ProcessorReport`] describing the outcome
+    /// of every asset. Unlike [`AssetProcessor::start`], this does not listen for changes afterwards.
+    ///
+    /// If [`ProcessorBatchSettings::clean`] is set, all processed assets will be removed first.
+    /// If [`ProcessorBatchSettings::check`] is set, nothing will be written. Assets that would be processed are
+    /// reported as [`ProcessOutcome::Stale`] instead.
+    #[cfg(all(not(target_arch = "wasm32"), feature = "multi_threaded"))]
+    pub fn process_assets_batch(&self, settings: &ProcessorBatchSettings) -> ProcessorReport {
+        if settings.clean {
+            bevy_tasks::block_on(self.remove_all_processed_assets());
+        }
+        self.data
+            .check_only
+            .store(settings.check, Ordering::Relaxed);
+        *self.data.batch_outcomes.lock() = Some(HashMap::new());
+        self.process_assets();
+        let outcomes = self.data.batch_outcomes.lock().take().unwrap_or_default();
+        self.data.check_only.store(false, Ordering::Relaxed);
+        ProcessorReport::from_outcomes(settings.check, outcomes)
+    }
+
+    /// Removes every processed asset (and its meta) from every processed [`AssetSource`].
+    async fn remove_all_processed_assets(&self) {
+        for source in self.sources().iter_processed() {
+            let Ok(processed_writer) = source.processed_writer() else {
+                continue;
+            };
+            if let Err(err) = processed_writer
+                .remove_assets_in_directory(Path::new(""))
+                .await
+            {
+                match err {
+                    AssetWriterError::Io(err) => {
+                        // the processed folder not existing yet is equivalent to it being clean
+                        if err.kind() != ErrorKind::NotFound {
+                            error!(
+                                "Failed to remove processed assets for source {}: {err}",
+                                source.id()
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    }
+
     /// Listens for changes to assets in the source [`AssetSource`] and update state accordingly.
     // PERF: parallelize change event processing
     pub async fn listen_for_source_change_events(&self) {
@@ -506,6 +563,25 @@ impl AssetProcessor {
         self.data.processors.read().get(key).cloned()
     }
 
+    /// Registers a migration of the [`Process::Settings`] of `P` from settings version `from_version` (stored as `Old`)
+    /// to `from_version + 1` (stored as `New`). See [`Process::SETTINGS_VERSION`] and [`MetaMigrations`].
+    ///
+    /// [`MetaMigrations`]: crate::meta::MetaMigrations
+    pub fn register_settings_migration<P: Process, Old, New>(
+        &self,
+        from_version: u32,
+        migrate: impl Fn(Old) -> New + Send + Sync + 'static,
+    ) where
+        Old: for<'de> serde::Deserialize<'de>,
+        New: serde::Serialize,
+    {
+        self.server.data.meta_migrations.write().add(
+            std::any::type_name::<P>(),
+            from_version,
+            migrate,
+        );
+    }
+
     /// Returns the processor with the given `processor_type_name`, if it exists.
     pub fn get_processor(&self, processor_type_name: &str) -> Option<Arc<dyn ErasedProcessor>> {
         let processors = self.data.processors.read();
@@ -518,42 +594,20 @@ impl AssetProcessor {
     /// This will validate transactions and recover failed transactions when necessary.
     #[allow(unused)]
     async fn initialize(&self) -> Result<(), InitializeError> {
-        self.validate_transaction_log_and_recover().await;
-        let mut asset_infos = self.data.asset_infos.write().await;
-
-        /// Retrieves asset paths recursively. If `clean_empty_folders_writer` is Some, it will be used to clean up empty
-        /// folders when they are discovered.
-        async fn get_asset_paths<'a>(
-            reader: &'a dyn ErasedAssetReader,
-            clean_empty_folders_writer: Option<&'a dyn ErasedAssetWriter>,
-            path: PathBuf,
-            paths: &'a mut Vec<PathBuf>,
-        ) -> Result<bool, AssetReaderError> {
-            if reader.is_directory(&path).await? {
-                let mut path_stream = reader.read_directory(&path).await?;
-                let mut contains_files = false;
-
-                while let Some(child_path) = path_stream.next().await {
-                    contains_files |= Box::pin(get_asset_paths(
-                        reader,
-                        clean_empty_folders_writer,
-                        child_path,
-                        paths,
-                    ))
-                    .await?;
-                }
-                if !contains_files && path.parent().is_some() {
-                    if let Some(writer) = clean_empty_folders_writer {
-                        // it is ok for this to fail as it is just a cleanup job.
-                        let _ = writer.remove_empty_directory(&path).await;
-                    }
-                }
-                Ok(contains_files)
-            } else {
-                paths.push(path);
-                Ok(true)
+        let check_only = self.data.check_only.load(Ordering::Relaxed);
+        // In "check only" mode, nothing is recovered or written. If the log is invalid, the existing processed
+        // assets can't be trusted, so they are all considered out of date.
+        let trust_processed_assets = if check_only {
+            let log_is_valid = ProcessorTransactionLog::validate().await.is_ok();
+            if !log_is_valid {
+                warn!("The processor transaction log is invalid. All processed assets will be considered out of date.");
             }
-        }
+            log_is_valid
+        } else {
+            self.validate_transaction_log_and_recover().await;
+            true
+        };
+        let mut asset_infos = self.data.asset_infos.write().await;
 
         for source in self.sources().iter_processed() {
             let Ok(processed_reader) = source.processed_reader() else {
@@ -575,7 +629,7 @@ impl AssetProcessor {
             let mut processed_paths = Vec::new();
             get_asset_paths(
                 processed_reader,
-                Some(processed_writer),
+                (!check_only).then_some(processed_writer),
                 PathBuf::from(""),
                 &mut processed_paths,
             )
@@ -586,6 +640,10 @@ impl AssetProcessor {
                 asset_infos.get_or_insert(AssetPath::from(path).with_source(source.id()));
             }
 
+            if !trust_processed_assets {
+                continue;
+            }
+
             for path in processed_paths {
                 let mut dependencies = Vec::new();
                 let asset_path = AssetPath::from(path).with_source(source.id());
@@ -610,18 +668,25 @@ impl AssetProcessor {
                                 }
                                 Err(err) => {
                                     trace!("Removing processed data for {asset_path} because meta could not be parsed: {err}");
-                                    self.remove_processed_asset_and_meta(source, asset_path.path())
+                                    if !check_only {
+                                        self.remove_processed_asset_and_meta(
+                                            source,
+                                            asset_path.path(),
+                                        )
                                         .await;
+                                    }
                                 }
                             }
                         }
                         Err(err) => {
                             trace!("Removing processed data for {asset_path} because meta failed to load: {err}");
-                            self.remove_processed_asset_and_meta(source, asset_path.path())
-                                .await;
+                            if !check_only {
+                                self.remove_processed_asset_and_meta(source, asset_path.path())
+                                    .await;
+                            }
                         }
                     }
-                } else {
+                } else if !check_only {
                     trace!("Removing processed data for non-existent asset {asset_path}");
                     self.remove_processed_asset_and_meta(source, asset_path.path())
                         .await;
@@ -686,6 +751,9 @@ impl AssetProcessor {
     async fn process_asset(&self, source: &AssetSource, path: PathBuf) {
         let asset_path = AssetPath::from(path).with_source(source.id());
         let result = self.process_asset_internal(source, &asset_path).await;
+        if let Some(outcomes) = self.data.batch_outcomes.lock().as_mut() {
+            outcomes.insert(asset_path.clone(), ProcessOutcome::from_result(&result));
+        }
         let mut infos = self.data.asset_infos.write().await;
         infos.finish_processing(asset_path, result).await;
     }
@@ -698,6 +766,7 @@ impl AssetProcessor {
         // TODO: The extension check was removed now that AssetPath is the input. is that ok?
         // TODO: check if already processing to protect against duplicate hot-reload events
         debug!("Processing {:?}", asset_path);
+        let check_only = self.data.check_only.load(Ordering::Relaxed);
         let server = &self.server;
         let path = asset_path.path();
         let reader = source.reader();
@@ -719,24 +788,47 @@ impl AssetProcessor {
                 let minimal: AssetMetaMinimal = ron::de::from_bytes(&meta_bytes).map_err(|e| {
                     ProcessError::DeserializeMetaError(DeserializeMetaError::DeserializeMinimal(e))
                 })?;
-                let (meta, processor) = match minimal.asset {
-                    AssetActionMinimal::Load { loader } => {
-                        let loader = server.get_asset_loader_with_type_name(&loader).await?;
+                match &minimal.asset {
+                    AssetActionMinimal::Load {
+                        loader: loader_name,
+                    } => {
+                        let loader = server.get_asset_loader_with_type_name(loader_name).await?;
+                        let meta_bytes = self
+                            .migrate_source_meta(
+                                source,
+                                asset_path,
+                                loader_name,
+                                loader.settings_version(),
+                                &minimal,
+                                meta_bytes,
+                            )
+                            .await?;
                         let meta = loader.deserialize_meta(&meta_bytes)?;
-                        (meta, None)
+                        (meta, meta_bytes, None)
                     }
-                    AssetActionMinimal::Process { processor } => {
-                        let processor = self
-                            .get_processor(&processor)
-                            .ok_or_else(|| ProcessError::MissingProcessor(processor))?;
+                    AssetActionMinimal::Process {
+                        processor: processor_name,
+                    } => {
+                        let processor = self.get_processor(processor_name).ok_or_else(|| {
+                            ProcessError::MissingProcessor(processor_name.clone())
+                        })?;
+                        let meta_bytes = self
+                            .migrate_source_meta(
+                                source,
+                                asset_path,
+                                processor_name,
+                                processor.settings_version(),
+                                &minimal,
+                                meta_bytes,
+                            )
+                            .await?;
                         let meta = processor.deserialize_meta(&meta_bytes)?;
-                        (meta, Some(processor))
+                        (meta, meta_bytes, Some(processor))
                     }
                     AssetActionMinimal::Ignore => {
                         return Ok(ProcessResult::Ignored);
                     }
-                };
-                (meta, meta_bytes, processor)
+                }
             }
             Err(AssetReaderError::NotFound(_path)) => {
                 let (meta, processor) = if let Some(processor) = asset_path
@@ -757,11 +849,13 @@ impl AssetProcessor {
                 };
                 let meta_bytes = meta.serialize();
                 // write meta to source location if it doesn't already exist
-                source
-                    .writer()?
-                    .write_meta_bytes(path, &meta_bytes)
-                    .await
-                    .map_err(writer_err)?;
+                if !check_only {
+                    source
+                        .writer()?
+                        .write_meta_bytes(path, &meta_bytes)
+                        .await
+                        .map_err(writer_err)?;
+                }
                 (meta, meta_bytes, processor)
             }
             Err(err) => {
@@ -817,6 +911,9 @@ impl AssetProcessor {
                 }
             }
         }
+        if check_only {
+            return Ok(ProcessResult::Stale);
+        }
         // Note: this lock must remain alive until all processed asset asset and meta writes have finished (or failed)
         // See ProcessedAssetInfo::file_transaction_lock docs for more info
         let _transaction_lock = {
@@ -829,7 +926,7 @@ impl AssetProcessor {
         // Directly writing to the asset destination in the processor necessitates this behavior
         // TODO: this class of failure can be recovered via re-processing + smarter log validation that allows for duplicate transactions in the event of failures
         self.log_begin_processing(asset_path).await;
-        if let Some(processor) = processor {
+        let processed_meta_bytes = if let Some(processor) = processor {
             let mut writer = processed_writer.write(path).await.map_err(writer_err)?;
             let mut processed_meta = {
                 let mut context =
@@ -861,6 +958,7 @@ impl AssetProcessor {
                 .write_meta_bytes(path, &meta_bytes)
                 .await
                 .map_err(writer_err)?;
+            meta_bytes
         } else {
             processed_writer
                 .write_bytes(path, &asset_bytes)
@@ -872,12 +970,108 @@ impl AssetProcessor {
                 .write_meta_bytes(path, &meta_bytes)
                 .await
                 .map_err(writer_err)?;
-        }
+            meta_bytes
+        };
+        self.validate_processed_asset(source, asset_path, &processed_meta_bytes)
+            .await?;
         self.log_end_processing(asset_path).await;
 
         Ok(ProcessResult::Processed(new_processed_info))
     }
 
+    /// Loads the processed asset at `asset_path` and runs the registered [`AssetValidator`]s on it (and on its labeled assets).
+    /// Every issue is logged. If any validator reported an error, this returns [`ProcessError::ValidationFailed`], which leaves
+    /// an "unfinished" log entry so the asset is processed again on the next run.
+    ///
+    /// [`AssetValidator`]: crate::AssetValidator
+    async fn validate_processed_asset(
+        &self,
+        source: &AssetSource,
+        asset_path: &AssetPath<'static>,
+        meta_bytes: &[u8],
+    ) -> Result<(), ProcessError> {
+        if self.server.data.validators.read().is_empty() {
+            return Ok(());
+        }
+        let minimal: AssetMetaMinimal = ron::de::from_bytes(meta_bytes).map_err(|e| {
+            ProcessError::DeserializeMetaError(DeserializeMetaError::DeserializeMinimal(e))
+        })?;
+        let AssetActionMinimal::Load { loader } = &minimal.asset else {
+            return Ok(());
+        };
+        let loader = self.server.get_asset_loader_with_type_name(loader).await?;
+        let meta = loader.deserialize_meta(meta_bytes)?;
+        let mut reader = source
+            .processed_reader()?
+            .read(asset_path.path())
+            .await
+            .map_err(|err| ProcessError::AssetReaderError {
+                path: asset_path.clone(),
+                err,
+            })?;
+        let loaded_asset = self
+            .server
+            .load_with_meta_loader_and_reader(
+                asset_path,
+                meta,
+                &*loader,
+                &mut *reader,
+                false,
+                false,
+            )
+            .await?;
+        let mut reports = Vec::new();
+        self.server.data.validators.read().validate_recursive(
+            asset_path,
+            &loaded_asset,
+            &mut reports,
+        );
+        for report in &reports {
+            report.log();
+        }
+        match reports.into_iter().find(AssetValidationReport::has_errors) {
+            Some(report) => Err(ProcessError::ValidationFailed(report)),
+            None => Ok(()),
+        }
+    }
+
+    /// Upgrades the source meta of `asset_path` to `settings_version` using the registered [`MetaMigrations`].
+    /// Unless the processor is only checking assets, the upgraded meta is written back to the source, so the
+    /// migration only needs to happen once.
+    ///
+    /// [`MetaMigrations`]: crate::meta::MetaMigrations
+    async fn migrate_source_meta(
+        &self,
+        source: &AssetSource,
+        asset_path: &AssetPath<'static>,
+        type_name: &str,
+        settings_version: u32,
+        minimal: &AssetMetaMinimal,
+        meta_bytes: Vec<u8>,
+    ) -> Result<Vec<u8>, ProcessError> {
+        if minimal.settings_version == settings_version {
+            return Ok(meta_bytes);
+        }
+        let meta_bytes =
+            self.server
+                .migrate_meta_bytes(type_name, settings_version, minimal, meta_bytes)?;
+        if !self.data.check_only.load(Ordering::Relaxed) {
+            debug!(
+                "Migrating meta for {asset_path} from settings version {} to {settings_version}",
+                minimal.settings_version
+            );
+            source
+                .writer()?
+                .write_meta_bytes(asset_path.path(), &meta_bytes)
+                .await
+                .map_err(|err| ProcessError::AssetWriterError {
+                    path: asset_path.clone(),
+                    err,
+                })?;
+        }
+        Ok(meta_bytes)
+    }
+
     async fn validate_transaction_log_and_recover(&self) {
         if let Err(err) = ProcessorTransactionLog::validate().await {
             let state_is_valid = match err {
@@ -979,6 +1173,8 @@ impl AssetProcessorData {
             finished_receiver,
             initialized_sender,
             initialized_receiver,
+            check_only: AtomicBool::new(false),
+            batch_outcomes: Mutex::new(None),
             state: async_lock::RwLock::new(ProcessorState::Initializing),
             log: Default::default(),
             processors: Default::default(),
@@ -1049,6 +1245,7 @@ struct InstrumentedAssetProcessor<T>(T);
 impl<T: Process> Process for InstrumentedAssetProcessor<T> {
     type Settings = T::Settings;
     type OutputLoader = T::OutputLoader;
+    const SETTINGS_VERSION: u32 = T::SETTINGS_VERSION;
 
     fn process<'a>(
         &'a self,
@@ -1061,6 +1258,7 @@ impl<T: Process> Process for InstrumentedAssetProcessor<T> {
         // Change the processor type for the `AssetMeta`, which works because we share the `Settings` type.
         let meta = AssetMeta {
             meta_format_version: meta.meta_format_version,
+            settings_version: meta.settings_version,
             processed_info: meta.processed_info,
             asset: meta.asset,
         };
@@ -1079,6 +1277,9 @@ pub enum ProcessResult {
     Processed(ProcessedInfo),
     SkippedNotChanged,
     Ignored,
+    /// The asset has changed and would have been processed, but the processor is only checking assets.
+    /// See [`ProcessorBatchSettings::check`].
+    Stale,
 }
 
 /// The final status of processing an asset
@@ -1230,6 +1431,10 @@ impl ProcessorAssetInfos {
             Ok(ProcessResult::Ignored) => {
                 debug!("Skipping processing (ignored) \"{:?}\"", asset_path);
             }
+            Ok(ProcessResult::Stale) => {
+                // Nothing is loaded in "check only" mode, so there is nobody waiting on this status
+                debug!("Asset \"{:?}\" is out of date (check only)", asset_path);
+            }
             Err(ProcessError::ExtensionRequired) => {
                 // Skip assets without extensions
             }
@@ -1356,6 +1561,40 @@ impl ProcessorAssetInfos {
     }
 }
 
+/// Retrieves asset paths recursively. If `clean_empty_folders_writer` is Some, it will be used to clean up empty
+/// folders when they are discovered.
+pub(crate) async fn get_asset_paths<'a>(
+    reader: &'a dyn ErasedAssetReader,
+    clean_empty_folders_writer: Option<&'a dyn ErasedAssetWriter>,
+    path: PathBuf,
+    paths: &'a mut Vec<PathBuf>,
+) -> Result<bool, AssetReaderError> {
+    if reader.is_directory(&path).await? {
+        let mut path_stream = reader.read_directory(&path).await?;
+        let mut contains_files = false;
+
+        while let Some(child_path) = path_stream.next().await {
+            contains_files |= Box::pin(get_asset_paths(
+                reader,
+                clean_empty_folders_writer,
+                child_path,
+                paths,
+            ))
+            .await?;
+        }
+        if !contains_files && path.parent().is_some() {
+            if let Some(writer) = clean_empty_folders_writer {
+                // it is ok for this to fail as it is just a cleanup job.
+                let _ = writer.remove_empty_directory(&path).await;
+            }
+        }
+        Ok(contains_files)
+    } else {
+        paths.push(path);
+        Ok(true)
+    }
+}
+
 /// The current state of the [`AssetProcessor`].
 #[derive(Copy, Clone, PartialEq, Eq)]
 pub enum ProcessorState {
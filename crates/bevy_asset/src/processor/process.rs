@@ -8,8 +8,8 @@ use crate::{
     processor::AssetProcessor,
     saver::{AssetSaver, SavedAsset},
     transformer::{AssetTransformer, TransformedAsset},
-    AssetLoadError, AssetLoader, AssetPath, DeserializeMetaError, ErasedLoadedAsset,
-    MissingAssetLoaderForExtensionError, MissingAssetLoaderForTypeNameError,
+    AssetLoadError, AssetLoader, AssetPath, AssetValidationReport, DeserializeMetaError,
+    ErasedLoadedAsset, MissingAssetLoaderForExtensionError, MissingAssetLoaderForTypeNameError,
 };
 use bevy_utils::{BoxedFuture, ConditionalSendFuture};
 use serde::{Deserialize, Serialize};
@@ -26,6 +26,10 @@ pub trait Process: Send + Sync + Sized + 'static {
     type Settings: Settings + Default + Serialize + for<'a> Deserialize<'a>;
     /// The [`AssetLoader`] that will be used to load the final processed asset.
     type OutputLoader: AssetLoader;
+    /// The version of [`Process::Settings`]. Increment this whenever a change to [`Process::Settings`] would break
+    /// deserializing existing meta files, and register a migration from the previous version with
+    /// [`AssetApp::register_asset_processor_settings_migration`](crate::AssetApp::register_asset_processor_settings_migration).
+    const SETTINGS_VERSION: u32 = 0;
     /// Processes the asset stored on `context` in some way using the settings stored on `meta`. The results are written to `writer`. The
     /// final written processed asset is loadable using [`Process::OutputLoader`]. This load will use the returned [`AssetLoader::Settings`].
     fn process<'a>(
@@ -165,6 +169,8 @@ pub enum ProcessError {
     AssetTransformError(Box<dyn std::error::Error + Send + Sync + 'static>),
     #[error("Assets without extensions are not supported.")]
     ExtensionRequired,
+    #[error("The processed asset {0}")]
+    ValidationFailed(AssetValidationReport),
 }
 
 impl<Loader, Transformer, Saver> Process for LoadTransformAndSave<Loader, Transformer, Saver>
@@ -258,6 +264,8 @@ pub trait ErasedProcessor: Send + Sync {
     fn deserialize_meta(&self, meta: &[u8]) -> Result<Box<dyn AssetMetaDyn>, DeserializeMetaError>;
     /// Returns the default type-erased [`AssetMeta`] for the underlying [`Process`] impl.
     fn default_meta(&self) -> Box<dyn AssetMetaDyn>;
+    /// Returns the [`Process::SETTINGS_VERSION`] of the underlying [`Process`] impl.
+    fn settings_version(&self) -> u32;
 }
 
 impl<P: Process> ErasedProcessor for P {
@@ -292,6 +300,10 @@ impl<P: Process> ErasedProcessor for P {
             settings: P::Settings::default(),
         }))
     }
+
+    fn settings_version(&self) -> u32 {
+        P::SETTINGS_VERSION
+    }
 }
 
 /// Provides scoped data access to the [`AssetProcessor`].
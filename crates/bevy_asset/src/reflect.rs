@@ -199,6 +199,8 @@ impl<A: Asset + FromReflect> FromType<A> for ReflectAsset {
 #[derive(Clone)]
 pub struct ReflectHandle {
     asset_type_id: TypeId,
+    asset_type_path: &'static str,
+    asset_short_type_path: &'static str,
     downcast_handle_untyped: fn(&dyn Any) -> Option<UntypedHandle>,
     typed: fn(UntypedHandle) -> Box<dyn Reflect>,
 }
@@ -208,6 +210,16 @@ impl ReflectHandle {
         self.asset_type_id
     }
 
+    /// The [`TypePath::type_path`] of the asset
+    pub fn asset_type_path(&self) -> &'static str {
+        self.asset_type_path
+    }
+
+    /// The [`TypePath::short_type_path`] of the asset
+    pub fn asset_short_type_path(&self) -> &'static str {
+        self.asset_short_type_path
+    }
+
     /// A way to go from a [`Handle<T>`] in a `dyn Any` to a [`UntypedHandle`]
     pub fn downcast_handle_untyped(&self, handle: &dyn Any) -> Option<UntypedHandle> {
         (self.downcast_handle_untyped)(handle)
@@ -224,6 +236,8 @@ impl<A: Asset> FromType<Handle<A>> for ReflectHandle {
     fn from_type() -> Self {
         ReflectHandle {
             asset_type_id: TypeId::of::<A>(),
+            asset_type_path: A::type_path(),
+            asset_short_type_path: A::short_type_path(),
             downcast_handle_untyped: |handle: &dyn Any| {
                 handle
                     .downcast_ref::<Handle<A>>()
@@ -1,8 +1,8 @@
 use crate::{
     meta::{AssetHash, MetaTransform},
-    Asset, AssetHandleProvider, AssetLoadError, AssetPath, DependencyLoadState, ErasedLoadedAsset,
-    Handle, InternalAssetEvent, LoadState, RecursiveDependencyLoadState, StrongHandle,
-    UntypedAssetId, UntypedHandle,
+    Asset, AssetHandleProvider, AssetLoadError, AssetPath, AssetReloadBatchCompleteEvent,
+    AssetValidationReport, DependencyLoadState, ErasedLoadedAsset, Handle, InternalAssetEvent,
+    LoadState, RecursiveDependencyLoadState, StrongHandle, UntypedAssetId, UntypedHandle,
 };
 use bevy_ecs::world::World;
 use bevy_tasks::Task;
@@ -35,9 +35,19 @@ pub(crate) struct AssetInfo {
     ///
     /// [`LoadedAsset`]: crate::loader::LoadedAsset
     loader_dependencies: HashMap<AssetPath<'static>, AssetHash>,
+    /// The assets this asset depends on (the handles loaded with [`LoadContext::load`] while loading it).
+    /// This will only be populated if [`AssetInfos::watching_for_changes`] is set to `true` to
+    /// save memory.
+    ///
+    /// [`LoadContext::load`]: crate::LoadContext::load
+    dependencies: HashSet<UntypedAssetId>,
     /// The number of handle drops to skip for this asset.
     /// See usage (and comments) in `get_or_create_path_handle` for context.
     handle_drops_to_skip: usize,
+    /// The issues found by the [`AssetValidator`]s of this asset's type when it was last loaded, if there were any.
+    ///
+    /// [`AssetValidator`]: crate::AssetValidator
+    pub(crate) validation_report: Option<AssetValidationReport>,
 }
 
 impl AssetInfo {
@@ -53,9 +63,11 @@ impl AssetInfo {
             loading_rec_dependencies: HashSet::default(),
             failed_rec_dependencies: HashSet::default(),
             loader_dependencies: HashMap::default(),
+            dependencies: HashSet::default(),
             dependants_waiting_on_load: HashSet::default(),
             dependants_waiting_on_recursive_dep_load: HashSet::default(),
             handle_drops_to_skip: 0,
+            validation_report: None,
         }
     }
 }
@@ -70,6 +82,11 @@ pub(crate) struct AssetInfos {
     /// Tracks assets that depend on the "key" asset path inside their asset loaders ("loader dependencies")
     /// This should only be set when watching for changes to avoid unnecessary work.
     pub(crate) loader_dependants: HashMap<AssetPath<'static>, HashSet<AssetPath<'static>>>,
+    /// Tracks assets that depend on the "key" asset through [`LoadContext::load`] (the reverse of [`AssetInfo::dependencies`]).
+    /// This should only be set when watching for changes to avoid unnecessary work.
+    ///
+    /// [`LoadContext::load`]: crate::LoadContext::load
+    pub(crate) dependants: HashMap<UntypedAssetId, HashSet<UntypedAssetId>>,
     /// Tracks living labeled assets for a given source asset.
     /// This should only be set when watching for changes to avoid unnecessary work.
     pub(crate) living_labeled_assets: HashMap<AssetPath<'static>, HashSet<Box<str>>>,
@@ -77,7 +94,49 @@ pub(crate) struct AssetInfos {
     pub(crate) dependency_loaded_event_sender: TypeIdMap<fn(&mut World, UntypedAssetId)>,
     pub(crate) dependency_failed_event_sender:
         TypeIdMap<fn(&mut World, UntypedAssetId, AssetPath<'static>, AssetLoadError)>,
+    pub(crate) dependency_modified_event_sender: TypeIdMap<fn(&mut World, UntypedAssetId)>,
+    pub(crate) validation_failed_event_sender: TypeIdMap<fn(&mut World, UntypedAssetId)>,
     pub(crate) pending_tasks: HashMap<UntypedAssetId, Task<()>>,
+    /// The reload batch that is currently in progress. See [`AssetReloadBatchCompleteEvent`].
+    pub(crate) reload_batch: ReloadBatch,
+}
+
+/// Tracks the reloads of the current reload batch. See [`AssetReloadBatchCompleteEvent`].
+#[derive(Default)]
+pub(crate) struct ReloadBatch {
+    /// The number of reloads in this batch that have not finished yet.
+    pending: usize,
+    paths: Vec<AssetPath<'static>>,
+    modified: Vec<UntypedAssetId>,
+}
+
+impl ReloadBatch {
+    /// Adds a reload of the given `path` to the batch. [`ReloadBatch::finish_reload`] must be called once the reload finishes.
+    pub(crate) fn start_reload(&mut self, path: &AssetPath<'static>) {
+        self.pending += 1;
+        if !self.paths.contains(path) {
+            self.paths.push(path.clone());
+        }
+    }
+
+    pub(crate) fn finish_reload(&mut self) {
+        self.pending = self.pending.saturating_sub(1);
+    }
+
+    /// Returns `true` if every reload in the batch has finished and there is at least one.
+    pub(crate) fn is_complete(&self) -> bool {
+        self.pending == 0 && !self.paths.is_empty()
+    }
+
+    /// Returns `true` if `path` (or the asset it is a labeled asset of) is being reloaded in this batch.
+    fn contains(&self, path: &AssetPath) -> bool {
+        let without_label = path.without_label();
+        self.pending > 0
+            && self
+                .paths
+                .iter()
+                .any(|batch_path| *batch_path == without_label || batch_path == path)
+    }
 }
 
 impl std::fmt::Debug for AssetInfos {
@@ -365,6 +424,7 @@ impl AssetInfos {
             &mut self.infos,
             &mut self.path_to_id,
             &mut self.loader_dependants,
+            &mut self.dependants,
             &mut self.living_labeled_assets,
             &mut self.pending_tasks,
             self.watching_for_changes,
@@ -386,6 +446,24 @@ impl AssetInfos {
         }
 
         loaded_asset.value.insert(loaded_asset_id, world);
+        if let Some(info) = self.infos.get_mut(&loaded_asset_id) {
+            // A new report (if any) is sent right after the asset is loaded
+            info.validation_report = None;
+        }
+        if let Some(path) = self
+            .infos
+            .get(&loaded_asset_id)
+            .and_then(|info| info.path.as_ref())
+        {
+            if self.reload_batch.contains(path)
+                && !self.reload_batch.modified.contains(&loaded_asset_id)
+            {
+                self.reload_batch.modified.push(loaded_asset_id);
+            }
+        }
+        if self.watching_for_changes {
+            self.track_dependencies(loaded_asset_id, &loaded_asset.dependencies);
+        }
         let mut loading_deps = loaded_asset.dependencies;
         let mut failed_deps = HashSet::new();
         let mut loading_rec_deps = loading_deps.clone();
@@ -532,6 +610,88 @@ impl AssetInfos {
         }
     }
 
+    /// Updates [`AssetInfos::dependants`] to match the current `dependencies` of the asset with the given `id`.
+    fn track_dependencies(&mut self, id: UntypedAssetId, dependencies: &HashSet<UntypedAssetId>) {
+        let Some(info) = self.infos.get_mut(&id) else {
+            return;
+        };
+        for removed in info.dependencies.difference(dependencies) {
+            Self::remove_dependant(&mut self.dependants, *removed, id);
+        }
+        for dependency in dependencies {
+            self.dependants.entry(*dependency).or_default().insert(id);
+        }
+        info.dependencies.clone_from(dependencies);
+    }
+
+    fn remove_dependant(
+        dependants: &mut HashMap<UntypedAssetId, HashSet<UntypedAssetId>>,
+        dependency: UntypedAssetId,
+        dependant: UntypedAssetId,
+    ) {
+        if let Entry::Occupied(mut entry) = dependants.entry(dependency) {
+            entry.get_mut().remove(&dependant);
+            if entry.get().is_empty() {
+                entry.remove();
+            }
+        }
+    }
+
+    /// Returns every asset that (recursively) depends on one of the given `modified` assets, in topological order:
+    /// an asset always comes after all of its dependencies.
+    fn dependants_in_topological_order(&self, modified: &[UntypedAssetId]) -> Vec<UntypedAssetId> {
+        fn visit(
+            id: UntypedAssetId,
+            dependants: &HashMap<UntypedAssetId, HashSet<UntypedAssetId>>,
+            visited: &mut HashSet<UntypedAssetId>,
+            post_order: &mut Vec<UntypedAssetId>,
+        ) {
+            if !visited.insert(id) {
+                return;
+            }
+            for dependant in dependants.get(&id).into_iter().flatten() {
+                visit(*dependant, dependants, visited, post_order);
+            }
+            post_order.push(id);
+        }
+
+        let mut visited = HashSet::new();
+        let mut order = Vec::new();
+        for id in modified {
+            visit(*id, &self.dependants, &mut visited, &mut order);
+        }
+        // The reverse post-order of a depth first search is a topological order
+        order.reverse();
+
+        // Modified assets are only included if they depend on another modified asset
+        let reached = visited
+            .iter()
+            .filter_map(|id| self.dependants.get(id))
+            .flatten()
+            .collect::<HashSet<_>>();
+        order.retain(|id| reached.contains(id));
+        order
+    }
+
+    /// If every reload of the current [`ReloadBatch`] has finished, ends the batch and returns the resulting
+    /// [`AssetReloadBatchCompleteEvent`].
+    pub(crate) fn take_completed_reload_batch(&mut self) -> Option<AssetReloadBatchCompleteEvent> {
+        if !self.reload_batch.is_complete() {
+            return None;
+        }
+        let batch = std::mem::take(&mut self.reload_batch);
+        if batch.modified.is_empty() {
+            // None of the reloaded assets were alive (or all reloads failed)
+            return None;
+        }
+        let dependency_modified = self.dependants_in_topological_order(&batch.modified);
+        Some(AssetReloadBatchCompleteEvent {
+            reloaded: batch.paths,
+            modified: batch.modified,
+            dependency_modified,
+        })
+    }
+
     /// Recursively propagates loaded state up the dependency tree.
     fn propagate_loaded_state(
         infos: &mut AssetInfos,
@@ -651,10 +811,12 @@ impl AssetInfos {
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn process_handle_drop_internal(
         infos: &mut HashMap<UntypedAssetId, AssetInfo>,
         path_to_id: &mut HashMap<AssetPath<'static>, TypeIdMap<UntypedAssetId>>,
         loader_dependants: &mut HashMap<AssetPath<'static>, HashSet<AssetPath<'static>>>,
+        dependants: &mut HashMap<UntypedAssetId, HashSet<UntypedAssetId>>,
         living_labeled_assets: &mut HashMap<AssetPath<'static>, HashSet<Box<str>>>,
         pending_tasks: &mut HashMap<UntypedAssetId, Task<()>>,
         watching_for_changes: bool,
@@ -676,6 +838,12 @@ impl AssetInfos {
         let type_id = entry.key().type_id();
 
         let info = entry.remove();
+        if watching_for_changes {
+            for dependency in &info.dependencies {
+                Self::remove_dependant(dependants, *dependency, id);
+            }
+            dependants.remove(&id);
+        }
         let Some(path) = &info.path else {
             return true;
         };
@@ -714,6 +882,7 @@ impl AssetInfos {
                         &mut self.infos,
                         &mut self.path_to_id,
                         &mut self.loader_dependants,
+                        &mut self.dependants,
                         &mut self.living_labeled_assets,
                         &mut self.pending_tasks,
                         self.watching_for_changes,
@@ -307,6 +307,7 @@ impl<T: AssetLoader> AssetLoader for InstrumentedAssetLoader<T> {
     type Asset = T::Asset;
     type Settings = T::Settings;
     type Error = T::Error;
+    const SETTINGS_VERSION: u32 = T::SETTINGS_VERSION;
 
     fn load<'a>(
         &'a self,
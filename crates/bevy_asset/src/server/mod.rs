@@ -10,12 +10,13 @@ use crate::{
     loader::{AssetLoader, ErasedAssetLoader, LoadContext, LoadedAsset},
     meta::{
         loader_settings_meta_transform, AssetActionMinimal, AssetMetaDyn, AssetMetaMinimal,
-        MetaTransform, Settings,
+        MetaMigrations, MetaTransform, Settings,
     },
     path::AssetPath,
-    Asset, AssetEvent, AssetHandleProvider, AssetId, AssetLoadFailedEvent, AssetMetaCheck, Assets,
-    DeserializeMetaError, ErasedLoadedAsset, Handle, LoadedUntypedAsset, UntypedAssetId,
-    UntypedAssetLoadFailedEvent, UntypedHandle,
+    Asset, AssetEvent, AssetHandleProvider, AssetId, AssetLoadFailedEvent, AssetMetaCheck,
+    AssetValidationReport, AssetValidator, AssetValidators, Assets, DeserializeMetaError,
+    ErasedLoadedAsset, Handle, LoadedUntypedAsset, UntypedAssetId, UntypedAssetLoadFailedEvent,
+    UntypedHandle,
 };
 use atomicow::CowArc;
 use bevy_ecs::prelude::*;
@@ -59,6 +60,8 @@ pub struct AssetServer {
 pub(crate) struct AssetServerData {
     pub(crate) infos: RwLock<AssetInfos>,
     pub(crate) loaders: Arc<RwLock<AssetLoaders>>,
+    pub(crate) meta_migrations: Arc<RwLock<MetaMigrations>>,
+    pub(crate) validators: Arc<RwLock<AssetValidators>>,
     asset_event_sender: Sender<InternalAssetEvent>,
     asset_event_receiver: Receiver<InternalAssetEvent>,
     sources: AssetSources,
@@ -82,6 +85,8 @@ impl AssetServer {
         Self::new_with_loaders(
             sources,
             Default::default(),
+            Default::default(),
+            Default::default(),
             mode,
             AssetMetaCheck::Always,
             watching_for_changes,
@@ -99,6 +104,8 @@ impl AssetServer {
         Self::new_with_loaders(
             sources,
             Default::default(),
+            Default::default(),
+            Default::default(),
             mode,
             meta_check,
             watching_for_changes,
@@ -108,6 +115,8 @@ impl AssetServer {
     pub(crate) fn new_with_loaders(
         sources: AssetSources,
         loaders: Arc<RwLock<AssetLoaders>>,
+        meta_migrations: Arc<RwLock<MetaMigrations>>,
+        validators: Arc<RwLock<AssetValidators>>,
         mode: AssetServerMode,
         meta_check: AssetMetaCheck,
         watching_for_changes: bool,
@@ -123,6 +132,8 @@ impl AssetServer {
                 asset_event_sender,
                 asset_event_receiver,
                 loaders,
+                meta_migrations,
+                validators,
                 infos: RwLock::new(infos),
             }),
         }
@@ -146,6 +157,47 @@ impl AssetServer {
         self.data.loaders.write().push(loader);
     }
 
+    /// Registers a migration of the [`AssetLoader::Settings`] of `L` from settings version `from_version` (stored as `Old`)
+    /// to `from_version + 1` (stored as `New`). See [`AssetLoader::SETTINGS_VERSION`] and [`MetaMigrations`].
+    pub fn register_loader_settings_migration<L: AssetLoader, Old, New>(
+        &self,
+        from_version: u32,
+        migrate: impl Fn(Old) -> New + Send + Sync + 'static,
+    ) where
+        Old: for<'de> serde::Deserialize<'de>,
+        New: serde::Serialize,
+    {
+        self.data
+            .meta_migrations
+            .write()
+            .add(std::any::type_name::<L>(), from_version, migrate);
+    }
+
+    /// Registers a new [`AssetValidator`]. It will run for every asset of type [`AssetValidator::Asset`] loaded from now on.
+    pub fn register_validator<V: AssetValidator>(&self, validator: V) {
+        self.data.validators.write().push(validator);
+    }
+
+    /// Upgrades the serialized `meta_bytes` to `current_version` if `minimal` was written with an older settings version.
+    /// `type_name` is the type name of the [`AssetLoader`] or [`Process`](crate::processor::Process) the meta is for.
+    pub(crate) fn migrate_meta_bytes(
+        &self,
+        type_name: &str,
+        current_version: u32,
+        minimal: &AssetMetaMinimal,
+        meta_bytes: Vec<u8>,
+    ) -> Result<Vec<u8>, DeserializeMetaError> {
+        if minimal.settings_version == current_version {
+            return Ok(meta_bytes);
+        }
+        Ok(self.data.meta_migrations.read().migrate(
+            type_name,
+            &meta_bytes,
+            minimal.settings_version,
+            current_version,
+        )?)
+    }
+
     /// Registers a new [`Asset`] type. [`Asset`] types must be registered before assets of that type can be loaded.
     pub fn register_asset<A: Asset>(&self, assets: &Assets<A>) {
         self.register_handle_provider(assets.get_handle_provider());
@@ -154,6 +206,16 @@ impl AssetServer {
                 .resource_mut::<Events<AssetEvent<A>>>()
                 .send(AssetEvent::LoadedWithDependencies { id: id.typed() });
         }
+        fn dependency_modified_sender<A: Asset>(world: &mut World, id: UntypedAssetId) {
+            world
+                .resource_mut::<Events<AssetEvent<A>>>()
+                .send(AssetEvent::DependencyModified { id: id.typed() });
+        }
+        fn validation_failed_sender<A: Asset>(world: &mut World, id: UntypedAssetId) {
+            world
+                .resource_mut::<Events<AssetEvent<A>>>()
+                .send(AssetEvent::ValidationFailed { id: id.typed() });
+        }
         fn failed_sender<A: Asset>(
             world: &mut World,
             id: UntypedAssetId,
@@ -178,6 +240,14 @@ impl AssetServer {
         infos
             .dependency_failed_event_sender
             .insert(TypeId::of::<A>(), failed_sender::<A>);
+
+        infos
+            .dependency_modified_event_sender
+            .insert(TypeId::of::<A>(), dependency_modified_sender::<A>);
+
+        infos
+            .validation_failed_event_sender
+            .insert(TypeId::of::<A>(), validation_failed_sender::<A>);
     }
 
     pub(crate) fn register_handle_provider(&self, handle_provider: AssetHandleProvider) {
@@ -371,16 +441,37 @@ impl AssetServer {
         meta_transform: Option<MetaTransform>,
         guard: G,
     ) -> Handle<A> {
+        self.load_erased_with_meta_transform(
+            path,
+            TypeId::of::<A>(),
+            std::any::type_name::<A>(),
+            meta_transform,
+            guard,
+        )
+        .typed_unchecked()
+    }
+
+    /// Same as [`AssetServer::load_with_meta_transform`], but for an asset type that is only known at runtime.
+    pub(crate) fn load_erased_with_meta_transform<'a, G: Send + Sync + 'static>(
+        &self,
+        path: impl Into<AssetPath<'a>>,
+        type_id: TypeId,
+        type_name: &'static str,
+        meta_transform: Option<MetaTransform>,
+        guard: G,
+    ) -> UntypedHandle {
         let path = path.into().into_owned();
         let mut infos = self.data.infos.write();
-        let (handle, should_load) = infos.get_or_create_path_handle::<A>(
+        let (handle, should_load) = infos.get_or_create_path_handle_untyped(
             path.clone(),
+            type_id,
+            type_name,
             HandleLoadingMode::Request,
             meta_transform,
         );
 
         if should_load {
-            let owned_handle = Some(handle.clone().untyped());
+            let owned_handle = Some(handle.clone());
             let server = self.clone();
             let task = IoTaskPool::get().spawn(async move {
                 if let Err(err) = server.load_internal(owned_handle, path, false, None).await {
@@ -390,7 +481,7 @@ impl AssetServer {
             });
 
             #[cfg(not(any(target_arch = "wasm32", not(feature = "multi_threaded"))))]
-            infos.pending_tasks.insert(handle.id().untyped(), task);
+            infos.pending_tasks.insert(handle.id(), task);
 
             #[cfg(any(target_arch = "wasm32", not(feature = "multi_threaded")))]
             task.detach();
@@ -626,7 +717,7 @@ impl AssetServer {
                     handle.unwrap()
                 };
 
-                self.send_loaded_asset(base_handle.id(), loaded_asset);
+                self.send_loaded_asset(base_handle.id(), &base_path.clone_owned(), loaded_asset);
                 Ok(final_handle)
             }
             Err(err) => {
@@ -642,18 +733,45 @@ impl AssetServer {
 
     /// Sends a load event for the given `loaded_asset` and does the same recursively for all
     /// labeled assets.
-    fn send_loaded_asset(&self, id: UntypedAssetId, mut loaded_asset: ErasedLoadedAsset) {
-        for (_, labeled_asset) in loaded_asset.labeled_assets.drain() {
-            self.send_loaded_asset(labeled_asset.handle.id(), labeled_asset.asset);
+    /// Every asset is validated using the registered [`AssetValidator`]s before it is sent.
+    fn send_loaded_asset(
+        &self,
+        id: UntypedAssetId,
+        path: &AssetPath<'static>,
+        mut loaded_asset: ErasedLoadedAsset,
+    ) {
+        for (label, labeled_asset) in loaded_asset.labeled_assets.drain() {
+            self.send_loaded_asset(
+                labeled_asset.handle.id(),
+                &path.clone().with_label(label),
+                labeled_asset.asset,
+            );
         }
 
+        let report = self.data.validators.read().validate(path, &loaded_asset);
         self.send_asset_event(InternalAssetEvent::Loaded { id, loaded_asset });
+        if let Some(report) = report {
+            report.log();
+            self.send_asset_event(InternalAssetEvent::Validated { id, report });
+        }
     }
 
     /// Kicks off a reload of the asset stored at the given path. This will only reload the asset if it currently loaded.
+    ///
+    /// The reload is added to the current reload batch. Once every reload in the batch has finished, an
+    /// [`AssetEvent::DependencyModified`] is emitted for every asset that depends on a reloaded asset, followed by an
+    /// [`AssetReloadBatchCompleteEvent`].
+    ///
+    /// [`AssetReloadBatchCompleteEvent`]: crate::AssetReloadBatchCompleteEvent
     pub fn reload<'a>(&self, path: impl Into<AssetPath<'a>>) {
-        let server = self.clone();
         let path = path.into().into_owned();
+        self.data.infos.write().reload_batch.start_reload(&path);
+        self.reload_internal(path);
+    }
+
+    /// Kicks off a reload of the asset stored at the given path. The reload must already have been added to the current reload batch.
+    fn reload_internal(&self, path: AssetPath<'static>) {
+        let server = self.clone();
         IoTaskPool::get()
             .spawn(async move {
                 let mut reloaded = false;
@@ -678,6 +796,8 @@ impl AssetServer {
                         error!("{}", err);
                     }
                 }
+
+                server.send_asset_event(InternalAssetEvent::ReloadFinished);
             })
             .detach();
     }
@@ -905,6 +1025,19 @@ impl AssetServer {
             .map(|i| (i.load_state.clone(), i.dep_load_state, i.rec_dep_load_state))
     }
 
+    /// Retrieves the [`AssetValidationReport`] produced when the asset with the given `id` was last loaded.
+    /// This returns [`None`] if the [`AssetValidator`]s for the asset's type found no issues (or if no validators are registered).
+    pub fn get_validation_report(
+        &self,
+        id: impl Into<UntypedAssetId>,
+    ) -> Option<AssetValidationReport> {
+        self.data
+            .infos
+            .read()
+            .get(id.into())
+            .and_then(|i| i.validation_report.clone())
+    }
+
     /// Retrieves the main [`LoadState`] of a given asset `id`.
     ///
     /// Note that this is "just" the root asset load state. To check if an asset _and_ its recursive
@@ -1057,6 +1190,26 @@ impl AssetServer {
         self.data.loaders.write().reserve::<L>(extensions);
     }
 
+    /// Retrieve a handle for the given path and asset type. This will create a handle (and [`AssetInfo`]) if it does not exist
+    pub(crate) fn get_or_create_path_handle_untyped<'a>(
+        &self,
+        path: impl Into<AssetPath<'a>>,
+        type_id: TypeId,
+        type_name: &'static str,
+        meta_transform: Option<MetaTransform>,
+    ) -> UntypedHandle {
+        let mut infos = self.data.infos.write();
+        infos
+            .get_or_create_path_handle_untyped(
+                path.into().into_owned(),
+                type_id,
+                type_name,
+                HandleLoadingMode::NotLoading,
+                meta_transform,
+            )
+            .0
+    }
+
     /// Retrieve a handle for the given path. This will create a handle (and [`AssetInfo`]) if it does not exist
     pub(crate) fn get_or_create_path_handle<'a, A: Asset>(
         &self,
@@ -1112,7 +1265,7 @@ impl AssetServer {
                                 error: DeserializeMetaError::DeserializeMinimal(e).into(),
                             }
                         })?;
-                    let loader_name = match minimal.asset {
+                    let loader_name = match &minimal.asset {
                         AssetActionMinimal::Load { loader } => loader,
                         AssetActionMinimal::Process { .. } => {
                             return Err(AssetLoadError::CannotLoadProcessedAsset {
@@ -1126,12 +1279,18 @@ impl AssetServer {
                         }
                     };
                     let loader = self.get_asset_loader_with_type_name(&loader_name).await?;
-                    let meta = loader.deserialize_meta(&meta_bytes).map_err(|e| {
-                        AssetLoadError::DeserializeMeta {
+                    let meta = self
+                        .migrate_meta_bytes(
+                            &loader_name,
+                            loader.settings_version(),
+                            &minimal,
+                            meta_bytes,
+                        )
+                        .and_then(|meta_bytes| loader.deserialize_meta(&meta_bytes))
+                        .map_err(|e| AssetLoadError::DeserializeMeta {
                             path: asset_path.clone_owned(),
                             error: e.into(),
-                        }
-                    })?;
+                        })?;
 
                     Ok((meta, loader, reader))
                 }
@@ -1249,6 +1408,24 @@ pub fn handle_internal_asset_events(world: &mut World) {
                         .expect("Asset failed event sender should exist");
                     sender(world, id, path, error);
                 }
+                InternalAssetEvent::ReloadFinished => {
+                    infos.reload_batch.finish_reload();
+                }
+                InternalAssetEvent::Validated { id, report } => {
+                    let has_errors = report.has_errors();
+                    let Some(info) = infos.get_mut(id) else {
+                        // The handle was dropped since the asset was loaded
+                        continue;
+                    };
+                    info.validation_report = Some(report);
+                    if has_errors {
+                        let sender = infos
+                            .validation_failed_event_sender
+                            .get(&id.type_id())
+                            .expect("Asset event sender should exist");
+                        sender(world, id);
+                    }
+                }
             }
         }
 
@@ -1327,7 +1504,19 @@ pub fn handle_internal_asset_events(world: &mut World) {
 
         for path in paths_to_reload {
             info!("Reloading {path} because it has changed");
-            server.reload(path);
+            infos.reload_batch.start_reload(&path);
+            server.reload_internal(path);
+        }
+
+        if let Some(batch) = infos.take_completed_reload_batch() {
+            for id in &batch.dependency_modified {
+                let sender = infos
+                    .dependency_modified_event_sender
+                    .get(&id.type_id())
+                    .expect("Asset dependency modified event sender should exist");
+                sender(world, *id);
+            }
+            world.send_event(batch);
         }
 
         #[cfg(not(any(target_arch = "wasm32", not(feature = "multi_threaded"))))]
@@ -1352,6 +1541,13 @@ pub(crate) enum InternalAssetEvent {
         path: AssetPath<'static>,
         error: AssetLoadError,
     },
+    /// A reload started by [`AssetServer::reload`] has finished.
+    ReloadFinished,
+    /// The [`AssetValidator`]s of a loaded asset found issues. This is sent after the corresponding [`InternalAssetEvent::Loaded`].
+    Validated {
+        id: UntypedAssetId,
+        report: AssetValidationReport,
+    },
 }
 
 /// The load state of an asset.
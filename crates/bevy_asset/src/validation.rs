@@ -0,0 +1,281 @@
+use crate::{Asset, AssetPath, ErasedLoadedAsset};
+use bevy_utils::{
+    tracing::{error, warn},
+    TypeIdMap,
+};
+use serde::{Deserialize, Serialize};
+use std::{any::TypeId, fmt::Display, sync::Arc};
+
+/// Checks the contents of loaded [`Asset`]s of a given type, such as "textures must have power of two dimensions" or
+/// "meshes must have normals".
+///
+/// [`AssetLoader`](crate::AssetLoader) errors only catch assets that cannot be parsed. Validators catch assets that parse
+/// just fine but contain content bugs. Validators run every time an asset of their type is loaded by the [`AssetServer`](crate::AssetServer),
+/// and every time the [`AssetProcessor`](crate::processor::AssetProcessor) writes a processed asset of their type.
+///
+/// Issues are collected into an [`AssetValidationReport`]. Reports are logged, can be retrieved using
+/// [`AssetServer::get_validation_report`](crate::AssetServer::get_validation_report), and assets whose report contains errors
+/// emit [`AssetEvent::ValidationFailed`](crate::AssetEvent::ValidationFailed). Validation errors do not prevent loaded assets
+/// from being added to [`Assets`](crate::Assets), but they do cause processing to fail.
+///
+/// Validators are registered using [`AssetApp::register_asset_validator`](crate::AssetApp::register_asset_validator).
+pub trait AssetValidator: Send + Sync + 'static {
+    /// The type of [`Asset`] this validator checks.
+    type Asset: Asset;
+
+    /// Checks the given `asset`, recording any issues in `context`.
+    fn validate(&self, asset: &Self::Asset, context: &mut ValidationContext);
+}
+
+/// The context passed to [`AssetValidator::validate`], used to record [`ValidationIssue`]s.
+pub struct ValidationContext<'a> {
+    path: &'a AssetPath<'static>,
+    validator: &'static str,
+    issues: &'a mut Vec<ValidationIssue>,
+}
+
+impl<'a> ValidationContext<'a> {
+    /// The path of the asset being validated. This includes the label for labeled assets.
+    pub fn path(&self) -> &AssetPath<'static> {
+        self.path
+    }
+
+    /// Records a [`ValidationSeverity::Error`] issue.
+    pub fn error(&mut self, message: impl Into<String>) {
+        self.issue(ValidationSeverity::Error, message);
+    }
+
+    /// Records a [`ValidationSeverity::Warning`] issue.
+    pub fn warning(&mut self, message: impl Into<String>) {
+        self.issue(ValidationSeverity::Warning, message);
+    }
+
+    /// Records an issue with the given `severity`.
+    pub fn issue(&mut self, severity: ValidationSeverity, message: impl Into<String>) {
+        self.issues.push(ValidationIssue {
+            validator: self.validator.to_string(),
+            severity,
+            message: message.into(),
+        });
+    }
+}
+
+/// How severe a [`ValidationIssue`] is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum ValidationSeverity {
+    /// The asset is usable, but likely not what was intended.
+    Warning,
+    /// The asset is broken. Processing an asset with errors fails.
+    Error,
+}
+
+/// A single issue found by an [`AssetValidator`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValidationIssue {
+    /// The type name of the [`AssetValidator`] that found this issue.
+    pub validator: String,
+    /// How severe this issue is.
+    pub severity: ValidationSeverity,
+    /// A human readable description of the issue.
+    pub message: String,
+}
+
+/// The issues found by all [`AssetValidator`]s registered for a single asset.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AssetValidationReport {
+    /// The path of the validated asset. This includes the label for labeled assets.
+    pub path: AssetPath<'static>,
+    /// The issues found, in the order the validators were registered.
+    pub issues: Vec<ValidationIssue>,
+}
+
+impl AssetValidationReport {
+    /// Iterates over the issues with [`ValidationSeverity::Error`].
+    pub fn errors(&self) -> impl Iterator<Item = &ValidationIssue> {
+        self.issues
+            .iter()
+            .filter(|issue| issue.severity == ValidationSeverity::Error)
+    }
+
+    /// Iterates over the issues with [`ValidationSeverity::Warning`].
+    pub fn warnings(&self) -> impl Iterator<Item = &ValidationIssue> {
+        self.issues
+            .iter()
+            .filter(|issue| issue.severity == ValidationSeverity::Warning)
+    }
+
+    /// Returns `true` if any issue is a [`ValidationSeverity::Error`].
+    pub fn has_errors(&self) -> bool {
+        self.errors().next().is_some()
+    }
+
+    /// Logs every issue in this report, using `error!` for errors and `warn!` for warnings.
+    pub(crate) fn log(&self) {
+        for issue in &self.issues {
+            match issue.severity {
+                ValidationSeverity::Error => {
+                    error!("Asset '{}' failed validation: {}", self.path, issue);
+                }
+                ValidationSeverity::Warning => {
+                    warn!("Asset '{}' has a validation warning: {}", self.path, issue);
+                }
+            }
+        }
+    }
+}
+
+impl Display for ValidationIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({})", self.message, self.validator)
+    }
+}
+
+impl Display for AssetValidationReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "'{}' failed validation:", self.path)?;
+        for issue in self.errors() {
+            write!(f, " {issue};")?;
+        }
+        Ok(())
+    }
+}
+
+/// A type-erased counterpart to [`AssetValidator`].
+pub(crate) trait ErasedAssetValidator: Send + Sync + 'static {
+    fn validate(&self, asset: &ErasedLoadedAsset, context: &mut ValidationContext);
+}
+
+impl<V: AssetValidator> ErasedAssetValidator for V {
+    fn validate(&self, asset: &ErasedLoadedAsset, context: &mut ValidationContext) {
+        if let Some(asset) = asset.get::<V::Asset>() {
+            AssetValidator::validate(self, asset, context);
+        }
+    }
+}
+
+/// The [`AssetValidator`]s registered for each [`Asset`] type.
+#[derive(Default)]
+pub(crate) struct AssetValidators {
+    validators: TypeIdMap<Vec<(&'static str, Arc<dyn ErasedAssetValidator>)>>,
+}
+
+impl AssetValidators {
+    pub(crate) fn push<V: AssetValidator>(&mut self, validator: V) {
+        self.validators
+            .entry(TypeId::of::<V::Asset>())
+            .or_default()
+            .push((std::any::type_name::<V>(), Arc::new(validator)));
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.validators.is_empty()
+    }
+
+    /// Runs every validator registered for the type of `asset`. This does not validate labeled assets.
+    /// Returns [`None`] if no issues were found.
+    pub(crate) fn validate(
+        &self,
+        path: &AssetPath<'static>,
+        asset: &ErasedLoadedAsset,
+    ) -> Option<AssetValidationReport> {
+        let validators = self.validators.get(&asset.asset_type_id())?;
+        let mut issues = Vec::new();
+        for (validator_name, validator) in validators {
+            let mut context = ValidationContext {
+                path,
+                validator: validator_name,
+                issues: &mut issues,
+            };
+            validator.validate(asset, &mut context);
+        }
+        if issues.is_empty() {
+            None
+        } else {
+            Some(AssetValidationReport {
+                path: path.clone(),
+                issues,
+            })
+        }
+    }
+
+    /// Runs [`AssetValidators::validate`] for `asset` and, recursively, all of its labeled assets.
+    pub(crate) fn validate_recursive(
+        &self,
+        path: &AssetPath<'static>,
+        asset: &ErasedLoadedAsset,
+        reports: &mut Vec<AssetValidationReport>,
+    ) {
+        reports.extend(self.validate(path, asset));
+        for (label, labeled_asset) in &asset.labeled_assets {
+            let labeled_path = path.clone().with_label(label.clone());
+            self.validate_recursive(&labeled_path, &labeled_asset.asset, reports);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{self as bevy_asset, LoadedAsset};
+    use bevy_reflect::TypePath;
+
+    #[derive(Asset, TypePath)]
+    struct Texture {
+        width: u32,
+        height: u32,
+    }
+
+    struct PowerOfTwo;
+
+    impl AssetValidator for PowerOfTwo {
+        type Asset = Texture;
+
+        fn validate(&self, asset: &Texture, context: &mut ValidationContext) {
+            if !asset.width.is_power_of_two() || !asset.height.is_power_of_two() {
+                context.error(format!(
+                    "{}x{} is not a power of two",
+                    asset.width, asset.height
+                ));
+            }
+            if asset.width != asset.height {
+                context.warning("texture is not square");
+            }
+        }
+    }
+
+    #[test]
+    fn validate_report() {
+        let mut validators = AssetValidators::default();
+        validators.push(PowerOfTwo);
+        let path = AssetPath::from("texture.png");
+
+        let valid: ErasedLoadedAsset = LoadedAsset::from(Texture {
+            width: 64,
+            height: 64,
+        })
+        .into();
+        assert!(validators.validate(&path, &valid).is_none());
+
+        let invalid: ErasedLoadedAsset = LoadedAsset::from(Texture {
+            width: 100,
+            height: 64,
+        })
+        .into();
+        let report = validators.validate(&path, &invalid).unwrap();
+        assert_eq!(report.path, path);
+        assert!(report.has_errors());
+        assert_eq!(report.errors().count(), 1);
+        assert_eq!(report.warnings().count(), 1);
+        assert_eq!(
+            report.issues[0].validator,
+            std::any::type_name::<PowerOfTwo>()
+        );
+        assert_eq!(
+            report.to_string(),
+            format!(
+                "'texture.png' failed validation: 100x64 is not a power of two ({});",
+                std::any::type_name::<PowerOfTwo>()
+            )
+        );
+    }
+}
@@ -0,0 +1,605 @@
+use std::any::TypeId;
+use std::borrow::Cow;
+use std::sync::Arc;
+
+use bevy_reflect::func::args::{ArgList, Ownership};
+use bevy_reflect::func::{FunctionError, Return};
+use bevy_reflect::{DynamicTuple, FromReflect, PartialReflect, TypePath};
+use bevy_utils::HashMap;
+use thiserror::Error;
+
+use crate as bevy_ecs;
+use crate::{
+    change_detection::Ref,
+    entity::Entity,
+    reflect::{AppFunctionRegistry, AppTypeRegistry, ReflectComponent, ReflectResource},
+    system::{IntoSystem, Resource, SystemId},
+    world::World,
+};
+
+/// The type-erased runner stored for each system in a [`SystemFunctionRegistry`].
+type SystemFunction = Arc<
+    dyn Fn(&mut World, Vec<Box<dyn PartialReflect>>) -> WorldCallResult + Send + Sync + 'static,
+>;
+
+/// The result of calling a function or system with [`World::call_function`].
+///
+/// Unit returns are reported as `None`.
+/// Functions that return a reference have the referenced value cloned,
+/// since the borrow cannot outlive the call.
+pub type WorldCallResult = Result<Option<Box<dyn PartialReflect>>, WorldCallError>;
+
+/// A [`Resource`] storing systems that can be run by name with reflected input.
+///
+/// Systems are added with [`World::register_system_function`]
+/// and run with [`World::call_function`].
+#[derive(Resource, Clone, Default)]
+pub struct SystemFunctionRegistry {
+    systems: HashMap<Cow<'static, str>, SystemFunction>,
+}
+
+impl SystemFunctionRegistry {
+    /// Returns `true` if a system with the given name has been registered.
+    pub fn contains(&self, name: &str) -> bool {
+        self.systems.contains_key(name)
+    }
+
+    /// Returns an iterator over the names of all registered systems.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.systems.keys().map(AsRef::as_ref)
+    }
+
+    /// Returns the number of registered systems.
+    pub fn len(&self) -> usize {
+        self.systems.len()
+    }
+
+    /// Returns `true` if no systems have been registered.
+    pub fn is_empty(&self) -> bool {
+        self.systems.is_empty()
+    }
+}
+
+/// An error returned by [`World::call_function`].
+#[derive(Debug, Error)]
+pub enum WorldCallError {
+    /// No function or system was registered under the given name.
+    #[error("no function or system named `{0}` was registered")]
+    NotFound(Cow<'static, str>),
+    /// The number of provided arguments did not match the number the function expects.
+    #[error("expected {expected} arguments but received {received}")]
+    ArgCountMismatch {
+        /// The number of arguments that had to be provided by the caller.
+        expected: usize,
+        /// The number of arguments the caller provided.
+        received: usize,
+    },
+    /// A component argument was not given an [`Entity`] to fetch the component from.
+    #[error("argument {index} must be an `Entity` to resolve the component `{type_path}`")]
+    ExpectedEntity {
+        /// The index of the function argument.
+        index: usize,
+        /// The type path of the component.
+        type_path: &'static str,
+    },
+    /// The entity given for a component argument does not exist.
+    #[error("argument {index} refers to the entity {entity}, which does not exist")]
+    NoSuchEntity {
+        /// The index of the function argument.
+        index: usize,
+        /// The entity that could not be found.
+        entity: Entity,
+    },
+    /// The entity given for a component argument does not have that component.
+    #[error("argument {index} requires the entity {entity} to have the component `{type_path}`")]
+    MissingComponent {
+        /// The index of the function argument.
+        index: usize,
+        /// The entity that is missing the component.
+        entity: Entity,
+        /// The type path of the component.
+        type_path: &'static str,
+    },
+    /// A resource argument could not be found in the world.
+    #[error("argument {index} requires the resource `{type_path}`, which does not exist")]
+    MissingResource {
+        /// The index of the function argument.
+        index: usize,
+        /// The type path of the resource.
+        type_path: &'static str,
+    },
+    /// The same component or resource was requested by more than one argument.
+    #[error(
+        "argument {index} accesses `{type_path}`, which is already borrowed by another argument"
+    )]
+    ConflictingAccess {
+        /// The index of the function argument.
+        index: usize,
+        /// The type path of the component or resource.
+        type_path: &'static str,
+    },
+    /// The provided arguments could not be converted into the input of a system.
+    #[error(
+        "the arguments could not be converted into `{type_path}`, the input of system `{name}`"
+    )]
+    InvalidSystemInput {
+        /// The name of the system.
+        name: Cow<'static, str>,
+        /// The type path of the system's input.
+        type_path: &'static str,
+    },
+    /// The registered system could not be run.
+    #[error("the system `{name}` could not be run: {reason}")]
+    System {
+        /// The name of the system.
+        name: Cow<'static, str>,
+        /// The reason the system could not be run.
+        reason: String,
+    },
+    /// The function itself returned an error.
+    #[error(transparent)]
+    Function(#[from] FunctionError),
+}
+
+/// Where the value of a single function argument comes from.
+enum ArgSource {
+    /// A component on an entity, borrowed from the world.
+    Component {
+        entity: Entity,
+        component: ReflectComponent,
+        type_path: &'static str,
+    },
+    /// A resource, borrowed from the world.
+    Resource {
+        resource: ReflectResource,
+        type_path: &'static str,
+    },
+    /// A caller-provided value passed by value.
+    Owned(Option<Box<dyn PartialReflect>>),
+    /// A caller-provided value passed by reference.
+    Borrowed(Box<dyn PartialReflect>),
+}
+
+impl World {
+    /// Calls the function or system registered under `name`, resolving its arguments
+    /// from the given reflected values and from this world.
+    ///
+    /// Functions are looked up in the [`AppFunctionRegistry`] first,
+    /// followed by the systems added with [`World::register_system_function`].
+    ///
+    /// For functions, each argument is resolved based on its type in the [`AppTypeRegistry`]:
+    /// - `&T` and `&mut T`, where `T` is registered with [`ReflectResource`],
+    ///   borrow the resource from the world and consume no caller-provided value.
+    /// - `&T` and `&mut T`, where `T` is registered with [`ReflectComponent`],
+    ///   consume the next caller-provided value, which must be an [`Entity`],
+    ///   and borrow the component from that entity.
+    /// - Any other argument consumes the next caller-provided value as-is.
+    ///
+    /// Each component or resource may only be borrowed by a single argument per call.
+    ///
+    /// For systems, the provided values are converted into the system's input with [`FromReflect`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_ecs::reflect::{AppFunctionRegistry, AppTypeRegistry, ReflectComponent};
+    /// # use bevy_reflect::Reflect;
+    /// #[derive(Component, Reflect)]
+    /// #[reflect(Component)]
+    /// struct Health(f32);
+    ///
+    /// impl Health {
+    ///     fn damage(&mut self, amount: f32) {
+    ///         self.0 -= amount;
+    ///     }
+    /// }
+    ///
+    /// let mut world = World::new();
+    /// world.init_resource::<AppTypeRegistry>();
+    /// world.init_resource::<AppFunctionRegistry>();
+    /// world.resource::<AppTypeRegistry>().write().register::<Health>();
+    /// world
+    ///     .resource::<AppFunctionRegistry>()
+    ///     .write()
+    ///     .register_with_name("damage", Health::damage)
+    ///     .unwrap();
+    ///
+    /// let entity = world.spawn(Health(10.0)).id();
+    /// world
+    ///     .call_function("damage", vec![Box::new(entity), Box::new(3.0_f32)])
+    ///     .unwrap();
+    ///
+    /// assert_eq!(world.get::<Health>(entity).unwrap().0, 7.0);
+    /// ```
+    pub fn call_function(
+        &mut self,
+        name: &str,
+        args: Vec<Box<dyn PartialReflect>>,
+    ) -> WorldCallResult {
+        let function = self
+            .get_resource::<AppFunctionRegistry>()
+            .and_then(|registry| registry.read().get(name).cloned());
+
+        let Some(function) = function else {
+            let system = self
+                .get_resource::<SystemFunctionRegistry>()
+                .and_then(|registry| registry.systems.get(name).cloned())
+                .ok_or_else(|| WorldCallError::NotFound(Cow::Owned(name.to_owned())))?;
+            return system(self, args);
+        };
+
+        let mut sources = self.resolve_arg_sources(function.info().args(), args)?;
+
+        let world = self.as_unsafe_world_cell();
+        let mut list = ArgList::new();
+        for (index, (source, info)) in sources.iter_mut().zip(function.info().args()).enumerate() {
+            let mutable = info.ownership() == Ownership::Mut;
+            list = match source {
+                ArgSource::Component {
+                    entity,
+                    component,
+                    type_path,
+                } => {
+                    let cell = world
+                        .get_entity(*entity)
+                        .ok_or(WorldCallError::NoSuchEntity {
+                            index,
+                            entity: *entity,
+                        })?;
+                    // SAFETY: `resolve_arg_sources` ensures no other argument accesses this
+                    // component on this entity, and the world is mutably borrowed for the call.
+                    let value = unsafe { component.reflect_unchecked_mut(cell) }.ok_or(
+                        WorldCallError::MissingComponent {
+                            index,
+                            entity: *entity,
+                            type_path,
+                        },
+                    )?;
+                    if mutable {
+                        list.push_mut(value.into_inner().as_partial_reflect_mut())
+                    } else {
+                        list.push_ref(Ref::from(value).into_inner().as_partial_reflect())
+                    }
+                }
+                ArgSource::Resource {
+                    resource,
+                    type_path,
+                } => {
+                    // SAFETY: `resolve_arg_sources` ensures no other argument accesses this
+                    // resource, and the world is mutably borrowed for the call.
+                    let value = unsafe { resource.reflect_unchecked_mut(world) }
+                        .ok_or(WorldCallError::MissingResource { index, type_path })?;
+                    if mutable {
+                        list.push_mut(value.into_inner().as_partial_reflect_mut())
+                    } else {
+                        list.push_ref(Ref::from(value).into_inner().as_partial_reflect())
+                    }
+                }
+                ArgSource::Owned(value) => list.push_boxed(value.take().unwrap()),
+                ArgSource::Borrowed(value) => {
+                    let value = &mut **value;
+                    if mutable {
+                        list.push_mut(value)
+                    } else {
+                        list.push_ref(value)
+                    }
+                }
+            };
+        }
+
+        Ok(match function.call(list)? {
+            Return::Unit => None,
+            Return::Owned(value) => Some(value),
+            Return::Ref(value) => Some(value.clone_value()),
+            Return::Mut(value) => Some(value.clone_value()),
+        })
+    }
+
+    /// Registers a system that can be run by name with [`World::call_function`].
+    ///
+    /// The system is also registered as a one-shot system, and its [`SystemId`] is returned.
+    ///
+    /// When called, a single provided value is converted directly into the system's input `I`.
+    /// Otherwise, the provided values are collected into a tuple first,
+    /// so systems taking no input or a tuple of values can be called with any number of arguments.
+    ///
+    /// Registering a system under a name that is already in use replaces the previous system.
+    pub fn register_system_function<I, O, M>(
+        &mut self,
+        name: impl Into<Cow<'static, str>>,
+        system: impl IntoSystem<I, O, M> + 'static,
+    ) -> SystemId<I, O>
+    where
+        I: FromReflect + TypePath,
+        O: PartialReflect,
+    {
+        let name = name.into();
+        let id = self.register_system(system);
+
+        let system_name = name.clone();
+        let runner: SystemFunction = Arc::new(move |world, args| {
+            let input =
+                system_input::<I>(args).ok_or_else(|| WorldCallError::InvalidSystemInput {
+                    name: system_name.clone(),
+                    type_path: I::type_path(),
+                })?;
+            let output =
+                world
+                    .run_system_with_input(id, input)
+                    .map_err(|error| WorldCallError::System {
+                        name: system_name.clone(),
+                        reason: error.to_string(),
+                    })?;
+            Ok((TypeId::of::<O>() != TypeId::of::<()>())
+                .then(|| Box::new(output) as Box<dyn PartialReflect>))
+        });
+
+        self.get_resource_or_insert_with(SystemFunctionRegistry::default)
+            .systems
+            .insert(name, runner);
+        id
+    }
+
+    /// Decides where each argument of a function comes from,
+    /// consuming the caller-provided values in order.
+    fn resolve_arg_sources(
+        &self,
+        infos: &[bevy_reflect::func::args::ArgInfo],
+        args: Vec<Box<dyn PartialReflect>>,
+    ) -> Result<Vec<ArgSource>, WorldCallError> {
+        let type_registry = self.get_resource::<AppTypeRegistry>().cloned();
+        let type_registry = type_registry.as_ref().map(|registry| registry.read());
+
+        let received = args.len();
+        let mut args = args.into_iter();
+        let mut sources = Vec::with_capacity(infos.len());
+        let mut accessed: Vec<(Option<Entity>, TypeId)> = Vec::new();
+
+        for (index, info) in infos.iter().enumerate() {
+            // Reference arguments report the type path of the reference itself,
+            // so strip it to find the registration of the referenced type.
+            let type_path = match info.ownership() {
+                Ownership::Owned => info.type_path(),
+                Ownership::Ref => info.type_path().trim_start_matches('&'),
+                Ownership::Mut => info.type_path().trim_start_matches("&mut "),
+            };
+            let registration = match info.ownership() {
+                Ownership::Owned => None,
+                Ownership::Ref | Ownership::Mut => type_registry
+                    .as_ref()
+                    .and_then(|registry| registry.get_with_type_path(type_path)),
+            };
+
+            let resource = registration.and_then(|registration| {
+                Some((registration, registration.data::<ReflectResource>()?))
+            });
+            let component = registration.and_then(|registration| {
+                Some((registration, registration.data::<ReflectComponent>()?))
+            });
+
+            let source = if let Some((registration, resource)) = resource {
+                if accessed.contains(&(None, registration.type_id())) {
+                    return Err(WorldCallError::ConflictingAccess { index, type_path });
+                }
+                accessed.push((None, registration.type_id()));
+                ArgSource::Resource {
+                    resource: resource.clone(),
+                    type_path,
+                }
+            } else {
+                let value = args.next().ok_or(WorldCallError::ArgCountMismatch {
+                    expected: index + 1,
+                    received,
+                })?;
+
+                if let Some((registration, component)) = component {
+                    let entity = Entity::from_reflect(value.as_ref())
+                        .ok_or(WorldCallError::ExpectedEntity { index, type_path })?;
+                    if accessed.contains(&(Some(entity), registration.type_id())) {
+                        return Err(WorldCallError::ConflictingAccess { index, type_path });
+                    }
+                    accessed.push((Some(entity), registration.type_id()));
+                    ArgSource::Component {
+                        entity,
+                        component: component.clone(),
+                        type_path,
+                    }
+                } else if info.ownership() == Ownership::Owned {
+                    ArgSource::Owned(Some(value))
+                } else {
+                    ArgSource::Borrowed(value)
+                }
+            };
+            sources.push(source);
+        }
+
+        let remaining = args.len();
+        if remaining > 0 {
+            return Err(WorldCallError::ArgCountMismatch {
+                expected: received - remaining,
+                received,
+            });
+        }
+
+        Ok(sources)
+    }
+}
+
+/// Converts the values provided to a system function into the system's input.
+fn system_input<I: FromReflect>(mut args: Vec<Box<dyn PartialReflect>>) -> Option<I> {
+    if args.len() == 1 {
+        if let Some(input) = I::from_reflect(args[0].as_ref()) {
+            return Some(input);
+        }
+    }
+
+    let mut tuple = DynamicTuple::default();
+    for arg in args.drain(..) {
+        tuple.insert_boxed(arg);
+    }
+    I::from_reflect(&tuple)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+    use crate::system::In;
+    use bevy_reflect::Reflect;
+
+    #[derive(Component, Reflect, Debug, PartialEq)]
+    #[reflect(Component)]
+    struct Health(f32);
+
+    impl Health {
+        fn heal(&mut self, amount: f32) -> f32 {
+            self.0 += amount;
+            self.0
+        }
+    }
+
+    #[derive(Resource, Reflect, Default)]
+    #[reflect(Resource)]
+    struct Multiplier(f32);
+
+    fn scaled_damage(health: &mut Health, multiplier: &Multiplier, amount: f32) {
+        health.0 -= amount * multiplier.0;
+    }
+
+    fn transfer(from: &mut Health, to: &mut Health, amount: f32) {
+        from.0 -= amount;
+        to.0 += amount;
+    }
+
+    fn setup() -> World {
+        let mut world = World::new();
+        world.init_resource::<AppTypeRegistry>();
+        world.init_resource::<AppFunctionRegistry>();
+        {
+            let mut registry = world.resource::<AppTypeRegistry>().write();
+            registry.register::<Health>();
+            registry.register::<Multiplier>();
+        }
+        {
+            let mut functions = world.resource::<AppFunctionRegistry>().write();
+            functions.register_with_name("heal", Health::heal).unwrap();
+            functions
+                .register_with_name("scaled_damage", scaled_damage)
+                .unwrap();
+            functions.register_with_name("transfer", transfer).unwrap();
+        }
+        world.insert_resource(Multiplier(2.0));
+        world
+    }
+
+    #[test]
+    fn should_resolve_component_and_resource_arguments() {
+        let mut world = setup();
+        let entity = world.spawn(Health(10.0)).id();
+
+        let result = world
+            .call_function("heal", vec![Box::new(entity), Box::new(5.0_f32)])
+            .unwrap()
+            .unwrap();
+        assert_eq!(result.try_downcast_ref::<f32>(), Some(&15.0));
+
+        world
+            .call_function("scaled_damage", vec![Box::new(entity), Box::new(3.0_f32)])
+            .unwrap();
+        assert_eq!(world.get::<Health>(entity), Some(&Health(9.0)));
+    }
+
+    #[test]
+    fn should_report_resolution_errors() {
+        let mut world = setup();
+        let entity = world.spawn(Health(10.0)).id();
+        let empty = world.spawn_empty().id();
+
+        assert!(matches!(
+            world.call_function("missing", Vec::new()),
+            Err(WorldCallError::NotFound(_))
+        ));
+        assert!(matches!(
+            world.call_function("heal", vec![Box::new(1.0_f32), Box::new(5.0_f32)]),
+            Err(WorldCallError::ExpectedEntity { index: 0, .. })
+        ));
+        assert!(matches!(
+            world.call_function("heal", vec![Box::new(empty), Box::new(5.0_f32)]),
+            Err(WorldCallError::MissingComponent { index: 0, .. })
+        ));
+        assert!(matches!(
+            world.call_function("heal", vec![Box::new(entity)]),
+            Err(WorldCallError::ArgCountMismatch {
+                expected: 2,
+                received: 1
+            })
+        ));
+        assert!(matches!(
+            world.call_function(
+                "transfer",
+                vec![Box::new(entity), Box::new(entity), Box::new(1.0_f32)]
+            ),
+            Err(WorldCallError::ConflictingAccess { index: 1, .. })
+        ));
+
+        world.remove_resource::<Multiplier>();
+        assert!(matches!(
+            world.call_function("scaled_damage", vec![Box::new(entity), Box::new(1.0_f32)]),
+            Err(WorldCallError::MissingResource { index: 1, .. })
+        ));
+        assert_eq!(world.get::<Health>(entity), Some(&Health(10.0)));
+    }
+
+    #[test]
+    fn should_borrow_distinct_entities() {
+        let mut world = setup();
+        let a = world.spawn(Health(10.0)).id();
+        let b = world.spawn(Health(0.0)).id();
+
+        world
+            .call_function(
+                "transfer",
+                vec![Box::new(a), Box::new(b), Box::new(4.0_f32)],
+            )
+            .unwrap();
+
+        assert_eq!(world.get::<Health>(a), Some(&Health(6.0)));
+        assert_eq!(world.get::<Health>(b), Some(&Health(4.0)));
+    }
+
+    #[test]
+    fn should_run_system_functions() {
+        let mut world = setup();
+
+        fn add(In((a, b)): In<(f32, f32)>, mut multiplier: ResMut<Multiplier>) -> f32 {
+            multiplier.0 = a + b;
+            multiplier.0
+        }
+
+        fn double(In(value): In<f32>) -> f32 {
+            value * 2.0
+        }
+
+        world.register_system_function("add", add);
+        world.register_system_function("double", double);
+
+        let result = world
+            .call_function("add", vec![Box::new(1.0_f32), Box::new(2.0_f32)])
+            .unwrap()
+            .unwrap();
+        assert_eq!(result.try_downcast_ref::<f32>(), Some(&3.0));
+        assert_eq!(world.resource::<Multiplier>().0, 3.0);
+
+        let result = world
+            .call_function("double", vec![Box::new(4.0_f32)])
+            .unwrap()
+            .unwrap();
+        assert_eq!(result.try_downcast_ref::<f32>(), Some(&8.0));
+
+        assert!(matches!(
+            world.call_function("double", vec![Box::new("four".to_string())]),
+            Err(WorldCallError::InvalidSystemInput { .. })
+        ));
+    }
+}
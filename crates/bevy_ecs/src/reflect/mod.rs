@@ -14,6 +14,8 @@ mod bundle;
 mod component;
 mod entity_commands;
 mod from_world;
+#[cfg(feature = "reflect_functions")]
+mod function;
 mod map_entities;
 mod resource;
 
@@ -21,6 +23,8 @@ pub use bundle::{ReflectBundle, ReflectBundleFns};
 pub use component::{ReflectComponent, ReflectComponentFns};
 pub use entity_commands::ReflectCommandExt;
 pub use from_world::{ReflectFromWorld, ReflectFromWorldFns};
+#[cfg(feature = "reflect_functions")]
+pub use function::{SystemFunctionRegistry, WorldCallError, WorldCallResult};
 pub use map_entities::{ReflectMapEntities, ReflectMapEntitiesResource};
 pub use resource::{ReflectResource, ReflectResourceFns};
 
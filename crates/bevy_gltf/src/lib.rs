@@ -96,7 +96,7 @@
 //! You can use [`GltfAssetLabel`] to ensure you are using the correct label.
 
 #[cfg(feature = "bevy_animation")]
-use bevy_animation::AnimationClip;
+use bevy_animation::{event::ReflectAnimationEvent, AnimationClip};
 use bevy_utils::HashMap;
 
 mod loader;
@@ -105,6 +105,8 @@ pub use loader::*;
 
 use bevy_app::prelude::*;
 use bevy_asset::{Asset, AssetApp, AssetPath, Handle};
+#[cfg(feature = "bevy_animation")]
+use bevy_ecs::event::Event;
 use bevy_ecs::{prelude::Component, reflect::ReflectComponent};
 use bevy_pbr::StandardMaterial;
 use bevy_reflect::{Reflect, TypePath};
@@ -155,6 +157,9 @@ impl Plugin for GltfPlugin {
             .init_asset::<GltfMesh>()
             .init_asset::<GltfSkin>()
             .preregister_asset_loader::<GltfLoader>(&["gltf", "glb"]);
+
+        #[cfg(feature = "bevy_animation")]
+        app.register_type::<GltfAnimationEvent>();
     }
 
     fn finish(&self, app: &mut App) {
@@ -456,6 +461,24 @@ pub struct GltfMaterialExtras {
     pub value: String,
 }
 
+/// An animation event read from the extras of a glTF animation.
+///
+/// Events are listed in the `events` array of the animation's extras, for example
+/// `{"events": [{"time": 0.42, "name": "footstep", "node": 3, "value": {"volume": 0.5}}]}`.
+///
+/// `node` is the optional index of the node whose entity the event is triggered on.
+/// Without it, the event is triggered on the entity with the `AnimationPlayer`.
+/// `value` is optional and may contain any JSON.
+#[cfg(feature = "bevy_animation")]
+#[derive(Clone, Debug, Reflect, Default, PartialEq, Event)]
+#[reflect(AnimationEvent)]
+pub struct GltfAnimationEvent {
+    /// The name of the event.
+    pub name: String,
+    /// The JSON content of the event's `value`, if present.
+    pub value: Option<String>,
+}
+
 /// Labels that can be used to load part of a glTF
 ///
 /// You can use [`GltfAssetLabel::from_asset`] to add it to an asset path
@@ -3,6 +3,8 @@ use crate::{
     GltfMeshExtras, GltfNode, GltfSceneExtras, GltfSkin,
 };
 
+#[cfg(feature = "bevy_animation")]
+use crate::GltfAnimationEvent;
 #[cfg(feature = "bevy_animation")]
 use bevy_animation::{AnimationTarget, AnimationTargetId};
 use bevy_asset::{
@@ -328,6 +330,40 @@ async fn load_gltf<'a, 'b, 'c>(
                     );
                 }
             }
+            if let Some(extras) = animation.extras().as_ref() {
+                match serde_json::from_str::<AnimationEventExtras>(extras.get()) {
+                    Ok(extras) => {
+                        for event in extras.events {
+                            let gltf_event = GltfAnimationEvent {
+                                name: event.name,
+                                value: event.value.map(|value| value.to_string()),
+                            };
+                            match event.node {
+                                None => animation_clip.add_event(event.time, gltf_event),
+                                Some(node) => {
+                                    if let Some((root_index, path)) = paths.get(&node) {
+                                        animation_roots.insert(*root_index);
+                                        animation_clip.add_event_to_target(
+                                            AnimationTargetId::from_names(path.iter()),
+                                            event.time,
+                                            gltf_event,
+                                        );
+                                    } else {
+                                        warn!(
+                                            "Animation event ignored for node {}: part of its hierarchy is missing a name",
+                                            node
+                                        );
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    Err(err) => warn!(
+                        "Animation events ignored for animation {}: invalid extras: {err}",
+                        animation.index()
+                    ),
+                }
+            }
             let handle = load_context.add_labeled_asset(
                 GltfAssetLabel::Animation(animation.index()).to_string(),
                 animation_clip,
@@ -1878,6 +1914,27 @@ struct MorphTargetNames {
     pub target_names: Vec<String>,
 }
 
+/// The animation events listed in the extras of a glTF animation.
+///
+/// See [`GltfAnimationEvent`](crate::GltfAnimationEvent) for the format.
+#[cfg(feature = "bevy_animation")]
+#[derive(Deserialize)]
+struct AnimationEventExtras {
+    #[serde(default)]
+    events: Vec<AnimationEventExtra>,
+}
+
+#[cfg(feature = "bevy_animation")]
+#[derive(Deserialize)]
+struct AnimationEventExtra {
+    time: f32,
+    name: String,
+    #[serde(default)]
+    node: Option<usize>,
+    #[serde(default)]
+    value: Option<Value>,
+}
+
 // A helper structure for `load_node` that contains information about the
 // nearest ancestor animation root.
 #[cfg(feature = "bevy_animation")]
@@ -0,0 +1,350 @@
+//! Easing functions, which map the linear progress of a transition to eased
+//! progress over the [unit interval].
+//!
+//! [unit interval]: Interval::UNIT
+
+use crate::{
+    cubic_splines::CubicSegment,
+    curve::{Curve, Interval},
+    ops::{self, FloatPow},
+    Vec2,
+};
+use std::f32::consts::{FRAC_PI_2, PI, TAU};
+
+#[cfg(feature = "bevy_reflect")]
+use bevy_reflect::Reflect;
+
+/// A common easing function, as a [`Curve<f32>`] over the [unit interval].
+///
+/// Each function maps linear progress from 0 to 1 to eased progress, which
+/// starts at 0 and ends at 1, but may overshoot in between. See
+/// [easings.net](https://easings.net) for plots of most of these.
+///
+/// For custom cubic Bézier easing, [`CubicSegment::new_bezier`] also
+/// implements [`Curve<f32>`].
+///
+/// [unit interval]: Interval::UNIT
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "bevy_reflect", derive(Reflect), reflect(Debug, PartialEq))]
+pub enum EaseFunction {
+    /// `f(t) = t`
+    Linear,
+
+    /// `f(t) = t²`
+    QuadraticIn,
+    /// `f(t) = -(t * (t - 2.0))`
+    QuadraticOut,
+    /// Behaves as `EaseFunction::QuadraticIn` for t < 0.5 and as `EaseFunction::QuadraticOut` for t >= 0.5
+    QuadraticInOut,
+
+    /// `f(t) = t³`
+    CubicIn,
+    /// `f(t) = (t - 1.0)³ + 1.0`
+    CubicOut,
+    /// Behaves as `EaseFunction::CubicIn` for t < 0.5 and as `EaseFunction::CubicOut` for t >= 0.5
+    CubicInOut,
+
+    /// `f(t) = t⁴`
+    QuarticIn,
+    /// `f(t) = (t - 1.0)³ * (1.0 - t) + 1.0`
+    QuarticOut,
+    /// Behaves as `EaseFunction::QuarticIn` for t < 0.5 and as `EaseFunction::QuarticOut` for t >= 0.5
+    QuarticInOut,
+
+    /// `f(t) = t⁵`
+    QuinticIn,
+    /// `f(t) = (t - 1.0)⁵ + 1.0`
+    QuinticOut,
+    /// Behaves as `EaseFunction::QuinticIn` for t < 0.5 and as `EaseFunction::QuinticOut` for t >= 0.5
+    QuinticInOut,
+
+    /// `f(t) = 1.0 - cos(t * π / 2.0)`
+    SineIn,
+    /// `f(t) = sin(t * π / 2.0)`
+    SineOut,
+    /// Behaves as `EaseFunction::SineIn` for t < 0.5 and as `EaseFunction::SineOut` for t >= 0.5
+    SineInOut,
+
+    /// `f(t) = 1.0 - sqrt(1.0 - t²)`
+    CircularIn,
+    /// `f(t) = sqrt((2.0 - t) * t)`
+    CircularOut,
+    /// Behaves as `EaseFunction::CircularIn` for t < 0.5 and as `EaseFunction::CircularOut` for t >= 0.5
+    CircularInOut,
+
+    /// `f(t) = 2.0^(10.0 * (t - 1.0))`
+    ExponentialIn,
+    /// `f(t) = 1.0 - 2.0^(-10.0 * t)`
+    ExponentialOut,
+    /// Behaves as `EaseFunction::ExponentialIn` for t < 0.5 and as `EaseFunction::ExponentialOut` for t >= 0.5
+    ExponentialInOut,
+
+    /// `f(t) = -2.0^(10.0 * t - 10.0) * sin((t * 10.0 - 10.75) * 2.0 * π / 3.0)`
+    ElasticIn,
+    /// `f(t) = 2.0^(-10.0 * t) * sin((t * 10.0 - 0.75) * 2.0 * π / 3.0) + 1.0`
+    ElasticOut,
+    /// Behaves as `EaseFunction::ElasticIn` for t < 0.5 and as `EaseFunction::ElasticOut` for t >= 0.5
+    ElasticInOut,
+
+    /// `f(t) = 2.70158 * t³ - 1.70158 * t²`
+    BackIn,
+    /// `f(t) = 1.0 + 2.70158 * (t - 1.0)³ + 1.70158 * (t - 1.0)²`
+    BackOut,
+    /// Behaves as `EaseFunction::BackIn` for t < 0.5 and as `EaseFunction::BackOut` for t >= 0.5
+    BackInOut,
+
+    /// Bouncy at the start!
+    BounceIn,
+    /// Bouncy at the end!
+    BounceOut,
+    /// Behaves as `EaseFunction::BounceIn` for t < 0.5 and as `EaseFunction::BounceOut` for t >= 0.5
+    BounceInOut,
+
+    /// Jumps in `n` equal steps, at the end of each step, so that
+    /// `f(t) = floor(t * n) / n`.
+    Steps(usize),
+
+    /// A damped spring that oscillates around 1 with the given angular
+    /// frequency: `f(t) = 1.0 - (1.0 - t)² * (2.0 * sin(ω * t) / ω + cos(ω * t))`
+    ///
+    /// With an angular frequency of 0, the spring doesn't oscillate:
+    /// `f(t) = 1.0 - (1.0 - t)² * (2.0 * t + 1.0)`
+    Elastic(f32),
+}
+
+impl EaseFunction {
+    /// Evaluates this easing function at `t`, which is clamped to the unit
+    /// interval.
+    pub fn ease(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            EaseFunction::Linear => t,
+
+            EaseFunction::QuadraticIn => t.squared(),
+            EaseFunction::QuadraticOut => 1.0 - (1.0 - t).squared(),
+            EaseFunction::QuadraticInOut => in_out(t, FloatPow::squared),
+
+            EaseFunction::CubicIn => t.cubed(),
+            EaseFunction::CubicOut => 1.0 - (1.0 - t).cubed(),
+            EaseFunction::CubicInOut => in_out(t, FloatPow::cubed),
+
+            EaseFunction::QuarticIn => t.squared().squared(),
+            EaseFunction::QuarticOut => 1.0 - (1.0 - t).squared().squared(),
+            EaseFunction::QuarticInOut => in_out(t, |t| t.squared().squared()),
+
+            EaseFunction::QuinticIn => quintic(t),
+            EaseFunction::QuinticOut => 1.0 - quintic(1.0 - t),
+            EaseFunction::QuinticInOut => in_out(t, quintic),
+
+            EaseFunction::SineIn => 1.0 - ops::cos(t * FRAC_PI_2),
+            EaseFunction::SineOut => ops::sin(t * FRAC_PI_2),
+            EaseFunction::SineInOut => -(ops::cos(PI * t) - 1.0) / 2.0,
+
+            EaseFunction::CircularIn => circular(t),
+            EaseFunction::CircularOut => 1.0 - circular(1.0 - t),
+            EaseFunction::CircularInOut => in_out(t, circular),
+
+            EaseFunction::ExponentialIn => exponential(t),
+            EaseFunction::ExponentialOut => 1.0 - exponential(1.0 - t),
+            EaseFunction::ExponentialInOut => in_out(t, exponential),
+
+            EaseFunction::ElasticIn => elastic(t),
+            EaseFunction::ElasticOut => 1.0 - elastic(1.0 - t),
+            EaseFunction::ElasticInOut => in_out(t, elastic),
+
+            EaseFunction::BackIn => back(t),
+            EaseFunction::BackOut => 1.0 - back(1.0 - t),
+            EaseFunction::BackInOut => in_out(t, back),
+
+            EaseFunction::BounceIn => 1.0 - bounce_out(1.0 - t),
+            EaseFunction::BounceOut => bounce_out(t),
+            EaseFunction::BounceInOut => in_out(t, |t| 1.0 - bounce_out(1.0 - t)),
+
+            EaseFunction::Steps(steps) => {
+                let steps = steps.max(1) as f32;
+                (t * steps).floor() / steps
+            }
+
+            EaseFunction::Elastic(omega) => {
+                // `sin(ω * t) / ω` tends to `t` as `ω` approaches 0.
+                let sine = if omega == 0.0 {
+                    t
+                } else {
+                    ops::sin(omega * t) / omega
+                };
+                1.0 - (1.0 - t).squared() * (2.0 * sine + ops::cos(omega * t))
+            }
+        }
+    }
+}
+
+impl Curve<f32> for EaseFunction {
+    #[inline]
+    fn domain(&self) -> Interval {
+        Interval::UNIT
+    }
+
+    #[inline]
+    fn sample_unchecked(&self, t: f32) -> f32 {
+        self.ease(t)
+    }
+}
+
+impl Curve<f32> for CubicSegment<Vec2> {
+    #[inline]
+    fn domain(&self) -> Interval {
+        Interval::UNIT
+    }
+
+    #[inline]
+    fn sample_unchecked(&self, t: f32) -> f32 {
+        self.ease(t)
+    }
+}
+
+/// Builds the in-out variant of an easing function from its in variant, by
+/// easing in over the first half and mirroring it over the second half.
+#[inline]
+fn in_out(t: f32, ease_in: impl Fn(f32) -> f32) -> f32 {
+    if t < 0.5 {
+        ease_in(2.0 * t) / 2.0
+    } else {
+        1.0 - ease_in(2.0 - 2.0 * t) / 2.0
+    }
+}
+
+#[inline]
+fn quintic(t: f32) -> f32 {
+    t.squared().squared() * t
+}
+
+#[inline]
+fn circular(t: f32) -> f32 {
+    1.0 - (1.0 - t.squared()).sqrt()
+}
+
+#[inline]
+fn exponential(t: f32) -> f32 {
+    if t <= 0.0 {
+        0.0
+    } else {
+        ops::exp2(10.0 * t - 10.0)
+    }
+}
+
+#[inline]
+fn elastic(t: f32) -> f32 {
+    if t <= 0.0 || t >= 1.0 {
+        t
+    } else {
+        -ops::exp2(10.0 * t - 10.0) * ops::sin((t * 10.0 - 10.75) * TAU / 3.0)
+    }
+}
+
+#[inline]
+fn back(t: f32) -> f32 {
+    const C1: f32 = 1.70158;
+    (C1 + 1.0) * t.cubed() - C1 * t.squared()
+}
+
+#[inline]
+fn bounce_out(t: f32) -> f32 {
+    const N1: f32 = 7.5625;
+    const D1: f32 = 2.75;
+    if t < 1.0 / D1 {
+        N1 * t.squared()
+    } else if t < 2.0 / D1 {
+        N1 * (t - 1.5 / D1).squared() + 0.75
+    } else if t < 2.5 / D1 {
+        N1 * (t - 2.25 / D1).squared() + 0.9375
+    } else {
+        N1 * (t - 2.625 / D1).squared() + 0.984375
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::EaseFunction;
+    use crate::{cubic_splines::CubicSegment, curve::Curve};
+
+    const FUNCTIONS: [EaseFunction; 32] = [
+        EaseFunction::Linear,
+        EaseFunction::QuadraticIn,
+        EaseFunction::QuadraticOut,
+        EaseFunction::QuadraticInOut,
+        EaseFunction::CubicIn,
+        EaseFunction::CubicOut,
+        EaseFunction::CubicInOut,
+        EaseFunction::QuarticIn,
+        EaseFunction::QuarticOut,
+        EaseFunction::QuarticInOut,
+        EaseFunction::QuinticIn,
+        EaseFunction::QuinticOut,
+        EaseFunction::QuinticInOut,
+        EaseFunction::SineIn,
+        EaseFunction::SineOut,
+        EaseFunction::SineInOut,
+        EaseFunction::CircularIn,
+        EaseFunction::CircularOut,
+        EaseFunction::CircularInOut,
+        EaseFunction::ExponentialIn,
+        EaseFunction::ExponentialOut,
+        EaseFunction::ExponentialInOut,
+        EaseFunction::ElasticIn,
+        EaseFunction::ElasticOut,
+        EaseFunction::ElasticInOut,
+        EaseFunction::BackIn,
+        EaseFunction::BackOut,
+        EaseFunction::BackInOut,
+        EaseFunction::BounceIn,
+        EaseFunction::BounceOut,
+        EaseFunction::BounceInOut,
+        EaseFunction::Steps(4),
+    ];
+
+    #[test]
+    fn ease_functions_start_and_end() {
+        for function in FUNCTIONS {
+            assert!(function.sample_clamped(0.0).abs() < 1e-3, "{function:?}");
+            assert!(
+                (function.sample_clamped(1.0) - 1.0).abs() < 1e-3,
+                "{function:?}"
+            );
+        }
+        assert!((EaseFunction::Elastic(30.0).sample_clamped(1.0) - 1.0).abs() < 1e-6);
+        assert_eq!(EaseFunction::Elastic(0.0).sample_clamped(0.0), 0.0);
+        assert_eq!(EaseFunction::Elastic(0.0).sample_clamped(1.0), 1.0);
+    }
+
+    #[test]
+    fn in_out_functions_are_symmetric() {
+        for function in FUNCTIONS {
+            let name = format!("{function:?}");
+            if !name.ends_with("InOut") {
+                continue;
+            }
+            assert!((function.ease(0.5) - 0.5).abs() < 1e-3, "{name}");
+            for t in [0.1, 0.2, 0.3, 0.4] {
+                let sum = function.ease(t) + function.ease(1.0 - t);
+                assert!((sum - 1.0).abs() < 1e-4, "{name} at {t}");
+            }
+        }
+    }
+
+    #[test]
+    fn ease_function_values() {
+        assert_eq!(EaseFunction::QuadraticIn.ease(0.5), 0.25);
+        assert_eq!(EaseFunction::CubicOut.ease(0.5), 0.875);
+        assert_eq!(EaseFunction::QuinticIn.ease(0.5), 0.03125);
+        assert_eq!(EaseFunction::Steps(4).ease(0.3), 0.25);
+        assert_eq!(EaseFunction::Steps(4).ease(0.99), 0.75);
+        assert!(EaseFunction::BackIn.ease(0.2) < 0.0);
+        assert!(EaseFunction::ElasticOut.ease(0.2) > 1.0);
+        assert!((EaseFunction::BounceOut.ease(0.5) - 0.765625).abs() < 1e-6);
+        assert_eq!(EaseFunction::Elastic(0.0).ease(0.5), 0.5);
+
+        let ease_in_out = CubicSegment::new_bezier((0.25, 0.1), (0.25, 1.0));
+        assert!((ease_in_out.sample_clamped(0.5) - ease_in_out.ease(0.5)).abs() < 1e-6);
+    }
+}
@@ -1,10 +1,12 @@
 //! The [`Curve`] trait, used to describe curves in a number of different domains. This module also
 //! contains the [`Interval`] type, along with a selection of core data structures used to back
-//! curves that are interpolated from samples.
+//! curves that are interpolated from samples, and the [`EaseFunction`] easing curves.
 
 pub mod cores;
+pub mod easing;
 pub mod interval;
 
+pub use easing::EaseFunction;
 pub use interval::{interval, Interval};
 use itertools::Itertools;
 
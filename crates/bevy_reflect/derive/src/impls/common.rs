@@ -115,6 +115,15 @@ pub fn common_partial_reflect_methods(
                 }
             })
         });
+    let clone_fn = meta.from_reflect().should_auto_derive().then(|| {
+        quote! {
+            #[inline]
+            fn reflect_clone(&self) -> #FQOption<#FQBox<dyn #bevy_reflect_path::Reflect>> {
+                <Self as #bevy_reflect_path::FromReflect>::from_reflect(self)
+                    .map(|value| #FQBox::new(value) as #FQBox<dyn #bevy_reflect_path::Reflect>)
+            }
+        }
+    });
 
     quote! {
         #[inline]
@@ -154,5 +163,7 @@ pub fn common_partial_reflect_methods(
         #partial_eq_fn
 
         #debug_fn
+
+        #clone_fn
     }
 }
@@ -512,6 +512,29 @@ pub fn derive_type_path(input: TokenStream) -> TokenStream {
 /// assert_eq!("Hello, World!", reflected_my_trait.print());
 /// ```
 ///
+/// # Trait Objects
+///
+/// Using `#[reflect_trait(trait_object)]` additionally makes `Box<dyn MyTrait>` reflectable,
+/// so it can be used as a field of a reflected type and serialized along with the type path
+/// of its contents.
+/// This requires the trait to have `Reflect` as a supertrait,
+/// and the contents are cloned through their `FromReflect` implementation.
+///
+/// When deserializing, the type of the contents must be registered along with `ReflectMyTrait`.
+///
+/// ```ignore (bevy_reflect is not accessible from this crate)
+/// # use bevy_reflect_derive::{Reflect, reflect_trait};
+/// #[reflect_trait(trait_object)]
+/// trait Shape: Reflect {
+///   fn area(&self) -> f32;
+/// }
+///
+/// #[derive(Reflect)]
+/// struct Drawing {
+///   shapes: Vec<Box<dyn Shape>>,
+/// }
+/// ```
+///
 /// [object-safe]: https://doc.rust-lang.org/reference/items/traits.html#object-safety
 #[proc_macro_attribute]
 pub fn reflect_trait(args: TokenStream, input: TokenStream) -> TokenStream {
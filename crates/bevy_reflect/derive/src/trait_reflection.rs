@@ -3,8 +3,37 @@ use bevy_macro_utils::{
     BevyManifest,
 };
 use proc_macro::TokenStream;
-use quote::quote;
-use syn::{parse::Parse, parse_macro_input, Attribute, ItemTrait, Token};
+use quote::{format_ident, quote};
+use syn::{parse::Parse, parse_macro_input, parse_quote, Attribute, Ident, ItemTrait, Token};
+
+/// The arguments passed to the `#[reflect_trait]` attribute.
+#[derive(Default)]
+struct TraitArgs {
+    /// Whether `Box<dyn Trait>` should be reflectable (`#[reflect_trait(trait_object)]`).
+    trait_object: bool,
+}
+
+impl Parse for TraitArgs {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let mut args = TraitArgs::default();
+        while !input.is_empty() {
+            let ident: Ident = input.parse()?;
+            if ident == "trait_object" {
+                args.trait_object = true;
+            } else {
+                return Err(syn::Error::new(
+                    ident.span(),
+                    "unknown argument, expected `trait_object`",
+                ));
+            }
+
+            if !input.is_empty() {
+                input.parse::<Token![,]>()?;
+            }
+        }
+        Ok(args)
+    }
+}
 
 pub(crate) struct TraitInfo {
     item_trait: ItemTrait,
@@ -28,14 +57,46 @@ impl Parse for TraitInfo {
 ///
 /// This generates a struct that takes the form `ReflectMyTrait`. An instance of this struct can then be
 /// used to perform the conversion.
-pub(crate) fn reflect_trait(_args: &TokenStream, input: TokenStream) -> TokenStream {
-    let trait_info = parse_macro_input!(input as TraitInfo);
-    let item_trait = &trait_info.item_trait;
-    let trait_ident = &item_trait.ident;
-    let trait_vis = &item_trait.vis;
-    let reflect_trait_ident = crate::utility::get_reflect_ident(&item_trait.ident.to_string());
+///
+/// With the `trait_object` argument, this also allows `Box<dyn MyTrait>` to be reflected.
+pub(crate) fn reflect_trait(args: &TokenStream, input: TokenStream) -> TokenStream {
+    let args = args.clone();
+    let args = parse_macro_input!(args as TraitArgs);
+    let mut trait_info = parse_macro_input!(input as TraitInfo);
+    let trait_ident = trait_info.item_trait.ident.clone();
+    let trait_vis = trait_info.item_trait.vis.clone();
+    let reflect_trait_ident = crate::utility::get_reflect_ident(&trait_ident.to_string());
     let bevy_reflect_path = BevyManifest::default().get_path("bevy_reflect");
 
+    let trait_object_impls = if args.trait_object {
+        if !trait_info.item_trait.generics.params.is_empty() {
+            return syn::Error::new_spanned(
+                &trait_info.item_trait.generics,
+                "`trait_object` is not supported for generic traits",
+            )
+            .into_compile_error()
+            .into();
+        }
+
+        let clone_trait_ident = format_ident!("__Reflect{}TraitObject", trait_ident);
+        let item_trait = &mut trait_info.item_trait;
+        item_trait.colon_token.get_or_insert_with(Default::default);
+        item_trait
+            .supertraits
+            .push(parse_quote!(#clone_trait_ident));
+
+        Some(impl_trait_object(
+            &trait_ident,
+            &trait_vis,
+            &clone_trait_ident,
+            &reflect_trait_ident,
+            &bevy_reflect_path,
+        ))
+    } else {
+        None
+    };
+    let item_trait = &trait_info.item_trait;
+
     let struct_doc = format!(
         " A type generated by the #[reflect_trait] macro for the `{trait_ident}` trait.\n\n This allows casting from `dyn Reflect` to `dyn {trait_ident}`.",
     );
@@ -92,5 +153,104 @@ pub(crate) fn reflect_trait(_args: &TokenStream, input: TokenStream) -> TokenStr
                 }
             }
         }
+
+        #trait_object_impls
     })
 }
+
+/// Generates the impls required to reflect `Box<dyn Trait>`.
+///
+/// Since a `dyn Trait` can't be cloned directly, a hidden supertrait is added to the trait
+/// which clones the underlying value through its `FromReflect` implementation.
+fn impl_trait_object(
+    trait_ident: &Ident,
+    trait_vis: &syn::Visibility,
+    clone_trait_ident: &Ident,
+    reflect_trait_ident: &Ident,
+    bevy_reflect_path: &syn::Path,
+) -> proc_macro2::TokenStream {
+    quote! {
+        #[doc(hidden)]
+        #trait_vis trait #clone_trait_ident {
+            #[doc(hidden)]
+            fn __reflect_clone_boxed(&self) -> #FQOption<#FQBox<dyn #trait_ident>>;
+        }
+
+        impl<T: #trait_ident> #clone_trait_ident for T {
+            fn __reflect_clone_boxed(&self) -> #FQOption<#FQBox<dyn #trait_ident>> {
+                let value = #bevy_reflect_path::PartialReflect::reflect_clone(self)?;
+                <dyn #bevy_reflect_path::Reflect>::downcast::<T>(value)
+                    .ok()
+                    .map(|value| value as #FQBox<dyn #trait_ident>)
+            }
+        }
+
+        impl #bevy_reflect_path::TypePath for dyn #trait_ident {
+            fn type_path() -> &'static str {
+                ::core::concat!("dyn ", ::core::module_path!(), "::", ::core::stringify!(#trait_ident))
+            }
+
+            fn short_type_path() -> &'static str {
+                ::core::concat!("dyn ", ::core::stringify!(#trait_ident))
+            }
+        }
+
+        impl #bevy_reflect_path::PartialReflectTraitObject for dyn #trait_ident {
+            fn as_partial(&self) -> &dyn #bevy_reflect_path::PartialReflect {
+                #bevy_reflect_path::PartialReflect::as_partial_reflect(self)
+            }
+
+            fn as_partial_mut(&mut self) -> &mut dyn #bevy_reflect_path::PartialReflect {
+                #bevy_reflect_path::PartialReflect::as_partial_reflect_mut(self)
+            }
+
+            fn into_partial(self: #FQBox<Self>) -> #FQBox<dyn #bevy_reflect_path::PartialReflect> {
+                #bevy_reflect_path::PartialReflect::into_partial_reflect(self)
+            }
+
+            fn clone_boxed(&self) -> #FQOption<#FQBox<Self>> {
+                #clone_trait_ident::__reflect_clone_boxed(self)
+            }
+
+            fn from_partial(
+                value: #FQBox<dyn #bevy_reflect_path::PartialReflect>,
+                registry: #FQOption<&#bevy_reflect_path::TypeRegistry>,
+            ) -> #FQResult<#FQBox<Self>, #FQBox<dyn #bevy_reflect_path::PartialReflect>> {
+                let value = #bevy_reflect_path::PartialReflect::try_into_reflect(value)?;
+                <Self as #bevy_reflect_path::ReflectTraitObject>::from_reflect(value, registry)
+                    .map_err(#bevy_reflect_path::PartialReflect::into_partial_reflect)
+            }
+        }
+
+        impl #bevy_reflect_path::ReflectTraitObject for dyn #trait_ident {
+            fn as_reflect(&self) -> &dyn #bevy_reflect_path::Reflect {
+                #bevy_reflect_path::Reflect::as_reflect(self)
+            }
+
+            fn as_reflect_mut(&mut self) -> &mut dyn #bevy_reflect_path::Reflect {
+                #bevy_reflect_path::Reflect::as_reflect_mut(self)
+            }
+
+            fn into_reflect(self: #FQBox<Self>) -> #FQBox<dyn #bevy_reflect_path::Reflect> {
+                #bevy_reflect_path::Reflect::into_reflect(self)
+            }
+
+            fn from_reflect(
+                value: #FQBox<dyn #bevy_reflect_path::Reflect>,
+                registry: #FQOption<&#bevy_reflect_path::TypeRegistry>,
+            ) -> #FQResult<#FQBox<Self>, #FQBox<dyn #bevy_reflect_path::Reflect>> {
+                let reflect_trait = value
+                    .get_represented_type_info()
+                    .zip(registry)
+                    .and_then(|(info, registry)| {
+                        registry.get_type_data::<#reflect_trait_ident>(info.type_id())
+                    });
+                let #FQOption::Some(reflect_trait) = reflect_trait else {
+                    return #FQResult::Err(value);
+                };
+
+                reflect_trait.get_boxed(value)
+            }
+        }
+    }
+}
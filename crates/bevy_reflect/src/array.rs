@@ -316,24 +316,19 @@ impl Array for DynamicArray {
     }
 }
 
-impl FromIterator<Box<dyn PartialReflect>> for DynamicArray {
-    fn from_iter<I: IntoIterator<Item = Box<dyn PartialReflect>>>(values: I) -> Self {
+impl<T: PartialReflect> FromIterator<T> for DynamicArray {
+    fn from_iter<I: IntoIterator<Item = T>>(values: I) -> Self {
         Self {
             represented_type: None,
-            values: values.into_iter().collect::<Vec<_>>().into_boxed_slice(),
+            values: values
+                .into_iter()
+                .map(|value| Box::new(value).into_partial_reflect())
+                .collect::<Vec<_>>()
+                .into_boxed_slice(),
         }
     }
 }
 
-impl<T: PartialReflect> FromIterator<T> for DynamicArray {
-    fn from_iter<I: IntoIterator<Item = T>>(values: I) -> Self {
-        values
-            .into_iter()
-            .map(|value| Box::new(value).into_partial_reflect())
-            .collect()
-    }
-}
-
 impl IntoIterator for DynamicArray {
     type Item = Box<dyn PartialReflect>;
     type IntoIter = std::vec::IntoIter<Self::Item>;
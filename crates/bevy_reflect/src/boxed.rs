@@ -0,0 +1,695 @@
+use std::any::Any;
+
+use crate::{
+    serde::Serializable,
+    utility::{GenericTypeInfoCell, GenericTypePathCell, NonGenericTypeInfoCell},
+    ApplyError, FromReflect, FromType, GetTypeRegistration, MaybeTyped, PartialReflect, Reflect,
+    ReflectFromReflect, ReflectKind, ReflectMut, ReflectOwned, ReflectRef, TypeInfo, TypePath,
+    TypeRegistration, TypeRegistry, Typed, ValueInfo,
+};
+
+/// A trait object type which can be reflected through a [`Box`].
+///
+/// This trait is implemented for `dyn PartialReflect`, and through [`ReflectTraitObject`],
+/// for `dyn Reflect` and any user trait marked with [`#[reflect_trait(trait_object)]`](crate::reflect_trait).
+/// Any `Box<T>` where `T` implements this trait implements [`PartialReflect`],
+/// so such boxes can be used as fields of reflected types.
+///
+/// A reflected box is transparent: all of its reflection methods forward to the value it contains.
+/// This means that a `Box<dyn Reflect>` containing a struct will report the struct's type path
+/// and type info, and can be downcast to the struct.
+/// The box itself can only be accessed through [`PartialReflect::as_reflect_box`],
+/// and its registration only holds the [`ReflectBox`] type data.
+///
+/// When serialized through the [`serde`] module, boxed values are written with the
+/// [type path] of their contents, so they can be deserialized without knowing their type.
+/// This requires the contained type to be registered, along with [`ReflectFromReflect`]
+/// and, for user traits, the trait's generated type data (e.g. `ReflectMyTrait`).
+///
+/// [`serde`]: crate::serde
+/// [type path]: TypePath::type_path
+pub trait PartialReflectTraitObject: TypePath + Send + Sync {
+    /// Returns the trait object as a [`PartialReflect`] trait object.
+    fn as_partial(&self) -> &dyn PartialReflect;
+
+    /// Returns the trait object as a mutable [`PartialReflect`] trait object.
+    fn as_partial_mut(&mut self) -> &mut dyn PartialReflect;
+
+    /// Casts the boxed trait object to a boxed [`PartialReflect`] trait object.
+    fn into_partial(self: Box<Self>) -> Box<dyn PartialReflect>;
+
+    /// Clones the trait object into a new box.
+    ///
+    /// Returns `None` if the contained type cannot be [cloned](PartialReflect::reflect_clone).
+    fn clone_boxed(&self) -> Option<Box<Self>>;
+
+    /// Attempts to cast the given value to this trait object.
+    ///
+    /// User traits require the `registry` to look up the trait's type data,
+    /// so `None` will cause them to always fail.
+    fn from_partial(
+        value: Box<dyn PartialReflect>,
+        registry: Option<&TypeRegistry>,
+    ) -> Result<Box<Self>, Box<dyn PartialReflect>>;
+}
+
+/// A trait object type whose contents always implement [`Reflect`].
+///
+/// This trait is implemented for `dyn Reflect` and any user trait marked with
+/// [`#[reflect_trait(trait_object)]`](crate::reflect_trait).
+/// Unlike `Box<dyn PartialReflect>`, which can hold dynamic values,
+/// any `Box<T>` where `T` implements this trait implements [`Reflect`] and [`FromReflect`].
+pub trait ReflectTraitObject: PartialReflectTraitObject {
+    /// Returns the trait object as a [`Reflect`] trait object.
+    fn as_reflect(&self) -> &dyn Reflect;
+
+    /// Returns the trait object as a mutable [`Reflect`] trait object.
+    fn as_reflect_mut(&mut self) -> &mut dyn Reflect;
+
+    /// Casts the boxed trait object to a boxed [`Reflect`] trait object.
+    fn into_reflect(self: Box<Self>) -> Box<dyn Reflect>;
+
+    /// Attempts to cast the given value to this trait object.
+    ///
+    /// User traits require the `registry` to look up the trait's type data,
+    /// so `None` will cause them to always fail.
+    fn from_reflect(
+        value: Box<dyn Reflect>,
+        registry: Option<&TypeRegistry>,
+    ) -> Result<Box<Self>, Box<dyn Reflect>>;
+}
+
+impl PartialReflectTraitObject for dyn Reflect {
+    fn as_partial(&self) -> &dyn PartialReflect {
+        self.as_partial_reflect()
+    }
+
+    fn as_partial_mut(&mut self) -> &mut dyn PartialReflect {
+        self.as_partial_reflect_mut()
+    }
+
+    fn into_partial(self: Box<Self>) -> Box<dyn PartialReflect> {
+        self.into_partial_reflect()
+    }
+
+    fn clone_boxed(&self) -> Option<Box<Self>> {
+        self.reflect_clone()
+    }
+
+    fn from_partial(
+        value: Box<dyn PartialReflect>,
+        _registry: Option<&TypeRegistry>,
+    ) -> Result<Box<Self>, Box<dyn PartialReflect>> {
+        value.try_into_reflect()
+    }
+}
+
+impl ReflectTraitObject for dyn Reflect {
+    fn as_reflect(&self) -> &dyn Reflect {
+        self
+    }
+
+    fn as_reflect_mut(&mut self) -> &mut dyn Reflect {
+        self
+    }
+
+    fn into_reflect(self: Box<Self>) -> Box<dyn Reflect> {
+        self
+    }
+
+    fn from_reflect(
+        value: Box<dyn Reflect>,
+        _registry: Option<&TypeRegistry>,
+    ) -> Result<Box<Self>, Box<dyn Reflect>> {
+        Ok(value)
+    }
+}
+
+impl PartialReflectTraitObject for dyn PartialReflect {
+    fn as_partial(&self) -> &dyn PartialReflect {
+        self
+    }
+
+    fn as_partial_mut(&mut self) -> &mut dyn PartialReflect {
+        self
+    }
+
+    fn into_partial(self: Box<Self>) -> Box<dyn PartialReflect> {
+        self
+    }
+
+    fn clone_boxed(&self) -> Option<Box<Self>> {
+        match self.reflect_clone() {
+            Some(value) => Some(value.into_partial_reflect()),
+            None => Some(self.clone_value()),
+        }
+    }
+
+    fn from_partial(
+        value: Box<dyn PartialReflect>,
+        _registry: Option<&TypeRegistry>,
+    ) -> Result<Box<Self>, Box<dyn PartialReflect>> {
+        Ok(value)
+    }
+}
+
+/// Type data for a reflected [`Box`] of a [`ReflectTraitObject`].
+///
+/// This is used by the [`serde`] module to box deserialized values.
+///
+/// [`serde`]: crate::serde
+#[derive(Clone)]
+pub struct ReflectBox {
+    box_value: fn(
+        Box<dyn PartialReflect>,
+        &TypeRegistry,
+    ) -> Result<Box<dyn PartialReflect>, Box<dyn PartialReflect>>,
+}
+
+impl ReflectBox {
+    /// Places the given value into the box type this was registered for.
+    ///
+    /// Dynamic values are first converted to their concrete type
+    /// using the [`ReflectFromReflect`] registered for the type they represent.
+    ///
+    /// Returns the value back if it cannot be stored in the box.
+    pub fn box_value(
+        &self,
+        value: Box<dyn PartialReflect>,
+        registry: &TypeRegistry,
+    ) -> Result<Box<dyn PartialReflect>, Box<dyn PartialReflect>> {
+        (self.box_value)(value, registry)
+    }
+}
+
+impl<T: PartialReflectTraitObject + ?Sized> FromType<Box<T>> for ReflectBox {
+    fn from_type() -> Self {
+        Self {
+            box_value: |value, registry| {
+                let concrete = if value.try_as_reflect().is_none() {
+                    value
+                        .get_represented_type_info()
+                        .and_then(|info| {
+                            registry.get_type_data::<ReflectFromReflect>(info.type_id())
+                        })
+                        .and_then(|from_reflect| from_reflect.from_reflect(value.as_ref()))
+                } else {
+                    None
+                };
+                let value = concrete
+                    .map(<dyn Reflect>::into_partial_reflect)
+                    .unwrap_or(value);
+
+                T::from_partial(value, Some(registry))
+                    .map(|boxed| Box::new(boxed) as Box<dyn PartialReflect>)
+            },
+        }
+    }
+}
+
+// Boxes report the type path of their contents through `DynamicTypePath`,
+// so that they are as transparent as the rest of their reflection methods.
+impl<T: PartialReflectTraitObject + ?Sized> TypePath for Box<T> {
+    fn type_path() -> &'static str {
+        static CELL: GenericTypePathCell = GenericTypePathCell::new();
+        CELL.get_or_insert::<Self, _>(|| format!("alloc::boxed::Box<{}>", T::type_path()))
+    }
+
+    fn short_type_path() -> &'static str {
+        static CELL: GenericTypePathCell = GenericTypePathCell::new();
+        CELL.get_or_insert::<Self, _>(|| format!("Box<{}>", T::short_type_path()))
+    }
+
+    fn type_ident() -> Option<&'static str> {
+        Some("Box")
+    }
+
+    fn crate_name() -> Option<&'static str> {
+        Some("alloc")
+    }
+
+    fn module_path() -> Option<&'static str> {
+        Some("alloc::boxed")
+    }
+
+    fn transparent_value(&self) -> Option<&dyn PartialReflect> {
+        Some((**self).as_partial())
+    }
+}
+
+impl<T: PartialReflectTraitObject + ?Sized> PartialReflect for Box<T> {
+    fn get_represented_type_info(&self) -> Option<&'static TypeInfo> {
+        (**self).as_partial().get_represented_type_info()
+    }
+
+    fn into_partial_reflect(self: Box<Self>) -> Box<dyn PartialReflect> {
+        T::into_partial(*self)
+    }
+
+    fn as_partial_reflect(&self) -> &dyn PartialReflect {
+        (**self).as_partial()
+    }
+
+    fn as_partial_reflect_mut(&mut self) -> &mut dyn PartialReflect {
+        (**self).as_partial_mut()
+    }
+
+    fn try_into_reflect(self: Box<Self>) -> Result<Box<dyn Reflect>, Box<dyn PartialReflect>> {
+        T::into_partial(*self).try_into_reflect()
+    }
+
+    fn try_as_reflect(&self) -> Option<&dyn Reflect> {
+        (**self).as_partial().try_as_reflect()
+    }
+
+    fn try_as_reflect_mut(&mut self) -> Option<&mut dyn Reflect> {
+        (**self).as_partial_mut().try_as_reflect_mut()
+    }
+
+    fn try_apply(&mut self, value: &dyn PartialReflect) -> Result<(), ApplyError> {
+        // Values of a different type replace the contents of the box entirely
+        let is_other_type = match (
+            self.get_represented_type_info(),
+            value.get_represented_type_info(),
+        ) {
+            (Some(info), Some(other)) => info.type_id() != other.type_id(),
+            _ => false,
+        };
+        if is_other_type {
+            if let Some(value) = box_from_partial(value) {
+                *self = value;
+                return Ok(());
+            }
+        }
+
+        (**self).as_partial_mut().try_apply(value)
+    }
+
+    fn reflect_kind(&self) -> ReflectKind {
+        (**self).as_partial().reflect_kind()
+    }
+
+    fn reflect_ref(&self) -> ReflectRef {
+        (**self).as_partial().reflect_ref()
+    }
+
+    fn reflect_mut(&mut self) -> ReflectMut {
+        (**self).as_partial_mut().reflect_mut()
+    }
+
+    fn reflect_owned(self: Box<Self>) -> ReflectOwned {
+        T::into_partial(*self).reflect_owned()
+    }
+
+    fn clone_value(&self) -> Box<dyn PartialReflect> {
+        (**self).as_partial().clone_value()
+    }
+
+    fn reflect_clone(&self) -> Option<Box<dyn Reflect>> {
+        (**self).as_partial().reflect_clone()
+    }
+
+    fn as_reflect_box(&self) -> Option<&dyn Any> {
+        Some(self)
+    }
+
+    fn reflect_hash(&self) -> Option<u64> {
+        (**self).as_partial().reflect_hash()
+    }
+
+    fn reflect_partial_eq(&self, value: &dyn PartialReflect) -> Option<bool> {
+        (**self).as_partial().reflect_partial_eq(value)
+    }
+
+    fn debug(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        (**self).as_partial().debug(f)
+    }
+
+    fn serializable(&self) -> Option<Serializable> {
+        (**self).as_partial().serializable()
+    }
+
+    fn is_dynamic(&self) -> bool {
+        (**self).as_partial().is_dynamic()
+    }
+}
+
+impl<T: ReflectTraitObject + ?Sized> Reflect for Box<T> {
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        T::into_reflect(*self).into_any()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        (**self).as_reflect().as_any()
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        (**self).as_reflect_mut().as_any_mut()
+    }
+
+    fn into_reflect(self: Box<Self>) -> Box<dyn Reflect> {
+        T::into_reflect(*self)
+    }
+
+    fn as_reflect(&self) -> &dyn Reflect {
+        (**self).as_reflect()
+    }
+
+    fn as_reflect_mut(&mut self) -> &mut dyn Reflect {
+        (**self).as_reflect_mut()
+    }
+
+    fn set(&mut self, value: Box<dyn Reflect>) -> Result<(), Box<dyn Reflect>> {
+        match T::from_reflect(value, None) {
+            Ok(value) => {
+                *self = value;
+                Ok(())
+            }
+            Err(value) => (**self).as_reflect_mut().set(value),
+        }
+    }
+}
+
+impl<T: ReflectTraitObject + ?Sized> FromReflect for Box<T> {
+    fn from_reflect(reflect: &dyn PartialReflect) -> Option<Self> {
+        box_from_partial(reflect)
+    }
+}
+
+/// Clones `value` into a `Box<T>`, or clones the box `value` is reflected through.
+fn box_from_partial<T: PartialReflectTraitObject + ?Sized>(
+    value: &dyn PartialReflect,
+) -> Option<Box<T>> {
+    if let Some(boxed) = value
+        .as_reflect_box()
+        .and_then(<dyn Any>::downcast_ref::<Box<T>>)
+    {
+        return (**boxed).clone_boxed();
+    }
+
+    let value = match value.reflect_clone() {
+        Some(value) => value.into_partial_reflect(),
+        None => value.clone_value(),
+    };
+    T::from_partial(value, None).ok()
+}
+
+// The static type info describes the box itself, which is only used for its registration
+// and as the declared type of fields. Box values report the type info of their contents.
+impl<T: ReflectTraitObject + ?Sized> Typed for Box<T> {
+    fn type_info() -> &'static TypeInfo {
+        static CELL: GenericTypeInfoCell = GenericTypeInfoCell::new();
+        CELL.get_or_insert::<Self, _>(|| TypeInfo::Value(ValueInfo::new::<Self>()))
+    }
+}
+
+impl<T: ReflectTraitObject + ?Sized> GetTypeRegistration for Box<T> {
+    fn get_type_registration() -> TypeRegistration {
+        // Boxes only register `ReflectBox`, since they reflect as their contents
+        // and other type data would cast or compare against the box type instead.
+        let mut registration = TypeRegistration::of::<Self>();
+        registration.insert::<ReflectBox>(FromType::<Self>::from_type());
+        registration
+    }
+}
+
+// `Box<dyn PartialReflect>` may hold a dynamic value, so it only implements `PartialReflect`,
+// and provides its type info and registration the same way as the dynamic types do.
+impl MaybeTyped for Box<dyn PartialReflect> {
+    fn maybe_type_info() -> Option<&'static TypeInfo> {
+        static CELL: NonGenericTypeInfoCell = NonGenericTypeInfoCell::new();
+        Some(CELL.get_or_set(|| TypeInfo::Value(ValueInfo::new::<Self>())))
+    }
+}
+
+impl GetTypeRegistration for Box<dyn PartialReflect> {
+    fn get_type_registration() -> TypeRegistration {
+        let mut registration = TypeRegistration::from_type_info(Self::maybe_type_info().unwrap());
+        registration.insert::<ReflectBox>(FromType::<Self>::from_type());
+        registration
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::serde::{ReflectSerializer, TypedReflectDeserializer, TypedReflectSerializer};
+    use crate::{self as bevy_reflect, DynamicTypePath};
+    use crate::{reflect_trait, DynamicStruct};
+    use serde::de::DeserializeSeed;
+
+    #[reflect_trait(trait_object)]
+    trait Shape: Reflect {
+        fn area(&self) -> f32;
+    }
+
+    #[derive(Reflect, Debug, PartialEq)]
+    #[reflect(Shape)]
+    struct Square(f32);
+
+    impl Shape for Square {
+        fn area(&self) -> f32 {
+            self.0 * self.0
+        }
+    }
+
+    #[derive(Reflect, Debug, PartialEq)]
+    #[reflect(Shape)]
+    struct Rect {
+        width: f32,
+        height: f32,
+    }
+
+    impl Shape for Rect {
+        fn area(&self) -> f32 {
+            self.width * self.height
+        }
+    }
+
+    #[derive(Reflect)]
+    struct Container {
+        value: Box<dyn Reflect>,
+        shapes: Vec<Box<dyn Shape>>,
+    }
+
+    #[derive(Reflect)]
+    #[reflect(from_reflect = false)]
+    struct PartialContainer {
+        partial: Box<dyn PartialReflect>,
+    }
+
+    fn registry() -> TypeRegistry {
+        let mut registry = TypeRegistry::default();
+        registry.register::<Container>();
+        registry.register::<PartialContainer>();
+        registry.register::<Square>();
+        registry.register::<Rect>();
+        registry.register::<i32>();
+        registry
+    }
+
+    #[test]
+    fn should_be_transparent() {
+        let value: Box<dyn Reflect> = Box::new(Square(2.0));
+
+        assert_eq!(
+            Some(Square::type_path()),
+            value.get_represented_type_info().map(TypeInfo::type_path)
+        );
+        assert_eq!(ReflectKind::TupleStruct, value.reflect_kind());
+        assert!((*value).as_reflect_box().is_none());
+
+        let boxed: &dyn PartialReflect = &value;
+        assert!(boxed.as_reflect_box().is_some());
+        assert_eq!(Some(&Square(2.0)), boxed.try_downcast_ref::<Square>());
+        assert_eq!(Some(true), boxed.reflect_partial_eq(&Square(2.0)));
+    }
+
+    #[test]
+    fn should_report_type_path_of_contents() {
+        let value: Box<dyn Reflect> = Box::new(Square(2.0));
+
+        assert_eq!(Square::type_path(), value.reflect_type_path());
+        let boxed: &dyn PartialReflect = &value;
+        assert_eq!(
+            boxed.get_represented_type_info().map(TypeInfo::type_path),
+            Some(boxed.reflect_type_path())
+        );
+        assert_eq!(Some("Square"), boxed.reflect_type_ident());
+
+        let shape: Box<dyn Shape> = Box::new(Rect {
+            width: 1.0,
+            height: 2.0,
+        });
+        let boxed: &dyn PartialReflect = &shape;
+        assert_eq!(Rect::type_path(), boxed.reflect_type_path());
+        assert_eq!(
+            boxed.try_as_reflect().map(|value| value.as_any().type_id()),
+            boxed.get_represented_type_info().map(TypeInfo::type_id)
+        );
+    }
+
+    #[test]
+    fn should_clone_with_from_reflect() {
+        let container = Container {
+            value: Box::new(123_i32),
+            shapes: vec![Box::new(Square(2.0))],
+        };
+
+        let cloned = Container::from_reflect(&container).unwrap();
+        assert_eq!(Some(&123), cloned.value.downcast_ref::<i32>());
+        assert_eq!(4.0, cloned.shapes[0].area());
+
+        let mut partial = PartialContainer {
+            partial: Box::new(0_i32),
+        };
+        partial.apply(&PartialContainer {
+            partial: Box::new(Rect {
+                width: 2.0,
+                height: 3.0,
+            }),
+        });
+        assert_eq!(
+            Some(&Rect {
+                width: 2.0,
+                height: 3.0
+            }),
+            partial.partial.try_downcast_ref::<Rect>()
+        );
+    }
+
+    #[test]
+    fn should_replace_contents_of_other_types() {
+        let mut value: Box<dyn Reflect> = Box::new(Square(2.0));
+
+        value.as_partial_reflect_mut().apply(&Square(3.0));
+        assert_eq!(Some(&Square(3.0)), value.downcast_ref::<Square>());
+
+        PartialReflect::apply(&mut value, &123_i32);
+        assert_eq!(Some(&123), value.downcast_ref::<i32>());
+
+        let mut patch = DynamicStruct::default();
+        patch.insert("value", Box::new(Square(1.0)) as Box<dyn Reflect>);
+        let mut container = Container {
+            value: Box::new(0_i32),
+            shapes: Vec::new(),
+        };
+        container.apply(&patch);
+        assert_eq!(Some(&Square(1.0)), container.value.downcast_ref::<Square>());
+    }
+
+    #[test]
+    fn should_set_contents() {
+        let mut value: Box<dyn Reflect> = Box::new(Square(2.0));
+        Reflect::set(&mut value, Box::new(123_i32)).unwrap();
+        assert_eq!(Some(&123), value.downcast_ref::<i32>());
+        assert!(Reflect::as_any(&value).is::<i32>());
+
+        // Without a registry, values can only be set to the type a trait object already holds
+        let mut shape: Box<dyn Shape> = Box::new(Square(2.0));
+        Reflect::set(&mut shape, Box::new(Square(3.0))).unwrap();
+        assert_eq!(9.0, shape.area());
+        let error = Reflect::set(&mut shape, Box::new(123_i32)).unwrap_err();
+        assert_eq!(Some(&123), error.downcast_ref::<i32>());
+    }
+
+    #[test]
+    fn should_serialize_with_type_path() {
+        let registry = registry();
+        let container = Container {
+            value: Box::new(123_i32),
+            shapes: vec![
+                Box::new(Square(2.0)),
+                Box::new(Rect {
+                    width: 2.0,
+                    height: 3.0,
+                }),
+            ],
+        };
+
+        let serializer = TypedReflectSerializer::new(&container, &registry);
+        let output = ron::to_string(&serializer).unwrap();
+        let expected = r#"(value:{"i32":123},shapes:[{"bevy_reflect::boxed::tests::Square":(2.0)},{"bevy_reflect::boxed::tests::Rect":(width:2.0,height:3.0)}])"#;
+        assert_eq!(expected, output);
+
+        // Dynamic representations of the container still tag their boxed fields
+        let dynamic = container.clone_value();
+        let output = ron::to_string(&TypedReflectSerializer::new(&*dynamic, &registry)).unwrap();
+        assert_eq!(expected, output);
+
+        // A box on its own is also tagged
+        let shape: Box<dyn Shape> = Box::new(Square(1.0));
+        let output = ron::to_string(&TypedReflectSerializer::new(&shape, &registry)).unwrap();
+        assert_eq!(r#"{"bevy_reflect::boxed::tests::Square":(1.0)}"#, output);
+        let output = ron::to_string(&ReflectSerializer::new(&shape, &registry)).unwrap();
+        assert_eq!(r#"{"bevy_reflect::boxed::tests::Square":(1.0)}"#, output);
+
+        let partial = PartialContainer {
+            partial: Box::new(Square(2.0)),
+        };
+        let output = ron::to_string(&TypedReflectSerializer::new(&partial, &registry)).unwrap();
+        assert_eq!(
+            r#"(partial:{"bevy_reflect::boxed::tests::Square":(2.0)})"#,
+            output
+        );
+    }
+
+    #[test]
+    fn should_deserialize_from_type_path() {
+        let registry = registry();
+        let input = r#"(value:{"i32":123},shapes:[{"bevy_reflect::boxed::tests::Square":(2.0)},{"bevy_reflect::boxed::tests::Rect":(width:2.0,height:3.0)}])"#;
+
+        let registration = registry.get(std::any::TypeId::of::<Container>()).unwrap();
+        let deserializer = TypedReflectDeserializer::new(registration, &registry);
+        let mut ron_deserializer = ron::de::Deserializer::from_str(input).unwrap();
+        let dynamic = deserializer.deserialize(&mut ron_deserializer).unwrap();
+
+        let container = Container::from_reflect(&*dynamic).unwrap();
+        assert_eq!(Some(&123), container.value.downcast_ref::<i32>());
+        let areas = container
+            .shapes
+            .iter()
+            .map(|shape| shape.area())
+            .collect::<Vec<_>>();
+        assert_eq!(vec![4.0, 6.0], areas);
+
+        let input = r#"(partial:{"bevy_reflect::boxed::tests::Square":(2.0)})"#;
+        let registration = registry
+            .get(std::any::TypeId::of::<PartialContainer>())
+            .unwrap();
+        let deserializer = TypedReflectDeserializer::new(registration, &registry);
+        let mut ron_deserializer = ron::de::Deserializer::from_str(input).unwrap();
+        let dynamic = deserializer.deserialize(&mut ron_deserializer).unwrap();
+
+        let mut partial = PartialContainer {
+            partial: Box::new(0_i32),
+        };
+        partial.apply(&*dynamic);
+        assert_eq!(
+            Some(&Square(2.0)),
+            partial.partial.try_downcast_ref::<Square>()
+        );
+    }
+
+    #[test]
+    fn should_fail_to_deserialize_unimplemented_trait() {
+        let registry = registry();
+        let input = r#"[{"i32":123}]"#;
+
+        let mut registry = registry;
+        registry.register::<Vec<Box<dyn Shape>>>();
+        let registration = registry
+            .get(std::any::TypeId::of::<Vec<Box<dyn Shape>>>())
+            .unwrap();
+        let deserializer = TypedReflectDeserializer::new(registration, &registry);
+        let mut ron_deserializer = ron::de::Deserializer::from_str(input).unwrap();
+        let error = deserializer
+            .deserialize(&mut ron_deserializer)
+            .unwrap_err()
+            .to_string();
+        assert!(
+            error.contains("cannot store a value of type `i32`"),
+            "{error}"
+        );
+    }
+}
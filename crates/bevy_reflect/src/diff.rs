@@ -0,0 +1,782 @@
+use crate::{
+    access::Access, ApplyError, Enum, List, Map, OffsetAccess, ParsedPath, PartialReflect,
+    ReflectKind, ReflectMut, ReflectPath, ReflectRef, Set, VariantType,
+};
+use std::borrow::Cow;
+use thiserror::Error;
+
+/// A single change between two reflected values.
+///
+/// Each change is addressed by the [`ParsedPath`] of the element it applies to,
+/// relative to the root value passed to [`diff`].
+#[derive(Debug)]
+pub enum DiffChange {
+    /// The element at `path` was replaced by `value`.
+    ///
+    /// This is produced for values that have no finer-grained representation
+    /// (such as primitives, or any type compared with [`PartialReflect::reflect_partial_eq`]),
+    /// and for elements whose type or kind changed.
+    Modified {
+        path: ParsedPath,
+        value: Box<dyn PartialReflect>,
+    },
+    /// The enum at `path` switched to a different variant.
+    ///
+    /// `value` holds the entire new enum value.
+    VariantChanged {
+        path: ParsedPath,
+        value: Box<dyn PartialReflect>,
+    },
+    /// `value` was inserted at `index` into the list at `path`.
+    ListInserted {
+        path: ParsedPath,
+        index: usize,
+        value: Box<dyn PartialReflect>,
+    },
+    /// The element at `index` was removed from the list at `path`.
+    ListRemoved { path: ParsedPath, index: usize },
+    /// `key` was inserted into the map at `path`, or its value was changed.
+    ///
+    /// Since paths cannot address map entries,
+    /// a change anywhere inside an entry's value replaces the whole value.
+    MapInserted {
+        path: ParsedPath,
+        key: Box<dyn PartialReflect>,
+        value: Box<dyn PartialReflect>,
+    },
+    /// `key` was removed from the map at `path`.
+    MapRemoved {
+        path: ParsedPath,
+        key: Box<dyn PartialReflect>,
+    },
+    /// `value` was inserted into the set at `path`.
+    SetInserted {
+        path: ParsedPath,
+        value: Box<dyn PartialReflect>,
+    },
+    /// `value` was removed from the set at `path`.
+    SetRemoved {
+        path: ParsedPath,
+        value: Box<dyn PartialReflect>,
+    },
+}
+
+impl DiffChange {
+    /// The path to the element this change applies to.
+    pub fn path(&self) -> &ParsedPath {
+        match self {
+            DiffChange::Modified { path, .. }
+            | DiffChange::VariantChanged { path, .. }
+            | DiffChange::ListInserted { path, .. }
+            | DiffChange::ListRemoved { path, .. }
+            | DiffChange::MapInserted { path, .. }
+            | DiffChange::MapRemoved { path, .. }
+            | DiffChange::SetInserted { path, .. }
+            | DiffChange::SetRemoved { path, .. } => path,
+        }
+    }
+
+    /// Applies this change to `target`.
+    pub fn apply(&self, target: &mut dyn PartialReflect) -> Result<(), ApplyDiffError> {
+        let path = self.path();
+        let element =
+            path.reflect_element_mut(target)
+                .map_err(|error| ApplyDiffError::InvalidPath {
+                    path: path.to_string(),
+                    message: error.to_string(),
+                })?;
+
+        match self {
+            DiffChange::Modified { value, .. } | DiffChange::VariantChanged { value, .. } => {
+                element.try_apply(value.as_ref())?;
+            }
+            DiffChange::ListInserted { index, value, .. } => {
+                let list = as_list(path, element)?;
+                if *index > list.len() {
+                    return Err(ApplyDiffError::IndexOutOfBounds {
+                        path: path.to_string(),
+                        index: *index,
+                        len: list.len(),
+                    });
+                }
+                list.insert(*index, value.clone_value());
+            }
+            DiffChange::ListRemoved { index, .. } => {
+                let list = as_list(path, element)?;
+                if *index >= list.len() {
+                    return Err(ApplyDiffError::IndexOutOfBounds {
+                        path: path.to_string(),
+                        index: *index,
+                        len: list.len(),
+                    });
+                }
+                list.remove(*index);
+            }
+            DiffChange::MapInserted { key, value, .. } => {
+                as_map(path, element)?.insert_boxed(key.clone_value(), value.clone_value());
+            }
+            DiffChange::MapRemoved { key, .. } => {
+                as_map(path, element)?.remove(key.as_ref()).ok_or_else(|| {
+                    ApplyDiffError::MissingEntry {
+                        path: path.to_string(),
+                    }
+                })?;
+            }
+            DiffChange::SetInserted { value, .. } => {
+                as_set(path, element)?.insert_boxed(value.clone_value());
+            }
+            DiffChange::SetRemoved { value, .. } => {
+                if !as_set(path, element)?.remove(value.as_ref()) {
+                    return Err(ApplyDiffError::MissingEntry {
+                        path: path.to_string(),
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The structural difference between two reflected values, as returned by [`diff`].
+///
+/// Changes are ordered so that applying them one after another with [`apply_diff`]
+/// turns the first value into the second.
+/// A diff can be serialized with [`ReflectDiffSerializer`].
+///
+/// [`ReflectDiffSerializer`]: crate::serde::ReflectDiffSerializer
+#[derive(Debug, Default)]
+pub struct ReflectDiff {
+    changes: Vec<DiffChange>,
+}
+
+impl ReflectDiff {
+    /// Creates a diff from a list of changes, which will be applied in order.
+    pub fn new(changes: Vec<DiffChange>) -> Self {
+        Self { changes }
+    }
+
+    /// The changes in this diff, in the order they are applied.
+    pub fn changes(&self) -> &[DiffChange] {
+        &self.changes
+    }
+
+    /// Consumes the diff, returning its changes.
+    pub fn into_changes(self) -> Vec<DiffChange> {
+        self.changes
+    }
+
+    /// Returns the number of changes in this diff.
+    pub fn len(&self) -> usize {
+        self.changes.len()
+    }
+
+    /// Returns true if the two compared values were equal.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Returns true if any change in this diff affects the element at `path`,
+    /// either directly, through one of its children, or through one of its parents.
+    ///
+    /// This is useful for marking modified fields in an inspector.
+    pub fn is_modified(&self, path: &ParsedPath) -> bool {
+        let path = accesses(path);
+        self.changes.iter().any(|change| {
+            let change = accesses(change.path());
+            path.clone().zip(change.clone()).all(|(a, b)| a == b)
+        })
+    }
+
+    /// Applies every change in this diff to `target`, in order.
+    ///
+    /// See [`apply_diff`].
+    pub fn apply(&self, target: &mut dyn PartialReflect) -> Result<(), ApplyDiffError> {
+        self.changes
+            .iter()
+            .try_for_each(|change| change.apply(target))
+    }
+}
+
+/// An error returned when applying a [`ReflectDiff`] to a value it does not fit.
+#[derive(Error, Debug)]
+pub enum ApplyDiffError {
+    #[error("cannot access `{path}`: {message}")]
+    /// The path of a change does not exist on the target.
+    InvalidPath { path: String, message: String },
+
+    #[error("expected a {expected} at `{path}` but found a {found}")]
+    /// A collection change targets an element of a different kind.
+    MismatchedKind {
+        path: String,
+        expected: ReflectKind,
+        found: ReflectKind,
+    },
+
+    #[error("index {index} is out of bounds for the list at `{path}` with length {len}")]
+    /// A list change targets an index past the end of the list.
+    IndexOutOfBounds {
+        path: String,
+        index: usize,
+        len: usize,
+    },
+
+    #[error("the removed entry does not exist in the collection at `{path}`")]
+    /// A map or set removal targets an entry that does not exist.
+    MissingEntry { path: String },
+
+    #[error(transparent)]
+    /// A replaced value could not be applied to the target.
+    Apply(#[from] ApplyError),
+}
+
+/// Computes the structural difference between `from` and `to`.
+///
+/// Structs, tuples, tuple structs, arrays and matching enum variants are compared field by field.
+/// Lists are compared element by element after skipping their common prefix and suffix,
+/// so that insertions and removals produce [`DiffChange::ListInserted`] and [`DiffChange::ListRemoved`].
+/// Maps and sets are compared by key.
+/// Everything else is compared with [`PartialReflect::reflect_partial_eq`],
+/// and values that cannot be compared are always considered modified.
+///
+/// # Example
+///
+/// ```
+/// # use bevy_reflect::{apply_diff, diff, DiffChange, ParsedPath, Reflect};
+/// #[derive(Reflect, Clone, PartialEq, Debug)]
+/// struct Player {
+///   name: String,
+///   items: Vec<u32>,
+/// }
+///
+/// let before = Player { name: "Ferris".into(), items: vec![1, 2, 3] };
+/// let after = Player { name: "Ferris".into(), items: vec![1, 4, 2, 3] };
+///
+/// let diff = diff(&before, &after);
+/// assert_eq!(1, diff.len());
+/// assert!(matches!(
+///     diff.changes()[0],
+///     DiffChange::ListInserted { index: 1, .. }
+/// ));
+/// assert!(diff.is_modified(&ParsedPath::parse("items").unwrap()));
+/// assert!(!diff.is_modified(&ParsedPath::parse("name").unwrap()));
+///
+/// let mut value = before.clone();
+/// apply_diff(&mut value, &diff).unwrap();
+/// assert_eq!(after, value);
+/// ```
+pub fn diff(from: &dyn PartialReflect, to: &dyn PartialReflect) -> ReflectDiff {
+    let mut differ = Differ {
+        changes: Vec::new(),
+        first_only: false,
+    };
+    differ.diff(&mut Vec::new(), from, to);
+    ReflectDiff::new(differ.changes)
+}
+
+/// Applies every change in `diff` to `target`, in order.
+///
+/// Changes applied before an error was encountered are not rolled back.
+pub fn apply_diff(
+    target: &mut dyn PartialReflect,
+    diff: &ReflectDiff,
+) -> Result<(), ApplyDiffError> {
+    diff.apply(target)
+}
+
+/// Returns true if `a` and `b` are structurally equal.
+///
+/// This compares the same way as [`diff`], but stops at the first difference.
+/// Unlike [`PartialReflect::reflect_partial_eq`], it looks inside types that do not register `PartialEq`,
+/// and it can compare dynamic values with concrete ones field by field.
+pub fn reflect_deep_eq(a: &dyn PartialReflect, b: &dyn PartialReflect) -> bool {
+    let mut differ = Differ {
+        changes: Vec::new(),
+        first_only: true,
+    };
+    differ.diff(&mut Vec::new(), a, b);
+    differ.changes.is_empty()
+}
+
+struct Differ {
+    changes: Vec<DiffChange>,
+    first_only: bool,
+}
+
+impl Differ {
+    fn is_done(&self) -> bool {
+        self.first_only && !self.changes.is_empty()
+    }
+
+    fn modified(&mut self, path: &[Access<'static>], to: &dyn PartialReflect) {
+        self.changes.push(DiffChange::Modified {
+            path: to_path(path),
+            value: to.clone_value(),
+        });
+    }
+
+    fn diff(
+        &mut self,
+        path: &mut Vec<Access<'static>>,
+        from: &dyn PartialReflect,
+        to: &dyn PartialReflect,
+    ) {
+        if self.is_done() {
+            return;
+        }
+
+        let same_type = match (
+            from.get_represented_type_info(),
+            to.get_represented_type_info(),
+        ) {
+            (Some(from), Some(to)) => from.type_id() == to.type_id(),
+            _ => true,
+        };
+        if !same_type || from.reflect_kind() != to.reflect_kind() {
+            self.modified(path, to);
+            return;
+        }
+
+        match (from.reflect_ref(), to.reflect_ref()) {
+            (ReflectRef::Struct(from), ReflectRef::Struct(to)) => {
+                let same_fields = from.field_len() == to.field_len()
+                    && to
+                        .iter_fields()
+                        .enumerate()
+                        .all(|(index, _)| from.field(to.name_at(index).unwrap()).is_some());
+                if !same_fields {
+                    self.modified(path, to.as_partial_reflect());
+                    return;
+                }
+                for (index, to_field) in to.iter_fields().enumerate() {
+                    let name = to.name_at(index).unwrap();
+                    let from_field = from.field(name).unwrap();
+                    path.push(Access::Field(Cow::Owned(name.to_string())));
+                    self.diff(path, from_field, to_field);
+                    path.pop();
+                }
+            }
+            (ReflectRef::TupleStruct(from), ReflectRef::TupleStruct(to)) => {
+                if from.field_len() != to.field_len() {
+                    self.modified(path, to.as_partial_reflect());
+                    return;
+                }
+                for (index, (from, to)) in from.iter_fields().zip(to.iter_fields()).enumerate() {
+                    path.push(Access::TupleIndex(index));
+                    self.diff(path, from, to);
+                    path.pop();
+                }
+            }
+            (ReflectRef::Tuple(from), ReflectRef::Tuple(to)) => {
+                if from.field_len() != to.field_len() {
+                    self.modified(path, to.as_partial_reflect());
+                    return;
+                }
+                for (index, (from, to)) in from.iter_fields().zip(to.iter_fields()).enumerate() {
+                    path.push(Access::TupleIndex(index));
+                    self.diff(path, from, to);
+                    path.pop();
+                }
+            }
+            (ReflectRef::Array(from), ReflectRef::Array(to)) => {
+                if from.len() != to.len() {
+                    self.modified(path, to.as_partial_reflect());
+                    return;
+                }
+                for (index, (from, to)) in from.iter().zip(to.iter()).enumerate() {
+                    path.push(Access::ListIndex(index));
+                    self.diff(path, from, to);
+                    path.pop();
+                }
+            }
+            (ReflectRef::List(from), ReflectRef::List(to)) => self.diff_list(path, from, to),
+            (ReflectRef::Map(from), ReflectRef::Map(to)) => self.diff_map(path, from, to),
+            (ReflectRef::Set(from), ReflectRef::Set(to)) => self.diff_set(path, from, to),
+            (ReflectRef::Enum(from), ReflectRef::Enum(to)) => self.diff_enum(path, from, to),
+            _ => {
+                if from.reflect_partial_eq(to) != Some(true) {
+                    self.modified(path, to);
+                }
+            }
+        }
+    }
+
+    fn diff_list(&mut self, path: &mut Vec<Access<'static>>, from: &dyn List, to: &dyn List) {
+        let (from_len, to_len) = (from.len(), to.len());
+        let min_len = from_len.min(to_len);
+
+        let prefix = (0..min_len)
+            .take_while(|&index| reflect_deep_eq(from.get(index).unwrap(), to.get(index).unwrap()))
+            .count();
+        let suffix = (0..min_len - prefix)
+            .take_while(|&offset| {
+                reflect_deep_eq(
+                    from.get(from_len - 1 - offset).unwrap(),
+                    to.get(to_len - 1 - offset).unwrap(),
+                )
+            })
+            .count();
+
+        // The differing middle sections, compared pairwise before inserting or removing the rest
+        let from_middle = from_len - prefix - suffix;
+        let to_middle = to_len - prefix - suffix;
+        for index in prefix..prefix + from_middle.min(to_middle) {
+            path.push(Access::ListIndex(index));
+            self.diff(path, from.get(index).unwrap(), to.get(index).unwrap());
+            path.pop();
+        }
+
+        if self.is_done() {
+            return;
+        }
+        let start = prefix + from_middle.min(to_middle);
+        for index in start..prefix + to_middle {
+            self.changes.push(DiffChange::ListInserted {
+                path: to_path(path),
+                index,
+                value: to.get(index).unwrap().clone_value(),
+            });
+        }
+        for _ in to_middle..from_middle {
+            self.changes.push(DiffChange::ListRemoved {
+                path: to_path(path),
+                index: start,
+            });
+        }
+    }
+
+    fn diff_map(&mut self, path: &mut Vec<Access<'static>>, from: &dyn Map, to: &dyn Map) {
+        for (key, value) in to.iter() {
+            if self.is_done() {
+                return;
+            }
+            let changed = from
+                .get(key)
+                .map_or(true, |from_value| !reflect_deep_eq(from_value, value));
+            if changed {
+                self.changes.push(DiffChange::MapInserted {
+                    path: to_path(path),
+                    key: key.clone_value(),
+                    value: value.clone_value(),
+                });
+            }
+        }
+        for (key, _) in from.iter() {
+            if self.is_done() {
+                return;
+            }
+            if to.get(key).is_none() {
+                self.changes.push(DiffChange::MapRemoved {
+                    path: to_path(path),
+                    key: key.clone_value(),
+                });
+            }
+        }
+    }
+
+    fn diff_set(&mut self, path: &mut Vec<Access<'static>>, from: &dyn Set, to: &dyn Set) {
+        for value in to.iter() {
+            if self.is_done() {
+                return;
+            }
+            if !from.contains(value) {
+                self.changes.push(DiffChange::SetInserted {
+                    path: to_path(path),
+                    value: value.clone_value(),
+                });
+            }
+        }
+        for value in from.iter() {
+            if self.is_done() {
+                return;
+            }
+            if !to.contains(value) {
+                self.changes.push(DiffChange::SetRemoved {
+                    path: to_path(path),
+                    value: value.clone_value(),
+                });
+            }
+        }
+    }
+
+    fn diff_enum(&mut self, path: &mut Vec<Access<'static>>, from: &dyn Enum, to: &dyn Enum) {
+        if from.variant_name() != to.variant_name()
+            || from.variant_type() != to.variant_type()
+            || from.field_len() != to.field_len()
+        {
+            self.changes.push(DiffChange::VariantChanged {
+                path: to_path(path),
+                value: to.clone_value(),
+            });
+            return;
+        }
+
+        for (index, to_field) in to.iter_fields().enumerate() {
+            let (access, from_field) = match to.variant_type() {
+                VariantType::Struct => {
+                    let name = to_field.name().unwrap();
+                    (
+                        Access::Field(Cow::Owned(name.to_string())),
+                        from.field(name),
+                    )
+                }
+                _ => (Access::TupleIndex(index), from.field_at(index)),
+            };
+            let Some(from_field) = from_field else {
+                self.changes.push(DiffChange::VariantChanged {
+                    path: to_path(path),
+                    value: to.clone_value(),
+                });
+                return;
+            };
+            path.push(access);
+            self.diff(path, from_field, to_field.value());
+            path.pop();
+        }
+    }
+}
+
+fn to_path(path: &[Access<'static>]) -> ParsedPath {
+    ParsedPath::from(path.to_vec())
+}
+
+fn accesses(path: &ParsedPath) -> impl Iterator<Item = &Access<'static>> + Clone {
+    path.0.iter().map(|OffsetAccess { access, .. }| access)
+}
+
+fn as_list<'a>(
+    path: &ParsedPath,
+    element: &'a mut dyn PartialReflect,
+) -> Result<&'a mut dyn List, ApplyDiffError> {
+    let found = element.reflect_kind();
+    match element.reflect_mut() {
+        ReflectMut::List(list) => Ok(list),
+        _ => Err(ApplyDiffError::MismatchedKind {
+            path: path.to_string(),
+            expected: ReflectKind::List,
+            found,
+        }),
+    }
+}
+
+fn as_map<'a>(
+    path: &ParsedPath,
+    element: &'a mut dyn PartialReflect,
+) -> Result<&'a mut dyn Map, ApplyDiffError> {
+    let found = element.reflect_kind();
+    match element.reflect_mut() {
+        ReflectMut::Map(map) => Ok(map),
+        _ => Err(ApplyDiffError::MismatchedKind {
+            path: path.to_string(),
+            expected: ReflectKind::Map,
+            found,
+        }),
+    }
+}
+
+fn as_set<'a>(
+    path: &ParsedPath,
+    element: &'a mut dyn PartialReflect,
+) -> Result<&'a mut dyn Set, ApplyDiffError> {
+    let found = element.reflect_kind();
+    match element.reflect_mut() {
+        ReflectMut::Set(set) => Ok(set),
+        _ => Err(ApplyDiffError::MismatchedKind {
+            path: path.to_string(),
+            expected: ReflectKind::Set,
+            found,
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{self as bevy_reflect, DynamicStruct, FromReflect, Reflect};
+    use bevy_utils::{HashMap, HashSet};
+
+    #[derive(Reflect, Clone, Debug, PartialEq)]
+    struct Inventory {
+        owner: Owner,
+        items: Vec<Item>,
+        counts: HashMap<String, u32>,
+        tags: HashSet<String>,
+        state: State,
+        position: (f32, f32),
+    }
+
+    #[derive(Reflect, Clone, Debug, PartialEq)]
+    struct Owner {
+        name: String,
+        level: u8,
+    }
+
+    #[derive(Reflect, Clone, Debug, PartialEq)]
+    struct Item(u32);
+
+    #[derive(Reflect, Clone, Debug, PartialEq)]
+    enum State {
+        Idle,
+        Moving { speed: f32 },
+        Carrying(Item, u8),
+    }
+
+    fn inventory() -> Inventory {
+        let mut counts = HashMap::default();
+        counts.insert(String::from("gold"), 10);
+        counts.insert(String::from("wood"), 5);
+        Inventory {
+            owner: Owner {
+                name: String::from("Ferris"),
+                level: 1,
+            },
+            items: vec![Item(1), Item(2), Item(3), Item(4)],
+            counts,
+            tags: HashSet::from_iter([String::from("a"), String::from("b")]),
+            state: State::Moving { speed: 1.0 },
+            position: (0.0, 0.0),
+        }
+    }
+
+    fn assert_round_trip(from: &Inventory, to: &Inventory) -> ReflectDiff {
+        let diff = diff(from, to);
+        let mut value = from.clone();
+        apply_diff(&mut value, &diff).unwrap();
+        assert_eq!(to, &value);
+        assert_eq!(diff.is_empty(), reflect_deep_eq(from, to));
+        diff
+    }
+
+    #[test]
+    fn should_diff_equal_values() {
+        let diff = assert_round_trip(&inventory(), &inventory());
+        assert!(diff.is_empty());
+    }
+
+    #[test]
+    fn should_diff_fields() {
+        let mut to = inventory();
+        to.owner.level = 2;
+        to.position.1 = 5.0;
+        to.state = State::Moving { speed: 2.0 };
+
+        let diff = assert_round_trip(&inventory(), &to);
+        let paths = diff
+            .changes()
+            .iter()
+            .map(|change| change.path().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(vec![".owner.level", ".state.speed", ".position.1"], paths);
+        assert!(diff.is_modified(&ParsedPath::parse("owner").unwrap()));
+        assert!(!diff.is_modified(&ParsedPath::parse("owner.name").unwrap()));
+    }
+
+    #[test]
+    fn should_diff_lists() {
+        let mut to = inventory();
+        to.items.insert(1, Item(10));
+        to.items.remove(3);
+        to.items[3].0 = 40;
+        let diff = assert_round_trip(&inventory(), &to);
+        assert_eq!(3, diff.len());
+
+        let mut to = inventory();
+        to.items.drain(1..3);
+        let diff = assert_round_trip(&inventory(), &to);
+        assert!(diff
+            .changes()
+            .iter()
+            .all(|change| matches!(change, DiffChange::ListRemoved { index: 1, .. })));
+
+        let mut to = inventory();
+        to.items.clear();
+        assert_round_trip(&inventory(), &to);
+        assert_round_trip(&to, &inventory());
+    }
+
+    #[test]
+    fn should_diff_maps_and_sets() {
+        let mut to = inventory();
+        to.counts.remove("wood");
+        to.counts.insert(String::from("gold"), 11);
+        to.counts.insert(String::from("stone"), 1);
+        to.tags.remove("a");
+        to.tags.insert(String::from("c"));
+
+        let diff = assert_round_trip(&inventory(), &to);
+        let count = |f: fn(&DiffChange) -> bool| diff.changes().iter().filter(|c| f(c)).count();
+        assert_eq!(2, count(|c| matches!(c, DiffChange::MapInserted { .. })));
+        assert_eq!(1, count(|c| matches!(c, DiffChange::MapRemoved { .. })));
+        assert_eq!(1, count(|c| matches!(c, DiffChange::SetInserted { .. })));
+        assert_eq!(1, count(|c| matches!(c, DiffChange::SetRemoved { .. })));
+    }
+
+    #[test]
+    fn should_diff_enum_variants() {
+        let mut to = inventory();
+        to.state = State::Carrying(Item(3), 2);
+        let diff = assert_round_trip(&inventory(), &to);
+        assert!(matches!(
+            diff.changes(),
+            [DiffChange::VariantChanged { .. }]
+        ));
+
+        let mut from = to.clone();
+        from.state = State::Carrying(Item(3), 1);
+        let diff = assert_round_trip(&from, &to);
+        assert_eq!(".state.1", diff.changes()[0].path().to_string());
+
+        let mut to = inventory();
+        to.state = State::Idle;
+        assert_round_trip(&inventory(), &to);
+    }
+
+    #[test]
+    fn should_diff_dynamic_values() {
+        let from = inventory();
+        let mut to = from.clone_value();
+        let mut owner = DynamicStruct::default();
+        owner.insert("name", String::from("Crab"));
+        owner.insert("level", 1u8);
+        ParsedPath::parse("owner")
+            .unwrap()
+            .reflect_element_mut(to.as_mut())
+            .unwrap()
+            .apply(&owner);
+
+        let diff = diff(&from, to.as_ref());
+        assert_eq!(1, diff.len());
+        assert_eq!(".owner.name", diff.changes()[0].path().to_string());
+
+        let mut value = from.clone();
+        diff.apply(&mut value).unwrap();
+        assert_eq!(Inventory::from_reflect(to.as_ref()).unwrap(), value);
+    }
+
+    #[test]
+    fn should_error_on_invalid_target() {
+        let mut to = inventory();
+        to.items.push(Item(5));
+        let diff = diff(&inventory(), &to);
+
+        let mut target = Owner {
+            name: String::new(),
+            level: 0,
+        };
+        assert!(matches!(
+            diff.apply(&mut target),
+            Err(ApplyDiffError::InvalidPath { .. })
+        ));
+
+        let mut target = inventory();
+        target.items.clear();
+        assert!(matches!(
+            diff.apply(&mut target),
+            Err(ApplyDiffError::IndexOutOfBounds {
+                index: 4,
+                len: 0,
+                ..
+            })
+        ));
+    }
+}
@@ -526,6 +526,8 @@
 //! [derive `Reflect`]: derive@crate::Reflect
 
 mod array;
+mod boxed;
+mod diff;
 mod fields;
 mod from_reflect;
 #[cfg(feature = "functions")]
@@ -578,6 +580,8 @@ pub mod prelude {
 }
 
 pub use array::*;
+pub use boxed::*;
+pub use diff::*;
 pub use enums::*;
 pub use fields::*;
 pub use from_reflect::*;
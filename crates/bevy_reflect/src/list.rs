@@ -197,11 +197,14 @@ impl DynamicList {
 
 impl List for DynamicList {
     fn get(&self, index: usize) -> Option<&dyn PartialReflect> {
-        self.values.get(index).map(|value| &**value)
+        self.values.as_slice().get(index).map(|value| &**value)
     }
 
     fn get_mut(&mut self, index: usize) -> Option<&mut dyn PartialReflect> {
-        self.values.get_mut(index).map(|value| &mut **value)
+        self.values
+            .as_mut_slice()
+            .get_mut(index)
+            .map(|value| &mut **value)
     }
 
     fn insert(&mut self, index: usize, element: Box<dyn PartialReflect>) {
@@ -339,24 +342,18 @@ impl Debug for DynamicList {
     }
 }
 
-impl FromIterator<Box<dyn PartialReflect>> for DynamicList {
-    fn from_iter<I: IntoIterator<Item = Box<dyn PartialReflect>>>(values: I) -> Self {
+impl<T: PartialReflect> FromIterator<T> for DynamicList {
+    fn from_iter<I: IntoIterator<Item = T>>(values: I) -> Self {
         Self {
             represented_type: None,
-            values: values.into_iter().collect(),
+            values: values
+                .into_iter()
+                .map(|field| Box::new(field).into_partial_reflect())
+                .collect(),
         }
     }
 }
 
-impl<T: PartialReflect> FromIterator<T> for DynamicList {
-    fn from_iter<I: IntoIterator<Item = T>>(values: I) -> Self {
-        values
-            .into_iter()
-            .map(|field| Box::new(field).into_partial_reflect())
-            .collect()
-    }
-}
-
 impl IntoIterator for DynamicList {
     type Item = Box<dyn PartialReflect>;
     type IntoIter = std::vec::IntoIter<Self::Item>;
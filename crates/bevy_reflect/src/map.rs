@@ -224,7 +224,10 @@ impl DynamicMap {
 
     /// Inserts a typed key-value pair into the map.
     pub fn insert<K: PartialReflect, V: PartialReflect>(&mut self, key: K, value: V) {
-        self.insert_boxed(Box::new(key), Box::new(value));
+        self.insert_boxed(
+            Box::new(key).into_partial_reflect(),
+            Box::new(value).into_partial_reflect(),
+        );
     }
 }
 
@@ -426,18 +429,6 @@ impl<'a> Iterator for MapIter<'a> {
     }
 }
 
-impl FromIterator<(Box<dyn PartialReflect>, Box<dyn PartialReflect>)> for DynamicMap {
-    fn from_iter<I: IntoIterator<Item = (Box<dyn PartialReflect>, Box<dyn PartialReflect>)>>(
-        items: I,
-    ) -> Self {
-        let mut map = Self::default();
-        for (key, value) in items.into_iter() {
-            map.insert_boxed(key, value);
-        }
-        map
-    }
-}
-
 impl<K: Reflect, V: Reflect> FromIterator<(K, V)> for DynamicMap {
     fn from_iter<I: IntoIterator<Item = (K, V)>>(items: I) -> Self {
         let mut map = Self::default();
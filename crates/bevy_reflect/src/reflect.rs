@@ -332,6 +332,34 @@ where
     /// use those subtraits' respective `clone_dynamic` methods.
     fn clone_value(&self) -> Box<dyn PartialReflect>;
 
+    /// Clones the value into a new instance of its concrete type.
+    ///
+    /// Unlike [`clone_value`], which may return a dynamic representation of the value,
+    /// this always returns a value of the same type, allowing it to be downcast.
+    ///
+    /// When deriving `Reflect`, this uses the derived [`FromReflect`] implementation.
+    /// Otherwise, it defaults to [`clone_value`], returning `None` if that
+    /// does not produce a concrete value (such as for dynamic types).
+    ///
+    /// [`clone_value`]: PartialReflect::clone_value
+    /// [`FromReflect`]: crate::FromReflect
+    fn reflect_clone(&self) -> Option<Box<dyn Reflect>> {
+        self.clone_value().try_into_reflect().ok()
+    }
+
+    /// Returns the [`Box`] this value is reflected through, if any.
+    ///
+    /// A reflected `Box<dyn Reflect>` (or any other [reflected trait object])
+    /// forwards all of its reflection methods to the value it contains.
+    /// This method is the only way to access the box itself, as `&dyn Any`.
+    ///
+    /// By default, this method will return `None`.
+    ///
+    /// [reflected trait object]: crate::PartialReflectTraitObject
+    fn as_reflect_box(&self) -> Option<&dyn Any> {
+        None
+    }
+
     /// Returns a hash of the value (which includes the type).
     ///
     /// If the underlying type does not support hashing, returns `None`.
@@ -0,0 +1,1435 @@
+use crate::serde::SerializationData;
+use crate::{
+    DynamicArray, DynamicEnum, DynamicList, DynamicMap, DynamicSet, DynamicStruct, DynamicTuple,
+    DynamicTupleStruct, DynamicVariant, Map, PartialReflect, ReflectDeserialize, ReflectKind,
+    ReflectRef, ReflectSerialize, Set, TypeInfo, TypeRegistration, TypeRegistry, VariantInfo,
+};
+use serde::de::{self, DeserializeSeed, IntoDeserializer, Visitor};
+use serde::ser::{self, Serialize};
+use std::any::TypeId;
+use std::fmt::Display;
+use std::io::{self, Read, Write};
+use thiserror::Error;
+
+/// An error that occurs while encoding or decoding a reflected value
+/// with [`BinaryReflectSerializer`] or [`BinaryReflectDeserializer`].
+#[derive(Error, Debug)]
+pub enum BinaryReflectError {
+    /// Reading from or writing to the underlying stream failed.
+    #[error(transparent)]
+    Io(#[from] io::Error),
+
+    /// The value has no represented [`TypeInfo`] to encode it against.
+    #[error("value of type `{0}` does not represent any type")]
+    NoRepresentedType(String),
+
+    /// A type needed to decode the value is not registered.
+    #[error("no registration found for type `{0}`")]
+    UnregisteredType(String),
+
+    /// An opaque value cannot be encoded without [`ReflectSerialize`].
+    #[error("type `{0}` did not register the `ReflectSerialize` type data")]
+    MissingSerialize(String),
+
+    /// An opaque value cannot be decoded without [`ReflectDeserialize`].
+    #[error("type `{0}` did not register the `ReflectDeserialize` type data")]
+    MissingDeserialize(String),
+
+    /// The value's kind does not match the kind of its represented type.
+    #[error("expected a value of kind `{expected}` for type `{type_path}` but found `{found}`")]
+    MismatchedKind {
+        type_path: String,
+        expected: ReflectKind,
+        found: ReflectKind,
+    },
+
+    /// A field listed in the type's [`TypeInfo`] is missing from the value.
+    #[error("value of type `{type_path}` is missing field `{field}`")]
+    MissingField { type_path: String, field: String },
+
+    /// An enum variant could not be found by name or index.
+    #[error("type `{type_path}` has no variant `{variant}`")]
+    InvalidVariant { type_path: String, variant: String },
+
+    /// An array value does not match the capacity of its type.
+    #[error("expected {expected} elements for type `{type_path}` but found {found}")]
+    InvalidLength {
+        type_path: String,
+        expected: usize,
+        found: usize,
+    },
+
+    /// A varint was longer than the integer it was decoded into.
+    #[error("encoded integer does not fit in the expected type")]
+    IntegerOverflow,
+
+    /// A byte other than `0` or `1` was read as a `bool`.
+    #[error("invalid bool byte `{0}`")]
+    InvalidBool(u8),
+
+    /// A byte other than `0` or `1` was read as the tag of an `Option`.
+    #[error("invalid option tag `{0}`")]
+    InvalidOptionTag(u8),
+
+    /// A value that is not a valid Unicode scalar value was read as a `char`.
+    #[error("invalid char `{0:#x}`")]
+    InvalidChar(u32),
+
+    /// A string or type path was not valid UTF-8.
+    #[error("invalid UTF-8 string: {0}")]
+    InvalidUtf8(#[from] std::string::FromUtf8Error),
+
+    /// A custom [`Serialize`] implementation did not report a length up front.
+    #[error("sequences and maps must have a known length")]
+    UnknownLength,
+
+    /// A custom [`Deserialize`](serde::Deserialize) implementation asked for a self-describing format.
+    #[error("the binary reflect format is not self-describing and cannot be used with `{0}`")]
+    NotSelfDescribing(&'static str),
+
+    /// An error reported by a custom [`Serialize`] or [`Deserialize`](serde::Deserialize) implementation.
+    #[error("{0}")]
+    Custom(String),
+}
+
+impl ser::Error for BinaryReflectError {
+    fn custom<T: Display>(msg: T) -> Self {
+        BinaryReflectError::Custom(msg.to_string())
+    }
+}
+
+impl de::Error for BinaryReflectError {
+    fn custom<T: Display>(msg: T) -> Self {
+        BinaryReflectError::Custom(msg.to_string())
+    }
+}
+
+/// A compact binary encoder for reflected values.
+///
+/// Unlike [`TypedReflectSerializer`], this does not go through a serde data format.
+/// Values are written positionally against their [`TypeInfo`]:
+/// struct fields are written in declaration order without their names,
+/// enum variants are written as indices,
+/// and integers and lengths are written as variable-length integers.
+/// The same [`TypeInfo`] must therefore be available when reading the value back
+/// with [`BinaryReflectDeserializer`].
+///
+/// Types that register [`ReflectSerialize`] are encoded with their own [`Serialize`] implementation
+/// using the same compact encoding,
+/// and fields marked with `#[reflect(skip_serializing)]` are not written at all.
+///
+/// Each call writes exactly one value, so several values can be streamed to the same writer
+/// and read back one at a time.
+///
+/// # Example
+///
+/// ```
+/// # use bevy_reflect::prelude::*;
+/// # use bevy_reflect::{TypeRegistry, serde::{BinaryReflectDeserializer, BinaryReflectSerializer}};
+/// #[derive(Reflect, PartialEq, Debug)]
+/// #[type_path = "my_crate"]
+/// struct MyStruct {
+///   value: i32
+/// }
+///
+/// let mut registry = TypeRegistry::default();
+/// registry.register::<MyStruct>();
+///
+/// let mut bytes = Vec::new();
+/// let serializer = BinaryReflectSerializer::new(&registry);
+/// serializer.serialize_typed(&MyStruct { value: 123 }, &mut bytes).unwrap();
+/// serializer.serialize_typed(&MyStruct { value: -1 }, &mut bytes).unwrap();
+///
+/// let registration = registry.get(std::any::TypeId::of::<MyStruct>()).unwrap();
+/// let deserializer = BinaryReflectDeserializer::new(&registry);
+/// let mut reader = bytes.as_slice();
+///
+/// let first = deserializer.deserialize_typed(registration, &mut reader).unwrap();
+/// let second = deserializer.deserialize_typed(registration, &mut reader).unwrap();
+/// assert_eq!(MyStruct { value: 123 }, MyStruct::from_reflect(&*first).unwrap());
+/// assert_eq!(MyStruct { value: -1 }, MyStruct::from_reflect(&*second).unwrap());
+/// assert!(reader.is_empty());
+/// ```
+///
+/// [`TypedReflectSerializer`]: crate::serde::TypedReflectSerializer
+pub struct BinaryReflectSerializer<'a> {
+    registry: &'a TypeRegistry,
+}
+
+impl<'a> BinaryReflectSerializer<'a> {
+    /// Creates a serializer that encodes values using the types in `registry`.
+    pub fn new(registry: &'a TypeRegistry) -> Self {
+        Self { registry }
+    }
+
+    /// Writes `value` prefixed by its type path,
+    /// so that it can be read back with [`BinaryReflectDeserializer::deserialize`].
+    pub fn serialize<W: Write>(
+        &self,
+        value: &dyn PartialReflect,
+        writer: W,
+    ) -> Result<(), BinaryReflectError> {
+        let type_info = represented_type_info(value)?;
+        let mut writer = BinaryWriter { writer };
+        writer.write_bytes(type_info.type_path().as_bytes())?;
+        self.write_value(value, &mut writer)
+    }
+
+    /// Writes `value` without any type information,
+    /// so that it can be read back with [`BinaryReflectDeserializer::deserialize_typed`].
+    pub fn serialize_typed<W: Write>(
+        &self,
+        value: &dyn PartialReflect,
+        writer: W,
+    ) -> Result<(), BinaryReflectError> {
+        self.write_value(value, &mut BinaryWriter { writer })
+    }
+
+    fn write_value<W: Write>(
+        &self,
+        value: &dyn PartialReflect,
+        writer: &mut BinaryWriter<W>,
+    ) -> Result<(), BinaryReflectError> {
+        let type_info = represented_type_info(value)?;
+        let type_path = type_info.type_path();
+
+        if let Some(reflect_serialize) = self
+            .registry
+            .get_type_data::<ReflectSerialize>(type_info.type_id())
+        {
+            if let Some(value) = value.try_as_reflect() {
+                return reflect_serialize
+                    .get_serializable(value)
+                    .borrow()
+                    .serialize(writer);
+            }
+        }
+
+        let serialization_data = self
+            .registry
+            .get_type_data::<SerializationData>(type_info.type_id());
+        let is_skipped =
+            |index| serialization_data.is_some_and(|data| data.is_field_skipped(index));
+
+        match (value.reflect_ref(), type_info) {
+            (ReflectRef::Struct(value), TypeInfo::Struct(info)) => {
+                for (index, field) in info.iter().enumerate() {
+                    if is_skipped(index) {
+                        continue;
+                    }
+                    let field = value
+                        .field(field.name())
+                        .ok_or_else(|| missing_field(type_path, field.name()))?;
+                    self.write_value(field, writer)?;
+                }
+            }
+            (ReflectRef::TupleStruct(value), TypeInfo::TupleStruct(info)) => {
+                for index in 0..info.field_len() {
+                    if is_skipped(index) {
+                        continue;
+                    }
+                    let field = value
+                        .field(index)
+                        .ok_or_else(|| missing_field(type_path, index))?;
+                    self.write_value(field, writer)?;
+                }
+            }
+            (ReflectRef::Tuple(value), TypeInfo::Tuple(info)) => {
+                for index in 0..info.field_len() {
+                    if is_skipped(index) {
+                        continue;
+                    }
+                    let field = value
+                        .field(index)
+                        .ok_or_else(|| missing_field(type_path, index))?;
+                    self.write_value(field, writer)?;
+                }
+            }
+            (ReflectRef::List(value), TypeInfo::List(_)) => {
+                writer.write_len(value.len())?;
+                for item in value.iter() {
+                    self.write_value(item, writer)?;
+                }
+            }
+            (ReflectRef::Array(value), TypeInfo::Array(info)) => {
+                if value.len() != info.capacity() {
+                    return Err(BinaryReflectError::InvalidLength {
+                        type_path: type_path.to_string(),
+                        expected: info.capacity(),
+                        found: value.len(),
+                    });
+                }
+                for item in value.iter() {
+                    self.write_value(item, writer)?;
+                }
+            }
+            (ReflectRef::Map(value), TypeInfo::Map(_)) => {
+                writer.write_len(value.len())?;
+                for (key, value) in value.iter() {
+                    self.write_value(key, writer)?;
+                    self.write_value(value, writer)?;
+                }
+            }
+            (ReflectRef::Set(value), TypeInfo::Set(_)) => {
+                writer.write_len(value.len())?;
+                for item in value.iter() {
+                    self.write_value(item, writer)?;
+                }
+            }
+            (ReflectRef::Enum(value), TypeInfo::Enum(info)) => {
+                let variant_name = value.variant_name();
+                let (index, variant) = info
+                    .index_of(variant_name)
+                    .and_then(|index| Some((index, info.variant_at(index)?)))
+                    .ok_or_else(|| BinaryReflectError::InvalidVariant {
+                        type_path: type_path.to_string(),
+                        variant: variant_name.to_string(),
+                    })?;
+                writer.write_varint(index as u128)?;
+                match variant {
+                    VariantInfo::Unit(_) => {}
+                    VariantInfo::Struct(variant) => {
+                        for field in variant.iter() {
+                            let field = value
+                                .field(field.name())
+                                .ok_or_else(|| missing_field(type_path, field.name()))?;
+                            self.write_value(field, writer)?;
+                        }
+                    }
+                    VariantInfo::Tuple(variant) => {
+                        for index in 0..variant.field_len() {
+                            let field = value
+                                .field_at(index)
+                                .ok_or_else(|| missing_field(type_path, index))?;
+                            self.write_value(field, writer)?;
+                        }
+                    }
+                }
+            }
+            (ReflectRef::Value(_), TypeInfo::Value(_)) => {
+                return Err(BinaryReflectError::MissingSerialize(type_path.to_string()));
+            }
+            (value, info) => {
+                return Err(BinaryReflectError::MismatchedKind {
+                    type_path: type_path.to_string(),
+                    expected: info.kind(),
+                    found: value.kind(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// A decoder for values written by [`BinaryReflectSerializer`].
+///
+/// Like [`TypedReflectDeserializer`], this returns a dynamic representation of the value
+/// (unless the type registers [`ReflectDeserialize`]),
+/// which can be converted to the concrete type using [`FromReflect`] or [`ReflectFromReflect`].
+/// Fields marked with `#[reflect(skip_serializing)]` are filled in with their default value.
+///
+/// Only the bytes belonging to a single value are consumed from the reader,
+/// so values can be read back one at a time from a stream.
+///
+/// [`TypedReflectDeserializer`]: crate::serde::TypedReflectDeserializer
+/// [`FromReflect`]: crate::FromReflect
+/// [`ReflectFromReflect`]: crate::ReflectFromReflect
+pub struct BinaryReflectDeserializer<'a> {
+    registry: &'a TypeRegistry,
+}
+
+impl<'a> BinaryReflectDeserializer<'a> {
+    /// Creates a deserializer that decodes values using the types in `registry`.
+    pub fn new(registry: &'a TypeRegistry) -> Self {
+        Self { registry }
+    }
+
+    /// Reads a value written by [`BinaryReflectSerializer::serialize`].
+    pub fn deserialize<R: Read>(
+        &self,
+        reader: R,
+    ) -> Result<Box<dyn PartialReflect>, BinaryReflectError> {
+        let mut reader = BinaryReader { reader };
+        let type_path = String::from_utf8(reader.read_bytes()?)?;
+        let registration = self
+            .registry
+            .get_with_type_path(&type_path)
+            .ok_or(BinaryReflectError::UnregisteredType(type_path))?;
+        self.read_value(registration, &mut reader)
+    }
+
+    /// Reads a value of the given type written by [`BinaryReflectSerializer::serialize_typed`].
+    pub fn deserialize_typed<R: Read>(
+        &self,
+        registration: &TypeRegistration,
+        reader: R,
+    ) -> Result<Box<dyn PartialReflect>, BinaryReflectError> {
+        self.read_value(registration, &mut BinaryReader { reader })
+    }
+
+    fn read_value<R: Read>(
+        &self,
+        registration: &TypeRegistration,
+        reader: &mut BinaryReader<R>,
+    ) -> Result<Box<dyn PartialReflect>, BinaryReflectError> {
+        if let Some(deserialize_reflect) = registration.data::<ReflectDeserialize>() {
+            let value = deserialize_reflect.deserialize(&mut *reader)?;
+            return Ok(value.into_partial_reflect());
+        }
+
+        let type_info = registration.type_info();
+        let serialization_data = registration.data::<SerializationData>();
+        let skipped_default = |index| {
+            serialization_data
+                .and_then(|data| data.generate_default(index))
+                .map(PartialReflect::into_partial_reflect)
+        };
+
+        let value: Box<dyn PartialReflect> = match type_info {
+            TypeInfo::Struct(info) => {
+                let mut dynamic_struct = DynamicStruct::default();
+                for (index, field) in info.iter().enumerate() {
+                    let value = match skipped_default(index) {
+                        Some(value) => value,
+                        None => self.read_field(field.type_id(), field.type_path(), reader)?,
+                    };
+                    dynamic_struct.insert_boxed(field.name(), value);
+                }
+                dynamic_struct.set_represented_type(Some(type_info));
+                Box::new(dynamic_struct)
+            }
+            TypeInfo::TupleStruct(info) => {
+                let mut dynamic_tuple_struct = DynamicTupleStruct::default();
+                for (index, field) in info.iter().enumerate() {
+                    let value = match skipped_default(index) {
+                        Some(value) => value,
+                        None => self.read_field(field.type_id(), field.type_path(), reader)?,
+                    };
+                    dynamic_tuple_struct.insert_boxed(value);
+                }
+                dynamic_tuple_struct.set_represented_type(Some(type_info));
+                Box::new(dynamic_tuple_struct)
+            }
+            TypeInfo::Tuple(info) => {
+                let mut dynamic_tuple = DynamicTuple::default();
+                for (index, field) in info.iter().enumerate() {
+                    let value = match skipped_default(index) {
+                        Some(value) => value,
+                        None => self.read_field(field.type_id(), field.type_path(), reader)?,
+                    };
+                    dynamic_tuple.insert_boxed(value);
+                }
+                dynamic_tuple.set_represented_type(Some(type_info));
+                Box::new(dynamic_tuple)
+            }
+            TypeInfo::List(info) => {
+                let item = info.item_ty();
+                let mut dynamic_list = DynamicList::default();
+                for _ in 0..reader.read_len()? {
+                    dynamic_list.push_box(self.read_field(item.id(), item.path(), reader)?);
+                }
+                dynamic_list.set_represented_type(Some(type_info));
+                Box::new(dynamic_list)
+            }
+            TypeInfo::Array(info) => {
+                let item = info.item_ty();
+                let items = (0..info.capacity())
+                    .map(|_| self.read_field(item.id(), item.path(), reader))
+                    .collect::<Result<Vec<_>, _>>()?;
+                let mut dynamic_array = DynamicArray::new(items.into_boxed_slice());
+                dynamic_array.set_represented_type(Some(type_info));
+                Box::new(dynamic_array)
+            }
+            TypeInfo::Map(info) => {
+                let (key, value) = (info.key_ty(), info.value_ty());
+                let mut dynamic_map = DynamicMap::default();
+                for _ in 0..reader.read_len()? {
+                    let key = self.read_field(key.id(), key.path(), reader)?;
+                    let value = self.read_field(value.id(), value.path(), reader)?;
+                    dynamic_map.insert_boxed(key, value);
+                }
+                dynamic_map.set_represented_type(Some(type_info));
+                Box::new(dynamic_map)
+            }
+            TypeInfo::Set(info) => {
+                let item = info.value_ty();
+                let mut dynamic_set = DynamicSet::default();
+                for _ in 0..reader.read_len()? {
+                    dynamic_set.insert_boxed(self.read_field(item.id(), item.path(), reader)?);
+                }
+                dynamic_set.set_represented_type(Some(type_info));
+                Box::new(dynamic_set)
+            }
+            TypeInfo::Enum(info) => {
+                let index = reader.read_varint(32)? as usize;
+                let variant =
+                    info.variant_at(index)
+                        .ok_or_else(|| BinaryReflectError::InvalidVariant {
+                            type_path: info.type_path().to_string(),
+                            variant: index.to_string(),
+                        })?;
+                let value: DynamicVariant = match variant {
+                    VariantInfo::Unit(_) => DynamicVariant::Unit,
+                    VariantInfo::Struct(variant) => {
+                        let mut dynamic_struct = DynamicStruct::default();
+                        for field in variant.iter() {
+                            let value =
+                                self.read_field(field.type_id(), field.type_path(), reader)?;
+                            dynamic_struct.insert_boxed(field.name(), value);
+                        }
+                        dynamic_struct.into()
+                    }
+                    VariantInfo::Tuple(variant) => {
+                        let mut dynamic_tuple = DynamicTuple::default();
+                        for field in variant.iter() {
+                            let value =
+                                self.read_field(field.type_id(), field.type_path(), reader)?;
+                            dynamic_tuple.insert_boxed(value);
+                        }
+                        dynamic_tuple.into()
+                    }
+                };
+                let mut dynamic_enum = DynamicEnum::new_with_index(index, variant.name(), value);
+                dynamic_enum.set_represented_type(Some(type_info));
+                Box::new(dynamic_enum)
+            }
+            TypeInfo::Value(info) => {
+                return Err(BinaryReflectError::MissingDeserialize(
+                    info.type_path().to_string(),
+                ));
+            }
+        };
+
+        Ok(value)
+    }
+
+    fn read_field<R: Read>(
+        &self,
+        type_id: TypeId,
+        type_path: &str,
+        reader: &mut BinaryReader<R>,
+    ) -> Result<Box<dyn PartialReflect>, BinaryReflectError> {
+        let registration = self
+            .registry
+            .get(type_id)
+            .ok_or_else(|| BinaryReflectError::UnregisteredType(type_path.to_string()))?;
+        self.read_value(registration, reader)
+    }
+}
+
+fn represented_type_info(
+    value: &dyn PartialReflect,
+) -> Result<&'static TypeInfo, BinaryReflectError> {
+    value
+        .get_represented_type_info()
+        .ok_or_else(|| BinaryReflectError::NoRepresentedType(value.reflect_type_path().to_string()))
+}
+
+fn missing_field(type_path: &str, field: impl Display) -> BinaryReflectError {
+    BinaryReflectError::MissingField {
+        type_path: type_path.to_string(),
+        field: field.to_string(),
+    }
+}
+
+fn zigzag_encode(value: i128) -> u128 {
+    ((value << 1) ^ (value >> 127)) as u128
+}
+
+fn zigzag_decode(value: u128) -> i128 {
+    (value >> 1) as i128 ^ -((value & 1) as i128)
+}
+
+/// The writing half of the compact encoding.
+///
+/// This is also a [`serde::Serializer`] so that [`ReflectSerialize`] types can be written with it.
+/// Integers wider than a byte are LEB128 varints (zigzag encoded when signed),
+/// floats are little-endian,
+/// and only sequences, maps, strings and byte arrays carry a length.
+struct BinaryWriter<W> {
+    writer: W,
+}
+
+impl<W: Write> BinaryWriter<W> {
+    fn write_byte(&mut self, byte: u8) -> Result<(), BinaryReflectError> {
+        Ok(self.writer.write_all(&[byte])?)
+    }
+
+    fn write_varint(&mut self, mut value: u128) -> Result<(), BinaryReflectError> {
+        let mut buf = [0; 19];
+        let mut len = 0;
+        loop {
+            let byte = (value & 0x7f) as u8;
+            value >>= 7;
+            if value == 0 {
+                buf[len] = byte;
+                len += 1;
+                break;
+            }
+            buf[len] = byte | 0x80;
+            len += 1;
+        }
+        Ok(self.writer.write_all(&buf[..len])?)
+    }
+
+    fn write_len(&mut self, len: usize) -> Result<(), BinaryReflectError> {
+        self.write_varint(len as u128)
+    }
+
+    fn write_bytes(&mut self, bytes: &[u8]) -> Result<(), BinaryReflectError> {
+        self.write_len(bytes.len())?;
+        Ok(self.writer.write_all(bytes)?)
+    }
+}
+
+impl<W: Write> ser::Serializer for &mut BinaryWriter<W> {
+    type Ok = ();
+    type Error = BinaryReflectError;
+    type SerializeSeq = Self;
+    type SerializeTuple = Self;
+    type SerializeTupleStruct = Self;
+    type SerializeTupleVariant = Self;
+    type SerializeMap = Self;
+    type SerializeStruct = Self;
+    type SerializeStructVariant = Self;
+
+    fn serialize_bool(self, v: bool) -> Result<(), Self::Error> {
+        self.write_byte(v as u8)
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<(), Self::Error> {
+        self.write_byte(v as u8)
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<(), Self::Error> {
+        self.write_varint(zigzag_encode(v as i128))
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<(), Self::Error> {
+        self.write_varint(zigzag_encode(v as i128))
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<(), Self::Error> {
+        self.write_varint(zigzag_encode(v as i128))
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<(), Self::Error> {
+        self.write_varint(zigzag_encode(v))
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<(), Self::Error> {
+        self.write_byte(v)
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<(), Self::Error> {
+        self.write_varint(v as u128)
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<(), Self::Error> {
+        self.write_varint(v as u128)
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<(), Self::Error> {
+        self.write_varint(v as u128)
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<(), Self::Error> {
+        self.write_varint(v)
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<(), Self::Error> {
+        Ok(self.writer.write_all(&v.to_le_bytes())?)
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<(), Self::Error> {
+        Ok(self.writer.write_all(&v.to_le_bytes())?)
+    }
+
+    fn serialize_char(self, v: char) -> Result<(), Self::Error> {
+        self.write_varint(v as u128)
+    }
+
+    fn serialize_str(self, v: &str) -> Result<(), Self::Error> {
+        self.write_bytes(v.as_bytes())
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<(), Self::Error> {
+        self.write_bytes(v)
+    }
+
+    fn serialize_none(self) -> Result<(), Self::Error> {
+        self.write_byte(0)
+    }
+
+    fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> Result<(), Self::Error> {
+        self.write_byte(1)?;
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+    ) -> Result<(), Self::Error> {
+        self.write_varint(variant_index as u128)
+    }
+
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<(), Self::Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+        value: &T,
+    ) -> Result<(), Self::Error> {
+        self.write_varint(variant_index as u128)?;
+        value.serialize(self)
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self, Self::Error> {
+        self.write_len(len.ok_or(BinaryReflectError::UnknownLength)?)?;
+        Ok(self)
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self, Self::Error> {
+        Ok(self)
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, _len: usize) -> Result<Self, Self::Error> {
+        Ok(self)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self, Self::Error> {
+        self.write_varint(variant_index as u128)?;
+        Ok(self)
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<Self, Self::Error> {
+        self.write_len(len.ok_or(BinaryReflectError::UnknownLength)?)?;
+        Ok(self)
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self, Self::Error> {
+        Ok(self)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self, Self::Error> {
+        self.write_varint(variant_index as u128)?;
+        Ok(self)
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+}
+
+impl<W: Write> ser::SerializeSeq for &mut BinaryWriter<W> {
+    type Ok = ();
+    type Error = BinaryReflectError;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Self::Error> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+impl<W: Write> ser::SerializeTuple for &mut BinaryWriter<W> {
+    type Ok = ();
+    type Error = BinaryReflectError;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Self::Error> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+impl<W: Write> ser::SerializeTupleStruct for &mut BinaryWriter<W> {
+    type Ok = ();
+    type Error = BinaryReflectError;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Self::Error> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+impl<W: Write> ser::SerializeTupleVariant for &mut BinaryWriter<W> {
+    type Ok = ();
+    type Error = BinaryReflectError;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Self::Error> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+impl<W: Write> ser::SerializeMap for &mut BinaryWriter<W> {
+    type Ok = ();
+    type Error = BinaryReflectError;
+
+    fn serialize_key<T: ?Sized + Serialize>(&mut self, key: &T) -> Result<(), Self::Error> {
+        key.serialize(&mut **self)
+    }
+
+    fn serialize_value<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Self::Error> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+impl<W: Write> ser::SerializeStruct for &mut BinaryWriter<W> {
+    type Ok = ();
+    type Error = BinaryReflectError;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        _key: &'static str,
+        value: &T,
+    ) -> Result<(), Self::Error> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+impl<W: Write> ser::SerializeStructVariant for &mut BinaryWriter<W> {
+    type Ok = ();
+    type Error = BinaryReflectError;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        _key: &'static str,
+        value: &T,
+    ) -> Result<(), Self::Error> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+/// The reading half of the compact encoding.
+///
+/// This is also a [`serde::Deserializer`] so that [`ReflectDeserialize`] types can be read with it.
+/// It never reads past the end of the current value and never allocates
+/// more than the input actually contains.
+struct BinaryReader<R> {
+    reader: R,
+}
+
+impl<R: Read> BinaryReader<R> {
+    fn read_byte(&mut self) -> Result<u8, BinaryReflectError> {
+        let mut buf = [0];
+        self.reader.read_exact(&mut buf)?;
+        Ok(buf[0])
+    }
+
+    fn read_array<const N: usize>(&mut self) -> Result<[u8; N], BinaryReflectError> {
+        let mut buf = [0; N];
+        self.reader.read_exact(&mut buf)?;
+        Ok(buf)
+    }
+
+    /// Reads a varint that must fit in `bits` bits.
+    fn read_varint(&mut self, bits: u32) -> Result<u128, BinaryReflectError> {
+        let mut value = 0u128;
+        let mut shift = 0;
+        loop {
+            if shift >= bits {
+                return Err(BinaryReflectError::IntegerOverflow);
+            }
+            let byte = self.read_byte()?;
+            let low = (byte & 0x7f) as u128;
+            if shift + 7 > bits && low >> (bits - shift) != 0 {
+                return Err(BinaryReflectError::IntegerOverflow);
+            }
+            value |= low << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+            shift += 7;
+        }
+    }
+
+    fn read_signed(&mut self, bits: u32) -> Result<i128, BinaryReflectError> {
+        let value = zigzag_decode(self.read_varint(bits)?);
+        if bits < 128 && (value >= 1 << (bits - 1) || value < -(1 << (bits - 1))) {
+            return Err(BinaryReflectError::IntegerOverflow);
+        }
+        Ok(value)
+    }
+
+    fn read_len(&mut self) -> Result<usize, BinaryReflectError> {
+        usize::try_from(self.read_varint(64)?).map_err(|_| BinaryReflectError::IntegerOverflow)
+    }
+
+    fn read_bytes(&mut self) -> Result<Vec<u8>, BinaryReflectError> {
+        let len = self.read_len()?;
+        let mut bytes = Vec::new();
+        self.reader
+            .by_ref()
+            .take(len as u64)
+            .read_to_end(&mut bytes)?;
+        if bytes.len() != len {
+            return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into());
+        }
+        Ok(bytes)
+    }
+}
+
+impl<'de, R: Read> de::Deserializer<'de> for &mut BinaryReader<R> {
+    type Error = BinaryReflectError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Self::Error> {
+        Err(BinaryReflectError::NotSelfDescribing("deserialize_any"))
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.read_byte()? {
+            0 => visitor.visit_bool(false),
+            1 => visitor.visit_bool(true),
+            byte => Err(BinaryReflectError::InvalidBool(byte)),
+        }
+    }
+
+    fn deserialize_i8<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_i8(self.read_byte()? as i8)
+    }
+
+    fn deserialize_i16<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_i16(self.read_signed(16)? as i16)
+    }
+
+    fn deserialize_i32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_i32(self.read_signed(32)? as i32)
+    }
+
+    fn deserialize_i64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_i64(self.read_signed(64)? as i64)
+    }
+
+    fn deserialize_i128<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_i128(self.read_signed(128)?)
+    }
+
+    fn deserialize_u8<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_u8(self.read_byte()?)
+    }
+
+    fn deserialize_u16<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_u16(self.read_varint(16)? as u16)
+    }
+
+    fn deserialize_u32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_u32(self.read_varint(32)? as u32)
+    }
+
+    fn deserialize_u64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_u64(self.read_varint(64)? as u64)
+    }
+
+    fn deserialize_u128<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_u128(self.read_varint(128)?)
+    }
+
+    fn deserialize_f32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_f32(f32::from_le_bytes(self.read_array()?))
+    }
+
+    fn deserialize_f64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_f64(f64::from_le_bytes(self.read_array()?))
+    }
+
+    fn deserialize_char<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        let code = self.read_varint(32)? as u32;
+        visitor.visit_char(char::from_u32(code).ok_or(BinaryReflectError::InvalidChar(code))?)
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.deserialize_string(visitor)
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_string(String::from_utf8(self.read_bytes()?)?)
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.deserialize_byte_buf(visitor)
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_byte_buf(self.read_bytes()?)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.read_byte()? {
+            0 => visitor.visit_none(),
+            1 => visitor.visit_some(self),
+            tag => Err(BinaryReflectError::InvalidOptionTag(tag)),
+        }
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        let len = self.read_len()?;
+        visitor.visit_seq(BinaryAccess { reader: self, len })
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_seq(BinaryAccess { reader: self, len })
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_seq(BinaryAccess { reader: self, len })
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        let len = self.read_len()?;
+        visitor.visit_map(BinaryAccess { reader: self, len })
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_seq(BinaryAccess {
+            reader: self,
+            len: fields.len(),
+        })
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_enum(self)
+    }
+
+    fn deserialize_identifier<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_u32(self.read_varint(32)? as u32)
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(
+        self,
+        _visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        Err(BinaryReflectError::NotSelfDescribing(
+            "deserialize_ignored_any",
+        ))
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+}
+
+struct BinaryAccess<'a, R> {
+    reader: &'a mut BinaryReader<R>,
+    len: usize,
+}
+
+impl<'de, 'a, R: Read> de::SeqAccess<'de> for BinaryAccess<'a, R> {
+    type Error = BinaryReflectError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Self::Error> {
+        if self.len == 0 {
+            return Ok(None);
+        }
+        self.len -= 1;
+        seed.deserialize(&mut *self.reader).map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.len)
+    }
+}
+
+impl<'de, 'a, R: Read> de::MapAccess<'de> for BinaryAccess<'a, R> {
+    type Error = BinaryReflectError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Self::Error> {
+        if self.len == 0 {
+            return Ok(None);
+        }
+        self.len -= 1;
+        seed.deserialize(&mut *self.reader).map(Some)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, Self::Error> {
+        seed.deserialize(&mut *self.reader)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.len)
+    }
+}
+
+impl<'de, R: Read> de::EnumAccess<'de> for &mut BinaryReader<R> {
+    type Error = BinaryReflectError;
+    type Variant = Self;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, Self), Self::Error> {
+        let index = self.read_varint(32)? as u32;
+        let value = seed.deserialize(IntoDeserializer::<Self::Error>::into_deserializer(index))?;
+        Ok((value, self))
+    }
+}
+
+impl<'de, R: Read> de::VariantAccess<'de> for &mut BinaryReader<R> {
+    type Error = BinaryReflectError;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(
+        self,
+        seed: T,
+    ) -> Result<T::Value, Self::Error> {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(
+        self,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_seq(BinaryAccess { reader: self, len })
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_seq(BinaryAccess {
+            reader: self,
+            len: fields.len(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::serde::{
+        BinaryReflectDeserializer, BinaryReflectError, BinaryReflectSerializer,
+        TypedReflectSerializer,
+    };
+    use crate::{
+        self as bevy_reflect, FromReflect, PartialReflect, Reflect, ReflectDeserialize,
+        ReflectSerialize, TypeRegistry,
+    };
+    use bevy_utils::HashMap;
+    use serde::{Deserialize, Serialize};
+    use std::any::TypeId;
+
+    #[derive(Reflect, Serialize, Deserialize, Debug, PartialEq, Clone)]
+    #[reflect(Serialize, Deserialize)]
+    struct CustomSerialize {
+        #[serde(rename = "renamed")]
+        value: String,
+        tag: Option<u8>,
+    }
+
+    #[derive(Reflect, Debug, PartialEq, Clone)]
+    enum Shape {
+        Empty,
+        Circle(f32),
+        Rect { width: u32, height: u32 },
+    }
+
+    #[derive(Reflect, Debug, PartialEq)]
+    #[reflect(PartialEq)]
+    struct Component {
+        id: u64,
+        delta: i32,
+        #[reflect(skip_serializing)]
+        #[reflect(default = "cached_default")]
+        cached: i32,
+        name: String,
+        position: (f32, f32, f32),
+        shapes: Vec<Shape>,
+        lookup: HashMap<u8, bool>,
+        fixed: [i16; 3],
+        custom: CustomSerialize,
+        maybe: Option<Shape>,
+    }
+
+    fn cached_default() -> i32 {
+        -1
+    }
+
+    fn get_registry() -> TypeRegistry {
+        let mut registry = TypeRegistry::default();
+        registry.register::<Component>();
+        registry.register::<Shape>();
+        registry.register::<CustomSerialize>();
+        registry.register::<Option<Shape>>();
+        registry.register::<Option<u8>>();
+        registry.register::<Vec<Shape>>();
+        registry.register::<HashMap<u8, bool>>();
+        registry.register::<[i16; 3]>();
+        registry.register::<(f32, f32, f32)>();
+        registry
+    }
+
+    fn component() -> Component {
+        let mut lookup = HashMap::default();
+        lookup.insert(3, true);
+        Component {
+            id: 300,
+            delta: -2,
+            cached: 123,
+            name: String::from("player"),
+            position: (1.0, 2.0, 3.0),
+            shapes: vec![
+                Shape::Empty,
+                Shape::Circle(0.5),
+                Shape::Rect {
+                    width: 4,
+                    height: 1000,
+                },
+            ],
+            lookup,
+            fixed: [-1, 0, i16::MAX],
+            custom: CustomSerialize {
+                value: String::from("custom"),
+                tag: Some(7),
+            },
+            maybe: None,
+        }
+    }
+
+    #[test]
+    fn should_round_trip_typed() {
+        let registry = get_registry();
+        let input = component();
+
+        let mut bytes = Vec::new();
+        BinaryReflectSerializer::new(&registry)
+            .serialize_typed(&input, &mut bytes)
+            .unwrap();
+
+        let registration = registry.get(TypeId::of::<Component>()).unwrap();
+        let output = BinaryReflectDeserializer::new(&registry)
+            .deserialize_typed(registration, bytes.as_slice())
+            .unwrap();
+        let output = Component::from_reflect(output.as_ref()).unwrap();
+
+        let expected = Component {
+            cached: -1,
+            ..component()
+        };
+        assert_eq!(expected, output);
+    }
+
+    #[test]
+    fn should_round_trip_dynamic_values() {
+        let registry = get_registry();
+        let input = component().clone_value();
+
+        let mut bytes = Vec::new();
+        BinaryReflectSerializer::new(&registry)
+            .serialize(input.as_ref(), &mut bytes)
+            .unwrap();
+
+        let output = BinaryReflectDeserializer::new(&registry)
+            .deserialize(bytes.as_slice())
+            .unwrap();
+        let output = Component::from_reflect(output.as_ref()).unwrap();
+        assert_eq!(
+            Component {
+                cached: -1,
+                ..component()
+            },
+            output
+        );
+    }
+
+    #[test]
+    fn should_stream_multiple_values() {
+        let registry = get_registry();
+        let values: Vec<Box<dyn PartialReflect>> = vec![
+            Box::new(Shape::Circle(1.5)),
+            Box::new(component()),
+            Box::new(Shape::Empty),
+        ];
+
+        let serializer = BinaryReflectSerializer::new(&registry);
+        let mut bytes = Vec::new();
+        for value in &values {
+            serializer.serialize(value.as_ref(), &mut bytes).unwrap();
+        }
+
+        let deserializer = BinaryReflectDeserializer::new(&registry);
+        let mut reader = bytes.as_slice();
+        for value in &values {
+            let output = deserializer.deserialize(&mut reader).unwrap();
+            assert_eq!(
+                value.get_represented_type_info().unwrap().type_id(),
+                output.get_represented_type_info().unwrap().type_id()
+            );
+        }
+        assert!(reader.is_empty());
+        assert!(matches!(
+            deserializer.deserialize(&mut reader),
+            Err(BinaryReflectError::Io(_))
+        ));
+    }
+
+    #[test]
+    fn should_be_smaller_than_bincode() {
+        let registry = get_registry();
+        let input = component();
+
+        let mut bytes = Vec::new();
+        BinaryReflectSerializer::new(&registry)
+            .serialize_typed(&input, &mut bytes)
+            .unwrap();
+
+        let bincode_bytes =
+            bincode::serialize(&TypedReflectSerializer::new(&input, &registry)).unwrap();
+
+        assert!(
+            bytes.len() * 2 < bincode_bytes.len(),
+            "binary: {}, bincode: {}",
+            bytes.len(),
+            bincode_bytes.len()
+        );
+    }
+
+    #[test]
+    fn should_error_on_invalid_input() {
+        let registry = get_registry();
+        let deserializer = BinaryReflectDeserializer::new(&registry);
+        let registration = registry.get(TypeId::of::<Shape>()).unwrap();
+
+        let result = deserializer.deserialize_typed(registration, [9u8].as_slice());
+        assert!(matches!(
+            result,
+            Err(BinaryReflectError::InvalidVariant { .. })
+        ));
+
+        // `Rect` with a width that overflows a `u32`.
+        // Errors from `ReflectDeserialize` values come back through `erased_serde` as custom errors.
+        let Err(error) = deserializer
+            .deserialize_typed(registration, [2u8, 0xff, 0xff, 0xff, 0xff, 0x7f].as_slice())
+        else {
+            panic!("expected an overflow error");
+        };
+        assert_eq!(
+            BinaryReflectError::IntegerOverflow.to_string(),
+            error.to_string()
+        );
+
+        // `Circle` with a truncated `f32`
+        let result = deserializer.deserialize_typed(registration, [1u8, 0, 0].as_slice());
+        assert!(result.is_err());
+
+        // Truncated before the variant index
+        let result = deserializer.deserialize_typed(registration, [].as_slice());
+        assert!(matches!(result, Err(BinaryReflectError::Io(_))));
+
+        #[derive(Reflect, Clone)]
+        #[reflect_value]
+        struct Opaque;
+
+        let result =
+            BinaryReflectSerializer::new(&registry).serialize_typed(&Opaque, &mut Vec::new());
+        assert!(matches!(
+            result,
+            Err(BinaryReflectError::MissingSerialize(_))
+        ));
+    }
+}
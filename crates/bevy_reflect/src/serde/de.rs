@@ -2,9 +2,9 @@ use crate::serde::SerializationData;
 use crate::{
     ArrayInfo, DynamicArray, DynamicEnum, DynamicList, DynamicMap, DynamicSet, DynamicStruct,
     DynamicTuple, DynamicTupleStruct, DynamicVariant, EnumInfo, ListInfo, Map, MapInfo, NamedField,
-    PartialReflect, Reflect, ReflectDeserialize, Set, SetInfo, StructInfo, StructVariantInfo,
-    TupleInfo, TupleStructInfo, TupleVariantInfo, TypeInfo, TypeRegistration, TypeRegistry,
-    VariantInfo,
+    PartialReflect, Reflect, ReflectBox, ReflectDeserialize, Set, SetInfo, StructInfo,
+    StructVariantInfo, TupleInfo, TupleStructInfo, TupleVariantInfo, TypeInfo, TypeRegistration,
+    TypeRegistry, VariantInfo,
 };
 use erased_serde::Deserializer;
 use serde::de::{
@@ -523,6 +523,19 @@ impl<'a, 'de> DeserializeSeed<'de> for TypedReflectDeserializer<'a> {
     {
         let type_path = self.registration.type_info().type_path();
 
+        // Boxed values are tagged with the type path of their contents
+        if let Some(reflect_box) = self.registration.data::<ReflectBox>() {
+            let value = ReflectDeserializer::new(self.registry).deserialize(deserializer)?;
+            return reflect_box
+                .box_value(value, self.registry)
+                .map_err(|value| {
+                    Error::custom(format_args!(
+                        "cannot store a value of type `{}` in a `{type_path}`",
+                        value.reflect_type_path()
+                    ))
+                });
+        }
+
         // Handle both Value case and types that have a custom `ReflectDeserialize`
         if let Some(deserialize_reflect) = self.registration.data::<ReflectDeserialize>() {
             let value = deserialize_reflect.deserialize(deserializer)?;
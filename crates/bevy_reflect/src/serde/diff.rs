@@ -0,0 +1,478 @@
+use crate::serde::{ReflectDeserializer, ReflectSerializer};
+use crate::{DiffChange, ParsedPath, PartialReflect, ReflectDiff, TypeRegistry};
+use serde::de::{DeserializeSeed, EnumAccess, Error, MapAccess, SeqAccess, VariantAccess, Visitor};
+use serde::ser::{SerializeSeq, SerializeStructVariant};
+use serde::{Deserializer, Serialize, Serializer};
+use std::fmt::{self, Formatter};
+
+const DIFF_CHANGE: &str = "DiffChange";
+const VARIANTS: &[&str] = &[
+    "Modified",
+    "VariantChanged",
+    "ListInserted",
+    "ListRemoved",
+    "MapInserted",
+    "MapRemoved",
+    "SetInserted",
+    "SetRemoved",
+];
+
+const PATH: &str = "path";
+const INDEX: &str = "index";
+const KEY: &str = "key";
+const VALUE: &str = "value";
+
+/// The fields of each variant in [`VARIANTS`], in serialization order.
+const VARIANT_FIELDS: &[&[&str]] = &[
+    &[PATH, VALUE],
+    &[PATH, VALUE],
+    &[PATH, INDEX, VALUE],
+    &[PATH, INDEX],
+    &[PATH, KEY, VALUE],
+    &[PATH, KEY],
+    &[PATH, VALUE],
+    &[PATH, VALUE],
+];
+
+/// A serializer for [`ReflectDiff`] values.
+///
+/// A diff is serialized as a sequence of changes.
+/// Each change is an enum variant named after its [`DiffChange`] variant,
+/// holding the change's path as a string and its values serialized with [`ReflectSerializer`],
+/// so every value in the diff must have a registered represented type.
+///
+/// # Example
+///
+/// ```
+/// # use bevy_reflect::prelude::*;
+/// # use bevy_reflect::{diff, TypeRegistry, serde::{ReflectDiffDeserializer, ReflectDiffSerializer}};
+/// # use serde::de::DeserializeSeed;
+/// #[derive(Reflect, Clone, PartialEq, Debug)]
+/// #[type_path = "my_crate"]
+/// struct MyStruct {
+///   value: i32,
+/// }
+///
+/// let mut registry = TypeRegistry::default();
+/// registry.register::<MyStruct>();
+///
+/// let diff = diff(&MyStruct { value: 1 }, &MyStruct { value: 2 });
+/// let output = ron::to_string(&ReflectDiffSerializer::new(&diff, &registry)).unwrap();
+/// assert_eq!(r#"[Modified(path:".value",value:{"i32":2})]"#, output);
+///
+/// let mut deserializer = ron::Deserializer::from_str(&output).unwrap();
+/// let diff = ReflectDiffDeserializer::new(&registry).deserialize(&mut deserializer).unwrap();
+///
+/// let mut value = MyStruct { value: 1 };
+/// diff.apply(&mut value).unwrap();
+/// assert_eq!(MyStruct { value: 2 }, value);
+/// ```
+pub struct ReflectDiffSerializer<'a> {
+    pub diff: &'a ReflectDiff,
+    pub registry: &'a TypeRegistry,
+}
+
+impl<'a> ReflectDiffSerializer<'a> {
+    pub fn new(diff: &'a ReflectDiff, registry: &'a TypeRegistry) -> Self {
+        Self { diff, registry }
+    }
+}
+
+impl<'a> Serialize for ReflectDiffSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_seq(Some(self.diff.len()))?;
+        for change in self.diff.changes() {
+            state.serialize_element(&DiffChangeSerializer {
+                change,
+                registry: self.registry,
+            })?;
+        }
+        state.end()
+    }
+}
+
+struct DiffChangeSerializer<'a> {
+    change: &'a DiffChange,
+    registry: &'a TypeRegistry,
+}
+
+impl<'a> Serialize for DiffChangeSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let (variant_index, index, key, value): (
+            u32,
+            Option<usize>,
+            Option<&dyn PartialReflect>,
+            Option<&dyn PartialReflect>,
+        ) = match self.change {
+            DiffChange::Modified { value, .. } => (0, None, None, Some(value.as_ref())),
+            DiffChange::VariantChanged { value, .. } => (1, None, None, Some(value.as_ref())),
+            DiffChange::ListInserted { index, value, .. } => {
+                (2, Some(*index), None, Some(value.as_ref()))
+            }
+            DiffChange::ListRemoved { index, .. } => (3, Some(*index), None, None),
+            DiffChange::MapInserted { key, value, .. } => {
+                (4, None, Some(key.as_ref()), Some(value.as_ref()))
+            }
+            DiffChange::MapRemoved { key, .. } => (5, None, Some(key.as_ref()), None),
+            DiffChange::SetInserted { value, .. } => (6, None, None, Some(value.as_ref())),
+            DiffChange::SetRemoved { value, .. } => (7, None, None, Some(value.as_ref())),
+        };
+
+        let fields = VARIANT_FIELDS[variant_index as usize];
+        let mut state = serializer.serialize_struct_variant(
+            DIFF_CHANGE,
+            variant_index,
+            VARIANTS[variant_index as usize],
+            fields.len(),
+        )?;
+        state.serialize_field(PATH, &self.change.path().to_string())?;
+        if let Some(index) = index {
+            state.serialize_field(INDEX, &index)?;
+        }
+        if let Some(key) = key {
+            state.serialize_field(KEY, &ReflectSerializer::new(key, self.registry))?;
+        }
+        if let Some(value) = value {
+            state.serialize_field(VALUE, &ReflectSerializer::new(value, self.registry))?;
+        }
+        state.end()
+    }
+}
+
+/// A deserializer for [`ReflectDiff`] values serialized with [`ReflectDiffSerializer`].
+pub struct ReflectDiffDeserializer<'a> {
+    registry: &'a TypeRegistry,
+}
+
+impl<'a> ReflectDiffDeserializer<'a> {
+    pub fn new(registry: &'a TypeRegistry) -> Self {
+        Self { registry }
+    }
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for ReflectDiffDeserializer<'a> {
+    type Value = ReflectDiff;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct ReflectDiffVisitor<'a> {
+            registry: &'a TypeRegistry,
+        }
+
+        impl<'a, 'de> Visitor<'de> for ReflectDiffVisitor<'a> {
+            type Value = ReflectDiff;
+
+            fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+                formatter.write_str("a sequence of diff changes")
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: SeqAccess<'de>,
+            {
+                let mut changes = Vec::new();
+                while let Some(change) = seq.next_element_seed(DiffChangeDeserializer {
+                    registry: self.registry,
+                })? {
+                    changes.push(change);
+                }
+                Ok(ReflectDiff::new(changes))
+            }
+        }
+
+        deserializer.deserialize_seq(ReflectDiffVisitor {
+            registry: self.registry,
+        })
+    }
+}
+
+struct DiffChangeDeserializer<'a> {
+    registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for DiffChangeDeserializer<'a> {
+    type Value = DiffChange;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_enum(DIFF_CHANGE, VARIANTS, self)
+    }
+}
+
+impl<'a, 'de> Visitor<'de> for DiffChangeDeserializer<'a> {
+    type Value = DiffChange;
+
+    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+        formatter.write_str("a diff change")
+    }
+
+    fn visit_enum<A>(self, data: A) -> Result<Self::Value, A::Error>
+    where
+        A: EnumAccess<'de>,
+    {
+        let (variant_index, variant) = data.variant_seed(Identifier { names: VARIANTS })?;
+        let fields = VARIANT_FIELDS[variant_index];
+        variant.struct_variant(
+            fields,
+            DiffChangeFieldsVisitor {
+                variant_index,
+                fields,
+                registry: self.registry,
+            },
+        )
+    }
+}
+
+struct DiffChangeFieldsVisitor<'a> {
+    variant_index: usize,
+    fields: &'static [&'static str],
+    registry: &'a TypeRegistry,
+}
+
+#[derive(Default)]
+struct DiffChangeFields {
+    path: Option<ParsedPath>,
+    index: Option<usize>,
+    key: Option<Box<dyn PartialReflect>>,
+    value: Option<Box<dyn PartialReflect>>,
+}
+
+impl<'a> DiffChangeFieldsVisitor<'a> {
+    fn build<E: Error>(&self, fields: DiffChangeFields) -> Result<DiffChange, E> {
+        let path = fields.path.ok_or_else(|| Error::missing_field(PATH))?;
+        let index = || fields.index.ok_or_else(|| Error::missing_field(INDEX));
+        let key = fields.key.ok_or_else(|| Error::missing_field(KEY));
+        let value = fields.value.ok_or_else(|| Error::missing_field(VALUE));
+
+        Ok(match self.variant_index {
+            0 => DiffChange::Modified {
+                path,
+                value: value?,
+            },
+            1 => DiffChange::VariantChanged {
+                path,
+                value: value?,
+            },
+            2 => DiffChange::ListInserted {
+                path,
+                index: index()?,
+                value: value?,
+            },
+            3 => DiffChange::ListRemoved {
+                path,
+                index: index()?,
+            },
+            4 => DiffChange::MapInserted {
+                path,
+                key: key?,
+                value: value?,
+            },
+            5 => DiffChange::MapRemoved { path, key: key? },
+            6 => DiffChange::SetInserted {
+                path,
+                value: value?,
+            },
+            _ => DiffChange::SetRemoved {
+                path,
+                value: value?,
+            },
+        })
+    }
+}
+
+impl<'a, 'de> Visitor<'de> for DiffChangeFieldsVisitor<'a> {
+    type Value = DiffChange;
+
+    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+        write!(formatter, "a `{}` change", VARIANTS[self.variant_index])
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut fields = DiffChangeFields::default();
+        for (index, field) in self.fields.iter().enumerate() {
+            let missing = || Error::invalid_length(index, &self);
+            match *field {
+                PATH => {
+                    let path: String = seq.next_element()?.ok_or_else(missing)?;
+                    fields.path = Some(parse_path(&path)?);
+                }
+                INDEX => fields.index = Some(seq.next_element()?.ok_or_else(missing)?),
+                KEY => {
+                    fields.key = Some(
+                        seq.next_element_seed(ReflectDeserializer::new(self.registry))?
+                            .ok_or_else(missing)?,
+                    );
+                }
+                _ => {
+                    fields.value = Some(
+                        seq.next_element_seed(ReflectDeserializer::new(self.registry))?
+                            .ok_or_else(missing)?,
+                    );
+                }
+            }
+        }
+        self.build(fields)
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut fields = DiffChangeFields::default();
+        while let Some(field) = map.next_key_seed(Identifier { names: self.fields })? {
+            match self.fields[field] {
+                PATH => fields.path = Some(parse_path(&map.next_value::<String>()?)?),
+                INDEX => fields.index = Some(map.next_value()?),
+                KEY => {
+                    fields.key =
+                        Some(map.next_value_seed(ReflectDeserializer::new(self.registry))?);
+                }
+                _ => {
+                    fields.value =
+                        Some(map.next_value_seed(ReflectDeserializer::new(self.registry))?);
+                }
+            }
+        }
+        self.build(fields)
+    }
+}
+
+fn parse_path<E: Error>(path: &str) -> Result<ParsedPath, E> {
+    ParsedPath::parse(path).map_err(|error| Error::custom(error.to_string()))
+}
+
+/// Deserializes a variant or field identifier into its index in `names`.
+struct Identifier {
+    names: &'static [&'static str],
+}
+
+impl<'de> DeserializeSeed<'de> for Identifier {
+    type Value = usize;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_identifier(self)
+    }
+}
+
+impl<'de> Visitor<'de> for Identifier {
+    type Value = usize;
+
+    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+        write!(formatter, "one of {:?}", self.names)
+    }
+
+    fn visit_u64<E: Error>(self, index: u64) -> Result<Self::Value, E> {
+        let index = index as usize;
+        if index < self.names.len() {
+            Ok(index)
+        } else {
+            Err(Error::invalid_value(
+                serde::de::Unexpected::Unsigned(index as u64),
+                &self,
+            ))
+        }
+    }
+
+    fn visit_str<E: Error>(self, name: &str) -> Result<Self::Value, E> {
+        self.names
+            .iter()
+            .position(|candidate| *candidate == name)
+            .ok_or_else(|| Error::unknown_variant(name, self.names))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::serde::{ReflectDiffDeserializer, ReflectDiffSerializer};
+    use crate::{self as bevy_reflect, diff, Reflect, TypeRegistry};
+    use bevy_utils::{HashMap, HashSet};
+    use bincode::Options;
+    use serde::de::DeserializeSeed;
+
+    #[derive(Reflect, Clone, Debug, PartialEq)]
+    struct MyStruct {
+        value: f32,
+        list: Vec<u8>,
+        map: HashMap<u8, String>,
+        set: HashSet<u8>,
+        option: Option<u8>,
+    }
+
+    fn get_registry() -> TypeRegistry {
+        let mut registry = TypeRegistry::default();
+        registry.register::<MyStruct>();
+        registry.register::<Option<u8>>();
+        registry
+    }
+
+    fn values() -> (MyStruct, MyStruct) {
+        let from = MyStruct {
+            value: 1.0,
+            list: vec![1, 2, 3],
+            map: HashMap::from_iter([(1, String::from("one")), (2, String::from("two"))]),
+            set: HashSet::from_iter([1, 2]),
+            option: None,
+        };
+        let to = MyStruct {
+            value: 2.0,
+            list: vec![1, 3, 4],
+            map: HashMap::from_iter([(1, String::from("uno")), (3, String::from("three"))]),
+            set: HashSet::from_iter([2, 3]),
+            option: Some(1),
+        };
+        (from, to)
+    }
+
+    #[test]
+    fn should_round_trip_ron() {
+        let registry = get_registry();
+        let (from, to) = values();
+        let diff = diff(&from, &to);
+
+        let output = ron::to_string(&ReflectDiffSerializer::new(&diff, &registry)).unwrap();
+        let mut deserializer = ron::Deserializer::from_str(&output).unwrap();
+        let diff = ReflectDiffDeserializer::new(&registry)
+            .deserialize(&mut deserializer)
+            .unwrap();
+
+        let mut value = from.clone();
+        diff.apply(&mut value).unwrap();
+        assert_eq!(to, value);
+    }
+
+    #[test]
+    fn should_round_trip_bincode() {
+        let registry = get_registry();
+        let (from, to) = values();
+        let diff = diff(&from, &to);
+
+        let options = bincode::DefaultOptions::new();
+        let output = options
+            .serialize(&ReflectDiffSerializer::new(&diff, &registry))
+            .unwrap();
+        let mut deserializer = bincode::Deserializer::from_slice(&output, options);
+        let diff = ReflectDiffDeserializer::new(&registry)
+            .deserialize(&mut deserializer)
+            .unwrap();
+
+        let mut value = from.clone();
+        diff.apply(&mut value).unwrap();
+        assert_eq!(to, value);
+    }
+}
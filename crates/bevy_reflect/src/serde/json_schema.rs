@@ -0,0 +1,958 @@
+use crate::attributes::CustomAttributes;
+use crate::serde::{SerializationData, TypedReflectSerializer};
+use crate::{
+    EnumInfo, NamedField, PartialReflect, Reflect, ReflectRef, ReflectSerialize, TypeInfo,
+    TypeRegistry, UnnamedField, VariantInfo,
+};
+use serde::ser::{Serialize, SerializeMap, Serializer};
+use std::any::TypeId;
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::fmt::{self, Debug, Formatter};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// The JSON Schema dialect of the documents produced by [`JsonSchemaGenerator`].
+pub const JSON_SCHEMA_DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// The keyword under which [custom attributes] are exported,
+/// as a map from the attribute's type path to its serialized value.
+///
+/// [custom attributes]: crate::attributes::CustomAttributes
+pub const JSON_SCHEMA_ATTRIBUTES_KEYWORD: &str = "x-reflect-attributes";
+
+/// A [JSON Schema], as produced by [`JsonSchemaGenerator`].
+///
+/// Schemas implement [`Serialize`], so they can be written out with any JSON serializer.
+///
+/// [JSON Schema]: https://json-schema.org
+#[derive(Clone, Debug)]
+pub enum JsonSchema<'a> {
+    /// A boolean schema, which accepts every value if `true` and no value if `false`.
+    Bool(bool),
+    /// A schema described by its keywords.
+    Object(Box<JsonSchemaObject<'a>>),
+}
+
+impl<'a> JsonSchema<'a> {
+    /// Returns the keywords of this schema, or `None` if it is a boolean schema.
+    pub fn as_object(&self) -> Option<&JsonSchemaObject<'a>> {
+        match self {
+            JsonSchema::Bool(_) => None,
+            JsonSchema::Object(object) => Some(object),
+        }
+    }
+
+    /// Returns the keywords of this schema mutably, or `None` if it is a boolean schema.
+    pub fn as_object_mut(&mut self) -> Option<&mut JsonSchemaObject<'a>> {
+        match self {
+            JsonSchema::Bool(_) => None,
+            JsonSchema::Object(object) => Some(object),
+        }
+    }
+}
+
+impl Default for JsonSchema<'_> {
+    /// The empty schema, which accepts every value.
+    fn default() -> Self {
+        JsonSchemaObject::default().into()
+    }
+}
+
+impl<'a> From<JsonSchemaObject<'a>> for JsonSchema<'a> {
+    fn from(object: JsonSchemaObject<'a>) -> Self {
+        JsonSchema::Object(Box::new(object))
+    }
+}
+
+impl Serialize for JsonSchema<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            JsonSchema::Bool(value) => serializer.serialize_bool(*value),
+            JsonSchema::Object(object) => object.serialize(serializer),
+        }
+    }
+}
+
+/// The type of value a [`JsonSchemaObject`] accepts, from its `type` keyword.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum JsonSchemaType {
+    Null,
+    Boolean,
+    Integer,
+    Number,
+    String,
+    Array,
+    Object,
+}
+
+impl JsonSchemaType {
+    /// The name of the type in a JSON Schema document.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            JsonSchemaType::Null => "null",
+            JsonSchemaType::Boolean => "boolean",
+            JsonSchemaType::Integer => "integer",
+            JsonSchemaType::Number => "number",
+            JsonSchemaType::String => "string",
+            JsonSchemaType::Array => "array",
+            JsonSchemaType::Object => "object",
+        }
+    }
+}
+
+impl Serialize for JsonSchemaType {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+/// The keywords of a [`JsonSchema`].
+///
+/// Only the keywords used by [`JsonSchemaGenerator`] are supported.
+/// Keywords that are `None` are left out of the serialized schema.
+#[derive(Clone, Debug, Default)]
+pub struct JsonSchemaObject<'a> {
+    /// The `$schema` keyword, naming the dialect of the document.
+    pub dialect: Option<Cow<'static, str>>,
+    /// The `$ref` keyword, referencing another schema by URI.
+    pub reference: Option<String>,
+    /// The `title` keyword.
+    pub title: Option<String>,
+    /// The `description` keyword.
+    pub description: Option<String>,
+    /// The `type` keyword.
+    pub schema_type: Option<JsonSchemaType>,
+    /// The `const` keyword, restricting the value to a single string.
+    pub constant: Option<String>,
+    /// The `pattern` keyword, a regular expression that strings must match.
+    pub pattern: Option<String>,
+    /// The `minimum` keyword.
+    pub minimum: Option<i128>,
+    /// The `maximum` keyword.
+    pub maximum: Option<i128>,
+    /// The `minLength` keyword.
+    pub min_length: Option<usize>,
+    /// The `maxLength` keyword.
+    pub max_length: Option<usize>,
+    /// The `properties` keyword.
+    pub properties: Option<BTreeMap<String, JsonSchema<'a>>>,
+    /// The `required` keyword, listing the properties that must be present.
+    pub required: Option<Vec<String>>,
+    /// The `additionalProperties` keyword.
+    pub additional_properties: Option<JsonSchema<'a>>,
+    /// The `minProperties` keyword.
+    pub min_properties: Option<usize>,
+    /// The `maxProperties` keyword.
+    pub max_properties: Option<usize>,
+    /// The `prefixItems` keyword.
+    pub prefix_items: Option<Vec<JsonSchema<'a>>>,
+    /// The `items` keyword.
+    pub items: Option<JsonSchema<'a>>,
+    /// The `minItems` keyword.
+    pub min_items: Option<usize>,
+    /// The `maxItems` keyword.
+    pub max_items: Option<usize>,
+    /// The `uniqueItems` keyword.
+    pub unique_items: Option<bool>,
+    /// The `anyOf` keyword.
+    pub any_of: Option<Vec<JsonSchema<'a>>>,
+    /// The `oneOf` keyword.
+    pub one_of: Option<Vec<JsonSchema<'a>>>,
+    /// The `$defs` keyword, holding the definitions that `$ref`s point to.
+    pub definitions: Option<BTreeMap<String, JsonSchema<'a>>>,
+    /// The [custom attributes] exported under [`JSON_SCHEMA_ATTRIBUTES_KEYWORD`].
+    ///
+    /// [custom attributes]: crate::attributes::CustomAttributes
+    pub attributes: Option<JsonSchemaAttributes<'a>>,
+}
+
+impl Serialize for JsonSchemaObject<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+
+        macro_rules! keywords {
+            ($($keyword:expr => $value:expr),* $(,)?) => {
+                $(
+                    if let Some(value) = &$value {
+                        map.serialize_entry($keyword, value)?;
+                    }
+                )*
+            };
+        }
+
+        keywords!(
+            "$schema" => self.dialect,
+            "$ref" => self.reference,
+            "title" => self.title,
+            "description" => self.description,
+            "type" => self.schema_type,
+            "const" => self.constant,
+            "pattern" => self.pattern,
+            "minimum" => self.minimum.map(Integer),
+            "maximum" => self.maximum.map(Integer),
+            "minLength" => self.min_length,
+            "maxLength" => self.max_length,
+            "properties" => self.properties,
+            "required" => self.required,
+            "additionalProperties" => self.additional_properties,
+            "minProperties" => self.min_properties,
+            "maxProperties" => self.max_properties,
+            "prefixItems" => self.prefix_items,
+            "items" => self.items,
+            "minItems" => self.min_items,
+            "maxItems" => self.max_items,
+            "uniqueItems" => self.unique_items,
+            "anyOf" => self.any_of,
+            "oneOf" => self.one_of,
+            "$defs" => self.definitions,
+            JSON_SCHEMA_ATTRIBUTES_KEYWORD => self.attributes,
+        );
+
+        map.end()
+    }
+}
+
+/// Serializes an integer bound as the smallest integer type that fits it,
+/// since many serializers don't support 128-bit integers.
+struct Integer(i128);
+
+impl Serialize for Integer {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if let Ok(value) = i64::try_from(self.0) {
+            serializer.serialize_i64(value)
+        } else if let Ok(value) = u64::try_from(self.0) {
+            serializer.serialize_u64(value)
+        } else {
+            serializer.serialize_i128(self.0)
+        }
+    }
+}
+
+/// The [custom attributes] of a type, field or variant,
+/// serialized as a map from each attribute's type path to its value.
+///
+/// [custom attributes]: crate::attributes::CustomAttributes
+#[derive(Clone)]
+pub struct JsonSchemaAttributes<'a> {
+    attributes: Vec<&'a dyn Reflect>,
+    registry: &'a TypeRegistry,
+}
+
+impl<'a> JsonSchemaAttributes<'a> {
+    /// Collects the attributes that can be serialized with the types in `registry`.
+    ///
+    /// Returns `None` if there are none.
+    pub fn new(attributes: &'a CustomAttributes, registry: &'a TypeRegistry) -> Option<Self> {
+        let attributes = attributes
+            .iter()
+            .map(|(_, attribute)| attribute)
+            .filter(|attribute| is_serializable(attribute.as_partial_reflect(), registry))
+            .collect::<Vec<_>>();
+        (!attributes.is_empty()).then_some(Self {
+            attributes,
+            registry,
+        })
+    }
+}
+
+impl Debug for JsonSchemaAttributes<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(
+                self.attributes
+                    .iter()
+                    .map(|attribute| attribute.reflect_type_path()),
+            )
+            .finish()
+    }
+}
+
+impl Serialize for JsonSchemaAttributes<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.attributes.len()))?;
+        for attribute in &self.attributes {
+            map.serialize_entry(
+                attribute.reflect_type_path(),
+                &TypedReflectSerializer::new(attribute.as_partial_reflect(), self.registry),
+            )?;
+        }
+        map.end()
+    }
+}
+
+/// Returns whether [`TypedReflectSerializer`] can serialize `value` with the types in `registry`.
+fn is_serializable(value: &dyn PartialReflect, registry: &TypeRegistry) -> bool {
+    let Some(type_info) = value.get_represented_type_info() else {
+        return false;
+    };
+    if registry
+        .get_type_data::<ReflectSerialize>(type_info.type_id())
+        .is_some()
+    {
+        return true;
+    }
+
+    let mut fields: Box<dyn Iterator<Item = &dyn PartialReflect>> = match value.reflect_ref() {
+        ReflectRef::Struct(value) => Box::new(value.iter_fields()),
+        ReflectRef::TupleStruct(value) => Box::new(value.iter_fields()),
+        ReflectRef::Tuple(value) => Box::new(value.iter_fields()),
+        ReflectRef::List(value) => Box::new(value.iter()),
+        ReflectRef::Array(value) => Box::new(value.iter()),
+        ReflectRef::Map(value) => Box::new(value.iter().flat_map(|(key, value)| [key, value])),
+        ReflectRef::Set(value) => Box::new(value.iter()),
+        ReflectRef::Enum(value) => Box::new(value.iter_fields().map(|field| field.value())),
+        ReflectRef::Value(_) => return false,
+    };
+    fields.all(|field| is_serializable(field, registry))
+}
+
+/// Type data providing a hand-written JSON Schema for a type.
+///
+/// [`JsonSchemaGenerator`] derives a type's schema from its [`TypeInfo`],
+/// which matches the output of [`ReflectSerializer`] unless the type registers [`ReflectSerialize`]
+/// with a `Serialize` implementation that does not follow its reflected shape.
+/// Such types can register this type data to describe their serialized form instead.
+///
+/// # Example
+///
+/// ```
+/// # use bevy_reflect::{Reflect, TypeRegistry};
+/// # use bevy_reflect::serde::{JsonSchemaObject, JsonSchemaType, ReflectJsonSchema};
+/// #[derive(Reflect)]
+/// struct Rgba(f32, f32, f32, f32);
+///
+/// let mut registry = TypeRegistry::new();
+/// registry.register::<Rgba>();
+/// registry
+///     .get_mut(std::any::TypeId::of::<Rgba>())
+///     .unwrap()
+///     .insert(ReflectJsonSchema::new(JsonSchemaObject {
+///         schema_type: Some(JsonSchemaType::String),
+///         pattern: Some(String::from("^#[0-9a-f]{8}$")),
+///         ..Default::default()
+///     }));
+/// ```
+///
+/// [`ReflectSerializer`]: crate::serde::ReflectSerializer
+#[derive(Clone, Debug)]
+pub struct ReflectJsonSchema {
+    schema: JsonSchema<'static>,
+}
+
+impl ReflectJsonSchema {
+    /// Creates type data that describes the type with `schema`.
+    pub fn new(schema: impl Into<JsonSchema<'static>>) -> Self {
+        Self {
+            schema: schema.into(),
+        }
+    }
+
+    /// The schema of the type's serialized form.
+    pub fn schema(&self) -> &JsonSchema<'static> {
+        &self.schema
+    }
+}
+
+/// Generates [JSON Schema] documents describing the JSON output of the reflection serializers.
+///
+/// Every registered type gets a definition under `$defs`, keyed by its type path,
+/// describing what [`TypedReflectSerializer`] produces for it.
+/// The root of the document produced by [`generate`] describes what [`ReflectSerializer`] produces:
+/// a single-entry map from a registered type path to a value of that type.
+///
+/// Fields marked with `#[reflect(skip_serializing)]` are left out,
+/// [custom attributes] are exported under [`JSON_SCHEMA_ATTRIBUTES_KEYWORD`],
+/// and doc comments are exported as descriptions when the `documentation` feature is enabled.
+///
+/// The generated [`JsonSchema`] implements [`Serialize`],
+/// so it can be written out with any JSON serializer.
+///
+/// # Example
+///
+/// ```
+/// # use bevy_reflect::{Reflect, TypeRegistry, serde::JsonSchemaGenerator};
+/// # use serde_json::json;
+/// #[derive(Reflect)]
+/// #[type_path = "my_crate"]
+/// struct MyStruct {
+///   value: Option<u8>,
+/// }
+///
+/// let mut registry = TypeRegistry::new();
+/// registry.register::<MyStruct>();
+///
+/// let schema = JsonSchemaGenerator::new(&registry).generate();
+/// let schema = serde_json::to_value(&schema).unwrap();
+/// assert_eq!(
+///     json!({ "$ref": "#/$defs/core::option::Option%3Cu8%3E" }),
+///     schema["$defs"]["my_crate::MyStruct"]["properties"]["value"],
+/// );
+/// ```
+///
+/// [JSON Schema]: https://json-schema.org
+/// [`generate`]: JsonSchemaGenerator::generate
+/// [`ReflectSerializer`]: crate::serde::ReflectSerializer
+/// [custom attributes]: crate::attributes::CustomAttributes
+pub struct JsonSchemaGenerator<'a> {
+    registry: &'a TypeRegistry,
+}
+
+impl<'a> JsonSchemaGenerator<'a> {
+    /// Creates a generator for the types in `registry`.
+    pub fn new(registry: &'a TypeRegistry) -> Self {
+        Self { registry }
+    }
+
+    /// Generates a document with a definition for every registered type.
+    pub fn generate(&self) -> JsonSchema<'a> {
+        let mut definitions = BTreeMap::new();
+        let mut properties = BTreeMap::new();
+        for registration in self.registry.iter() {
+            let type_path = registration.type_info().type_path();
+            definitions.insert(
+                type_path.to_string(),
+                self.type_schema(registration.type_info()),
+            );
+            properties.insert(type_path.to_string(), definition_ref(type_path));
+        }
+
+        JsonSchemaObject {
+            dialect: Some(Cow::Borrowed(JSON_SCHEMA_DIALECT)),
+            schema_type: Some(JsonSchemaType::Object),
+            properties: Some(properties),
+            additional_properties: Some(JsonSchema::Bool(false)),
+            min_properties: Some(1),
+            max_properties: Some(1),
+            definitions: Some(definitions),
+            ..Default::default()
+        }
+        .into()
+    }
+
+    /// Generates the schema for a single type.
+    ///
+    /// Registered types used by this type are referenced by their definition in [`generate`],
+    /// while unregistered ones are inlined.
+    ///
+    /// [`generate`]: JsonSchemaGenerator::generate
+    pub fn type_schema(&self, type_info: &'a TypeInfo) -> JsonSchema<'a> {
+        if let Some(schema) = self
+            .registry
+            .get_type_data::<ReflectJsonSchema>(type_info.type_id())
+        {
+            return schema.schema().clone();
+        }
+
+        let serialization_data = self
+            .registry
+            .get_type_data::<SerializationData>(type_info.type_id());
+        let is_skipped =
+            |index| serialization_data.is_some_and(|data| data.is_field_skipped(index));
+
+        let mut schema = match type_info {
+            TypeInfo::Struct(info) => self.struct_schema(
+                info.iter()
+                    .enumerate()
+                    .filter(|(index, _)| !is_skipped(*index))
+                    .map(|(_, field)| field),
+            ),
+            TypeInfo::TupleStruct(info) => self.tuple_schema(
+                info.iter()
+                    .enumerate()
+                    .filter(|(index, _)| !is_skipped(*index))
+                    .map(|(_, field)| field),
+            ),
+            TypeInfo::Tuple(info) => self.tuple_schema(info.iter()),
+            TypeInfo::List(info) => JsonSchemaObject {
+                schema_type: Some(JsonSchemaType::Array),
+                items: Some(self.field_schema(info.item_ty().id(), info.item_info())),
+                ..Default::default()
+            },
+            TypeInfo::Array(info) => JsonSchemaObject {
+                schema_type: Some(JsonSchemaType::Array),
+                items: Some(self.field_schema(info.item_ty().id(), info.item_info())),
+                min_items: Some(info.capacity()),
+                max_items: Some(info.capacity()),
+                ..Default::default()
+            },
+            TypeInfo::Set(info) => JsonSchemaObject {
+                schema_type: Some(JsonSchemaType::Array),
+                items: Some(self.field_schema(info.value_ty().id(), None)),
+                unique_items: Some(true),
+                ..Default::default()
+            },
+            TypeInfo::Map(info) => JsonSchemaObject {
+                schema_type: Some(JsonSchemaType::Object),
+                additional_properties: Some(
+                    self.field_schema(info.value_ty().id(), info.value_info()),
+                ),
+                ..Default::default()
+            },
+            TypeInfo::Enum(info) => self.enum_schema(info),
+            TypeInfo::Value(info) => value_schema(info.type_id()),
+        };
+
+        schema.title = Some(type_info.type_path_table().short_path().to_string());
+        #[cfg(feature = "documentation")]
+        describe(&mut schema, type_info.docs());
+        schema.attributes = match type_info {
+            TypeInfo::Struct(info) => self.attributes(info.custom_attributes()),
+            TypeInfo::TupleStruct(info) => self.attributes(info.custom_attributes()),
+            TypeInfo::Enum(info) => self.attributes(info.custom_attributes()),
+            _ => None,
+        };
+
+        schema.into()
+    }
+
+    fn struct_schema(&self, fields: impl Iterator<Item = &'a NamedField>) -> JsonSchemaObject<'a> {
+        let mut properties = BTreeMap::new();
+        let mut required = Vec::new();
+        for field in fields {
+            properties.insert(field.name().to_string(), self.named_field_schema(field));
+            required.push(field.name().to_string());
+        }
+        JsonSchemaObject {
+            schema_type: Some(JsonSchemaType::Object),
+            properties: Some(properties),
+            required: Some(required),
+            additional_properties: Some(JsonSchema::Bool(false)),
+            ..Default::default()
+        }
+    }
+
+    fn tuple_schema(&self, fields: impl Iterator<Item = &'a UnnamedField>) -> JsonSchemaObject<'a> {
+        let items = fields
+            .map(|field| self.unnamed_field_schema(field))
+            .collect::<Vec<_>>();
+        JsonSchemaObject {
+            schema_type: Some(JsonSchemaType::Array),
+            min_items: Some(items.len()),
+            max_items: Some(items.len()),
+            prefix_items: Some(items),
+            items: Some(JsonSchema::Bool(false)),
+            ..Default::default()
+        }
+    }
+
+    fn enum_schema(&self, info: &'a EnumInfo) -> JsonSchemaObject<'a> {
+        // `Option` is serialized as either `null` or its inner value
+        if info.type_path_table().module_path() == Some("core::option")
+            && info.type_path_table().ident() == Some("Option")
+        {
+            let some = match info.variant("Some") {
+                Some(VariantInfo::Tuple(variant)) if variant.field_len() == 1 => {
+                    self.unnamed_field_schema(variant.field_at(0).unwrap())
+                }
+                _ => JsonSchema::default(),
+            };
+            let null = JsonSchemaObject {
+                schema_type: Some(JsonSchemaType::Null),
+                ..Default::default()
+            };
+            return JsonSchemaObject {
+                any_of: Some(vec![null.into(), some]),
+                ..Default::default()
+            };
+        }
+
+        let variants = info
+            .iter()
+            .map(|variant| {
+                let mut schema = match variant {
+                    VariantInfo::Unit(_) => JsonSchemaObject {
+                        constant: Some(variant.name().to_string()),
+                        ..Default::default()
+                    },
+                    VariantInfo::Tuple(info) if info.field_len() == 1 => single_entry(
+                        variant.name(),
+                        self.unnamed_field_schema(info.field_at(0).unwrap()),
+                    ),
+                    VariantInfo::Tuple(info) => {
+                        single_entry(variant.name(), self.tuple_schema(info.iter()).into())
+                    }
+                    VariantInfo::Struct(info) => {
+                        single_entry(variant.name(), self.struct_schema(info.iter()).into())
+                    }
+                };
+                #[cfg(feature = "documentation")]
+                describe(&mut schema, variant.docs());
+                schema.attributes = self.attributes(variant.custom_attributes());
+                schema.into()
+            })
+            .collect::<Vec<_>>();
+
+        JsonSchemaObject {
+            one_of: Some(variants),
+            ..Default::default()
+        }
+    }
+
+    fn named_field_schema(&self, field: &'a NamedField) -> JsonSchema<'a> {
+        let mut schema = self.field_schema(field.type_id(), field.type_info());
+        if let Some(object) = schema.as_object_mut() {
+            #[cfg(feature = "documentation")]
+            describe(object, field.docs());
+            object.attributes = self.attributes(field.custom_attributes());
+        }
+        schema
+    }
+
+    fn unnamed_field_schema(&self, field: &'a UnnamedField) -> JsonSchema<'a> {
+        let mut schema = self.field_schema(field.type_id(), field.type_info());
+        if let Some(object) = schema.as_object_mut() {
+            #[cfg(feature = "documentation")]
+            describe(object, field.docs());
+            object.attributes = self.attributes(field.custom_attributes());
+        }
+        schema
+    }
+
+    fn field_schema(&self, type_id: TypeId, type_info: Option<&'a TypeInfo>) -> JsonSchema<'a> {
+        if let Some(registration) = self.registry.get(type_id) {
+            return definition_ref(registration.type_info().type_path());
+        }
+        type_info.map_or_else(JsonSchema::default, |info| self.type_schema(info))
+    }
+
+    fn attributes(&self, attributes: &'a CustomAttributes) -> Option<JsonSchemaAttributes<'a>> {
+        JsonSchemaAttributes::new(attributes, self.registry)
+    }
+}
+
+#[cfg(feature = "documentation")]
+fn describe(schema: &mut JsonSchemaObject, docs: Option<&str>) {
+    if let Some(docs) = docs {
+        schema.description = Some(docs.trim().to_string());
+    }
+}
+
+fn single_entry<'a>(name: &str, schema: JsonSchema<'a>) -> JsonSchemaObject<'a> {
+    JsonSchemaObject {
+        schema_type: Some(JsonSchemaType::Object),
+        properties: Some(BTreeMap::from([(name.to_string(), schema)])),
+        required: Some(vec![name.to_string()]),
+        additional_properties: Some(JsonSchema::Bool(false)),
+        ..Default::default()
+    }
+}
+
+/// Returns a `$ref` to the definition of `type_path`,
+/// percent-encoding the characters that are not allowed in a URI fragment.
+fn definition_ref(type_path: &str) -> JsonSchema<'static> {
+    let mut reference = String::from("#/$defs/");
+    for byte in type_path.bytes() {
+        match byte {
+            b'~' => reference.push_str("~0"),
+            b'/' => reference.push_str("~1"),
+            b'a'..=b'z'
+            | b'A'..=b'Z'
+            | b'0'..=b'9'
+            | b'-'
+            | b'.'
+            | b'_'
+            | b':'
+            | b'('
+            | b')'
+            | b',' => {
+                reference.push(byte as char);
+            }
+            _ => reference.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    JsonSchemaObject {
+        reference: Some(reference),
+        ..Default::default()
+    }
+    .into()
+}
+
+/// The schema of value types with a well-known serialized form.
+///
+/// Other value types are described by the empty schema, which accepts anything.
+fn value_schema(type_id: TypeId) -> JsonSchemaObject<'static> {
+    let typed = |schema_type| JsonSchemaObject {
+        schema_type: Some(schema_type),
+        ..Default::default()
+    };
+
+    macro_rules! integers {
+        ($($ty:ty),*) => {
+            $(
+                if type_id == TypeId::of::<$ty>() {
+                    return JsonSchemaObject {
+                        minimum: Some(<$ty>::MIN as i128),
+                        maximum: Some(<$ty>::MAX as i128),
+                        ..typed(JsonSchemaType::Integer)
+                    };
+                }
+            )*
+        };
+    }
+
+    macro_rules! strings {
+        ($($ty:ty),*) => {
+            if $(type_id == TypeId::of::<$ty>())||* {
+                return typed(JsonSchemaType::String);
+            }
+        };
+    }
+
+    integers!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+    strings!(
+        String,
+        &'static str,
+        Cow<'static, str>,
+        PathBuf,
+        &'static Path,
+        Cow<'static, Path>
+    );
+
+    #[cfg(feature = "smol_str")]
+    strings!(smol_str::SmolStr);
+
+    if type_id == TypeId::of::<bool>() {
+        typed(JsonSchemaType::Boolean)
+    } else if type_id == TypeId::of::<u128>() {
+        JsonSchemaObject {
+            minimum: Some(0),
+            ..typed(JsonSchemaType::Integer)
+        }
+    } else if type_id == TypeId::of::<i128>() {
+        typed(JsonSchemaType::Integer)
+    } else if type_id == TypeId::of::<f32>() || type_id == TypeId::of::<f64>() {
+        typed(JsonSchemaType::Number)
+    } else if type_id == TypeId::of::<char>() {
+        JsonSchemaObject {
+            min_length: Some(1),
+            max_length: Some(1),
+            ..typed(JsonSchemaType::String)
+        }
+    } else if type_id == TypeId::of::<Duration>() {
+        let secs = JsonSchemaObject {
+            minimum: Some(0),
+            maximum: Some(u64::MAX as i128),
+            ..typed(JsonSchemaType::Integer)
+        };
+        let nanos = JsonSchemaObject {
+            minimum: Some(0),
+            maximum: Some(999_999_999),
+            ..typed(JsonSchemaType::Integer)
+        };
+        JsonSchemaObject {
+            properties: Some(BTreeMap::from([
+                (String::from("secs"), secs.into()),
+                (String::from("nanos"), nanos.into()),
+            ])),
+            required: Some(vec![String::from("secs"), String::from("nanos")]),
+            additional_properties: Some(JsonSchema::Bool(false)),
+            ..typed(JsonSchemaType::Object)
+        }
+    } else {
+        JsonSchemaObject::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::serde::{
+        JsonSchemaGenerator, JsonSchemaObject, JsonSchemaType, ReflectJsonSchema, ReflectSerializer,
+    };
+    use crate::{self as bevy_reflect, Reflect, TypeRegistry};
+    use bevy_utils::HashMap;
+    use serde_json::json;
+    use std::any::TypeId;
+
+    #[derive(Reflect)]
+    #[type_path = "test"]
+    struct Label(String);
+
+    #[derive(Reflect)]
+    #[type_path = "test"]
+    struct MyStruct {
+        #[reflect(@Label(String::from("editor")))]
+        name: String,
+        #[reflect(skip_serializing)]
+        cached: u32,
+        tuple: (u8, bool),
+        list: Vec<f32>,
+        map: HashMap<String, i16>,
+        option: Option<MyEnum>,
+        opaque: Opaque,
+    }
+
+    #[derive(Reflect)]
+    #[type_path = "test"]
+    enum MyEnum {
+        Unit,
+        Newtype(u8),
+        Tuple(u8, u8),
+        Struct { value: char },
+    }
+
+    #[derive(Reflect)]
+    #[type_path = "test"]
+    struct Opaque(u8);
+
+    fn get_registry() -> TypeRegistry {
+        let mut registry = TypeRegistry::default();
+        registry.register::<MyStruct>();
+        registry.register::<Label>();
+        registry.register::<(u8, bool)>();
+        registry.register::<Vec<f32>>();
+        registry.register::<HashMap<String, i16>>();
+        registry.register::<Option<MyEnum>>();
+        registry.register::<Opaque>();
+        registry
+            .get_mut(TypeId::of::<Opaque>())
+            .unwrap()
+            .insert(ReflectJsonSchema::new(JsonSchemaObject {
+                schema_type: Some(JsonSchemaType::String),
+                ..Default::default()
+            }));
+        registry
+    }
+
+    #[test]
+    fn should_generate_struct_schema() {
+        let registry = get_registry();
+        let schema = serde_json::to_value(JsonSchemaGenerator::new(&registry).generate()).unwrap();
+
+        let expected = json!({
+            "title": "MyStruct",
+            "type": "object",
+            "properties": {
+                "name": {
+                    "$ref": "#/$defs/alloc::string::String",
+                    "x-reflect-attributes": { "test::Label": ["editor"] },
+                },
+                "tuple": { "$ref": "#/$defs/(u8,%20bool)" },
+                "list": { "$ref": "#/$defs/alloc::vec::Vec%3Cf32%3E" },
+                "map": {
+                    "$ref": "#/$defs/bevy_utils::hashbrown::HashMap%3Calloc::string::String,%20i16,%20bevy_utils::hashbrown::hash_map::DefaultHashBuilder%3E"
+                },
+                "option": { "$ref": "#/$defs/core::option::Option%3Ctest::MyEnum%3E" },
+                "opaque": { "$ref": "#/$defs/test::Opaque" },
+            },
+            "required": ["name", "tuple", "list", "map", "option", "opaque"],
+            "additionalProperties": false,
+        });
+        assert_eq!(expected, schema["$defs"]["test::MyStruct"]);
+        assert_eq!(json!({ "type": "string" }), schema["$defs"]["test::Opaque"]);
+        assert_eq!(
+            json!({ "$ref": "#/$defs/test::MyStruct" }),
+            schema["properties"]["test::MyStruct"]
+        );
+    }
+
+    #[test]
+    fn should_generate_enum_schema() {
+        let registry = get_registry();
+        let schema = serde_json::to_value(JsonSchemaGenerator::new(&registry).generate()).unwrap();
+
+        let expected = json!({
+            "title": "MyEnum",
+            "oneOf": [
+                { "const": "Unit" },
+                {
+                    "type": "object",
+                    "properties": { "Newtype": { "$ref": "#/$defs/u8" } },
+                    "required": ["Newtype"],
+                    "additionalProperties": false,
+                },
+                {
+                    "type": "object",
+                    "properties": {
+                        "Tuple": {
+                            "type": "array",
+                            "minItems": 2,
+                            "maxItems": 2,
+                            "prefixItems": [{ "$ref": "#/$defs/u8" }, { "$ref": "#/$defs/u8" }],
+                            "items": false,
+                        }
+                    },
+                    "required": ["Tuple"],
+                    "additionalProperties": false,
+                },
+                {
+                    "type": "object",
+                    "properties": {
+                        "Struct": {
+                            "type": "object",
+                            "properties": { "value": { "$ref": "#/$defs/char" } },
+                            "required": ["value"],
+                            "additionalProperties": false,
+                        }
+                    },
+                    "required": ["Struct"],
+                    "additionalProperties": false,
+                },
+            ],
+        });
+        assert_eq!(expected, schema["$defs"]["test::MyEnum"]);
+
+        let option = &schema["$defs"]["core::option::Option<test::MyEnum>"];
+        assert_eq!(
+            json!([{ "type": "null" }, { "$ref": "#/$defs/test::MyEnum" }]),
+            option["anyOf"]
+        );
+
+        assert_eq!(
+            json!({ "title": "u8", "type": "integer", "minimum": 0, "maximum": 255 }),
+            schema["$defs"]["u8"]
+        );
+    }
+
+    #[test]
+    fn should_match_serializer_output() {
+        let registry = get_registry();
+        let schema = serde_json::to_value(JsonSchemaGenerator::new(&registry).generate()).unwrap();
+
+        let value = MyStruct {
+            name: String::from("name"),
+            cached: 0,
+            tuple: (1, true),
+            list: vec![1.0],
+            map: HashMap::default(),
+            option: Some(MyEnum::Struct { value: 'x' }),
+            opaque: Opaque(0),
+        };
+        let output = serde_json::to_value(ReflectSerializer::new(&value, &registry)).unwrap();
+
+        let (type_path, output) = output.as_object().unwrap().iter().next().unwrap();
+        assert!(schema["properties"].get(type_path).is_some());
+
+        let definition = &schema["$defs"][type_path];
+        let properties = definition["properties"].as_object().unwrap();
+        let fields = output.as_object().unwrap();
+        assert_eq!(
+            properties.keys().collect::<Vec<_>>(),
+            fields.keys().collect::<Vec<_>>()
+        );
+        assert_eq!(json!({ "Struct": { "value": "x" } }), fields["option"]);
+    }
+
+    #[cfg(feature = "documentation")]
+    #[test]
+    fn should_export_docs() {
+        /// A documented struct.
+        #[derive(Reflect)]
+        #[type_path = "test"]
+        struct Documented {
+            /// A documented field.
+            value: u8,
+        }
+
+        let mut registry = TypeRegistry::new();
+        registry.register::<Documented>();
+        let schema = serde_json::to_value(JsonSchemaGenerator::new(&registry).generate()).unwrap();
+
+        let definition = &schema["$defs"]["test::Documented"];
+        assert_eq!(json!("A documented struct."), definition["description"]);
+        assert_eq!(
+            json!("A documented field."),
+            definition["properties"]["value"]["description"]
+        );
+    }
+}
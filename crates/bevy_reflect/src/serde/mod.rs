@@ -1,8 +1,16 @@
+mod binary;
 mod de;
+mod diff;
+#[cfg(feature = "json_schema")]
+mod json_schema;
 mod ser;
 mod type_data;
 
+pub use binary::*;
 pub use de::*;
+pub use diff::*;
+#[cfg(feature = "json_schema")]
+pub use json_schema::*;
 pub use ser::*;
 pub use type_data::*;
 
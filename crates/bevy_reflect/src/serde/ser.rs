@@ -1,6 +1,6 @@
 use crate::{
-    Array, Enum, List, Map, PartialReflect, ReflectRef, ReflectSerialize, Set, Struct, Tuple,
-    TupleStruct, TypeInfo, TypeRegistry, VariantInfo, VariantType,
+    Array, Enum, List, Map, PartialReflect, ReflectBox, ReflectRef, ReflectSerialize, Set, Struct,
+    Tuple, TupleStruct, TypeInfo, TypeRegistry, UnnamedField, VariantInfo, VariantType,
 };
 use serde::ser::{
     Error, SerializeStruct, SerializeStructVariant, SerializeTuple, SerializeTupleStruct,
@@ -10,6 +10,7 @@ use serde::{
     ser::{SerializeMap, SerializeSeq},
     Serialize,
 };
+use std::any::TypeId;
 
 use super::SerializationData;
 
@@ -132,7 +133,7 @@ impl<'a> Serialize for ReflectSerializer<'a> {
                     }
                 })?
                 .type_path(),
-            &TypedReflectSerializer::new(self.value, self.registry),
+            &TypedReflectSerializer::new(self.value.as_partial_reflect(), self.registry),
         )?;
         state.end()
     }
@@ -192,6 +193,11 @@ impl<'a> Serialize for TypedReflectSerializer<'a> {
     where
         S: serde::Serializer,
     {
+        // Boxed values are tagged with the type path of their contents
+        if self.value.as_reflect_box().is_some() {
+            return ReflectSerializer::new(self.value, self.registry).serialize(serializer);
+        }
+
         // Handle both Value case and types that have a custom `Serialize`
         let serializable = get_serializable::<S::Error>(self.value, self.registry);
         if let Ok(serializable) = serializable {
@@ -244,6 +250,48 @@ impl<'a> Serialize for TypedReflectSerializer<'a> {
     }
 }
 
+/// Serializes an element of a container, given the [`TypeId`] of the element's declared type.
+///
+/// Elements declared as a reflected [`Box`] are always tagged with their type path,
+/// even when the container is a dynamic one that no longer holds the box itself.
+struct ElementSerializer<'a> {
+    value: &'a dyn PartialReflect,
+    declared_type: Option<TypeId>,
+    registry: &'a TypeRegistry,
+}
+
+impl<'a> ElementSerializer<'a> {
+    fn new(
+        value: &'a dyn PartialReflect,
+        declared_type: Option<TypeId>,
+        registry: &'a TypeRegistry,
+    ) -> Self {
+        Self {
+            value,
+            declared_type,
+            registry,
+        }
+    }
+}
+
+impl<'a> Serialize for ElementSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let is_boxed = self
+            .declared_type
+            .and_then(|type_id| self.registry.get_type_data::<ReflectBox>(type_id))
+            .is_some();
+
+        if is_boxed {
+            ReflectSerializer::new(self.value, self.registry).serialize(serializer)
+        } else {
+            TypedReflectSerializer::new(self.value, self.registry).serialize(serializer)
+        }
+    }
+}
+
 pub struct ReflectValueSerializer<'a> {
     pub registry: &'a TypeRegistry,
     pub value: &'a dyn PartialReflect,
@@ -306,8 +354,11 @@ impl<'a> Serialize for StructSerializer<'a> {
             {
                 continue;
             }
-            let key = struct_info.field_at(index).unwrap().name();
-            state.serialize_field(key, &TypedReflectSerializer::new(value, self.registry))?;
+            let field_info = struct_info.field_at(index).unwrap();
+            state.serialize_field(
+                field_info.name(),
+                &ElementSerializer::new(value, Some(field_info.type_id()), self.registry),
+            )?;
         }
         state.end()
     }
@@ -359,7 +410,8 @@ impl<'a> Serialize for TupleStructSerializer<'a> {
             {
                 continue;
             }
-            state.serialize_field(&TypedReflectSerializer::new(value, self.registry))?;
+            let declared_type = tuple_struct_info.field_at(index).map(UnnamedField::type_id);
+            state.serialize_field(&ElementSerializer::new(value, declared_type, self.registry))?;
         }
         state.end()
     }
@@ -434,24 +486,35 @@ impl<'a> Serialize for EnumSerializer<'a> {
                     let field_info = struct_info.field_at(index).unwrap();
                     state.serialize_field(
                         field_info.name(),
-                        &TypedReflectSerializer::new(field.value(), self.registry),
+                        &ElementSerializer::new(
+                            field.value(),
+                            Some(field_info.type_id()),
+                            self.registry,
+                        ),
                     )?;
                 }
                 state.end()
             }
             VariantType::Tuple if field_len == 1 => {
                 let field = self.enum_value.field_at(0).unwrap();
+                let declared_type = match variant_info {
+                    VariantInfo::Tuple(tuple_info) => {
+                        tuple_info.field_at(0).map(UnnamedField::type_id)
+                    }
+                    _ => None,
+                };
+                let field = ElementSerializer::new(field, declared_type, self.registry);
 
                 if type_info.type_path_table().module_path() == Some("core::option")
                     && type_info.type_path_table().ident() == Some("Option")
                 {
-                    serializer.serialize_some(&TypedReflectSerializer::new(field, self.registry))
+                    serializer.serialize_some(&field)
                 } else {
                     serializer.serialize_newtype_variant(
                         enum_name,
                         variant_index,
                         variant_name,
-                        &TypedReflectSerializer::new(field, self.registry),
+                        &field,
                     )
                 }
             }
@@ -462,9 +525,16 @@ impl<'a> Serialize for EnumSerializer<'a> {
                     variant_name,
                     field_len,
                 )?;
-                for field in self.enum_value.iter_fields() {
-                    state.serialize_field(&TypedReflectSerializer::new(
+                for (index, field) in self.enum_value.iter_fields().enumerate() {
+                    let declared_type = match variant_info {
+                        VariantInfo::Tuple(tuple_info) => {
+                            tuple_info.field_at(index).map(UnnamedField::type_id)
+                        }
+                        _ => None,
+                    };
+                    state.serialize_field(&ElementSerializer::new(
                         field.value(),
+                        declared_type,
                         self.registry,
                     ))?;
                 }
@@ -484,10 +554,22 @@ impl<'a> Serialize for TupleSerializer<'a> {
     where
         S: serde::Serializer,
     {
+        let tuple_info = match self.tuple.get_represented_type_info() {
+            Some(TypeInfo::Tuple(tuple_info)) => Some(tuple_info),
+            _ => None,
+        };
+
         let mut state = serializer.serialize_tuple(self.tuple.field_len())?;
 
-        for value in self.tuple.iter_fields() {
-            state.serialize_element(&TypedReflectSerializer::new(value, self.registry))?;
+        for (index, value) in self.tuple.iter_fields().enumerate() {
+            let declared_type = tuple_info
+                .and_then(|info| info.field_at(index))
+                .map(UnnamedField::type_id);
+            state.serialize_element(&ElementSerializer::new(
+                value,
+                declared_type,
+                self.registry,
+            ))?;
         }
         state.end()
     }
@@ -503,11 +585,18 @@ impl<'a> Serialize for MapSerializer<'a> {
     where
         S: serde::Serializer,
     {
+        let (key_type, value_type) = match self.map.get_represented_type_info() {
+            Some(TypeInfo::Map(map_info)) => {
+                (Some(map_info.key_ty().id()), Some(map_info.value_ty().id()))
+            }
+            _ => (None, None),
+        };
+
         let mut state = serializer.serialize_map(Some(self.map.len()))?;
         for (key, value) in self.map.iter() {
             state.serialize_entry(
-                &TypedReflectSerializer::new(key, self.registry),
-                &TypedReflectSerializer::new(value, self.registry),
+                &ElementSerializer::new(key, key_type, self.registry),
+                &ElementSerializer::new(value, value_type, self.registry),
             )?;
         }
         state.end()
@@ -524,9 +613,14 @@ impl<'a> Serialize for SetSerializer<'a> {
     where
         S: serde::Serializer,
     {
+        let value_type = match self.set.get_represented_type_info() {
+            Some(TypeInfo::Set(set_info)) => Some(set_info.value_ty().id()),
+            _ => None,
+        };
+
         let mut state = serializer.serialize_seq(Some(self.set.len()))?;
         for value in self.set.iter() {
-            state.serialize_element(&TypedReflectSerializer::new(value, self.registry))?;
+            state.serialize_element(&ElementSerializer::new(value, value_type, self.registry))?;
         }
         state.end()
     }
@@ -542,9 +636,14 @@ impl<'a> Serialize for ListSerializer<'a> {
     where
         S: serde::Serializer,
     {
+        let item_type = match self.list.get_represented_type_info() {
+            Some(TypeInfo::List(list_info)) => Some(list_info.item_ty().id()),
+            _ => None,
+        };
+
         let mut state = serializer.serialize_seq(Some(self.list.len()))?;
         for value in self.list.iter() {
-            state.serialize_element(&TypedReflectSerializer::new(value, self.registry))?;
+            state.serialize_element(&ElementSerializer::new(value, item_type, self.registry))?;
         }
         state.end()
     }
@@ -560,9 +659,14 @@ impl<'a> Serialize for ArraySerializer<'a> {
     where
         S: serde::Serializer,
     {
+        let item_type = match self.array.get_represented_type_info() {
+            Some(TypeInfo::Array(array_info)) => Some(array_info.item_ty().id()),
+            _ => None,
+        };
+
         let mut state = serializer.serialize_tuple(self.array.len())?;
         for value in self.array.iter() {
-            state.serialize_element(&TypedReflectSerializer::new(value, self.registry))?;
+            state.serialize_element(&ElementSerializer::new(value, item_type, self.registry))?;
         }
         state.end()
     }
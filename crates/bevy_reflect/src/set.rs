@@ -150,7 +150,7 @@ impl DynamicSet {
 
     /// Inserts a typed value into the set.
     pub fn insert<V: Reflect>(&mut self, value: V) {
-        self.insert_boxed(Box::new(value));
+        self.insert_boxed(Box::new(value).into_partial_reflect());
     }
 
     fn internal_hash(value: &dyn PartialReflect) -> u64 {
@@ -332,21 +332,6 @@ impl Debug for DynamicSet {
     }
 }
 
-impl FromIterator<Box<dyn PartialReflect>> for DynamicSet {
-    fn from_iter<I: IntoIterator<Item = Box<dyn PartialReflect>>>(values: I) -> Self {
-        let mut this = Self {
-            represented_type: None,
-            hash_table: HashTable::new(),
-        };
-
-        for value in values {
-            this.insert_boxed(value);
-        }
-
-        this
-    }
-}
-
 impl<T: Reflect> FromIterator<T> for DynamicSet {
     fn from_iter<I: IntoIterator<Item = T>>(values: I) -> Self {
         let mut this = Self {
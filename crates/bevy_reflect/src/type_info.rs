@@ -499,7 +499,7 @@ pub struct ValueInfo {
 }
 
 impl ValueInfo {
-    pub fn new<T: Reflect + TypePath + ?Sized>() -> Self {
+    pub fn new<T: TypePath + ?Sized>() -> Self {
         Self {
             ty: Type::of::<T>(),
             #[cfg(feature = "documentation")]
@@ -1,3 +1,4 @@
+use crate::PartialReflect;
 use std::fmt;
 
 /// A static accessor to type paths and names.
@@ -126,6 +127,15 @@ pub trait TypePath: 'static {
     fn module_path() -> Option<&'static str> {
         None
     }
+
+    /// Returns the value whose type path [`DynamicTypePath`] reports in place of this type's.
+    ///
+    /// This lets wrappers that are transparent to reflection, like reflected boxes,
+    /// report the type path of their contents.
+    #[doc(hidden)]
+    fn transparent_value(&self) -> Option<&dyn PartialReflect> {
+        None
+    }
 }
 
 /// Dynamic dispatch for [`TypePath`].
@@ -157,27 +167,42 @@ pub trait DynamicTypePath {
 impl<T: TypePath> DynamicTypePath for T {
     #[inline]
     fn reflect_type_path(&self) -> &str {
-        Self::type_path()
+        match self.transparent_value() {
+            Some(value) => value.reflect_type_path(),
+            None => Self::type_path(),
+        }
     }
 
     #[inline]
     fn reflect_short_type_path(&self) -> &str {
-        Self::short_type_path()
+        match self.transparent_value() {
+            Some(value) => value.reflect_short_type_path(),
+            None => Self::short_type_path(),
+        }
     }
 
     #[inline]
     fn reflect_type_ident(&self) -> Option<&str> {
-        Self::type_ident()
+        match self.transparent_value() {
+            Some(value) => value.reflect_type_ident(),
+            None => Self::type_ident(),
+        }
     }
 
     #[inline]
     fn reflect_crate_name(&self) -> Option<&str> {
-        Self::crate_name()
+        match self.transparent_value() {
+            Some(value) => value.reflect_crate_name(),
+            None => Self::crate_name(),
+        }
     }
 
     #[inline]
     fn reflect_module_path(&self) -> Option<&str> {
-        Self::module_path()
+        match self.transparent_value() {
+            Some(value) => value.reflect_module_path(),
+            None => Self::module_path(),
+        }
     }
 }
 
@@ -519,6 +519,16 @@ impl TypeRegistration {
             type_info: T::type_info(),
         }
     }
+
+    /// Creates type registration information for the type described by `type_info`.
+    ///
+    /// This is used for types that can't implement [`Typed`], such as `Box<dyn PartialReflect>`.
+    pub(crate) fn from_type_info(type_info: &'static TypeInfo) -> Self {
+        Self {
+            data: Default::default(),
+            type_info,
+        }
+    }
 }
 
 impl Clone for TypeRegistration {
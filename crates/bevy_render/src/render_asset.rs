@@ -266,10 +266,14 @@ pub(crate) fn extract_render_asset<A: RenderAsset>(
             for event in events.read() {
                 #[allow(clippy::match_same_arms)]
                 match event {
-                    AssetEvent::Added { id } | AssetEvent::Modified { id } => {
+                    // Render assets can hold data prepared from their dependencies (such as the textures of a material),
+                    // so they are re-extracted when a dependency is modified
+                    AssetEvent::Added { id }
+                    | AssetEvent::Modified { id }
+                    | AssetEvent::DependencyModified { id } => {
                         changed_assets.insert(*id);
                     }
-                    AssetEvent::Removed { .. } => {}
+                    AssetEvent::Removed { .. } | AssetEvent::ValidationFailed { .. } => {}
                     AssetEvent::Unused { id } => {
                         changed_assets.remove(id);
                         removed.insert(*id);
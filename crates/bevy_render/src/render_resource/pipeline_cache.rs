@@ -936,7 +936,9 @@ impl PipelineCache {
                     }
                 }
                 AssetEvent::Removed { id } => cache.remove_shader(*id),
-                AssetEvent::Unused { .. } => {}
+                AssetEvent::Unused { .. }
+                | AssetEvent::DependencyModified { .. }
+                | AssetEvent::ValidationFailed { .. } => {}
                 AssetEvent::LoadedWithDependencies { .. } => {
                     // TODO: handle this
                 }
@@ -0,0 +1,476 @@
+use crate::{
+    ron,
+    serde::{SceneDeserializer, SceneSerializer},
+    serialize_ron, DynamicEntity, DynamicScene, SceneLoaderError,
+};
+use bevy_asset::{
+    io::{Reader, Writer},
+    saver::{AssetSaver, SavedAsset},
+    AssetLoader, AsyncWriteExt, LoadContext,
+};
+use bevy_ecs::{
+    entity::Entity,
+    reflect::AppTypeRegistry,
+    world::{FromWorld, World},
+};
+use bevy_reflect::{
+    serde::{TypedReflectDeserializer, TypedReflectSerializer},
+    PartialReflect, TypeRegistration, TypeRegistry, TypeRegistryArc,
+};
+use bevy_utils::HashMap;
+use bincode::Options;
+use serde::de::DeserializeSeed;
+use thiserror::Error;
+
+/// The magic bytes at the start of every binary scene.
+pub const BINARY_SCENE_MAGIC: [u8; 4] = *b"BSCN";
+/// The version of the binary scene format written by [`serialize_binary_scene`].
+pub const BINARY_SCENE_VERSION: u8 = 1;
+
+/// An error that occurs when reading or writing a binary scene.
+#[derive(Error, Debug)]
+pub enum BinarySceneError {
+    /// An [IO Error](std::io::Error)
+    #[error("Error while trying to write the binary scene: {0}")]
+    Io(#[from] std::io::Error),
+    /// The data does not start with [`BINARY_SCENE_MAGIC`].
+    #[error("the data is not a binary scene")]
+    InvalidMagic,
+    /// The data was written with an unsupported version of the format.
+    #[error("unsupported binary scene version {0}, expected {BINARY_SCENE_VERSION}")]
+    UnsupportedVersion(u8),
+    /// The data ended unexpectedly.
+    #[error("unexpected end of binary scene data")]
+    UnexpectedEnd,
+    /// The data continues after the end of the scene.
+    #[error("{0} unexpected trailing bytes after the binary scene")]
+    TrailingBytes(usize),
+    /// A type path in the type table is not valid UTF-8.
+    #[error("invalid type path in the binary scene's type table")]
+    InvalidTypePath,
+    /// An entity id is not a valid [`Entity`].
+    #[error("{0} is not a valid entity id")]
+    InvalidEntity(u64),
+    /// An entry references a type that is not in the type table.
+    #[error("type index {0} is out of bounds of the binary scene's type table")]
+    InvalidTypeIndex(u32),
+    /// The scene contains a type that is not registered in the type registry.
+    #[error("scene contains the unregistered type `{type_path}`")]
+    UnregisteredType {
+        /// The type path of the unregistered type.
+        type_path: String,
+    },
+    /// A value in the scene does not represent a type.
+    #[error("scene contains dynamic type `{type_path}` without a represented type")]
+    NoRepresentedType {
+        /// The type path of the dynamic value.
+        type_path: String,
+    },
+    /// An entry was not exactly as long as its length prefix.
+    #[error("the value of `{type_path}` does not match its length prefix")]
+    InvalidEntryLength {
+        /// The type path of the entry.
+        type_path: String,
+    },
+    /// A value could not be serialized or deserialized.
+    #[error("failed to encode or decode a value of `{type_path}`: {error}")]
+    Value {
+        /// The type path of the value.
+        type_path: String,
+        /// The underlying error.
+        error: bincode::Error,
+    },
+    /// The RON form of the scene could not be written.
+    #[error(transparent)]
+    Ron(#[from] ron::Error),
+    /// The RON form of the scene could not be parsed.
+    #[error(transparent)]
+    RonSpanned(#[from] ron::error::SpannedError),
+}
+
+/// Serializes `scene` into the binary scene format (`.scn.bin`).
+///
+/// The binary format is built on the same reflection-based serialization as the RON format, so every scene that can be written
+/// as RON can be written as binary and converted back without loss (see [`ron_to_binary_scene`] and [`binary_scene_to_ron`]).
+/// It is laid out as follows (all integers are little-endian):
+/// * [`BINARY_SCENE_MAGIC`] followed by the [`BINARY_SCENE_VERSION`] byte
+/// * the type table: a `u32` count of the type paths used in the scene, each written as a `u32` length followed by UTF-8 bytes
+/// * the resources: a `u32` count of entries
+/// * the entities: a `u32` count, followed by each entity as its `u64` bits and a `u32` count of component entries
+///
+/// Each resource or component entry is a `u32` index into the type table, followed by a `u32` length prefix and the value
+/// encoded with [`bincode`] from its [`TypedReflectSerializer`] representation. Types are stored once in the table instead of
+/// once per value, which keeps large scenes small.
+pub fn serialize_binary_scene(
+    scene: &DynamicScene,
+    registry: &TypeRegistry,
+) -> Result<Vec<u8>, BinarySceneError> {
+    let mut type_indices = HashMap::<&str, u32>::default();
+    let mut type_paths = Vec::new();
+    let mut body = Vec::new();
+    let mut write_entries = |body: &mut Vec<u8>, entries: &[Box<dyn PartialReflect>]| {
+        write_u32(body, entries.len() as u32);
+        for entry in entries {
+            let type_info = entry.get_represented_type_info().ok_or_else(|| {
+                BinarySceneError::NoRepresentedType {
+                    type_path: entry.reflect_type_path().to_string(),
+                }
+            })?;
+            let type_path = type_info.type_path();
+            let index = *type_indices.entry(type_path).or_insert_with(|| {
+                type_paths.push(type_path);
+                type_paths.len() as u32 - 1
+            });
+            let value = bincode_options()
+                .serialize(&TypedReflectSerializer::new(entry.as_ref(), registry))
+                .map_err(|error| BinarySceneError::Value {
+                    type_path: type_path.to_string(),
+                    error,
+                })?;
+            write_u32(body, index);
+            write_u32(body, value.len() as u32);
+            body.extend_from_slice(&value);
+        }
+        Ok::<_, BinarySceneError>(())
+    };
+
+    write_entries(&mut body, &scene.resources)?;
+    write_u32(&mut body, scene.entities.len() as u32);
+    for entity in &scene.entities {
+        body.extend_from_slice(&entity.entity.to_bits().to_le_bytes());
+        write_entries(&mut body, &entity.components)?;
+    }
+
+    let mut bytes = Vec::with_capacity(body.len() + 64);
+    bytes.extend_from_slice(&BINARY_SCENE_MAGIC);
+    bytes.push(BINARY_SCENE_VERSION);
+    write_u32(&mut bytes, type_paths.len() as u32);
+    for type_path in type_paths {
+        write_u32(&mut bytes, type_path.len() as u32);
+        bytes.extend_from_slice(type_path.as_bytes());
+    }
+    bytes.extend_from_slice(&body);
+    Ok(bytes)
+}
+
+/// Deserializes a scene written with [`serialize_binary_scene`].
+pub fn deserialize_binary_scene(
+    bytes: &[u8],
+    registry: &TypeRegistry,
+) -> Result<DynamicScene, BinarySceneError> {
+    let mut reader = ByteReader { bytes };
+    if reader.take(BINARY_SCENE_MAGIC.len())? != BINARY_SCENE_MAGIC {
+        return Err(BinarySceneError::InvalidMagic);
+    }
+    let version = reader.take(1)?[0];
+    if version != BINARY_SCENE_VERSION {
+        return Err(BinarySceneError::UnsupportedVersion(version));
+    }
+
+    let type_count = reader.u32()?;
+    let mut types = Vec::new();
+    for _ in 0..type_count {
+        let len = reader.u32()? as usize;
+        let type_path = std::str::from_utf8(reader.take(len)?)
+            .map_err(|_| BinarySceneError::InvalidTypePath)?;
+        let registration = registry.get_with_type_path(type_path).ok_or_else(|| {
+            BinarySceneError::UnregisteredType {
+                type_path: type_path.to_string(),
+            }
+        })?;
+        types.push(registration);
+    }
+
+    let resources = reader.entries(&types, registry)?;
+    let entity_count = reader.u32()?;
+    let mut entities = Vec::new();
+    for _ in 0..entity_count {
+        let bits = u64::from_le_bytes(reader.take(8)?.try_into().unwrap());
+        let entity =
+            Entity::try_from_bits(bits).map_err(|_| BinarySceneError::InvalidEntity(bits))?;
+        entities.push(DynamicEntity {
+            entity,
+            components: reader.entries(&types, registry)?,
+        });
+    }
+
+    if !reader.bytes.is_empty() {
+        return Err(BinarySceneError::TrailingBytes(reader.bytes.len()));
+    }
+    Ok(DynamicScene {
+        resources,
+        entities,
+    })
+}
+
+/// Converts a scene in the RON format (`.scn.ron`) to the binary format (`.scn.bin`).
+pub fn ron_to_binary_scene(
+    ron: &str,
+    registry: &TypeRegistry,
+) -> Result<Vec<u8>, BinarySceneError> {
+    let mut deserializer = ron::de::Deserializer::from_str(ron)?;
+    let scene = SceneDeserializer {
+        type_registry: registry,
+    }
+    .deserialize(&mut deserializer)
+    .map_err(|e| deserializer.span_error(e))?;
+    serialize_binary_scene(&scene, registry)
+}
+
+/// Converts a scene in the binary format (`.scn.bin`) to the RON format (`.scn.ron`).
+pub fn binary_scene_to_ron(
+    bytes: &[u8],
+    registry: &TypeRegistry,
+) -> Result<String, BinarySceneError> {
+    let scene = deserialize_binary_scene(bytes, registry)?;
+    Ok(serialize_ron(SceneSerializer::new(&scene, registry))?)
+}
+
+fn bincode_options() -> impl Options {
+    bincode::DefaultOptions::new()
+}
+
+fn write_u32(bytes: &mut Vec<u8>, value: u32) {
+    bytes.extend_from_slice(&value.to_le_bytes());
+}
+
+struct ByteReader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> ByteReader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], BinarySceneError> {
+        if self.bytes.len() < len {
+            return Err(BinarySceneError::UnexpectedEnd);
+        }
+        let (taken, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        Ok(taken)
+    }
+
+    fn u32(&mut self) -> Result<u32, BinarySceneError> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn entries(
+        &mut self,
+        types: &[&TypeRegistration],
+        registry: &TypeRegistry,
+    ) -> Result<Vec<Box<dyn PartialReflect>>, BinarySceneError> {
+        let count = self.u32()?;
+        let mut entries = Vec::new();
+        for _ in 0..count {
+            let index = self.u32()?;
+            let registration = *types
+                .get(index as usize)
+                .ok_or(BinarySceneError::InvalidTypeIndex(index))?;
+            let len = self.u32()? as usize;
+            let mut value = self.take(len)?;
+            let type_path = || registration.type_info().type_path().to_string();
+            let entry = bincode_options()
+                .deserialize_from_seed(
+                    TypedReflectDeserializer::new(registration, registry),
+                    &mut value,
+                )
+                .map_err(|error| BinarySceneError::Value {
+                    type_path: type_path(),
+                    error,
+                })?;
+            if !value.is_empty() {
+                return Err(BinarySceneError::InvalidEntryLength {
+                    type_path: type_path(),
+                });
+            }
+            entries.push(entry);
+        }
+        Ok(entries)
+    }
+}
+
+/// Asset loader for a Bevy dynamic scene in the binary format (`.scn.bin`).
+///
+/// The loader handles assets serialized with [`DynamicScene::serialize_binary`] or saved with the [`BinarySceneSaver`].
+#[derive(Debug)]
+pub struct BinarySceneLoader {
+    type_registry: TypeRegistryArc,
+}
+
+impl FromWorld for BinarySceneLoader {
+    fn from_world(world: &mut World) -> Self {
+        let type_registry = world.resource::<AppTypeRegistry>();
+        BinarySceneLoader {
+            type_registry: type_registry.0.clone(),
+        }
+    }
+}
+
+impl AssetLoader for BinarySceneLoader {
+    type Asset = DynamicScene;
+    type Settings = ();
+    type Error = SceneLoaderError;
+
+    async fn load<'a>(
+        &'a self,
+        reader: &'a mut dyn Reader,
+        _settings: &'a (),
+        _load_context: &'a mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        Ok(deserialize_binary_scene(
+            &bytes,
+            &self.type_registry.read(),
+        )?)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["scn.bin"]
+    }
+}
+
+/// Asset saver that writes a [`DynamicScene`] in the binary format (`.scn.bin`), to be loaded with the [`BinarySceneLoader`].
+///
+/// Combined with the [`SceneLoader`](crate::SceneLoader) in a [`LoadAndSave`](bevy_asset::processor::LoadAndSave) processor,
+/// this converts RON scenes to binary scenes during asset processing.
+#[derive(Debug)]
+pub struct BinarySceneSaver {
+    type_registry: TypeRegistryArc,
+}
+
+impl FromWorld for BinarySceneSaver {
+    fn from_world(world: &mut World) -> Self {
+        let type_registry = world.resource::<AppTypeRegistry>();
+        BinarySceneSaver {
+            type_registry: type_registry.0.clone(),
+        }
+    }
+}
+
+impl AssetSaver for BinarySceneSaver {
+    type Asset = DynamicScene;
+    type Settings = ();
+    type OutputLoader = BinarySceneLoader;
+    type Error = BinarySceneError;
+
+    async fn save<'a>(
+        &'a self,
+        writer: &'a mut Writer,
+        asset: SavedAsset<'a, Self::Asset>,
+        _settings: &'a Self::Settings,
+    ) -> Result<(), Self::Error> {
+        let bytes = serialize_binary_scene(&asset, &self.type_registry.read())?;
+        writer.write_all(&bytes).await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::prelude::{Component, ReflectComponent, ReflectResource, Resource};
+    use bevy_reflect::Reflect;
+
+    #[derive(Component, Reflect, Default)]
+    #[reflect(Component)]
+    struct Name(String);
+
+    #[derive(Component, Reflect, Default)]
+    #[reflect(Component)]
+    struct Health {
+        current: f32,
+        max: f32,
+    }
+
+    #[derive(Component, Reflect, Default)]
+    #[reflect(Component)]
+    struct Target(Option<Entity>);
+
+    #[derive(Resource, Reflect, Default)]
+    #[reflect(Resource)]
+    struct Score(u64);
+
+    fn create_registry() -> TypeRegistry {
+        let mut registry = TypeRegistry::default();
+        registry.register::<Name>();
+        registry.register::<Health>();
+        registry.register::<Target>();
+        registry.register::<Score>();
+        registry
+    }
+
+    const SCENE: &str = r#"(
+  resources: {
+    "bevy_scene::binary::tests::Score": (42),
+  },
+  entities: {
+    4294967296: (
+      components: {
+        "bevy_scene::binary::tests::Name": ("player"),
+        "bevy_scene::binary::tests::Health": (
+          current: 7.5,
+          max: 10.0,
+        ),
+      },
+    ),
+    4294967297: (
+      components: {
+        "bevy_scene::binary::tests::Name": ("enemy"),
+        "bevy_scene::binary::tests::Target": (Some(4294967296)),
+      },
+    ),
+  },
+)"#;
+
+    #[test]
+    fn round_trip_with_ron() {
+        let registry = create_registry();
+
+        let bytes = ron_to_binary_scene(SCENE, &registry).unwrap();
+        assert_eq!(bytes[..4], BINARY_SCENE_MAGIC);
+        assert!(bytes.len() < SCENE.len());
+        assert_eq!(binary_scene_to_ron(&bytes, &registry).unwrap(), SCENE);
+
+        let scene = deserialize_binary_scene(&bytes, &registry).unwrap();
+        assert_eq!(scene.resources.len(), 1);
+        assert_eq!(scene.entities.len(), 2);
+        assert_eq!(scene.serialize_binary(&registry).unwrap(), bytes);
+    }
+
+    #[test]
+    fn invalid_binary_scenes() {
+        let registry = create_registry();
+        let bytes = ron_to_binary_scene(SCENE, &registry).unwrap();
+
+        assert!(matches!(
+            deserialize_binary_scene(b"RIFF\x01", &registry),
+            Err(BinarySceneError::InvalidMagic)
+        ));
+        assert!(matches!(
+            deserialize_binary_scene(&bytes[..bytes.len() - 1], &registry),
+            Err(BinarySceneError::UnexpectedEnd)
+        ));
+
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        assert!(matches!(
+            deserialize_binary_scene(&trailing, &registry),
+            Err(BinarySceneError::TrailingBytes(1))
+        ));
+
+        let mut version = bytes.clone();
+        version[4] = BINARY_SCENE_VERSION + 1;
+        assert!(matches!(
+            deserialize_binary_scene(&version, &registry),
+            Err(BinarySceneError::UnsupportedVersion(_))
+        ));
+
+        let mut registry = TypeRegistry::default();
+        registry.register::<Name>();
+        let Err(BinarySceneError::UnregisteredType { type_path }) =
+            deserialize_binary_scene(&bytes, &registry)
+        else {
+            panic!("expected an unregistered type error");
+        };
+        assert_eq!(type_path, "bevy_scene::binary::tests::Score");
+    }
+}
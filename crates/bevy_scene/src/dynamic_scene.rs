@@ -193,6 +193,17 @@ impl DynamicScene {
     pub fn serialize(&self, registry: &TypeRegistry) -> Result<String, ron::Error> {
         serialize_ron(SceneSerializer::new(self, registry))
     }
+
+    /// Serialize this dynamic scene into the compact binary scene format.
+    ///
+    /// See [`serialize_binary_scene`](crate::serialize_binary_scene) for a description of the format.
+    #[cfg(feature = "serialize")]
+    pub fn serialize_binary(
+        &self,
+        registry: &TypeRegistry,
+    ) -> Result<Vec<u8>, crate::BinarySceneError> {
+        crate::serialize_binary_scene(self, registry)
+    }
 }
 
 /// Serialize a given Rust data structure into rust object notation (ron).
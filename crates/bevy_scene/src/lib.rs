@@ -11,10 +11,17 @@
 //! instantiated or removed from a world to allow composition. Scenes can be serialized/deserialized,
 //! for example to save part of the world state to a file.
 
+#[cfg(feature = "serialize")]
+mod binary;
 mod bundle;
 mod dynamic_scene;
 mod dynamic_scene_builder;
+mod prefab;
+mod prefab_loader;
+#[cfg(feature = "serialize")]
+mod save_game;
 mod scene;
+mod scene_diff;
 mod scene_filter;
 mod scene_loader;
 mod scene_spawner;
@@ -26,10 +33,17 @@ pub mod serde;
 pub use bevy_asset::ron;
 
 use bevy_ecs::schedule::IntoSystemConfigs;
+#[cfg(feature = "serialize")]
+pub use binary::*;
 pub use bundle::*;
 pub use dynamic_scene::*;
 pub use dynamic_scene_builder::*;
+pub use prefab::*;
+pub use prefab_loader::*;
+#[cfg(feature = "serialize")]
+pub use save_game::*;
 pub use scene::*;
+pub use scene_diff::*;
 pub use scene_filter::*;
 pub use scene_loader::*;
 pub use scene_spawner::*;
@@ -56,6 +70,8 @@ impl Plugin for ScenePlugin {
         app.init_asset::<DynamicScene>()
             .init_asset::<Scene>()
             .init_asset_loader::<SceneLoader>()
+            .init_asset_loader::<PrefabLoader>()
+            .init_asset_loader::<BinarySceneLoader>()
             .init_resource::<SceneSpawner>()
             .add_systems(SpawnScene, (scene_spawner, scene_spawner_system).chain());
 
@@ -0,0 +1,435 @@
+use crate::{DynamicScene, SceneSpawnError};
+use bevy_asset::AssetPath;
+use bevy_ecs::{
+    entity::{Entity, EntityHashMap},
+    reflect::{AppTypeRegistry, ReflectComponent, ReflectMapEntities},
+    world::{Command, World},
+};
+use bevy_hierarchy::{Parent, PushChild};
+use bevy_reflect::{ParsedPath, PartialReflect, ReflectPath, TypeRegistry};
+use bevy_utils::{HashMap, TypeIdMap};
+use std::any::TypeId;
+use thiserror::Error;
+
+/// A scene described as a set of entities that can be instances of other scenes ("prefabs"), with per-instance overrides.
+///
+/// Each [`PrefabEntity`] can reference a base scene asset, override fields of the components it inherits from it, and add or
+/// remove components. New entities (such as an extra child) can be added alongside the instances.
+///
+/// Prefabs are loaded from `.prefab.ron` files by the [`PrefabLoader`](crate::PrefabLoader), which resolves them into a
+/// regular [`DynamicScene`] using [`Prefab::build_scene`]. Base scenes are loaded as "load dependencies", so when
+/// watching for changes, editing a base scene reloads every prefab built from it, which in turn updates all of their spawned instances.
+#[derive(Default)]
+pub struct Prefab {
+    /// The entities of the prefab.
+    pub entities: Vec<PrefabEntity>,
+}
+
+/// An entity of a [`Prefab`].
+pub struct PrefabEntity {
+    /// The identifier of the entity, unique within the prefab.
+    ///
+    /// Components and [`PrefabEntity::parent`] reference other entities of the prefab using this identifier.
+    pub entity: Entity,
+    /// The scene this entity is an instance of, if any.
+    ///
+    /// All entities of the base scene are added to the built scene. This entity becomes the root of the instance, so the
+    /// base scene must have exactly one entity without a [`Parent`].
+    pub base: Option<AssetPath<'static>>,
+    /// The entity of the prefab this entity is a child of, if any.
+    pub parent: Option<Entity>,
+    /// Components added to the entity. Components that are already inherited from [`PrefabEntity::base`] are replaced.
+    pub components: Vec<Box<dyn PartialReflect>>,
+    /// Overrides of individual fields of the entity's components.
+    pub patches: Vec<FieldPatch>,
+    /// The components removed from the entity, such as components inherited from [`PrefabEntity::base`].
+    pub removed: Vec<TypeId>,
+}
+
+/// An override of a single (reflected) field of a component, used by [`PrefabEntity`] and [`SceneChange::SetField`](crate::SceneChange::SetField).
+#[derive(Debug)]
+pub struct FieldPatch {
+    /// The [`TypeId`] of the patched component.
+    pub component: TypeId,
+    /// The path of the field within the component, such as `current` or `stats.speed`. See [`bevy_reflect::GetPath`].
+    pub path: ParsedPath,
+    /// The value applied to the field.
+    pub value: Box<dyn PartialReflect>,
+}
+
+/// An error that occurs when building a [`DynamicScene`] from a [`Prefab`].
+#[derive(Error, Debug)]
+pub enum PrefabError {
+    /// Writing a base scene or a component to the world failed.
+    #[error(transparent)]
+    Spawn(#[from] SceneSpawnError),
+    /// A base scene was not provided to [`Prefab::build_scene`].
+    #[error("the base scene {path} of entity {entity} was not loaded")]
+    MissingBase {
+        /// The prefab entity that references the base scene.
+        entity: Entity,
+        /// The path of the base scene.
+        path: AssetPath<'static>,
+    },
+    /// A base scene does not have exactly one root entity.
+    #[error("the base scene {path} must have exactly one root entity (an entity without a `Parent`), but it has {roots}")]
+    InvalidBaseRoot {
+        /// The path of the base scene.
+        path: AssetPath<'static>,
+        /// The number of root entities of the base scene.
+        roots: usize,
+    },
+    /// An entity references an entity that is not part of the prefab.
+    #[error("entity {entity} references the entity {missing}, which is not part of the prefab")]
+    MissingEntity {
+        /// The prefab entity containing the reference.
+        entity: Entity,
+        /// The referenced entity.
+        missing: Entity,
+    },
+    /// A patched or removed component does not exist on the entity.
+    #[error("entity {entity} does not have the component `{type_path}`")]
+    MissingComponent {
+        /// The prefab entity.
+        entity: Entity,
+        /// The type path of the component.
+        type_path: String,
+    },
+    /// A field patch could not be applied.
+    #[error("failed to patch the field `{path}` of `{type_path}` on entity {entity}: {message}")]
+    InvalidPatch {
+        /// The prefab entity.
+        entity: Entity,
+        /// The type path of the patched component.
+        type_path: String,
+        /// The path of the patched field.
+        path: ParsedPath,
+        /// Why the patch could not be applied.
+        message: String,
+    },
+}
+
+impl Prefab {
+    /// Returns the paths of all base scenes referenced by this prefab.
+    pub fn bases(&self) -> impl Iterator<Item = &AssetPath<'static>> {
+        self.entities
+            .iter()
+            .filter_map(|entity| entity.base.as_ref())
+    }
+
+    /// Builds a [`DynamicScene`] by instancing the base scenes (provided in `bases`) of every entity and applying the
+    /// prefab's overrides on top of them.
+    pub fn build_scene(
+        &self,
+        bases: &HashMap<AssetPath<'static>, DynamicScene>,
+        type_registry: &AppTypeRegistry,
+    ) -> Result<DynamicScene, PrefabError> {
+        let mut world = World::new();
+        world.insert_resource(type_registry.clone());
+
+        // Spawn every entity (and the base scenes they are instances of) first, so entity references can be resolved
+        // regardless of the order of the entities.
+        let mut entity_map = EntityHashMap::default();
+        for prefab_entity in &self.entities {
+            let entity = match &prefab_entity.base {
+                Some(path) => {
+                    let base = bases.get(path).ok_or_else(|| PrefabError::MissingBase {
+                        entity: prefab_entity.entity,
+                        path: path.clone(),
+                    })?;
+                    let mut base_map = EntityHashMap::default();
+                    base.write_to_world_with(&mut world, &mut base_map, type_registry)?;
+                    let roots = base_map
+                        .values()
+                        .copied()
+                        .filter(|&entity| !world.entity(entity).contains::<Parent>())
+                        .collect::<Vec<_>>();
+                    let [root] = roots[..] else {
+                        return Err(PrefabError::InvalidBaseRoot {
+                            path: path.clone(),
+                            roots: roots.len(),
+                        });
+                    };
+                    root
+                }
+                None => world.spawn_empty().id(),
+            };
+            entity_map.insert(prefab_entity.entity, entity);
+        }
+
+        let type_registry = type_registry.read();
+        let mut scene_mappings: TypeIdMap<Vec<Entity>> = Default::default();
+        for prefab_entity in &self.entities {
+            let entity = entity_map[&prefab_entity.entity];
+
+            for type_id in &prefab_entity.removed {
+                let reflect_component = reflect_component(&type_registry, *type_id)?;
+                let mut entity_mut = world.entity_mut(entity);
+                if !reflect_component.contains(&entity_mut) {
+                    return Err(PrefabError::MissingComponent {
+                        entity: prefab_entity.entity,
+                        type_path: type_path(&type_registry, *type_id),
+                    });
+                }
+                reflect_component.remove(&mut entity_mut);
+            }
+
+            for component in &prefab_entity.components {
+                let type_info = component.get_represented_type_info().ok_or_else(|| {
+                    SceneSpawnError::NoRepresentedType {
+                        type_path: component.reflect_type_path().to_string(),
+                    }
+                })?;
+                let reflect_component = reflect_component(&type_registry, type_info.type_id())?;
+                // Components of the prefab reference entities of the prefab
+                if type_registry
+                    .get_type_data::<ReflectMapEntities>(type_info.type_id())
+                    .is_some()
+                {
+                    scene_mappings
+                        .entry(type_info.type_id())
+                        .or_default()
+                        .push(entity);
+                }
+                reflect_component.apply_or_insert(
+                    &mut world.entity_mut(entity),
+                    component.as_partial_reflect(),
+                    &type_registry,
+                );
+            }
+
+            for patch in &prefab_entity.patches {
+                let reflect_component = reflect_component(&type_registry, patch.component)?;
+                let invalid_patch = |message: String| PrefabError::InvalidPatch {
+                    entity: prefab_entity.entity,
+                    type_path: type_path(&type_registry, patch.component),
+                    path: patch.path.clone(),
+                    message,
+                };
+                let mut entity_mut = world.entity_mut(entity);
+                let Some(mut component) = reflect_component.reflect_mut(&mut entity_mut) else {
+                    return Err(PrefabError::MissingComponent {
+                        entity: prefab_entity.entity,
+                        type_path: type_path(&type_registry, patch.component),
+                    });
+                };
+                let field = (&patch.path)
+                    .reflect_element_mut(component.as_partial_reflect_mut())
+                    .map_err(|err| invalid_patch(err.to_string()))?;
+                field
+                    .try_apply(patch.value.as_ref())
+                    .map_err(|err| invalid_patch(err.to_string()))?;
+            }
+
+            if let Some(parent) = prefab_entity.parent {
+                let &parent = entity_map.get(&parent).ok_or(PrefabError::MissingEntity {
+                    entity: prefab_entity.entity,
+                    missing: parent,
+                })?;
+                PushChild {
+                    parent,
+                    child: entity,
+                }
+                .apply(&mut world);
+            }
+        }
+
+        for (type_id, entities) in scene_mappings {
+            if let Some(map_entities) = type_registry.get_type_data::<ReflectMapEntities>(type_id) {
+                map_entities.map_entities(&mut world, &mut entity_map, &entities);
+            }
+        }
+        drop(type_registry);
+
+        Ok(DynamicScene::from_world(&world))
+    }
+}
+
+fn reflect_component(
+    type_registry: &TypeRegistry,
+    type_id: TypeId,
+) -> Result<&ReflectComponent, SceneSpawnError> {
+    type_registry
+        .get_type_data::<ReflectComponent>(type_id)
+        .ok_or_else(|| SceneSpawnError::UnregisteredComponent {
+            type_path: type_path(type_registry, type_id),
+        })
+}
+
+fn type_path(type_registry: &TypeRegistry, type_id: TypeId) -> String {
+    type_registry
+        .get_type_info(type_id)
+        .map(|type_info| type_info.type_path().to_string())
+        .unwrap_or_else(|| format!("{type_id:?}"))
+}
+
+#[cfg(all(test, feature = "serialize"))]
+mod tests {
+    use super::*;
+    use crate::{ron, serde::PrefabDeserializer, DynamicSceneBuilder};
+    use bevy_ecs::{component::Component, reflect::ReflectComponent};
+    use bevy_hierarchy::{BuildChildren, ChildBuild, Children};
+    use bevy_reflect::Reflect;
+    use serde::de::DeserializeSeed;
+
+    #[derive(Component, Reflect, Default, Debug, PartialEq)]
+    #[reflect(Component)]
+    struct Health {
+        current: f32,
+        max: f32,
+    }
+
+    #[derive(Component, Reflect, Default)]
+    #[reflect(Component)]
+    struct Wander;
+
+    #[derive(Component, Reflect, Default)]
+    #[reflect(Component)]
+    struct Boss;
+
+    #[derive(Component, Reflect, Default)]
+    #[reflect(Component)]
+    struct Hat;
+
+    fn type_registry() -> AppTypeRegistry {
+        let type_registry = AppTypeRegistry::default();
+        {
+            let mut registry = type_registry.write();
+            registry.register::<Health>();
+            registry.register::<Wander>();
+            registry.register::<Boss>();
+            registry.register::<Hat>();
+            registry.register::<f32>();
+            registry.register::<Parent>();
+            registry.register::<Children>();
+        }
+        type_registry
+    }
+
+    /// An enemy with a single weapon child.
+    fn enemy_scene(type_registry: &AppTypeRegistry) -> DynamicScene {
+        let mut world = World::new();
+        world.insert_resource(type_registry.clone());
+        world
+            .spawn((
+                Health {
+                    current: 100.0,
+                    max: 100.0,
+                },
+                Wander,
+            ))
+            .with_children(|parent| {
+                parent.spawn_empty();
+            });
+        DynamicSceneBuilder::from_world(&world)
+            .extract_entities(world.iter_entities().map(|entity| entity.id()))
+            .build()
+    }
+
+    fn parse(ron: &str, type_registry: &AppTypeRegistry) -> Prefab {
+        let mut deserializer = ron::de::Deserializer::from_str(ron).unwrap();
+        PrefabDeserializer {
+            type_registry: &type_registry.read(),
+        }
+        .deserialize(&mut deserializer)
+        .unwrap()
+    }
+
+    #[test]
+    fn build_prefab_scene() {
+        let type_registry = type_registry();
+        let prefab = parse(
+            r#"(
+                entities: {
+                    4294967296: (
+                        base: "enemy.scn.ron",
+                        patches: {
+                            "bevy_scene::prefab::tests::Health": {
+                                "current": { "f32": 50.0 },
+                            },
+                        },
+                        components: {
+                            "bevy_scene::prefab::tests::Boss": (),
+                        },
+                        removed: ["bevy_scene::prefab::tests::Wander"],
+                    ),
+                    4294967297: (
+                        parent: 4294967296,
+                        components: {
+                            "bevy_scene::prefab::tests::Hat": (),
+                        },
+                    ),
+                },
+            )"#,
+            &type_registry,
+        );
+        assert_eq!(
+            prefab.bases().collect::<Vec<_>>(),
+            vec![&AssetPath::from("enemy.scn.ron")]
+        );
+
+        let mut bases = HashMap::new();
+        bases.insert(
+            AssetPath::from("enemy.scn.ron"),
+            enemy_scene(&type_registry),
+        );
+        let scene = prefab.build_scene(&bases, &type_registry).unwrap();
+
+        let mut world = World::new();
+        world.insert_resource(type_registry.clone());
+        scene
+            .write_to_world(&mut world, &mut EntityHashMap::default())
+            .unwrap();
+
+        let (enemy, health, children) =
+            world.query::<(Entity, &Health, &Children)>().single(&world);
+        assert_eq!(
+            *health,
+            Health {
+                current: 50.0,
+                max: 100.0
+            }
+        );
+        assert_eq!(children.len(), 2);
+        assert!(world.entity(enemy).contains::<Boss>());
+        assert!(!world.entity(enemy).contains::<Wander>());
+        let hat = world
+            .query_filtered::<&Parent, bevy_ecs::query::With<Hat>>()
+            .single(&world);
+        assert_eq!(hat.get(), enemy);
+    }
+
+    #[test]
+    fn invalid_patch() {
+        let type_registry = type_registry();
+        let prefab = parse(
+            r#"(
+                entities: {
+                    4294967296: (
+                        base: "enemy.scn.ron",
+                        patches: {
+                            "bevy_scene::prefab::tests::Health": {
+                                "armor": { "f32": 5.0 },
+                            },
+                        },
+                    ),
+                },
+            )"#,
+            &type_registry,
+        );
+        let mut bases = HashMap::new();
+        assert!(matches!(
+            prefab.build_scene(&bases, &type_registry),
+            Err(PrefabError::MissingBase { .. })
+        ));
+
+        bases.insert(
+            AssetPath::from("enemy.scn.ron"),
+            enemy_scene(&type_registry),
+        );
+        assert!(matches!(
+            prefab.build_scene(&bases, &type_registry),
+            Err(PrefabError::InvalidPatch { .. })
+        ));
+    }
+}
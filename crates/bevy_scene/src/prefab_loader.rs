@@ -0,0 +1,94 @@
+use crate::ron;
+#[cfg(feature = "serialize")]
+use crate::serde::PrefabDeserializer;
+use crate::{DynamicScene, PrefabError};
+use bevy_asset::{io::Reader, AssetLoader, LoadContext, LoadDirectError};
+use bevy_ecs::reflect::AppTypeRegistry;
+use bevy_ecs::world::{FromWorld, World};
+use bevy_reflect::TypeRegistryArc;
+#[cfg(feature = "serialize")]
+use bevy_utils::HashMap;
+#[cfg(feature = "serialize")]
+use serde::de::DeserializeSeed;
+use thiserror::Error;
+
+/// Asset loader for a Bevy prefab (`.prefab.ron`), which loads it as a [`DynamicScene`].
+///
+/// See [`Prefab`](crate::Prefab) for how prefabs are resolved and [`PrefabDeserializer`](crate::serde::PrefabDeserializer)
+/// for the file format.
+#[derive(Debug)]
+pub struct PrefabLoader {
+    type_registry: TypeRegistryArc,
+}
+
+impl FromWorld for PrefabLoader {
+    fn from_world(world: &mut World) -> Self {
+        let type_registry = world.resource::<AppTypeRegistry>();
+        PrefabLoader {
+            type_registry: type_registry.0.clone(),
+        }
+    }
+}
+
+/// Possible errors that can be produced by [`PrefabLoader`]
+#[non_exhaustive]
+#[derive(Debug, Error)]
+pub enum PrefabLoaderError {
+    /// An [IO Error](std::io::Error)
+    #[error("Error while trying to read the prefab file: {0}")]
+    Io(#[from] std::io::Error),
+    /// A [RON Error](ron::error::SpannedError)
+    #[error("Could not parse RON: {0}")]
+    RonSpannedError(#[from] ron::error::SpannedError),
+    /// A base scene of the prefab could not be loaded.
+    #[error(transparent)]
+    LoadBase(#[from] LoadDirectError),
+    /// The prefab could not be resolved into a scene.
+    #[error(transparent)]
+    Prefab(#[from] PrefabError),
+}
+
+#[cfg(feature = "serialize")]
+impl AssetLoader for PrefabLoader {
+    type Asset = DynamicScene;
+    type Settings = ();
+    type Error = PrefabLoaderError;
+
+    async fn load<'a>(
+        &'a self,
+        reader: &'a mut dyn Reader,
+        _settings: &'a (),
+        load_context: &'a mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let prefab = {
+            let mut deserializer = ron::de::Deserializer::from_bytes(&bytes)?;
+            let prefab_deserializer = PrefabDeserializer {
+                type_registry: &self.type_registry.read(),
+            };
+            prefab_deserializer
+                .deserialize(&mut deserializer)
+                .map_err(|e| deserializer.span_error(e))?
+        };
+
+        // Base scenes are "load dependencies", so changing one of them reloads this prefab
+        let mut bases = HashMap::new();
+        for path in prefab.bases() {
+            if !bases.contains_key(path) {
+                let base = load_context
+                    .loader()
+                    .direct()
+                    .load::<DynamicScene>(path.clone())
+                    .await?;
+                bases.insert(path.clone(), base.take());
+            }
+        }
+
+        Ok(prefab.build_scene(&bases, &AppTypeRegistry(self.type_registry.clone()))?)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["prefab.ron"]
+    }
+}
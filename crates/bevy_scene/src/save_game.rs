@@ -0,0 +1,1071 @@
+use crate::{
+    ron,
+    serde::{SceneSerializer, ENTITY_FIELD_COMPONENTS, SCENE_ENTITIES, SCENE_RESOURCES},
+    serialize_ron, DynamicEntity, DynamicScene,
+};
+use bevy_ecs::entity::Entity;
+use bevy_reflect::{
+    serde::{TypeRegistrationDeserializer, TypedReflectDeserializer},
+    DynamicStruct, DynamicTupleStruct, GetTypeRegistration, PartialReflect, ReflectFromReflect,
+    ReflectRef, TypeInfo, TypeRegistration, TypeRegistry,
+};
+use bevy_utils::{HashMap, HashSet};
+use serde::{
+    de::{DeserializeSeed, Error, IgnoredAny, MapAccess, SeqAccess, Visitor},
+    ser::SerializeStruct,
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+use std::{collections::BTreeMap, fmt::Formatter, sync::Arc};
+
+/// Name of the serialized save game struct type.
+pub const SAVE_GAME_STRUCT: &str = "SaveGame";
+/// Name of the serialized version field in a save game struct.
+pub const SAVE_GAME_VERSION: &str = "version";
+/// Name of the serialized types field in a save game struct.
+pub const SAVE_GAME_TYPES: &str = "types";
+/// Name of the serialized scene field in a save game struct.
+pub const SAVE_GAME_SCENE: &str = "scene";
+
+/// The layout of a type stored in a save game, used to detect types whose shape changed since the save was written.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TypeLayout {
+    /// A struct, with the type path of each of its fields.
+    Struct(BTreeMap<String, String>),
+    /// A tuple struct, with the type path of each of its fields.
+    TupleStruct(Vec<String>),
+    /// Any other type. Changes to the layout of these types are not tracked.
+    Other,
+}
+
+impl TypeLayout {
+    /// Returns the layout of the type described by `type_info`.
+    pub fn of(type_info: &TypeInfo) -> Self {
+        match type_info {
+            TypeInfo::Struct(info) => TypeLayout::Struct(
+                info.iter()
+                    .map(|field| (field.name().to_string(), field.type_path().to_string()))
+                    .collect(),
+            ),
+            TypeInfo::TupleStruct(info) => TypeLayout::TupleStruct(
+                info.iter()
+                    .map(|field| field.type_path().to_string())
+                    .collect(),
+            ),
+            _ => TypeLayout::Other,
+        }
+    }
+}
+
+/// The schema of a save game: the version of the game that wrote it, and the layout of every type it contains.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SaveSchema {
+    /// The version of the save game format, chosen by the game. It is compared against the versions of
+    /// registered migrations (see [`ReflectMigrations`]).
+    pub version: u32,
+    /// The layout of every component and resource type in the save, and of the types of their fields, by type path.
+    pub types: BTreeMap<String, TypeLayout>,
+}
+
+impl SaveSchema {
+    /// Collects the layouts of the types in `scene`.
+    ///
+    /// The types of the fields of structs and tuple structs are collected recursively. Types nested in lists, maps or enums are not.
+    pub fn new(scene: &DynamicScene, registry: &TypeRegistry, version: u32) -> Self {
+        let mut schema = SaveSchema {
+            version,
+            types: BTreeMap::new(),
+        };
+        let values = scene
+            .resources
+            .iter()
+            .chain(scene.entities.iter().flat_map(|entity| &entity.components));
+        for value in values {
+            if let Some(type_info) = value.get_represented_type_info() {
+                schema.add_type(type_info, registry);
+            }
+        }
+        schema
+    }
+
+    fn add_type(&mut self, type_info: &TypeInfo, registry: &TypeRegistry) {
+        let type_path = type_info.type_path();
+        if self.types.contains_key(type_path) {
+            return;
+        }
+        self.types
+            .insert(type_path.to_string(), TypeLayout::of(type_info));
+
+        let field_types: Vec<_> = match type_info {
+            TypeInfo::Struct(info) => info
+                .iter()
+                .map(|field| {
+                    field
+                        .type_info()
+                        .or_else(|| registry.get_type_info(field.type_id()))
+                })
+                .collect(),
+            TypeInfo::TupleStruct(info) => info
+                .iter()
+                .map(|field| {
+                    field
+                        .type_info()
+                        .or_else(|| registry.get_type_info(field.type_id()))
+                })
+                .collect(),
+            _ => Vec::new(),
+        };
+        for field_type in field_types.into_iter().flatten() {
+            self.add_type(field_type, registry);
+        }
+    }
+}
+
+/// A function that migrates the data of a type written by an older version of a game. See [`ReflectMigrations`].
+pub type MigrationFn =
+    dyn Fn(Box<dyn PartialReflect>) -> Result<Box<dyn PartialReflect>, String> + Send + Sync;
+
+/// Type data holding the migrations of a type, used when loading save games with [`SaveGameDeserializer`].
+///
+/// Each migration has a version, and migrates data from save games written with a lower [`SaveSchema::version`].
+/// When loading an old save game, the migrations of each type run in order of their version, before the data is
+/// converted to the current type with [`FromReflect`](bevy_reflect::FromReflect).
+///
+/// Migrations receive the data in its dynamic form. If the layout of the type changed since the save game was written,
+/// the data has its old layout: a [`DynamicStruct`] with the old fields of a struct, or a [`DynamicTupleStruct`] with the
+/// old fields of a tuple struct.
+///
+/// Migrations are registered using [`RegisterMigrationExt`].
+#[derive(Clone, Default)]
+pub struct ReflectMigrations {
+    migrations: Vec<(u32, Arc<MigrationFn>)>,
+}
+
+impl ReflectMigrations {
+    /// Adds a migration for data written with a version lower than `version`.
+    pub fn push(
+        &mut self,
+        version: u32,
+        migration: impl Fn(Box<dyn PartialReflect>) -> Result<Box<dyn PartialReflect>, String>
+            + Send
+            + Sync
+            + 'static,
+    ) {
+        let index = self.migrations.partition_point(|(v, _)| *v <= version);
+        self.migrations
+            .insert(index, (version, Arc::new(migration)));
+    }
+
+    /// Returns `true` if data written with `version` needs to be migrated.
+    pub fn needs_migration(&self, version: u32) -> bool {
+        self.migrations.iter().any(|(v, _)| *v > version)
+    }
+
+    /// Runs the migrations for data written with `version`, in order.
+    pub fn migrate(
+        &self,
+        version: u32,
+        mut value: Box<dyn PartialReflect>,
+    ) -> Result<Box<dyn PartialReflect>, String> {
+        for (_, migration) in self.migrations.iter().filter(|(v, _)| *v > version) {
+            value = migration(value)?;
+        }
+        Ok(value)
+    }
+}
+
+/// Extension trait for registering [`ReflectMigrations`] on a [`TypeRegistry`].
+pub trait RegisterMigrationExt {
+    /// Registers a migration of `T` for save games written with a version lower than `version`. `T` is registered if it isn't already.
+    fn register_migration<T: GetTypeRegistration>(
+        &mut self,
+        version: u32,
+        migration: impl Fn(Box<dyn PartialReflect>) -> Result<Box<dyn PartialReflect>, String>
+            + Send
+            + Sync
+            + 'static,
+    ) -> &mut Self;
+
+    /// Registers a migration of the struct `T` that modifies the fields of its data in place.
+    ///
+    /// See [`RegisterMigrationExt::register_migration`].
+    fn register_struct_migration<T: GetTypeRegistration>(
+        &mut self,
+        version: u32,
+        migration: impl Fn(&mut DynamicStruct) + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.register_migration::<T>(version, move |value| {
+            let ReflectRef::Struct(value) = value.reflect_ref() else {
+                return Err(format!(
+                    "expected a struct, found {}",
+                    value.reflect_type_path()
+                ));
+            };
+            let mut value = value.clone_dynamic();
+            migration(&mut value);
+            Ok(Box::new(value))
+        })
+    }
+}
+
+impl RegisterMigrationExt for TypeRegistry {
+    fn register_migration<T: GetTypeRegistration>(
+        &mut self,
+        version: u32,
+        migration: impl Fn(Box<dyn PartialReflect>) -> Result<Box<dyn PartialReflect>, String>
+            + Send
+            + Sync
+            + 'static,
+    ) -> &mut Self {
+        self.register::<T>();
+        let registration = self.get_mut(std::any::TypeId::of::<T>()).unwrap();
+        if registration.data::<ReflectMigrations>().is_none() {
+            registration.insert(ReflectMigrations::default());
+        }
+        registration
+            .data_mut::<ReflectMigrations>()
+            .unwrap()
+            .push(version, migration);
+        self
+    }
+}
+
+/// Serializes `scene` as a save game, along with its [`SaveSchema`].
+///
+/// See [`SaveGameSerializer`] for the format.
+pub fn serialize_save_game(
+    scene: &DynamicScene,
+    registry: &TypeRegistry,
+    version: u32,
+) -> Result<String, ron::Error> {
+    serialize_ron(SaveGameSerializer::new(scene, registry, version))
+}
+
+/// Deserializes a save game written by [`serialize_save_game`], migrating its data to the current layout of its types.
+///
+/// See [`SaveGameDeserializer`].
+pub fn deserialize_save_game(
+    input: &str,
+    registry: &TypeRegistry,
+) -> Result<DynamicScene, ron::error::SpannedError> {
+    let mut deserializer = ron::de::Deserializer::from_str(input)?;
+    SaveGameDeserializer {
+        type_registry: registry,
+    }
+    .deserialize(&mut deserializer)
+    .map_err(|e| deserializer.span_error(e))
+}
+
+/// Serializer for a save game: a [`DynamicScene`] along with its [`SaveSchema`].
+///
+/// The scene is written in the regular scene format, after the version of the save and the layout of its types:
+///
+/// ```ron
+/// (
+///   version: 1,
+///   types: {
+///     "f32": Other,
+///     "my_game::Health": Struct({
+///       "hp": "f32",
+///     }),
+///   },
+///   scene: (
+///     resources: {},
+///     entities: {
+///       4294967296: (
+///         components: {
+///           "my_game::Health": (
+///             hp: 7.5,
+///           ),
+///         },
+///       ),
+///     },
+///   ),
+/// )
+/// ```
+pub struct SaveGameSerializer<'a> {
+    /// The scene to serialize.
+    pub scene: &'a DynamicScene,
+    /// The schema of the scene.
+    pub schema: SaveSchema,
+    /// The type registry containing the types present in the scene.
+    pub registry: &'a TypeRegistry,
+}
+
+impl<'a> SaveGameSerializer<'a> {
+    /// Creates a save game serializer, collecting the [`SaveSchema`] of `scene`.
+    pub fn new(scene: &'a DynamicScene, registry: &'a TypeRegistry, version: u32) -> Self {
+        SaveGameSerializer {
+            scene,
+            schema: SaveSchema::new(scene, registry, version),
+            registry,
+        }
+    }
+}
+
+impl<'a> Serialize for SaveGameSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct(SAVE_GAME_STRUCT, 3)?;
+        state.serialize_field(SAVE_GAME_VERSION, &self.schema.version)?;
+        state.serialize_field(SAVE_GAME_TYPES, &self.schema.types)?;
+        state.serialize_field(
+            SAVE_GAME_SCENE,
+            &SceneSerializer::new(self.scene, self.registry),
+        )?;
+        state.end()
+    }
+}
+
+/// Handles deserialization of save games written by [`SaveGameSerializer`].
+///
+/// Types whose layout is unchanged since the save game was written are deserialized like in a regular scene.
+/// Types whose layout changed, or that have [`ReflectMigrations`] for the version of the save game, are deserialized with
+/// the layout stored in the save game, migrated, and then converted to the current type using [`ReflectFromReflect`].
+/// Fields whose type is no longer registered and that were removed from their struct are skipped.
+///
+/// Save games are read in the order they are written: the version and the types must come before the scene.
+pub struct SaveGameDeserializer<'a> {
+    /// Type registry in which the components and resources types used in the save game are registered.
+    pub type_registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for SaveGameDeserializer<'a> {
+    type Value = DynamicScene;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_struct(
+            SAVE_GAME_STRUCT,
+            &[SAVE_GAME_VERSION, SAVE_GAME_TYPES, SAVE_GAME_SCENE],
+            self,
+        )
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "lowercase")]
+enum SaveGameField {
+    Version,
+    Types,
+    Scene,
+}
+
+impl<'a, 'de> Visitor<'de> for SaveGameDeserializer<'a> {
+    type Value = DynamicScene;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("save game struct")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut version = None;
+        let mut types = None;
+        let mut scene = None;
+        while let Some(key) = map.next_key()? {
+            match key {
+                SaveGameField::Version => {
+                    if version.is_some() {
+                        return Err(Error::duplicate_field(SAVE_GAME_VERSION));
+                    }
+                    version = Some(map.next_value()?);
+                }
+                SaveGameField::Types => {
+                    if types.is_some() {
+                        return Err(Error::duplicate_field(SAVE_GAME_TYPES));
+                    }
+                    types = Some(map.next_value()?);
+                }
+                SaveGameField::Scene => {
+                    if scene.is_some() {
+                        return Err(Error::duplicate_field(SAVE_GAME_SCENE));
+                    }
+                    let schema = SaveSchema {
+                        version: version.ok_or_else(|| Error::missing_field(SAVE_GAME_VERSION))?,
+                        types: types
+                            .take()
+                            .ok_or_else(|| Error::missing_field(SAVE_GAME_TYPES))?,
+                    };
+                    let context = SchemaContext::new(schema, self.type_registry);
+                    scene = Some(map.next_value_seed(SaveSceneDeserializer { context: &context })?);
+                }
+            }
+        }
+        scene.ok_or_else(|| Error::missing_field(SAVE_GAME_SCENE))
+    }
+}
+
+/// The [`SaveSchema`] of a save game being deserialized, along with the types that need to be deserialized using it.
+struct SchemaContext<'a> {
+    schema: SaveSchema,
+    registry: &'a TypeRegistry,
+    /// The types that changed, or contain fields of types that changed, or need to be migrated.
+    changed: HashSet<String>,
+}
+
+impl<'a> SchemaContext<'a> {
+    fn new(schema: SaveSchema, registry: &'a TypeRegistry) -> Self {
+        let mut context = SchemaContext {
+            schema,
+            registry,
+            changed: HashSet::new(),
+        };
+        let mut visited = HashMap::new();
+        for type_path in context.schema.types.keys() {
+            context.is_changed(type_path, &mut visited);
+        }
+        context.changed = visited
+            .into_iter()
+            .filter(|(_, changed)| *changed)
+            .map(|(type_path, _)| type_path)
+            .collect();
+        context
+    }
+
+    fn is_changed(&self, type_path: &str, visited: &mut HashMap<String, bool>) -> bool {
+        if let Some(&changed) = visited.get(type_path) {
+            return changed;
+        }
+        // Guard against recursive types while the type is being checked
+        visited.insert(type_path.to_string(), false);
+
+        let Some(layout) = self.schema.types.get(type_path) else {
+            return false;
+        };
+        let changed = match self.registry.get_with_type_path(type_path) {
+            None => true,
+            Some(registration) => {
+                TypeLayout::of(registration.type_info()) != *layout
+                    || registration
+                        .data::<ReflectMigrations>()
+                        .is_some_and(|migrations| migrations.needs_migration(self.schema.version))
+                    || match layout {
+                        TypeLayout::Struct(fields) => fields
+                            .values()
+                            .any(|field_type| self.is_changed(field_type, visited)),
+                        TypeLayout::TupleStruct(fields) => fields
+                            .iter()
+                            .any(|field_type| self.is_changed(field_type, visited)),
+                        TypeLayout::Other => false,
+                    }
+            }
+        };
+        visited.insert(type_path.to_string(), changed);
+        changed
+    }
+}
+
+/// Deserializes a value of a registered type, using the layout stored in the save game if it changed.
+struct SchemaValueDeserializer<'a, 'c> {
+    registration: &'a TypeRegistration,
+    context: &'c SchemaContext<'a>,
+}
+
+impl<'a, 'c, 'de> DeserializeSeed<'de> for SchemaValueDeserializer<'a, 'c> {
+    type Value = Box<dyn PartialReflect>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let type_info = self.registration.type_info();
+        let type_path = type_info.type_path();
+        if !self.context.changed.contains(type_path) {
+            return TypedReflectDeserializer::new(self.registration, self.context.registry)
+                .deserialize(deserializer);
+        }
+
+        let name = type_info.type_path_table().ident().unwrap_or_default();
+        let value = match self.context.schema.types.get(type_path) {
+            Some(TypeLayout::Struct(fields)) => deserializer.deserialize_struct(
+                name,
+                &[],
+                OldStructVisitor {
+                    registration: self.registration,
+                    fields,
+                    context: self.context,
+                },
+            )?,
+            Some(TypeLayout::TupleStruct(fields)) => deserializer.deserialize_tuple_struct(
+                name,
+                fields.len(),
+                OldTupleStructVisitor {
+                    fields,
+                    context: self.context,
+                },
+            )?,
+            _ => TypedReflectDeserializer::new(self.registration, self.context.registry)
+                .deserialize(deserializer)?
+                .clone_value(),
+        };
+
+        let value = match self.registration.data::<ReflectMigrations>() {
+            Some(migrations) => migrations
+                .migrate(self.context.schema.version, value)
+                .map_err(|err| {
+                    Error::custom(format_args!("failed to migrate `{type_path}`: {err}"))
+                })?,
+            None => value,
+        };
+        let from_reflect = self
+            .registration
+            .data::<ReflectFromReflect>()
+            .ok_or_else(|| {
+                Error::custom(format_args!(
+                    "`{type_path}` changed since the save game was written, but does not reflect `FromReflect`"
+                ))
+            })?;
+        let value = from_reflect.from_reflect(value.as_ref()).ok_or_else(|| {
+            Error::custom(format_args!(
+                "the data of `{type_path}` does not match its current layout. consider registering a migration for it"
+            ))
+        })?;
+        Ok(value.into_partial_reflect())
+    }
+}
+
+/// Deserializes the value of a field with the type path stored in the save game.
+struct SchemaFieldDeserializer<'a, 'c> {
+    type_path: &'c str,
+    context: &'c SchemaContext<'a>,
+}
+
+impl<'a, 'c, 'de> DeserializeSeed<'de> for SchemaFieldDeserializer<'a, 'c> {
+    type Value = Box<dyn PartialReflect>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let registration = self
+            .context
+            .registry
+            .get_with_type_path(self.type_path)
+            .ok_or_else(|| {
+                Error::custom(format_args!(
+                    "no registration found for type `{}`",
+                    self.type_path
+                ))
+            })?;
+        SchemaValueDeserializer {
+            registration,
+            context: self.context,
+        }
+        .deserialize(deserializer)
+    }
+}
+
+struct Ident(String);
+
+impl<'de> Deserialize<'de> for Ident {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct IdentVisitor;
+
+        impl<'de> Visitor<'de> for IdentVisitor {
+            type Value = Ident;
+
+            fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+                formatter.write_str("identifier")
+            }
+
+            fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+            where
+                E: Error,
+            {
+                Ok(Ident(value.to_string()))
+            }
+
+            fn visit_string<E>(self, value: String) -> Result<Self::Value, E>
+            where
+                E: Error,
+            {
+                Ok(Ident(value))
+            }
+        }
+
+        deserializer.deserialize_identifier(IdentVisitor)
+    }
+}
+
+struct OldStructVisitor<'a, 'c> {
+    registration: &'a TypeRegistration,
+    fields: &'c BTreeMap<String, String>,
+    context: &'c SchemaContext<'a>,
+}
+
+impl<'a, 'c, 'de> Visitor<'de> for OldStructVisitor<'a, 'c> {
+    type Value = Box<dyn PartialReflect>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("struct")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let TypeInfo::Struct(current) = self.registration.type_info() else {
+            unreachable!("only structs are deserialized with their old struct layout")
+        };
+        let mut value = DynamicStruct::default();
+        while let Some(Ident(name)) = map.next_key()? {
+            let field_type = self.fields.get(&name).ok_or_else(|| {
+                Error::custom(format_args!(
+                    "field `{name}` is not part of the layout stored in the save game"
+                ))
+            })?;
+            let removed = current.field(&name).is_none();
+            if removed
+                && self
+                    .context
+                    .registry
+                    .get_with_type_path(field_type)
+                    .is_none()
+            {
+                map.next_value::<IgnoredAny>()?;
+                continue;
+            }
+            let field = map.next_value_seed(SchemaFieldDeserializer {
+                type_path: field_type,
+                context: self.context,
+            })?;
+            value.insert_boxed(name, field);
+        }
+        Ok(Box::new(value))
+    }
+}
+
+struct OldTupleStructVisitor<'a, 'c> {
+    fields: &'c [String],
+    context: &'c SchemaContext<'a>,
+}
+
+impl<'a, 'c, 'de> Visitor<'de> for OldTupleStructVisitor<'a, 'c> {
+    type Value = Box<dyn PartialReflect>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("tuple struct")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut value = DynamicTupleStruct::default();
+        for (index, field_type) in self.fields.iter().enumerate() {
+            let field = seq
+                .next_element_seed(SchemaFieldDeserializer {
+                    type_path: field_type,
+                    context: self.context,
+                })?
+                .ok_or_else(|| Error::invalid_length(index, &self))?;
+            value.insert_boxed(field);
+        }
+        Ok(Box::new(value))
+    }
+
+    fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let [field_type] = self.fields else {
+            return Err(Error::invalid_length(1, &self));
+        };
+        let mut value = DynamicTupleStruct::default();
+        value.insert_boxed(
+            SchemaFieldDeserializer {
+                type_path: field_type,
+                context: self.context,
+            }
+            .deserialize(deserializer)?,
+        );
+        Ok(Box::new(value))
+    }
+}
+
+struct SaveSceneDeserializer<'a, 'c> {
+    context: &'c SchemaContext<'a>,
+}
+
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "lowercase")]
+enum SceneField {
+    Resources,
+    Entities,
+}
+
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "lowercase")]
+enum EntityField {
+    Components,
+}
+
+impl<'a, 'c, 'de> DeserializeSeed<'de> for SaveSceneDeserializer<'a, 'c> {
+    type Value = DynamicScene;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_struct(
+            crate::serde::SCENE_STRUCT,
+            &[SCENE_RESOURCES, SCENE_ENTITIES],
+            self,
+        )
+    }
+}
+
+impl<'a, 'c, 'de> Visitor<'de> for SaveSceneDeserializer<'a, 'c> {
+    type Value = DynamicScene;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("scene struct")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut resources = None;
+        let mut entities = None;
+        while let Some(key) = map.next_key()? {
+            match key {
+                SceneField::Resources => {
+                    if resources.is_some() {
+                        return Err(Error::duplicate_field(SCENE_RESOURCES));
+                    }
+                    resources = Some(map.next_value_seed(SaveValuesDeserializer {
+                        context: self.context,
+                    })?);
+                }
+                SceneField::Entities => {
+                    if entities.is_some() {
+                        return Err(Error::duplicate_field(SCENE_ENTITIES));
+                    }
+                    entities = Some(map.next_value_seed(SaveEntitiesDeserializer {
+                        context: self.context,
+                    })?);
+                }
+            }
+        }
+
+        Ok(DynamicScene {
+            resources: resources.ok_or_else(|| Error::missing_field(SCENE_RESOURCES))?,
+            entities: entities.ok_or_else(|| Error::missing_field(SCENE_ENTITIES))?,
+        })
+    }
+}
+
+struct SaveEntitiesDeserializer<'a, 'c> {
+    context: &'c SchemaContext<'a>,
+}
+
+impl<'a, 'c, 'de> DeserializeSeed<'de> for SaveEntitiesDeserializer<'a, 'c> {
+    type Value = Vec<DynamicEntity>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'a, 'c, 'de> Visitor<'de> for SaveEntitiesDeserializer<'a, 'c> {
+    type Value = Vec<DynamicEntity>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("map of entities")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut entities = Vec::new();
+        while let Some(entity) = map.next_key::<Entity>()? {
+            let components = map.next_value_seed(SaveEntityDeserializer {
+                context: self.context,
+            })?;
+            entities.push(DynamicEntity { entity, components });
+        }
+        Ok(entities)
+    }
+}
+
+struct SaveEntityDeserializer<'a, 'c> {
+    context: &'c SchemaContext<'a>,
+}
+
+impl<'a, 'c, 'de> DeserializeSeed<'de> for SaveEntityDeserializer<'a, 'c> {
+    type Value = Vec<Box<dyn PartialReflect>>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_struct(
+            crate::serde::ENTITY_STRUCT,
+            &[ENTITY_FIELD_COMPONENTS],
+            self,
+        )
+    }
+}
+
+impl<'a, 'c, 'de> Visitor<'de> for SaveEntityDeserializer<'a, 'c> {
+    type Value = Vec<Box<dyn PartialReflect>>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("entity struct")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut components = None;
+        while let Some(EntityField::Components) = map.next_key()? {
+            if components.is_some() {
+                return Err(Error::duplicate_field(ENTITY_FIELD_COMPONENTS));
+            }
+            components = Some(map.next_value_seed(SaveValuesDeserializer {
+                context: self.context,
+            })?);
+        }
+        components.ok_or_else(|| Error::missing_field(ENTITY_FIELD_COMPONENTS))
+    }
+}
+
+struct SaveValuesDeserializer<'a, 'c> {
+    context: &'c SchemaContext<'a>,
+}
+
+impl<'a, 'c, 'de> DeserializeSeed<'de> for SaveValuesDeserializer<'a, 'c> {
+    type Value = Vec<Box<dyn PartialReflect>>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'a, 'c, 'de> Visitor<'de> for SaveValuesDeserializer<'a, 'c> {
+    type Value = Vec<Box<dyn PartialReflect>>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("map of reflect types")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut added = HashSet::new();
+        let mut entries = Vec::new();
+        while let Some(registration) =
+            map.next_key_seed(TypeRegistrationDeserializer::new(self.context.registry))?
+        {
+            if !added.insert(registration.type_id()) {
+                return Err(Error::custom(format_args!(
+                    "duplicate reflect type: `{}`",
+                    registration.type_info().type_path(),
+                )));
+            }
+            entries.push(map.next_value_seed(SchemaValueDeserializer {
+                registration,
+                context: self.context,
+            })?);
+        }
+        Ok(entries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::prelude::{Component, ReflectComponent};
+    use bevy_reflect::{Reflect, Struct};
+
+    mod v1 {
+        use super::*;
+
+        #[derive(Component, Reflect, Default)]
+        #[reflect(Component)]
+        #[type_path = "game"]
+        pub struct Health {
+            pub hp: f32,
+        }
+
+        #[derive(Component, Reflect, Default)]
+        #[reflect(Component)]
+        #[type_path = "game"]
+        pub struct Stats {
+            pub health: Health,
+            pub level: u32,
+        }
+    }
+
+    mod v2 {
+        use super::*;
+
+        #[derive(Component, Reflect, Default, Debug, PartialEq)]
+        #[reflect(Component)]
+        #[type_path = "game"]
+        pub struct Health {
+            pub current: f32,
+            pub max: f32,
+        }
+
+        #[derive(Component, Reflect, Default, Debug, PartialEq)]
+        #[reflect(Component)]
+        #[type_path = "game"]
+        pub struct Stats {
+            pub health: Health,
+            pub level: u32,
+        }
+    }
+
+    #[derive(Component, Reflect, Default, Debug, PartialEq)]
+    #[reflect(Component)]
+    #[type_path = "game"]
+    struct Name(String);
+
+    fn save_v1() -> String {
+        let mut registry = TypeRegistry::default();
+        registry.register::<v1::Stats>();
+        registry.register::<Name>();
+        let scene = DynamicScene {
+            resources: Vec::new(),
+            entities: vec![DynamicEntity {
+                entity: Entity::from_raw(0),
+                components: vec![
+                    Box::new(v1::Stats {
+                        health: v1::Health { hp: 7.5 },
+                        level: 3,
+                    }),
+                    Box::new(Name("player".into())),
+                ],
+            }],
+        };
+        serialize_save_game(&scene, &registry, 1).unwrap()
+    }
+
+    #[test]
+    fn serialize_schema() {
+        let expected = r#"(
+  version: 1,
+  types: {
+    "alloc::string::String": Other,
+    "f32": Other,
+    "game::Health": Struct({
+      "hp": "f32",
+    }),
+    "game::Name": TupleStruct([
+      "alloc::string::String",
+    ]),
+    "game::Stats": Struct({
+      "health": "game::Health",
+      "level": "u32",
+    }),
+    "u32": Other,
+  },
+  scene: (
+    resources: {},
+    entities: {
+      4294967296: (
+        components: {
+          "game::Stats": (
+            health: (
+              hp: 7.5,
+            ),
+            level: 3,
+          ),
+          "game::Name": ("player"),
+        },
+      ),
+    },
+  ),
+)"#;
+        assert_eq!(save_v1(), expected);
+    }
+
+    #[test]
+    fn migrate_changed_types() {
+        let save = save_v1();
+        let mut registry = TypeRegistry::default();
+        registry.register::<v2::Stats>();
+        registry.register::<Name>();
+
+        // Without a migration, the old data does not match the new layout of `Health`
+        let Err(err) = deserialize_save_game(&save, &registry) else {
+            panic!("expected the save game to fail to load");
+        };
+        assert!(err.to_string().contains("game::Health"), "{err}");
+
+        registry.register_struct_migration::<v2::Health>(2, |health| {
+            let hp = health.field("hp").unwrap().clone_value();
+            health.insert_boxed("current", hp);
+            health.insert("max", 10.0f32);
+        });
+        let scene = deserialize_save_game(&save, &registry).unwrap();
+        let components = &scene.entities[0].components;
+        assert_eq!(
+            components[0].try_downcast_ref::<v2::Stats>(),
+            Some(&v2::Stats {
+                health: v2::Health {
+                    current: 7.5,
+                    max: 10.0,
+                },
+                level: 3,
+            })
+        );
+        // Unchanged types are deserialized as usual
+        assert!(components[1]
+            .reflect_partial_eq(&Name("player".into()))
+            .unwrap());
+
+        // Saves written by the current version are not migrated
+        let save = serialize_save_game(&scene, &registry, 2).unwrap();
+        let scene = deserialize_save_game(&save, &registry).unwrap();
+        assert!(scene.entities[0].components[0]
+            .reflect_partial_eq(&v2::Stats {
+                health: v2::Health {
+                    current: 7.5,
+                    max: 10.0,
+                },
+                level: 3,
+            })
+            .unwrap());
+    }
+
+    #[test]
+    fn migrate_unchanged_layout() {
+        let save = save_v1();
+        let mut registry = TypeRegistry::default();
+        registry.register::<v1::Stats>();
+        registry.register::<Name>();
+        registry.register_migration::<Name>(2, |name| {
+            let ReflectRef::TupleStruct(name) = name.reflect_ref() else {
+                return Err("expected a tuple struct".into());
+            };
+            let name = name.field(0).unwrap().try_downcast_ref::<String>().unwrap();
+            Ok(Box::new(Name(name.to_uppercase())))
+        });
+
+        let scene = deserialize_save_game(&save, &registry).unwrap();
+        assert!(scene.entities[0].components[1]
+            .reflect_partial_eq(&Name("PLAYER".into()))
+            .unwrap());
+    }
+}
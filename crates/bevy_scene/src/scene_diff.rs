@@ -0,0 +1,873 @@
+use crate::{
+    DynamicEntity, DynamicScene, DynamicSceneBuilder, FieldPatch, InstanceId, SceneFilter,
+    SceneSpawnError,
+};
+use bevy_ecs::{
+    entity::{Entity, EntityHashMap},
+    reflect::{AppTypeRegistry, ReflectComponent},
+    world::World,
+};
+use bevy_reflect::{
+    diff, DiffChange, ParsedPath, PartialReflect, ReflectMut, ReflectPath, TypeInfo, TypeRegistry,
+};
+use std::any::TypeId;
+use thiserror::Error;
+
+/// A list of changes that turns one set of entities into another, computed with [`ScenePatch::diff`] or
+/// [`ScenePatch::diff_world`].
+///
+/// Patches can be applied to a [`World`] using [`ScenePatch::apply`], to a spawned scene instance using
+/// [`SceneSpawner::apply_patch_to_instance`](crate::SceneSpawner::apply_patch_to_instance), or to a [`DynamicScene`]
+/// using [`ScenePatch::apply_to_scene`]. This makes them suitable for undo/redo, sending edits of a level over the network,
+/// or saving only the changes made to a level instead of the whole level.
+///
+/// Entities are identified by their id in the scene, like in [`DynamicEntity::entity`]. Resources are not diffed.
+#[derive(Default, Debug)]
+pub struct ScenePatch {
+    /// The changes of the patch, in the order they are applied.
+    ///
+    /// Patches computed by [`ScenePatch::diff`] spawn entities first and despawn entities last, so that components
+    /// can reference the spawned entities.
+    pub changes: Vec<SceneChange>,
+}
+
+/// A single change of a [`ScenePatch`].
+#[derive(Debug)]
+pub enum SceneChange {
+    /// Spawns an empty entity.
+    SpawnEntity {
+        /// The id of the entity in the scene.
+        entity: Entity,
+    },
+    /// Despawns an entity. This does not despawn its children.
+    DespawnEntity {
+        /// The id of the entity in the scene.
+        entity: Entity,
+    },
+    /// Inserts a component, replacing the existing component of the same type.
+    InsertComponent {
+        /// The id of the entity in the scene.
+        entity: Entity,
+        /// The value of the component.
+        component: Box<dyn PartialReflect>,
+    },
+    /// Removes a component.
+    RemoveComponent {
+        /// The id of the entity in the scene.
+        entity: Entity,
+        /// The [`TypeId`] of the removed component.
+        component: TypeId,
+    },
+    /// Sets a single (reflected) field of a component.
+    SetField {
+        /// The id of the entity in the scene.
+        entity: Entity,
+        /// The changed field and its new value.
+        patch: FieldPatch,
+    },
+    /// Inserts or removes an element of a list, map or set within a component.
+    EditCollection {
+        /// The id of the entity in the scene.
+        entity: Entity,
+        /// The [`TypeId`] of the edited component.
+        component: TypeId,
+        /// The insertion or removal, relative to the component.
+        change: DiffChange,
+    },
+}
+
+impl SceneChange {
+    /// The id of the entity in the scene this change applies to.
+    pub fn entity(&self) -> Entity {
+        match self {
+            SceneChange::SpawnEntity { entity }
+            | SceneChange::DespawnEntity { entity }
+            | SceneChange::InsertComponent { entity, .. }
+            | SceneChange::RemoveComponent { entity, .. }
+            | SceneChange::SetField { entity, .. }
+            | SceneChange::EditCollection { entity, .. } => *entity,
+        }
+    }
+
+    /// Applies this change to `world`. See [`ScenePatch::apply`].
+    pub fn apply(
+        &self,
+        world: &mut World,
+        entity_map: &mut EntityHashMap<Entity>,
+        type_registry: &TypeRegistry,
+    ) -> Result<(), ScenePatchError> {
+        match self {
+            SceneChange::SpawnEntity { entity } => {
+                entity_map
+                    .entry(*entity)
+                    .or_insert_with(|| world.spawn_empty().id());
+            }
+            SceneChange::DespawnEntity { entity } => {
+                if let Some(world_entity) = entity_map.remove(entity) {
+                    world.despawn(world_entity);
+                }
+            }
+            SceneChange::InsertComponent { entity, component } => {
+                let type_id = represented_type_id(component.as_ref())?;
+                let reflect_component = reflect_component(type_registry, type_id)?;
+                let mut component = component.clone_value();
+                map_entities(component.as_mut(), entity_map);
+                let mut entity_mut = world_entity_mut(world, entity_map, *entity)?;
+                reflect_component.apply_or_insert(
+                    &mut entity_mut,
+                    component.as_ref(),
+                    type_registry,
+                );
+            }
+            SceneChange::RemoveComponent { entity, component } => {
+                let reflect_component = reflect_component(type_registry, *component)?;
+                let mut entity_mut = world_entity_mut(world, entity_map, *entity)?;
+                reflect_component.remove(&mut entity_mut);
+            }
+            SceneChange::SetField { entity, patch } => {
+                let reflect_component = reflect_component(type_registry, patch.component)?;
+                let mut value = patch.value.clone_value();
+                map_entities(value.as_mut(), entity_map);
+                let mut entity_mut = world_entity_mut(world, entity_map, *entity)?;
+                let mut component =
+                    reflect_component
+                        .reflect_mut(&mut entity_mut)
+                        .ok_or_else(|| ScenePatchError::MissingComponent {
+                            entity: *entity,
+                            type_path: type_path(type_registry, patch.component),
+                        })?;
+                set_field(
+                    component.as_partial_reflect_mut(),
+                    &patch.path,
+                    value.as_ref(),
+                )
+                .map_err(|message| ScenePatchError::InvalidField {
+                    entity: *entity,
+                    type_path: type_path(type_registry, patch.component),
+                    path: patch.path.to_string(),
+                    message,
+                })?;
+            }
+            SceneChange::EditCollection {
+                entity,
+                component: type_id,
+                change,
+            } => {
+                let reflect_component = reflect_component(type_registry, *type_id)?;
+                let change = map_change_entities(change, entity_map);
+                let mut entity_mut = world_entity_mut(world, entity_map, *entity)?;
+                let mut component =
+                    reflect_component
+                        .reflect_mut(&mut entity_mut)
+                        .ok_or_else(|| ScenePatchError::MissingComponent {
+                            entity: *entity,
+                            type_path: type_path(type_registry, *type_id),
+                        })?;
+                change
+                    .apply(component.as_partial_reflect_mut())
+                    .map_err(|err| ScenePatchError::InvalidField {
+                        entity: *entity,
+                        type_path: type_path(type_registry, *type_id),
+                        path: change.path().to_string(),
+                        message: err.to_string(),
+                    })?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// An error that occurs when computing or applying a [`ScenePatch`].
+#[derive(Error, Debug)]
+pub enum ScenePatchError {
+    /// A component is not registered or does not represent a type.
+    #[error(transparent)]
+    Spawn(#[from] SceneSpawnError),
+    /// The scene instance does not exist.
+    #[error("scene instance {0:?} does not exist")]
+    NonExistentInstance(InstanceId),
+    /// The patch references an entity that does not exist.
+    #[error("the patch references entity {0}, which does not exist")]
+    MissingEntity(Entity),
+    /// The patch sets a field of a component the entity does not have.
+    #[error("entity {entity} does not have the patched component `{type_path}`")]
+    MissingComponent {
+        /// The id of the entity in the scene.
+        entity: Entity,
+        /// The type path of the missing component.
+        type_path: String,
+    },
+    /// A field of a component could not be set.
+    #[error("failed to set `{path}` of `{type_path}` on entity {entity}: {message}")]
+    InvalidField {
+        /// The id of the entity in the scene.
+        entity: Entity,
+        /// The type path of the patched component.
+        type_path: String,
+        /// The path of the field.
+        path: String,
+        /// A description of the error.
+        message: String,
+    },
+}
+
+impl ScenePatch {
+    /// Computes the changes that turn the entities of `from` into the entities of `to`.
+    ///
+    /// Entities are matched by their id. Components that differ are compared with [`bevy_reflect::diff`]: changed
+    /// fields become [`SceneChange::SetField`], elements inserted into or removed from lists, maps and sets become
+    /// [`SceneChange::EditCollection`], and components that can't be diffed field by field are replaced as a whole.
+    pub fn diff(from: &DynamicScene, to: &DynamicScene) -> Result<Self, ScenePatchError> {
+        let from_indices = entity_indices(from);
+        let to_indices = entity_indices(to);
+        let mut spawned = Vec::new();
+        let mut changed = Vec::new();
+        let mut despawned = Vec::new();
+
+        for to_entity in &to.entities {
+            let entity = to_entity.entity;
+            let Some(from_entity) = from_indices
+                .get(&entity)
+                .map(|&index| &from.entities[index])
+            else {
+                spawned.push(SceneChange::SpawnEntity { entity });
+                for component in &to_entity.components {
+                    changed.push(SceneChange::InsertComponent {
+                        entity,
+                        component: component.clone_value(),
+                    });
+                }
+                continue;
+            };
+
+            for component in &to_entity.components {
+                let type_id = represented_type_id(component.as_ref())?;
+                let Some(from_component) = find_component(from_entity, type_id)? else {
+                    changed.push(SceneChange::InsertComponent {
+                        entity,
+                        component: component.clone_value(),
+                    });
+                    continue;
+                };
+                for change in diff(from_component, component.as_ref()).into_changes() {
+                    changed.push(match change {
+                        DiffChange::Modified { path, value }
+                        | DiffChange::VariantChanged { path, value } => {
+                            if path.0.is_empty() {
+                                SceneChange::InsertComponent {
+                                    entity,
+                                    component: value,
+                                }
+                            } else {
+                                SceneChange::SetField {
+                                    entity,
+                                    patch: FieldPatch {
+                                        component: type_id,
+                                        path,
+                                        value,
+                                    },
+                                }
+                            }
+                        }
+                        change => SceneChange::EditCollection {
+                            entity,
+                            component: type_id,
+                            change,
+                        },
+                    });
+                }
+            }
+            for component in &from_entity.components {
+                let type_id = represented_type_id(component.as_ref())?;
+                if find_component(to_entity, type_id)?.is_none() {
+                    changed.push(SceneChange::RemoveComponent {
+                        entity,
+                        component: type_id,
+                    });
+                }
+            }
+        }
+
+        for from_entity in &from.entities {
+            if !to_indices.contains_key(&from_entity.entity) {
+                despawned.push(SceneChange::DespawnEntity {
+                    entity: from_entity.entity,
+                });
+            }
+        }
+
+        spawned.append(&mut changed);
+        spawned.append(&mut despawned);
+        Ok(Self { changes: spawned })
+    }
+
+    /// Computes the changes that turn the entities of `scene` into the live entities of `world` they were written to.
+    ///
+    /// `entity_map` maps the entities of the scene to the entities of the world, as filled in by
+    /// [`DynamicScene::write_to_world`]. Only the components allowed by `filter` are extracted from the world, which can
+    /// be used to ignore components that are computed at runtime, such as `GlobalTransform`. References to entities
+    /// in the world are mapped back to entities in the scene. References to entities that are not part of the scene are kept as is.
+    pub fn diff_world(
+        scene: &DynamicScene,
+        world: &World,
+        entity_map: &EntityHashMap<Entity>,
+        filter: SceneFilter,
+    ) -> Result<Self, ScenePatchError> {
+        let mut live = DynamicSceneBuilder::from_world(world)
+            .with_filter(filter)
+            .extract_entities(
+                entity_map
+                    .values()
+                    .copied()
+                    .filter(|&entity| world.get_entity(entity).is_some()),
+            )
+            .build();
+
+        let scene_entities: EntityHashMap<Entity> =
+            entity_map.iter().map(|(&from, &to)| (to, from)).collect();
+        for live_entity in &mut live.entities {
+            live_entity.entity = scene_entities[&live_entity.entity];
+            for component in &mut live_entity.components {
+                map_entities(component.as_partial_reflect_mut(), &scene_entities);
+            }
+        }
+        let scene_indices = entity_indices(scene);
+        live.entities
+            .sort_by_key(|entity| scene_indices.get(&entity.entity).copied());
+
+        Self::diff(scene, &live)
+    }
+
+    /// Applies the changes of this patch to `world`.
+    ///
+    /// `entity_map` maps the entities of the scene to the entities of the world, as filled in by
+    /// [`DynamicScene::write_to_world`]. Spawned entities are added to it and despawned entities are removed from it.
+    /// References to entities of the scene in inserted components and set fields are mapped to entities of the world.
+    pub fn apply(
+        &self,
+        world: &mut World,
+        entity_map: &mut EntityHashMap<Entity>,
+        type_registry: &AppTypeRegistry,
+    ) -> Result<(), ScenePatchError> {
+        let type_registry = type_registry.read();
+        for change in &self.changes {
+            change.apply(world, entity_map, &type_registry)?;
+        }
+        Ok(())
+    }
+
+    /// Applies the changes of this patch to the entities of `scene`.
+    ///
+    /// Applying the patch computed by [`ScenePatch::diff`] to `from` makes its entities equal to the entities of `to`,
+    /// though components inserted into existing entities are added after their other components.
+    pub fn apply_to_scene(&self, scene: &mut DynamicScene) -> Result<(), ScenePatchError> {
+        let find_entity = |scene: &mut DynamicScene, entity: Entity| {
+            scene
+                .entities
+                .iter_mut()
+                .position(|e| e.entity == entity)
+                .ok_or(ScenePatchError::MissingEntity(entity))
+        };
+
+        for change in &self.changes {
+            match change {
+                SceneChange::SpawnEntity { entity } => {
+                    if !scene.entities.iter().any(|e| e.entity == *entity) {
+                        scene.entities.push(DynamicEntity {
+                            entity: *entity,
+                            components: Vec::new(),
+                        });
+                    }
+                }
+                SceneChange::DespawnEntity { entity } => {
+                    scene.entities.retain(|e| e.entity != *entity);
+                }
+                SceneChange::InsertComponent { entity, component } => {
+                    let type_id = represented_type_id(component.as_ref())?;
+                    let index = find_entity(scene, *entity)?;
+                    let components = &mut scene.entities[index].components;
+                    let existing = components
+                        .iter()
+                        .position(|c| represented_type_id(c.as_ref()).ok() == Some(type_id));
+                    match existing {
+                        Some(existing) => components[existing] = component.clone_value(),
+                        None => components.push(component.clone_value()),
+                    }
+                }
+                SceneChange::RemoveComponent { entity, component } => {
+                    let index = find_entity(scene, *entity)?;
+                    scene.entities[index]
+                        .components
+                        .retain(|c| represented_type_id(c.as_ref()).ok() != Some(*component));
+                }
+                SceneChange::SetField { entity, patch } => {
+                    let index = find_entity(scene, *entity)?;
+                    let component = scene.entities[index]
+                        .components
+                        .iter_mut()
+                        .find(|c| represented_type_id(c.as_ref()).ok() == Some(patch.component))
+                        .ok_or_else(|| ScenePatchError::MissingComponent {
+                            entity: *entity,
+                            type_path: format!("{:?}", patch.component),
+                        })?;
+                    let type_path = component.reflect_type_path().to_string();
+                    set_field(component.as_mut(), &patch.path, patch.value.as_ref()).map_err(
+                        |message| ScenePatchError::InvalidField {
+                            entity: *entity,
+                            type_path,
+                            path: patch.path.to_string(),
+                            message,
+                        },
+                    )?;
+                }
+                SceneChange::EditCollection {
+                    entity,
+                    component: type_id,
+                    change,
+                } => {
+                    let index = find_entity(scene, *entity)?;
+                    let component = scene.entities[index]
+                        .components
+                        .iter_mut()
+                        .find(|c| represented_type_id(c.as_ref()).ok() == Some(*type_id))
+                        .ok_or_else(|| ScenePatchError::MissingComponent {
+                            entity: *entity,
+                            type_path: format!("{type_id:?}"),
+                        })?;
+                    let type_path = component.reflect_type_path().to_string();
+                    change.apply(component.as_mut()).map_err(|err| {
+                        ScenePatchError::InvalidField {
+                            entity: *entity,
+                            type_path,
+                            path: change.path().to_string(),
+                            message: err.to_string(),
+                        }
+                    })?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Maps each entity of `scene` to its index in [`DynamicScene::entities`].
+fn entity_indices(scene: &DynamicScene) -> EntityHashMap<usize> {
+    scene
+        .entities
+        .iter()
+        .enumerate()
+        .map(|(index, entity)| (entity.entity, index))
+        .collect()
+}
+
+fn represented_type_id(value: &dyn PartialReflect) -> Result<TypeId, SceneSpawnError> {
+    value
+        .get_represented_type_info()
+        .map(TypeInfo::type_id)
+        .ok_or_else(|| SceneSpawnError::NoRepresentedType {
+            type_path: value.reflect_type_path().to_string(),
+        })
+}
+
+fn find_component(
+    entity: &DynamicEntity,
+    type_id: TypeId,
+) -> Result<Option<&dyn PartialReflect>, SceneSpawnError> {
+    for component in &entity.components {
+        if represented_type_id(component.as_ref())? == type_id {
+            return Ok(Some(component.as_ref()));
+        }
+    }
+    Ok(None)
+}
+
+fn reflect_component(
+    type_registry: &TypeRegistry,
+    type_id: TypeId,
+) -> Result<&ReflectComponent, SceneSpawnError> {
+    let registration =
+        type_registry
+            .get(type_id)
+            .ok_or_else(|| SceneSpawnError::UnregisteredType {
+                std_type_name: format!("{type_id:?}"),
+            })?;
+    registration
+        .data::<ReflectComponent>()
+        .ok_or_else(|| SceneSpawnError::UnregisteredComponent {
+            type_path: registration.type_info().type_path().to_string(),
+        })
+}
+
+fn type_path(type_registry: &TypeRegistry, type_id: TypeId) -> String {
+    type_registry
+        .get(type_id)
+        .map(|registration| registration.type_info().type_path().to_string())
+        .unwrap_or_else(|| format!("{type_id:?}"))
+}
+
+fn world_entity_mut<'w>(
+    world: &'w mut World,
+    entity_map: &EntityHashMap<Entity>,
+    entity: Entity,
+) -> Result<bevy_ecs::world::EntityWorldMut<'w>, ScenePatchError> {
+    entity_map
+        .get(&entity)
+        .and_then(|&world_entity| world.get_entity_mut(world_entity))
+        .ok_or(ScenePatchError::MissingEntity(entity))
+}
+
+fn set_field(
+    component: &mut dyn PartialReflect,
+    path: &ParsedPath,
+    value: &dyn PartialReflect,
+) -> Result<(), String> {
+    let field = path
+        .reflect_element_mut(component)
+        .map_err(|err| err.to_string())?;
+    field.try_apply(value).map_err(|err| err.to_string())
+}
+
+/// Clones `change`, mapping the entities in its values with [`map_entities`].
+fn map_change_entities(change: &DiffChange, entity_map: &EntityHashMap<Entity>) -> DiffChange {
+    let map = |value: &dyn PartialReflect| {
+        let mut value = value.clone_value();
+        map_entities(value.as_mut(), entity_map);
+        value
+    };
+    match change {
+        DiffChange::Modified { path, value } => DiffChange::Modified {
+            path: path.clone(),
+            value: map(value.as_ref()),
+        },
+        DiffChange::VariantChanged { path, value } => DiffChange::VariantChanged {
+            path: path.clone(),
+            value: map(value.as_ref()),
+        },
+        DiffChange::ListInserted { path, index, value } => DiffChange::ListInserted {
+            path: path.clone(),
+            index: *index,
+            value: map(value.as_ref()),
+        },
+        DiffChange::ListRemoved { path, index } => DiffChange::ListRemoved {
+            path: path.clone(),
+            index: *index,
+        },
+        DiffChange::MapInserted { path, key, value } => DiffChange::MapInserted {
+            path: path.clone(),
+            key: map(key.as_ref()),
+            value: map(value.as_ref()),
+        },
+        DiffChange::MapRemoved { path, key } => DiffChange::MapRemoved {
+            path: path.clone(),
+            key: map(key.as_ref()),
+        },
+        DiffChange::SetInserted { path, value } => DiffChange::SetInserted {
+            path: path.clone(),
+            value: map(value.as_ref()),
+        },
+        DiffChange::SetRemoved { path, value } => DiffChange::SetRemoved {
+            path: path.clone(),
+            value: map(value.as_ref()),
+        },
+    }
+}
+
+/// Replaces every [`Entity`] in `value` that is a key of `entity_map` with its value.
+fn map_entities(value: &mut dyn PartialReflect, entity_map: &EntityHashMap<Entity>) {
+    if let Some(entity) = value.try_downcast_mut::<Entity>() {
+        if let Some(&mapped) = entity_map.get(entity) {
+            *entity = mapped;
+        }
+        return;
+    }
+    match value.reflect_mut() {
+        ReflectMut::Struct(value) => {
+            for index in 0..value.field_len() {
+                map_entities(value.field_at_mut(index).unwrap(), entity_map);
+            }
+        }
+        ReflectMut::TupleStruct(value) => {
+            for index in 0..value.field_len() {
+                map_entities(value.field_mut(index).unwrap(), entity_map);
+            }
+        }
+        ReflectMut::Tuple(value) => {
+            for index in 0..value.field_len() {
+                map_entities(value.field_mut(index).unwrap(), entity_map);
+            }
+        }
+        ReflectMut::List(value) => {
+            for index in 0..value.len() {
+                map_entities(value.get_mut(index).unwrap(), entity_map);
+            }
+        }
+        ReflectMut::Array(value) => {
+            for index in 0..value.len() {
+                map_entities(value.get_mut(index).unwrap(), entity_map);
+            }
+        }
+        ReflectMut::Map(value) => {
+            for index in 0..value.len() {
+                map_entities(value.get_at_mut(index).unwrap().1, entity_map);
+            }
+        }
+        ReflectMut::Enum(value) => {
+            for index in 0..value.field_len() {
+                map_entities(value.field_at_mut(index).unwrap(), entity_map);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SceneSpawner;
+    use bevy_asset::Assets;
+    use bevy_ecs::entity::{EntityMapper, MapEntities};
+    use bevy_ecs::prelude::{Component, ReflectComponent};
+    use bevy_ecs::reflect::ReflectMapEntities;
+    use bevy_reflect::Reflect;
+
+    #[derive(Component, Reflect, Default, Debug, PartialEq)]
+    #[reflect(Component)]
+    struct Health {
+        current: f32,
+        max: f32,
+    }
+
+    #[derive(Component, Reflect, Default, Debug, PartialEq)]
+    #[reflect(Component)]
+    struct Name(String);
+
+    #[derive(Component, Reflect, Debug, PartialEq)]
+    #[reflect(Component, MapEntities)]
+    struct Target(Entity);
+
+    #[derive(Component, Reflect, Default, Debug, PartialEq)]
+    #[reflect(Component)]
+    struct Followers(Vec<Entity>);
+
+    impl MapEntities for Target {
+        fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+            self.0 = entity_mapper.map_entity(self.0);
+        }
+    }
+
+    fn create_world() -> World {
+        let mut world = World::new();
+        world.insert_resource(Assets::<DynamicScene>::default());
+        let registry = AppTypeRegistry::default();
+        {
+            let mut registry = registry.write();
+            registry.register::<Health>();
+            registry.register::<Name>();
+            registry.register::<Target>();
+            registry.register::<Followers>();
+        }
+        world.insert_resource(registry);
+        world
+    }
+
+    #[test]
+    fn diff_and_apply_to_scene() {
+        let mut world = create_world();
+        let player = world
+            .spawn((
+                Name("player".into()),
+                Health {
+                    current: 10.0,
+                    max: 10.0,
+                },
+            ))
+            .id();
+        let enemy = world.spawn(Name("enemy".into())).id();
+        let from = DynamicScene::from_world(&world);
+
+        world
+            .entity_mut(player)
+            .get_mut::<Health>()
+            .unwrap()
+            .current = 2.5;
+        world.entity_mut(player).remove::<Name>();
+        world.despawn(enemy);
+        let boss = world.spawn((Name("boss".into()), Target(player))).id();
+        let to = DynamicScene::from_world(&world);
+
+        let patch = ScenePatch::diff(&from, &to).unwrap();
+        assert!(matches!(
+            patch.changes[0],
+            SceneChange::SpawnEntity { entity } if entity == boss
+        ));
+        let field = patch
+            .changes
+            .iter()
+            .find_map(|change| match change {
+                SceneChange::SetField { entity, patch } if *entity == player => Some(patch),
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(field.component, TypeId::of::<Health>());
+        assert_eq!(field.path.to_string(), ".current");
+        assert!(patch.changes.iter().any(|change| matches!(
+            change,
+            SceneChange::RemoveComponent { entity, component }
+                if *entity == player && *component == TypeId::of::<Name>()
+        )));
+        assert!(matches!(
+            patch.changes.last().unwrap(),
+            SceneChange::DespawnEntity { entity } if *entity == enemy
+        ));
+
+        let mut patched = DynamicScene::from_world(&create_world());
+        patched.entities = from.entities;
+        patch.apply_to_scene(&mut patched).unwrap();
+        assert!(ScenePatch::diff(&patched, &to).unwrap().changes.is_empty());
+    }
+
+    #[test]
+    fn diff_and_apply_collection_edits() {
+        let mut scene_world = create_world();
+        let leader = scene_world.spawn(Followers::default()).id();
+        let first = scene_world.spawn_empty().id();
+        let from = DynamicScene::from_world(&scene_world);
+
+        let second = scene_world.spawn_empty().id();
+        scene_world.get_mut::<Followers>(leader).unwrap().0 = vec![first, second];
+        let to = DynamicScene::from_world(&scene_world);
+
+        let patch = ScenePatch::diff(&from, &to).unwrap();
+        assert_eq!(
+            patch
+                .changes
+                .iter()
+                .filter(|change| matches!(change, SceneChange::EditCollection { .. }))
+                .count(),
+            2
+        );
+
+        // Inserted entities are mapped to the entities of the world
+        let mut world = create_world();
+        let mut entity_map = EntityHashMap::default();
+        from.write_to_world(&mut world, &mut entity_map).unwrap();
+        let registry = world.resource::<AppTypeRegistry>().clone();
+        patch.apply(&mut world, &mut entity_map, &registry).unwrap();
+        assert_eq!(
+            world.get::<Followers>(entity_map[&leader]).unwrap().0,
+            vec![entity_map[&first], entity_map[&second]]
+        );
+
+        let mut patched = DynamicScene::from_world(&create_world());
+        patched.entities = from.entities;
+        patch.apply_to_scene(&mut patched).unwrap();
+        assert!(ScenePatch::diff(&patched, &to).unwrap().changes.is_empty());
+    }
+
+    #[test]
+    fn diff_replaces_components_with_mismatched_fields() {
+        let entity = Entity::from_raw(0);
+        let mut partial = bevy_reflect::DynamicStruct::default();
+        partial.set_represented_type(Some(<Health as bevy_reflect::Typed>::type_info()));
+        partial.insert("current", 5.0f32);
+        partial.insert("maximum", 10.0f32);
+        let scene = |component: Box<dyn PartialReflect>| DynamicScene {
+            resources: Vec::new(),
+            entities: vec![DynamicEntity {
+                entity,
+                components: vec![component],
+            }],
+        };
+        let from = scene(Box::new(partial));
+        let to = scene(Box::new(Health {
+            current: 2.0,
+            max: 20.0,
+        }));
+
+        let patch = ScenePatch::diff(&from, &to).unwrap();
+        assert_eq!(patch.changes.len(), 1);
+        assert!(matches!(
+            &patch.changes[0],
+            SceneChange::InsertComponent { component, .. }
+                if component.reflect_partial_eq(&Health { current: 2.0, max: 20.0 }) == Some(true)
+        ));
+    }
+
+    #[test]
+    fn diff_and_apply_to_instance() {
+        let mut scene_world = create_world();
+        let player = scene_world
+            .spawn(Health {
+                current: 10.0,
+                max: 10.0,
+            })
+            .id();
+        scene_world.spawn(Target(player));
+        let scene = DynamicScene::from_world(&scene_world);
+
+        let mut world = create_world();
+        let scene_id = world
+            .resource_mut::<Assets<DynamicScene>>()
+            .add(DynamicScene::from_world(&scene_world));
+        let mut scene_spawner = SceneSpawner::default();
+        let edited = scene_spawner
+            .spawn_dynamic_sync(&mut world, &scene_id)
+            .unwrap();
+        let other = scene_spawner
+            .spawn_dynamic_sync(&mut world, &scene_id)
+            .unwrap();
+        let find_player = |world: &World, scene_spawner: &SceneSpawner, instance| {
+            scene_spawner
+                .iter_instance_entities(instance)
+                .find(|&entity| world.get::<Health>(entity).is_some())
+                .unwrap()
+        };
+
+        // Edit the first instance, and copy the edit to the second one
+        let edited_player = find_player(&world, &scene_spawner, edited);
+        world.get_mut::<Health>(edited_player).unwrap().max = 20.0;
+        let patch = scene_spawner
+            .diff_instance(&world, edited, &scene, SceneFilter::default())
+            .unwrap();
+        assert_eq!(patch.changes.len(), 1);
+        assert!(matches!(
+            &patch.changes[0],
+            SceneChange::SetField { entity, .. } if *entity == player
+        ));
+
+        scene_spawner
+            .apply_patch_to_instance(&mut world, other, &patch)
+            .unwrap();
+        let other_player = find_player(&world, &scene_spawner, other);
+        assert_eq!(world.get::<Health>(other_player).unwrap().max, 20.0);
+
+        // Spawned entities are added to the instance, and references to entities of the scene are mapped
+        let companion = Entity::from_raw(100);
+        let spawn_companion = ScenePatch {
+            changes: vec![
+                SceneChange::SpawnEntity { entity: companion },
+                SceneChange::InsertComponent {
+                    entity: companion,
+                    component: Box::new(Target(player)),
+                },
+            ],
+        };
+        scene_spawner
+            .apply_patch_to_instance(&mut world, other, &spawn_companion)
+            .unwrap();
+        assert_eq!(scene_spawner.iter_instance_entities(other).count(), 3);
+        let targets = scene_spawner
+            .iter_instance_entities(other)
+            .filter(|&entity| world.get::<Target>(entity) == Some(&Target(other_player)))
+            .count();
+        assert_eq!(targets, 2);
+
+        let patch = scene_spawner
+            .diff_instance(&world, other, &scene, SceneFilter::default())
+            .unwrap();
+        assert!(matches!(
+            patch.changes[0],
+            SceneChange::SpawnEntity { entity } if entity == companion
+        ));
+    }
+}
@@ -37,6 +37,10 @@ pub enum SceneLoaderError {
     /// A [RON Error](ron::error::SpannedError)
     #[error("Could not parse RON: {0}")]
     RonSpannedError(#[from] ron::error::SpannedError),
+    /// A [binary scene error](crate::BinarySceneError)
+    #[cfg(feature = "serialize")]
+    #[error("Could not read binary scene: {0}")]
+    Binary(#[from] crate::BinarySceneError),
 }
 
 #[cfg(feature = "serialize")]
@@ -1,4 +1,4 @@
-use crate::{DynamicScene, Scene};
+use crate::{DynamicEntity, DynamicScene, Scene, SceneFilter, ScenePatch, ScenePatchError};
 use bevy_asset::{AssetEvent, AssetId, Assets, Handle};
 use bevy_ecs::entity::EntityHashMap;
 use bevy_ecs::{
@@ -9,7 +9,10 @@ use bevy_ecs::{
     world::{Command, Mut, World},
 };
 use bevy_hierarchy::{BuildChildren, DespawnRecursiveExt, Parent, PushChild};
-use bevy_utils::{tracing::error, HashMap, HashSet};
+use bevy_utils::{
+    tracing::{error, warn},
+    HashMap, HashSet,
+};
 use thiserror::Error;
 use uuid::Uuid;
 
@@ -31,6 +34,27 @@ pub struct InstanceInfo {
     pub entity_map: EntityHashMap<Entity>,
 }
 
+/// How [`SceneSpawner`] updates the instances of a [`DynamicScene`] when the scene is modified, such as when its asset is reloaded.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SceneReloadMode {
+    /// Writes the whole modified scene to the entities of each instance.
+    ///
+    /// This overwrites every component of the scene, including components that were changed at runtime but not in the
+    /// scene. Entities and components that were removed from the scene are kept.
+    #[default]
+    Overwrite,
+    /// Computes the changes between the previous and the modified scene with [`ScenePatch::diff`], and applies only
+    /// those changes to each instance.
+    ///
+    /// Entities are matched by their id in the scene ([`DynamicEntity::entity`]), so entities that are still part of the
+    /// scene are updated in place: their [`Entity`] ids stay valid, and components that were added at runtime are kept.
+    /// Entities and components removed from the scene are removed from the instances. Changes that can't be applied,
+    /// for example because an entity of the instance was despawned at runtime, are skipped with a warning.
+    ///
+    /// This keeps a copy of the entities of every spawned dynamic scene.
+    Reconcile,
+}
+
 /// Unique id identifying a scene instance.
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
 pub struct InstanceId(Uuid);
@@ -60,6 +84,9 @@ impl InstanceId {
 /// - [`spawn_as_child`](Self::spawn_as_child)
 /// - [`despawn`](Self::despawn)
 /// - [`despawn_instance`](Self::despawn_instance)
+///
+/// When a spawned [`DynamicScene`] is modified, its instances are updated according to the [`SceneReloadMode`],
+/// which can be changed with [`set_reload_mode`](Self::set_reload_mode).
 #[derive(Default, Resource)]
 pub struct SceneSpawner {
     pub(crate) spawned_dynamic_scenes: HashMap<AssetId<DynamicScene>, HashSet<InstanceId>>,
@@ -70,6 +97,9 @@ pub struct SceneSpawner {
     scenes_to_despawn: Vec<AssetId<DynamicScene>>,
     instances_to_despawn: Vec<InstanceId>,
     scenes_with_parent: Vec<(InstanceId, Entity)>,
+    reload_mode: SceneReloadMode,
+    /// The entities of each spawned dynamic scene as they were last spawned, used by [`SceneReloadMode::Reconcile`].
+    scene_snapshots: HashMap<AssetId<DynamicScene>, DynamicScene>,
 }
 
 /// Errors that can occur when spawning a scene.
@@ -112,6 +142,9 @@ pub enum SceneSpawnError {
         /// The dynamic instance type.
         type_path: String,
     },
+    /// A modified scene could not be diffed against its previous version.
+    #[error("failed to reconcile the modified scene with its instances: {0}")]
+    Reconcile(Box<ScenePatchError>),
     /// Dynamic scene with the given id does not exist.
     #[error("scene does not exist")]
     NonExistentScene {
@@ -127,6 +160,22 @@ pub enum SceneSpawnError {
 }
 
 impl SceneSpawner {
+    /// Returns how instances of modified dynamic scenes are updated.
+    pub fn reload_mode(&self) -> SceneReloadMode {
+        self.reload_mode
+    }
+
+    /// Sets how instances of modified dynamic scenes are updated.
+    ///
+    /// With [`SceneReloadMode::Reconcile`], scenes that were spawned before the mode was set are overwritten the next time
+    /// they are modified, and reconciled after that.
+    pub fn set_reload_mode(&mut self, reload_mode: SceneReloadMode) {
+        self.reload_mode = reload_mode;
+        if reload_mode != SceneReloadMode::Reconcile {
+            self.scene_snapshots.clear();
+        }
+    }
+
     /// Schedule the spawn of a new instance of the provided dynamic scene.
     pub fn spawn_dynamic(&mut self, id: impl Into<Handle<DynamicScene>>) -> InstanceId {
         let instance_id = InstanceId::new();
@@ -180,7 +229,9 @@ impl SceneSpawner {
         world: &mut World,
         id: impl Into<AssetId<DynamicScene>>,
     ) -> Result<(), SceneSpawnError> {
-        if let Some(instance_ids) = self.spawned_dynamic_scenes.remove(&id.into()) {
+        let id = id.into();
+        self.scene_snapshots.remove(&id);
+        if let Some(instance_ids) = self.spawned_dynamic_scenes.remove(&id) {
             for instance_id in instance_ids {
                 self.despawn_instance_sync(world, &instance_id);
             }
@@ -214,9 +265,58 @@ impl SceneSpawner {
             .insert(instance_id, InstanceInfo { entity_map });
         let spawned = self.spawned_dynamic_scenes.entry(id).or_default();
         spawned.insert(instance_id);
+        self.snapshot_scene(world, id);
         Ok(instance_id)
     }
 
+    /// Keeps a copy of the entities of the dynamic scene, if it doesn't exist yet and the scene will be reconciled.
+    fn snapshot_scene(&mut self, world: &World, id: AssetId<DynamicScene>) {
+        if self.reload_mode != SceneReloadMode::Reconcile || self.scene_snapshots.contains_key(&id)
+        {
+            return;
+        }
+        if let Some(scene) = world.resource::<Assets<DynamicScene>>().get(id) {
+            self.scene_snapshots.insert(id, snapshot(scene));
+        }
+    }
+
+    /// Applies the changes between the snapshot of a dynamic scene and its current version to its instances.
+    fn reconcile_spawned_scene(
+        &mut self,
+        world: &mut World,
+        id: AssetId<DynamicScene>,
+        previous: &DynamicScene,
+    ) -> Result<(), SceneSpawnError> {
+        let (patch, current) = {
+            let scenes = world.resource::<Assets<DynamicScene>>();
+            let scene = scenes
+                .get(id)
+                .ok_or(SceneSpawnError::NonExistentScene { id })?;
+            let patch = ScenePatch::diff(previous, scene)
+                .map_err(|err| SceneSpawnError::Reconcile(Box::new(err)))?;
+            (patch, snapshot(scene))
+        };
+        self.scene_snapshots.insert(id, current);
+
+        let type_registry = world.resource::<AppTypeRegistry>().clone();
+        let type_registry = type_registry.read();
+        let Some(instance_ids) = self.spawned_dynamic_scenes.get(&id) else {
+            return Ok(());
+        };
+        for instance_id in instance_ids {
+            let Some(instance_info) = self.spawned_instances.get_mut(instance_id) else {
+                continue;
+            };
+            for change in &patch.changes {
+                if let Err(err) = change.apply(world, &mut instance_info.entity_map, &type_registry)
+                {
+                    warn!("Skipped a change while reconciling a modified scene: {err}");
+                }
+            }
+        }
+        Ok(())
+    }
+
     fn spawn_dynamic_internal(
         world: &mut World,
         id: AssetId<DynamicScene>,
@@ -267,12 +367,17 @@ impl SceneSpawner {
     /// Iterate through all instances of the provided scenes and update those immediately.
     ///
     /// Useful for updating already spawned scene instances after their corresponding scene has been modified.
+    /// Instances are updated according to the [`SceneReloadMode`].
     pub fn update_spawned_scenes(
         &mut self,
         world: &mut World,
         scene_ids: &[AssetId<DynamicScene>],
     ) -> Result<(), SceneSpawnError> {
         for id in scene_ids {
+            if let Some(previous) = self.scene_snapshots.remove(id) {
+                self.reconcile_spawned_scene(world, *id, &previous)?;
+                continue;
+            }
             if let Some(spawned_instances) = self.spawned_dynamic_scenes.get(id) {
                 for instance_id in spawned_instances {
                     if let Some(instance_info) = self.spawned_instances.get_mut(instance_id) {
@@ -280,6 +385,7 @@ impl SceneSpawner {
                     }
                 }
             }
+            self.snapshot_scene(world, *id);
         }
         Ok(())
     }
@@ -319,6 +425,7 @@ impl SceneSpawner {
                         .entry(handle.id())
                         .or_insert_with(HashSet::new);
                     spawned.insert(instance_id);
+                    self.snapshot_scene(world, handle.id());
 
                     // Scenes with parents need more setup before they are ready.
                     // See `set_scene_instance_parent_sync()`.
@@ -418,6 +525,59 @@ impl SceneSpawner {
             .flatten()
             .copied()
     }
+
+    /// Computes the changes that turn `scene` into the live entities of a spawned instance.
+    ///
+    /// See [`ScenePatch::diff_world`] for how components are extracted with `filter`.
+    pub fn diff_instance(
+        &self,
+        world: &World,
+        instance_id: InstanceId,
+        scene: &DynamicScene,
+        filter: SceneFilter,
+    ) -> Result<ScenePatch, ScenePatchError> {
+        let instance = self
+            .spawned_instances
+            .get(&instance_id)
+            .ok_or(ScenePatchError::NonExistentInstance(instance_id))?;
+        ScenePatch::diff_world(scene, world, &instance.entity_map, filter)
+    }
+
+    /// Applies `patch` to the entities of a spawned instance.
+    ///
+    /// Entities spawned or despawned by the patch are added to or removed from the instance.
+    pub fn apply_patch_to_instance(
+        &mut self,
+        world: &mut World,
+        instance_id: InstanceId,
+        patch: &ScenePatch,
+    ) -> Result<(), ScenePatchError> {
+        let instance = self
+            .spawned_instances
+            .get_mut(&instance_id)
+            .ok_or(ScenePatchError::NonExistentInstance(instance_id))?;
+        let type_registry = world.resource::<AppTypeRegistry>().clone();
+        patch.apply(world, &mut instance.entity_map, &type_registry)
+    }
+}
+
+/// Copies the entities of `scene`, to be diffed against its next version.
+fn snapshot(scene: &DynamicScene) -> DynamicScene {
+    DynamicScene {
+        resources: Vec::new(),
+        entities: scene
+            .entities
+            .iter()
+            .map(|entity| DynamicEntity {
+                entity: entity.entity,
+                components: entity
+                    .components
+                    .iter()
+                    .map(|component| component.clone_value())
+                    .collect(),
+            })
+            .collect(),
+    }
 }
 
 /// System that handles scheduled scene instance spawning and despawning through a [`SceneSpawner`].
@@ -729,4 +889,77 @@ mod tests {
         app.update();
         check(app.world_mut(), 0);
     }
+
+    #[derive(Reflect, Component, Debug, PartialEq, Eq, Clone, Copy, Default)]
+    #[reflect(Component)]
+    struct B(usize);
+
+    #[derive(Component)]
+    struct RuntimeOnly;
+
+    #[test]
+    fn reconcile_modified_scene() {
+        let mut app = App::new();
+        app.add_plugins((AssetPlugin::default(), ScenePlugin));
+        app.register_type::<A>().register_type::<B>();
+        app.world_mut()
+            .resource_mut::<SceneSpawner>()
+            .set_reload_mode(SceneReloadMode::Reconcile);
+
+        let mut scene_world = World::new();
+        scene_world.insert_resource(app.world().resource::<AppTypeRegistry>().clone());
+        let kept = scene_world.spawn((A(1), B(1))).id();
+        let removed = scene_world.spawn(A(2)).id();
+        let scene = DynamicScene::from_world(&scene_world);
+        let handle = app
+            .world_mut()
+            .resource_mut::<Assets<DynamicScene>>()
+            .add(scene);
+
+        let instance_id = app
+            .world_mut()
+            .resource_mut::<SceneSpawner>()
+            .spawn_dynamic(handle.clone());
+        app.update();
+
+        let find = |world: &mut World, a: usize| {
+            world
+                .query::<(Entity, &A)>()
+                .iter(world)
+                .find(|(_, &A(value))| value == a)
+                .map(|(entity, _)| entity)
+        };
+        let kept_instance = find(app.world_mut(), 1).unwrap();
+        let removed_instance = find(app.world_mut(), 2).unwrap();
+
+        // Change the instance at runtime
+        app.world_mut()
+            .entity_mut(kept_instance)
+            .insert((RuntimeOnly, B(5)));
+
+        // Modify the scene: change `A` of the kept entity, remove an entity and add another one
+        scene_world.entity_mut(kept).insert(A(10));
+        scene_world.despawn(removed);
+        scene_world.spawn(A(3));
+        app.world_mut()
+            .resource_mut::<Assets<DynamicScene>>()
+            .insert(&handle, DynamicScene::from_world(&scene_world));
+        app.update();
+        app.update();
+
+        let world = app.world_mut();
+        assert_eq!(find(world, 10), Some(kept_instance));
+        let kept_instance = world.entity(kept_instance);
+        assert!(kept_instance.contains::<RuntimeOnly>());
+        assert_eq!(kept_instance.get::<B>(), Some(&B(5)));
+        assert!(world.get_entity(removed_instance).is_none());
+        let added_instance = find(world, 3).unwrap();
+
+        let scene_spawner = world.resource::<SceneSpawner>();
+        let mut entities: Vec<_> = scene_spawner.iter_instance_entities(instance_id).collect();
+        entities.sort();
+        let mut expected = vec![find(world, 10).unwrap(), added_instance];
+        expected.sort();
+        assert_eq!(entities, expected);
+    }
 }
@@ -1,13 +1,14 @@
 //! `serde` serialization and deserialization implementation for Bevy scenes.
 
-use crate::{DynamicEntity, DynamicScene};
+use crate::{DynamicEntity, DynamicScene, FieldPatch, Prefab, PrefabEntity};
+use bevy_asset::AssetPath;
 use bevy_ecs::entity::Entity;
 use bevy_reflect::serde::{TypedReflectDeserializer, TypedReflectSerializer};
-use bevy_reflect::PartialReflect;
 use bevy_reflect::{
     serde::{ReflectDeserializer, TypeRegistrationDeserializer},
     TypeRegistry,
 };
+use bevy_reflect::{ParsedPath, PartialReflect};
 use bevy_utils::HashSet;
 use serde::ser::SerializeMap;
 use serde::{
@@ -15,7 +16,7 @@ use serde::{
     ser::SerializeStruct,
     Deserialize, Deserializer, Serialize, Serializer,
 };
-use std::fmt::Formatter;
+use std::{any::TypeId, fmt::Formatter};
 
 /// Name of the serialized scene struct type.
 pub const SCENE_STRUCT: &str = "Scene";
@@ -480,6 +481,363 @@ impl<'a, 'de> Visitor<'de> for SceneMapVisitor<'a> {
     }
 }
 
+/// Name of the serialized prefab struct type.
+pub const PREFAB_STRUCT: &str = "Prefab";
+/// Name of the serialized prefab entity struct type.
+pub const PREFAB_ENTITY_STRUCT: &str = "PrefabEntity";
+/// Name of the serialized base scene field in a prefab entity struct.
+pub const PREFAB_ENTITY_FIELD_BASE: &str = "base";
+/// Name of the serialized parent field in a prefab entity struct.
+pub const PREFAB_ENTITY_FIELD_PARENT: &str = "parent";
+/// Name of the serialized field patches field in a prefab entity struct.
+pub const PREFAB_ENTITY_FIELD_PATCHES: &str = "patches";
+/// Name of the serialized removed components field in a prefab entity struct.
+pub const PREFAB_ENTITY_FIELD_REMOVED: &str = "removed";
+
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "lowercase")]
+enum PrefabField {
+    Entities,
+}
+
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "lowercase")]
+enum PrefabEntityField {
+    Base,
+    Parent,
+    Components,
+    Patches,
+    Removed,
+}
+
+/// Handles prefab deserialization.
+///
+/// Prefabs are a map of entities, where every field of an entity is optional:
+///
+/// ```ron
+/// (
+///   entities: {
+///     4294967296: (
+///       // This entity is an instance of the given scene
+///       base: "enemy.scn.ron",
+///       // Fields of inherited components, by component type and field path. Values are written with their type.
+///       patches: {
+///         "game::Health": {
+///           "current": { "f32": 50.0 },
+///         },
+///       },
+///       // Added (or replaced) components
+///       components: {
+///         "game::Boss": (),
+///       },
+///       // Removed components
+///       removed: ["game::Wander"],
+///     ),
+///     4294967297: (
+///       parent: 4294967296,
+///       components: {
+///         "game::Hat": (),
+///       },
+///     ),
+///   },
+/// )
+/// ```
+pub struct PrefabDeserializer<'a> {
+    /// Type registry in which the component types used in the prefab to deserialize are registered.
+    pub type_registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for PrefabDeserializer<'a> {
+    type Value = Prefab;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_struct(
+            PREFAB_STRUCT,
+            &[SCENE_ENTITIES],
+            PrefabVisitor {
+                type_registry: self.type_registry,
+            },
+        )
+    }
+}
+
+struct PrefabVisitor<'a> {
+    type_registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> Visitor<'de> for PrefabVisitor<'a> {
+    type Value = Prefab;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("prefab struct")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut entities = None;
+        while let Some(key) = map.next_key()? {
+            match key {
+                PrefabField::Entities => {
+                    if entities.is_some() {
+                        return Err(Error::duplicate_field(SCENE_ENTITIES));
+                    }
+                    entities = Some(map.next_value_seed(PrefabEntitiesDeserializer {
+                        type_registry: self.type_registry,
+                    })?);
+                }
+            }
+        }
+
+        let entities = entities.ok_or_else(|| Error::missing_field(SCENE_ENTITIES))?;
+        Ok(Prefab { entities })
+    }
+}
+
+struct PrefabEntitiesDeserializer<'a> {
+    type_registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for PrefabEntitiesDeserializer<'a> {
+    type Value = Vec<PrefabEntity>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'a, 'de> Visitor<'de> for PrefabEntitiesDeserializer<'a> {
+    type Value = Vec<PrefabEntity>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("map of prefab entities")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut entities = Vec::new();
+        while let Some(entity) = map.next_key::<Entity>()? {
+            entities.push(map.next_value_seed(PrefabEntityDeserializer {
+                entity,
+                type_registry: self.type_registry,
+            })?);
+        }
+        Ok(entities)
+    }
+}
+
+struct PrefabEntityDeserializer<'a> {
+    entity: Entity,
+    type_registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for PrefabEntityDeserializer<'a> {
+    type Value = PrefabEntity;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_struct(
+            PREFAB_ENTITY_STRUCT,
+            &[
+                PREFAB_ENTITY_FIELD_BASE,
+                PREFAB_ENTITY_FIELD_PARENT,
+                ENTITY_FIELD_COMPONENTS,
+                PREFAB_ENTITY_FIELD_PATCHES,
+                PREFAB_ENTITY_FIELD_REMOVED,
+            ],
+            self,
+        )
+    }
+}
+
+impl<'a, 'de> Visitor<'de> for PrefabEntityDeserializer<'a> {
+    type Value = PrefabEntity;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("prefab entity struct")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut prefab_entity = PrefabEntity {
+            entity: self.entity,
+            base: None,
+            parent: None,
+            components: Vec::new(),
+            patches: Vec::new(),
+            removed: Vec::new(),
+        };
+        let mut seen = HashSet::new();
+        while let Some(key) = map.next_key::<PrefabEntityField>()? {
+            let name = match key {
+                PrefabEntityField::Base => {
+                    prefab_entity.base = Some(AssetPath::from(map.next_value::<String>()?));
+                    PREFAB_ENTITY_FIELD_BASE
+                }
+                PrefabEntityField::Parent => {
+                    prefab_entity.parent = Some(map.next_value::<Entity>()?);
+                    PREFAB_ENTITY_FIELD_PARENT
+                }
+                PrefabEntityField::Components => {
+                    prefab_entity.components = map.next_value_seed(SceneMapDeserializer {
+                        registry: self.type_registry,
+                    })?;
+                    ENTITY_FIELD_COMPONENTS
+                }
+                PrefabEntityField::Patches => {
+                    prefab_entity.patches = map.next_value_seed(FieldPatchesDeserializer {
+                        type_registry: self.type_registry,
+                    })?;
+                    PREFAB_ENTITY_FIELD_PATCHES
+                }
+                PrefabEntityField::Removed => {
+                    prefab_entity.removed = map.next_value_seed(RemovedComponentsDeserializer {
+                        type_registry: self.type_registry,
+                    })?;
+                    PREFAB_ENTITY_FIELD_REMOVED
+                }
+            };
+            if !seen.insert(name) {
+                return Err(Error::duplicate_field(name));
+            }
+        }
+        Ok(prefab_entity)
+    }
+}
+
+/// Deserializes a map of component type to a map of field path to (typed) value.
+struct FieldPatchesDeserializer<'a> {
+    type_registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for FieldPatchesDeserializer<'a> {
+    type Value = Vec<FieldPatch>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'a, 'de> Visitor<'de> for FieldPatchesDeserializer<'a> {
+    type Value = Vec<FieldPatch>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("map of component types to field patches")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut patches = Vec::new();
+        while let Some(registration) =
+            map.next_key_seed(TypeRegistrationDeserializer::new(self.type_registry))?
+        {
+            map.next_value_seed(ComponentPatchesDeserializer {
+                component: registration.type_id(),
+                type_registry: self.type_registry,
+                patches: &mut patches,
+            })?;
+        }
+        Ok(patches)
+    }
+}
+
+/// Deserializes the field patches of a single component into `patches`.
+struct ComponentPatchesDeserializer<'a, 'p> {
+    component: TypeId,
+    type_registry: &'a TypeRegistry,
+    patches: &'p mut Vec<FieldPatch>,
+}
+
+impl<'a, 'p, 'de> DeserializeSeed<'de> for ComponentPatchesDeserializer<'a, 'p> {
+    type Value = ();
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'a, 'p, 'de> Visitor<'de> for ComponentPatchesDeserializer<'a, 'p> {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("map of field paths to values")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        while let Some(path) = map.next_key::<String>()? {
+            let path = ParsedPath::parse(&path).map_err(Error::custom)?;
+            let value = map.next_value_seed(ReflectDeserializer::new(self.type_registry))?;
+            self.patches.push(FieldPatch {
+                component: self.component,
+                path,
+                value,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Deserializes a sequence of component types.
+struct RemovedComponentsDeserializer<'a> {
+    type_registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for RemovedComponentsDeserializer<'a> {
+    type Value = Vec<TypeId>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'a, 'de> Visitor<'de> for RemovedComponentsDeserializer<'a> {
+    type Value = Vec<TypeId>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("sequence of component types")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut removed = Vec::new();
+        while let Some(registration) =
+            seq.next_element_seed(TypeRegistrationDeserializer::new(self.type_registry))?
+        {
+            removed.push(registration.type_id());
+        }
+        Ok(removed)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::ron;
@@ -0,0 +1,425 @@
+use std::fmt::{self, Formatter};
+
+use bevy_animation::{AnimationClip, AnimationTargetId};
+use bevy_asset::{io::Reader, Asset, AssetLoader, AssetPath, Handle, LoadContext};
+use bevy_core::Name;
+use bevy_math::{URect, UVec2};
+use bevy_reflect::TypePath;
+use bevy_render::texture::Image;
+use bevy_utils::HashMap;
+use serde::{
+    de::{MapAccess, SeqAccess, Visitor},
+    Deserialize, Deserializer, Serialize,
+};
+use thiserror::Error;
+
+use crate::{Flipbook, FlipbookFrame, TextureAtlasLayout};
+
+/// A sprite sheet loaded by the [`AsepriteLoader`], along with its
+/// animations.
+///
+/// To play one of the animations, give the sprite a [`TextureAtlas`] with the
+/// [`SpriteSheet::layout`], and an
+/// [`AnimationTarget`](bevy_animation::AnimationTarget) with the ID from the
+/// [`AsepriteLoaderSettings`], then play the clip with an
+/// [`AnimationPlayer`](bevy_animation::AnimationPlayer).
+///
+/// [`TextureAtlas`]: crate::TextureAtlas
+#[derive(Asset, TypePath, Debug)]
+pub struct SpriteSheet {
+    /// The image that contains all the frames.
+    #[dependency]
+    pub image: Handle<Image>,
+    /// The layout of the frames within [`SpriteSheet::image`], with one
+    /// section per frame, in order.
+    pub layout: Handle<TextureAtlasLayout>,
+    /// A clip that plays all the frames in order.
+    pub all_frames: Handle<AnimationClip>,
+    /// A clip for each tag of the sprite sheet, by name.
+    pub animations: HashMap<String, Handle<AnimationClip>>,
+}
+
+/// Labels that can be used to load parts of a [`SpriteSheet`].
+///
+/// ```
+/// # use bevy_asset::{AssetServer, Handle};
+/// # use bevy_animation::AnimationClip;
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_sprite::SpriteSheetAssetLabel;
+/// fn load_walk_cycle(asset_server: Res<AssetServer>) {
+///     let walk: Handle<AnimationClip> = asset_server.load(
+///         SpriteSheetAssetLabel::Animation("walk".into()).from_asset("hero.aseprite.json"),
+///     );
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SpriteSheetAssetLabel {
+    /// `Layout`: the [`TextureAtlasLayout`] of the sprite sheet
+    Layout,
+    /// `AllFrames`: an [`AnimationClip`] that plays all the frames
+    AllFrames,
+    /// `Animation/{}`: the [`AnimationClip`] of the tag with the given name
+    Animation(String),
+}
+
+/// Loads [`SpriteSheet`]s from the JSON files that Aseprite exports
+/// alongside sprite sheet images.
+///
+/// Both the array and the hash forms of the frame list are supported. Each
+/// frame tag becomes an [`AnimationClip`], played in the tag's direction;
+/// since repetition is controlled by the
+/// [`AnimationPlayer`](bevy_animation::AnimationPlayer), the tag's repeat
+/// count is ignored.
+///
+/// The files must have the `.aseprite.json` extension.
+#[derive(Default)]
+pub struct AsepriteLoader;
+
+/// Settings for the [`AsepriteLoader`].
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AsepriteLoaderSettings {
+    /// The ID of the [`AnimationTarget`](bevy_animation::AnimationTarget)
+    /// that the clips animate.
+    ///
+    /// Defaults to the ID of an entity named `sprite`.
+    pub target: AnimationTargetId,
+}
+
+/// An error that occurs when loading an Aseprite sprite sheet.
+#[derive(Error, Debug)]
+pub enum AsepriteLoaderError {
+    /// An I/O error occurred.
+    #[error("failed to read the sprite sheet: {0}")]
+    Io(#[from] std::io::Error),
+    /// The JSON was malformed.
+    #[error("failed to parse the sprite sheet: {0}")]
+    Json(#[from] serde_json::Error),
+    /// The sprite sheet has no frames.
+    #[error("the sprite sheet has no frames")]
+    NoFrames,
+    /// A frame tag refers to frames that don't exist.
+    #[error(
+        "the tag `{name}` spans frames {from}..={to}, but there are only {frame_count} frames"
+    )]
+    InvalidTag {
+        /// The name of the tag.
+        name: String,
+        /// The first frame of the tag.
+        from: usize,
+        /// The last frame of the tag.
+        to: usize,
+        /// The number of frames in the sprite sheet.
+        frame_count: usize,
+    },
+    /// The frames of a clip, named by its label, don't add up to a positive
+    /// duration.
+    #[error("the clip `{0}` doesn't have a positive duration")]
+    InvalidDuration(String),
+}
+
+#[derive(Deserialize)]
+struct AsepriteJson {
+    frames: AsepriteFrames,
+    meta: AsepriteMeta,
+}
+
+/// The frames of the sprite sheet, in the order they appear in the file.
+struct AsepriteFrames(Vec<AsepriteFrame>);
+
+#[derive(Deserialize)]
+struct AsepriteFrame {
+    frame: AsepriteRect,
+    /// The duration of the frame in milliseconds.
+    duration: u32,
+}
+
+#[derive(Deserialize)]
+struct AsepriteRect {
+    x: u32,
+    y: u32,
+    w: u32,
+    h: u32,
+}
+
+#[derive(Deserialize)]
+struct AsepriteMeta {
+    image: String,
+    size: AsepriteSize,
+    #[serde(default, rename = "frameTags")]
+    frame_tags: Vec<AsepriteTag>,
+}
+
+#[derive(Deserialize)]
+struct AsepriteSize {
+    w: u32,
+    h: u32,
+}
+
+#[derive(Deserialize)]
+struct AsepriteTag {
+    name: String,
+    from: usize,
+    to: usize,
+    #[serde(default)]
+    direction: AsepriteDirection,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum AsepriteDirection {
+    #[default]
+    Forward,
+    Reverse,
+    Pingpong,
+    PingpongReverse,
+}
+
+/// The contents of an Aseprite sprite sheet, before its assets are created.
+struct ParsedSpriteSheet {
+    image: String,
+    layout: TextureAtlasLayout,
+    all_frames: Flipbook,
+    animations: Vec<(String, Flipbook)>,
+}
+
+impl SpriteSheetAssetLabel {
+    /// Returns the [`AssetPath`] of this label within the sprite sheet at
+    /// `path`.
+    pub fn from_asset(&self, path: impl Into<AssetPath<'static>>) -> AssetPath<'static> {
+        path.into().with_label(self.to_string())
+    }
+}
+
+impl fmt::Display for SpriteSheetAssetLabel {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            SpriteSheetAssetLabel::Layout => f.write_str("Layout"),
+            SpriteSheetAssetLabel::AllFrames => f.write_str("AllFrames"),
+            SpriteSheetAssetLabel::Animation(name) => write!(f, "Animation/{name}"),
+        }
+    }
+}
+
+impl Default for AsepriteLoaderSettings {
+    fn default() -> Self {
+        Self {
+            target: AnimationTargetId::from_name(&Name::new("sprite")),
+        }
+    }
+}
+
+impl AssetLoader for AsepriteLoader {
+    type Asset = SpriteSheet;
+
+    type Settings = AsepriteLoaderSettings;
+
+    type Error = AsepriteLoaderError;
+
+    async fn load<'a>(
+        &'a self,
+        reader: &'a mut dyn Reader,
+        settings: &'a AsepriteLoaderSettings,
+        load_context: &'a mut LoadContext<'_>,
+    ) -> Result<SpriteSheet, AsepriteLoaderError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let sheet = parse_sprite_sheet(&bytes)?;
+
+        let image_path = load_context.path().parent().unwrap().join(&sheet.image);
+        let image = load_context.load(image_path);
+        let layout =
+            load_context.add_labeled_asset(SpriteSheetAssetLabel::Layout.to_string(), sheet.layout);
+
+        let mut add_clip = |label: SpriteSheetAssetLabel, flipbook: Flipbook| {
+            let label = label.to_string();
+            match flipbook.to_clip(settings.target) {
+                Ok(clip) => Ok(load_context.add_labeled_asset(label, clip)),
+                Err(_) => Err(AsepriteLoaderError::InvalidDuration(label)),
+            }
+        };
+        let all_frames = add_clip(SpriteSheetAssetLabel::AllFrames, sheet.all_frames)?;
+        let mut animations = HashMap::default();
+        for (name, flipbook) in sheet.animations {
+            let clip = add_clip(SpriteSheetAssetLabel::Animation(name.clone()), flipbook)?;
+            animations.insert(name, clip);
+        }
+
+        Ok(SpriteSheet {
+            image,
+            layout,
+            all_frames,
+            animations,
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["aseprite.json"]
+    }
+}
+
+/// Parses the JSON that Aseprite exports into a layout and flipbooks.
+fn parse_sprite_sheet(bytes: &[u8]) -> Result<ParsedSpriteSheet, AsepriteLoaderError> {
+    let json: AsepriteJson = serde_json::from_slice(bytes)?;
+    let frames = json.frames.0;
+    if frames.is_empty() {
+        return Err(AsepriteLoaderError::NoFrames);
+    }
+
+    let mut layout = TextureAtlasLayout::new_empty(UVec2::new(json.meta.size.w, json.meta.size.h));
+    for frame in &frames {
+        let AsepriteRect { x, y, w, h } = frame.frame;
+        layout.add_texture(URect::new(x, y, x + w, y + h));
+    }
+
+    let flipbook = |indices: &mut dyn Iterator<Item = usize>| {
+        Flipbook::new(indices.map(|index| FlipbookFrame {
+            index,
+            duration: frames[index].duration as f32 / 1000.0,
+        }))
+    };
+
+    let mut animations = vec![];
+    for tag in json.meta.frame_tags {
+        let AsepriteTag {
+            name,
+            from,
+            to,
+            direction,
+        } = tag;
+        if from > to || to >= frames.len() {
+            return Err(AsepriteLoaderError::InvalidTag {
+                name,
+                from,
+                to,
+                frame_count: frames.len(),
+            });
+        }
+
+        // Ping-pong animations don't repeat the frames they turn around at.
+        let flipbook = match direction {
+            AsepriteDirection::Forward => flipbook(&mut (from..=to)),
+            AsepriteDirection::Reverse => flipbook(&mut (from..=to).rev()),
+            AsepriteDirection::Pingpong => flipbook(&mut (from..=to).chain(((from + 1)..to).rev())),
+            AsepriteDirection::PingpongReverse => {
+                flipbook(&mut (from..=to).rev().chain((from + 1)..to))
+            }
+        };
+        animations.push((name, flipbook));
+    }
+
+    Ok(ParsedSpriteSheet {
+        image: json.meta.image,
+        layout,
+        all_frames: flipbook(&mut (0..frames.len())),
+        animations,
+    })
+}
+
+impl<'de> Deserialize<'de> for AsepriteFrames {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct FramesVisitor;
+
+        impl<'de> Visitor<'de> for FramesVisitor {
+            type Value = AsepriteFrames;
+
+            fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+                formatter.write_str("an array or a map of frames")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let mut frames = vec![];
+                while let Some(frame) = seq.next_element()? {
+                    frames.push(frame);
+                }
+                Ok(AsepriteFrames(frames))
+            }
+
+            // Frames are in the order of the file, not of their names.
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut frames = vec![];
+                while let Some((_, frame)) = map.next_entry::<String, AsepriteFrame>()? {
+                    frames.push(frame);
+                }
+                Ok(AsepriteFrames(frames))
+            }
+        }
+
+        deserializer.deserialize_any(FramesVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_math::URect;
+
+    use super::{parse_sprite_sheet, AsepriteLoaderError};
+
+    const SHEET: &str = r#"{
+        "frames": {
+            "hero 10.aseprite": { "frame": { "x": 0, "y": 0, "w": 16, "h": 24 }, "duration": 100 },
+            "hero 2.aseprite": { "frame": { "x": 16, "y": 0, "w": 16, "h": 24 }, "duration": 200 },
+            "hero 3.aseprite": { "frame": { "x": 32, "y": 0, "w": 16, "h": 24 }, "duration": 100 },
+            "hero 4.aseprite": { "frame": { "x": 0, "y": 24, "w": 16, "h": 24 }, "duration": 50 }
+        },
+        "meta": {
+            "app": "https://www.aseprite.org/",
+            "image": "hero.png",
+            "size": { "w": 48, "h": 48 },
+            "frameTags": [
+                { "name": "idle", "from": 0, "to": 1, "direction": "forward" },
+                { "name": "walk", "from": 1, "to": 3, "direction": "pingpong", "repeat": "2" },
+                { "name": "fall", "from": 2, "to": 3, "direction": "reverse" }
+            ]
+        }
+    }"#;
+
+    #[test]
+    fn parse() {
+        let sheet = parse_sprite_sheet(SHEET.as_bytes()).unwrap();
+        assert_eq!(sheet.image, "hero.png");
+        assert_eq!(sheet.layout.len(), 4);
+        assert_eq!(sheet.layout.textures[1], URect::new(16, 0, 32, 24));
+        assert_eq!(sheet.layout.textures[3], URect::new(0, 24, 16, 48));
+        assert_eq!(sheet.all_frames.frames.len(), 4);
+        assert!((sheet.all_frames.duration() - 0.45).abs() < 1e-6);
+
+        let indices = |name: &str| {
+            let (_, flipbook) = sheet.animations.iter().find(|(n, _)| n == name).unwrap();
+            flipbook.frames.iter().map(|f| f.index).collect::<Vec<_>>()
+        };
+        assert_eq!(indices("idle"), vec![0, 1]);
+        assert_eq!(indices("walk"), vec![1, 2, 3, 2]);
+        assert_eq!(indices("fall"), vec![3, 2]);
+    }
+
+    #[test]
+    fn parse_array() {
+        let sheet = parse_sprite_sheet(
+            br#"{
+                "frames": [
+                    { "frame": { "x": 0, "y": 0, "w": 8, "h": 8 }, "duration": 100 },
+                    { "frame": { "x": 8, "y": 0, "w": 8, "h": 8 }, "duration": 100 }
+                ],
+                "meta": { "image": "coin.png", "size": { "w": 16, "h": 8 } }
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(sheet.layout.textures[1], URect::new(8, 0, 16, 8));
+        assert!(sheet.animations.is_empty());
+
+        let error = parse_sprite_sheet(
+            br#"{
+                "frames": [{ "frame": { "x": 0, "y": 0, "w": 8, "h": 8 }, "duration": 100 }],
+                "meta": {
+                    "image": "coin.png",
+                    "size": { "w": 8, "h": 8 },
+                    "frameTags": [{ "name": "spin", "from": 0, "to": 4 }]
+                }
+            }"#,
+        );
+        assert!(matches!(
+            error,
+            Err(AsepriteLoaderError::InvalidTag { frame_count: 1, .. })
+        ));
+    }
+}
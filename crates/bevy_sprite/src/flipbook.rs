@@ -0,0 +1,192 @@
+use bevy_animation::{
+    animation_curves::{AnimatableCurve, AnimatedField, SteppedKeyframeCurve},
+    AnimationClip, AnimationTargetId,
+};
+use bevy_math::curve::cores::UnevenCoreError;
+use bevy_reflect::Reflect;
+use serde::{Deserialize, Serialize};
+
+use crate::TextureAtlas;
+
+/// A single frame of a [`Flipbook`].
+#[derive(Clone, Copy, Debug, PartialEq, Reflect, Serialize, Deserialize)]
+pub struct FlipbookFrame {
+    /// The index of the section of the [`TextureAtlasLayout`](crate::TextureAtlasLayout)
+    /// to show during this frame.
+    pub index: usize,
+    /// How long to show this frame for, in seconds.
+    pub duration: f32,
+}
+
+/// A sprite-sheet animation, which shows a sequence of sections of a
+/// [`TextureAtlas`] one after the other.
+///
+/// A flipbook is turned into an [`AnimationClip`] that animates
+/// [`TextureAtlas::index`], so it's played by an
+/// [`AnimationPlayer`](bevy_animation::AnimationPlayer) like any other clip,
+/// and can be used in animation graphs and transitions. The sprite entity
+/// needs an [`AnimationTarget`](bevy_animation::AnimationTarget) whose ID
+/// matches the one the clip was built for; it can be its own player.
+///
+/// Other properties of the [`Sprite`](crate::Sprite), such as whether it's
+/// flipped or its color, can be animated in the same clip with property
+/// curves:
+///
+/// ```
+/// # use bevy_animation::{animation_curves::*, AnimationTargetId};
+/// # use bevy_color::Color;
+/// # use bevy_core::Name;
+/// # use bevy_sprite::{Flipbook, Sprite};
+/// let target = AnimationTargetId::from_name(&Name::new("hero"));
+/// let mut clip = Flipbook::from_indices(0..6, 0.1).to_clip(target).unwrap();
+///
+/// // Face left for the second half of the run cycle.
+/// clip.add_property_curve_to_target(
+///     target,
+///     AnimatableCurve::new(
+///         AnimatedField::component::<Sprite>("flip_x"),
+///         SteppedKeyframeCurve::new([(0.0, false), (0.3, true), (0.6, true)]).unwrap(),
+///     ),
+/// );
+///
+/// // Flash red when the cycle starts.
+/// clip.add_property_curve_to_target(
+///     target,
+///     AnimatableCurve::new(
+///         AnimatedField::component::<Sprite>("color"),
+///         AnimatableKeyframeCurve::new([(0.0, Color::srgb(1.0, 0.0, 0.0)), (0.1, Color::WHITE)])
+///             .unwrap(),
+///     ),
+/// );
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Reflect, Serialize, Deserialize)]
+pub struct Flipbook {
+    /// The frames of the animation, in order.
+    pub frames: Vec<FlipbookFrame>,
+}
+
+impl Flipbook {
+    /// Creates a flipbook from its frames.
+    pub fn new(frames: impl IntoIterator<Item = FlipbookFrame>) -> Self {
+        Self {
+            frames: frames.into_iter().collect(),
+        }
+    }
+
+    /// Creates a flipbook that shows each of the given atlas sections for
+    /// `frame_duration` seconds.
+    pub fn from_indices(indices: impl IntoIterator<Item = usize>, frame_duration: f32) -> Self {
+        Self::new(indices.into_iter().map(|index| FlipbookFrame {
+            index,
+            duration: frame_duration,
+        }))
+    }
+
+    /// The total duration of the animation, in seconds.
+    pub fn duration(&self) -> f32 {
+        self.frames.iter().map(|frame| frame.duration).sum()
+    }
+
+    /// Creates the curve that animates [`TextureAtlas::index`] through the
+    /// frames.
+    ///
+    /// Returns an error if the flipbook has no frames, or its total duration
+    /// isn't positive.
+    pub fn curve(
+        &self,
+    ) -> Result<AnimatableCurve<usize, SteppedKeyframeCurve<usize>>, UnevenCoreError> {
+        let curve = SteppedKeyframeCurve::from_durations(
+            self.frames
+                .iter()
+                .map(|frame| (frame.index, frame.duration)),
+        )?;
+        Ok(AnimatableCurve::new(
+            AnimatedField::component::<TextureAtlas>("index"),
+            curve,
+        ))
+    }
+
+    /// Adds the curve that animates [`TextureAtlas::index`] to the
+    /// [`AnimationTarget`](bevy_animation::AnimationTarget) with the given ID
+    /// in `clip`.
+    pub fn add_to_clip(
+        &self,
+        clip: &mut AnimationClip,
+        target: AnimationTargetId,
+    ) -> Result<(), UnevenCoreError> {
+        clip.add_property_curve_to_target(target, self.curve()?);
+        Ok(())
+    }
+
+    /// Creates a clip that plays this flipbook on the
+    /// [`AnimationTarget`](bevy_animation::AnimationTarget) with the given ID.
+    pub fn to_clip(&self, target: AnimationTargetId) -> Result<AnimationClip, UnevenCoreError> {
+        let mut clip = AnimationClip::default();
+        self.add_to_clip(&mut clip, target)?;
+        Ok(clip)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_animation::{
+        animate_properties, graph::AnimationGraph, AnimationClip, AnimationPlayer, AnimationTarget,
+        AnimationTargetId,
+    };
+    use bevy_asset::Assets;
+    use bevy_core::Name;
+    use bevy_ecs::{reflect::AppTypeRegistry, system::RunSystemOnce, world::World};
+
+    use super::{Flipbook, FlipbookFrame};
+    use crate::TextureAtlas;
+
+    #[test]
+    fn play_flipbook() {
+        let mut world = World::new();
+        world.init_resource::<AppTypeRegistry>();
+        world
+            .resource::<AppTypeRegistry>()
+            .write()
+            .register::<TextureAtlas>();
+
+        let target_id = AnimationTargetId::from_name(&Name::new("sprite"));
+        let flipbook = Flipbook::new([
+            FlipbookFrame {
+                index: 4,
+                duration: 0.1,
+            },
+            FlipbookFrame {
+                index: 7,
+                duration: 0.3,
+            },
+            FlipbookFrame {
+                index: 2,
+                duration: 0.1,
+            },
+        ]);
+        let clip = flipbook.to_clip(target_id).unwrap();
+        assert_eq!(clip.duration(), flipbook.duration());
+        assert!(Flipbook::default().to_clip(target_id).is_err());
+
+        let mut clips = Assets::<AnimationClip>::default();
+        let (graph, node_index) = AnimationGraph::from_clip(clips.add(clip));
+        let mut graphs = Assets::<AnimationGraph>::default();
+        let graph = graphs.add(graph);
+        world.insert_resource(clips);
+        world.insert_resource(graphs);
+
+        let sprite = world.spawn((graph, TextureAtlas::default())).id();
+        world.entity_mut(sprite).insert(AnimationTarget {
+            id: target_id,
+            player: sprite,
+        });
+
+        for (seek_time, index) in [(0.05, 4), (0.2, 7), (0.45, 2), (0.5, 2)] {
+            let mut player = AnimationPlayer::default();
+            player.play(node_index).seek_to(seek_time);
+            world.entity_mut(sprite).insert(player);
+            world.run_system_once(animate_properties);
+            assert_eq!(world.get::<TextureAtlas>(sprite).unwrap().index, index);
+        }
+    }
+}
@@ -8,8 +8,12 @@
 )]
 
 //! Provides 2D sprite rendering functionality.
+#[cfg(feature = "bevy_animation")]
+mod aseprite;
 mod bundle;
 mod dynamic_texture_atlas_builder;
+#[cfg(feature = "bevy_animation")]
+mod flipbook;
 mod mesh2d;
 #[cfg(feature = "bevy_picking")]
 mod picking_backend;
@@ -24,6 +28,13 @@ pub mod prelude {
     #[doc(hidden)]
     pub use crate::bundle::SpriteSheetBundle;
 
+    #[cfg(feature = "bevy_animation")]
+    #[doc(hidden)]
+    pub use crate::{
+        aseprite::{SpriteSheet, SpriteSheetAssetLabel},
+        flipbook::{Flipbook, FlipbookFrame},
+    };
+
     #[doc(hidden)]
     pub use crate::{
         bundle::SpriteBundle,
@@ -34,9 +45,13 @@ pub mod prelude {
     };
 }
 
+#[cfg(feature = "bevy_animation")]
+pub use aseprite::*;
 use bevy_reflect::{std_traits::ReflectDefault, Reflect};
 pub use bundle::*;
 pub use dynamic_texture_atlas_builder::*;
+#[cfg(feature = "bevy_animation")]
+pub use flipbook::*;
 pub use mesh2d::*;
 pub use render::*;
 pub use sprite::*;
@@ -138,6 +153,17 @@ impl Plugin for SpritePlugin {
         #[cfg(feature = "bevy_picking")]
         app.add_plugins(picking_backend::SpritePickingBackend);
 
+        #[cfg(feature = "bevy_animation")]
+        {
+            use bevy_animation::animation_curves::{AnimatableCurve, SteppedKeyframeCurve};
+
+            app.init_asset::<SpriteSheet>()
+                .init_asset_loader::<AsepriteLoader>()
+                .register_type::<Flipbook>()
+                .register_type::<AnimatableCurve<usize, SteppedKeyframeCurve<usize>>>()
+                .register_type::<AnimatableCurve<bool, SteppedKeyframeCurve<bool>>>();
+        }
+
         if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
             render_app
                 .init_resource::<ImageBindGroups>()
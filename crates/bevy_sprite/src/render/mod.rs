@@ -624,7 +624,9 @@ pub fn prepare_sprite_image_bind_groups(
         match event {
             AssetEvent::Added { .. } |
             // Images don't have dependencies
-            AssetEvent::LoadedWithDependencies { .. } => {}
+            AssetEvent::LoadedWithDependencies { .. } |
+            AssetEvent::DependencyModified { .. } |
+            AssetEvent::ValidationFailed { .. } => {}
             AssetEvent::Unused { id } | AssetEvent::Modified { id } | AssetEvent::Removed { id } => {
                 image_bind_groups.values.remove(id);
             }
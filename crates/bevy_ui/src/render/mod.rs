@@ -894,7 +894,9 @@ pub fn prepare_uinodes(
             AssetEvent::Added { .. } |
             AssetEvent::Unused { .. } |
             // Images don't have dependencies
-            AssetEvent::LoadedWithDependencies { .. } => {}
+            AssetEvent::LoadedWithDependencies { .. } |
+            AssetEvent::DependencyModified { .. } |
+            AssetEvent::ValidationFailed { .. } => {}
             AssetEvent::Modified { id } | AssetEvent::Removed { id } => {
                 image_bind_groups.values.remove(id);
             }
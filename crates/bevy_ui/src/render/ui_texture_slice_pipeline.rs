@@ -363,7 +363,9 @@ pub fn prepare_ui_slices(
             AssetEvent::Added { .. } |
             AssetEvent::Unused { .. } |
             // Images don't have dependencies
-            AssetEvent::LoadedWithDependencies { .. } => {}
+            AssetEvent::LoadedWithDependencies { .. } |
+            AssetEvent::DependencyModified { .. } |
+            AssetEvent::ValidationFailed { .. } => {}
             AssetEvent::Modified { id } | AssetEvent::Removed { id } => {
                 image_bind_groups.values.remove(id);
             }